@@ -0,0 +1,12 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS idx_views_clients_campaign_id_client_id;
+
+DELETE FROM views_clients v
+USING views_clients d
+WHERE v.campaign_id = d.campaign_id AND v.client_id = d.client_id AND v.id > d.id;
+
+ALTER TABLE views_clients DROP COLUMN IF EXISTS id;
+ALTER TABLE views_clients ADD PRIMARY KEY (campaign_id, client_id);
+
+ALTER TABLE campaigns DROP COLUMN IF EXISTS frequency_cap;
@@ -0,0 +1,8 @@
+-- Add up migration script here
+
+ALTER TABLE campaigns ADD COLUMN IF NOT EXISTS frequency_cap JSONB;
+
+ALTER TABLE views_clients DROP CONSTRAINT IF EXISTS views_clients_pkey;
+ALTER TABLE views_clients ADD COLUMN IF NOT EXISTS id UUID PRIMARY KEY DEFAULT uuid_generate_v4();
+
+CREATE INDEX IF NOT EXISTS idx_views_clients_campaign_id_client_id ON views_clients (campaign_id, client_id);
@@ -0,0 +1,7 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS idx_views_clients_campaign_id_advanced_time;
+DROP INDEX IF EXISTS idx_clicks_clients_campaign_id_advanced_time;
+
+ALTER TABLE campaigns DROP COLUMN IF EXISTS daily_budget;
+ALTER TABLE campaigns DROP COLUMN IF EXISTS budget;
@@ -0,0 +1,7 @@
+-- Add up migration script here
+
+ALTER TABLE campaigns ADD COLUMN IF NOT EXISTS budget NUMERIC(12, 2) CHECK (budget >= 0);
+ALTER TABLE campaigns ADD COLUMN IF NOT EXISTS daily_budget NUMERIC(12, 2) CHECK (daily_budget >= 0);
+
+CREATE INDEX IF NOT EXISTS idx_views_clients_campaign_id_advanced_time ON views_clients (campaign_id, advanced_time);
+CREATE INDEX IF NOT EXISTS idx_clicks_clients_campaign_id_advanced_time ON clicks_clients (campaign_id, advanced_time);
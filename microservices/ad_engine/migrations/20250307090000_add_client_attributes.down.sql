@@ -0,0 +1,3 @@
+-- Add down migration script here
+
+ALTER TABLE clients DROP COLUMN IF EXISTS attributes;
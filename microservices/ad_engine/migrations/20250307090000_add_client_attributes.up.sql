@@ -0,0 +1,3 @@
+-- Add up migration script here
+
+ALTER TABLE clients ADD COLUMN IF NOT EXISTS attributes JSONB NOT NULL DEFAULT '{}'::JSONB;
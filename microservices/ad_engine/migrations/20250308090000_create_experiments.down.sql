@@ -0,0 +1,12 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS idx_clicks_clients_experiment_id;
+DROP INDEX IF EXISTS idx_views_clients_experiment_id;
+
+ALTER TABLE clicks_clients DROP COLUMN IF EXISTS experiment_arm;
+ALTER TABLE clicks_clients DROP COLUMN IF EXISTS experiment_id;
+
+ALTER TABLE views_clients DROP COLUMN IF EXISTS experiment_arm;
+ALTER TABLE views_clients DROP COLUMN IF EXISTS experiment_id;
+
+DROP TABLE IF EXISTS experiments;
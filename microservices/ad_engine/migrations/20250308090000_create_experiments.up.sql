@@ -0,0 +1,22 @@
+-- Add up migration script here
+
+CREATE TABLE IF NOT EXISTS experiments (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name TEXT NOT NULL,
+    arms JSONB NOT NULL,
+    start_date INT NOT NULL,
+    end_date INT
+);
+
+-- Only one experiment can be running at a time
+CREATE UNIQUE INDEX IF NOT EXISTS idx_experiments_running ON experiments ((end_date IS NULL)) WHERE end_date IS NULL;
+
+ALTER TABLE views_clients ADD COLUMN IF NOT EXISTS experiment_id UUID REFERENCES experiments(id);
+ALTER TABLE views_clients ADD COLUMN IF NOT EXISTS experiment_arm TEXT;
+
+ALTER TABLE clicks_clients ADD COLUMN IF NOT EXISTS experiment_id UUID REFERENCES experiments(id);
+ALTER TABLE clicks_clients ADD COLUMN IF NOT EXISTS experiment_arm TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_views_clients_experiment_id ON views_clients (experiment_id, experiment_arm);
+
+CREATE INDEX IF NOT EXISTS idx_clicks_clients_experiment_id ON clicks_clients (experiment_id, experiment_arm);
@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS idx_generation_jobs_unfinished;
+
+DROP TABLE IF EXISTS generation_jobs;
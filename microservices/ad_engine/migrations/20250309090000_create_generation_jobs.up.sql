@@ -0,0 +1,24 @@
+-- Add up migration script here
+
+CREATE TABLE IF NOT EXISTS generation_jobs (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    advertiser_id UUID NOT NULL REFERENCES advertisers(id),
+    campaign_id UUID NOT NULL REFERENCES campaigns(id) ON DELETE CASCADE,
+    generate_type TEXT NOT NULL CHECK (generate_type IN ('ALL', 'TITLE', 'TEXT')),
+    source_title TEXT NOT NULL,
+    source_text TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'PENDING'
+        CHECK (status IN ('PENDING', 'RUNNING', 'DONE', 'FAILED', 'ACCEPTED', 'REJECTED')),
+    ad_title TEXT,
+    ad_text TEXT,
+    error TEXT,
+    -- A running job belongs to the task holding the lease until it expires
+    lease_id UUID,
+    lease_expires_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Pending jobs and running jobs with an expired lease are resumed on startup
+CREATE INDEX IF NOT EXISTS idx_generation_jobs_unfinished ON generation_jobs (created_at)
+    WHERE status IN ('PENDING', 'RUNNING');
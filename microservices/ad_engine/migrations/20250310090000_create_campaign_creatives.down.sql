@@ -0,0 +1,10 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS idx_clicks_clients_creative_id;
+DROP INDEX IF EXISTS idx_views_clients_creative_id;
+
+ALTER TABLE clicks_clients DROP COLUMN IF EXISTS creative_id;
+
+ALTER TABLE views_clients DROP COLUMN IF EXISTS creative_id;
+
+DROP TABLE IF EXISTS campaign_creatives;
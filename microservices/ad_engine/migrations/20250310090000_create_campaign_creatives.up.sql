@@ -0,0 +1,23 @@
+-- Add up migration script here
+
+CREATE TABLE IF NOT EXISTS campaign_creatives (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    campaign_id UUID NOT NULL REFERENCES campaigns(id) ON DELETE CASCADE,
+    ad_title TEXT NOT NULL,
+    ad_text TEXT NOT NULL,
+    image_names TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    deleted_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_campaign_creatives_campaign_id ON campaign_creatives (campaign_id);
+
+-- Creatives are deleted softly by `deleted_at`, so recorded events keep their creative
+
+ALTER TABLE views_clients ADD COLUMN IF NOT EXISTS creative_id UUID REFERENCES campaign_creatives(id) ON DELETE RESTRICT;
+
+ALTER TABLE clicks_clients ADD COLUMN IF NOT EXISTS creative_id UUID REFERENCES campaign_creatives(id) ON DELETE RESTRICT;
+
+CREATE INDEX IF NOT EXISTS idx_views_clients_creative_id ON views_clients (creative_id);
+
+CREATE INDEX IF NOT EXISTS idx_clicks_clients_creative_id ON clicks_clients (creative_id);
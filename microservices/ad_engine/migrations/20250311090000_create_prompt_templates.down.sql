@@ -0,0 +1,24 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS idx_clicks_clients_prompt_version;
+DROP INDEX IF EXISTS idx_views_clients_prompt_version;
+
+ALTER TABLE clicks_clients DROP COLUMN IF EXISTS prompt_version_id;
+
+ALTER TABLE views_clients DROP COLUMN IF EXISTS prompt_version_id;
+
+DROP INDEX IF EXISTS idx_campaign_creatives_prompt_version;
+
+ALTER TABLE campaign_creatives DROP COLUMN IF EXISTS prompt_version_id;
+
+ALTER TABLE generation_jobs DROP COLUMN IF EXISTS prompt_version_id;
+
+ALTER TABLE campaigns DROP COLUMN IF EXISTS prompt_version_id;
+
+DROP TABLE IF EXISTS advertiser_brand_voices;
+
+DROP TABLE IF EXISTS prompt_template_versions;
+
+DROP INDEX IF EXISTS idx_prompt_templates_default;
+
+DROP TABLE IF EXISTS prompt_templates;
@@ -0,0 +1,57 @@
+-- Add up migration script here
+
+CREATE TABLE IF NOT EXISTS prompt_templates (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name TEXT NOT NULL UNIQUE,
+    is_default BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- At most one template is used when the advertiser has not chosen one
+CREATE UNIQUE INDEX IF NOT EXISTS idx_prompt_templates_default ON prompt_templates (is_default)
+    WHERE is_default;
+
+-- Versions are never changed, the latest version of a template is used
+CREATE TABLE IF NOT EXISTS prompt_template_versions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    template_id UUID NOT NULL REFERENCES prompt_templates(id) ON DELETE CASCADE,
+    version INTEGER NOT NULL,
+    title_prompt TEXT NOT NULL,
+    text_prompt TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (template_id, version)
+);
+
+CREATE TABLE IF NOT EXISTS advertiser_brand_voices (
+    advertiser_id UUID PRIMARY KEY REFERENCES advertisers(id) ON DELETE CASCADE,
+    tone TEXT NOT NULL,
+    language TEXT NOT NULL,
+    guidelines TEXT NOT NULL DEFAULT '',
+    template_id UUID REFERENCES prompt_templates(id) ON DELETE SET NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Version of the template which generated the texts, NULL for texts written
+-- by the advertiser or generated with the prompts from config
+ALTER TABLE campaigns
+    ADD COLUMN IF NOT EXISTS prompt_version_id UUID REFERENCES prompt_template_versions(id) ON DELETE SET NULL;
+
+ALTER TABLE generation_jobs
+    ADD COLUMN IF NOT EXISTS prompt_version_id UUID REFERENCES prompt_template_versions(id) ON DELETE SET NULL;
+
+ALTER TABLE campaign_creatives
+    ADD COLUMN IF NOT EXISTS prompt_version_id UUID REFERENCES prompt_template_versions(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_campaign_creatives_prompt_version ON campaign_creatives (prompt_version_id);
+
+-- Version of the template which generated the served texts, recorded with the
+-- event so regenerating the texts doesn't move earlier events to the new version
+ALTER TABLE views_clients
+    ADD COLUMN IF NOT EXISTS prompt_version_id UUID REFERENCES prompt_template_versions(id) ON DELETE SET NULL;
+
+ALTER TABLE clicks_clients
+    ADD COLUMN IF NOT EXISTS prompt_version_id UUID REFERENCES prompt_template_versions(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_views_clients_prompt_version ON views_clients (prompt_version_id);
+
+CREATE INDEX IF NOT EXISTS idx_clicks_clients_prompt_version ON clicks_clients (prompt_version_id);
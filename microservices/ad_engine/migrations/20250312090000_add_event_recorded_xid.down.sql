@@ -0,0 +1,8 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS idx_clicks_clients_recorded_xid;
+DROP INDEX IF EXISTS idx_views_clients_recorded_xid;
+
+ALTER TABLE clicks_clients DROP COLUMN IF EXISTS recorded_xid;
+
+ALTER TABLE views_clients DROP COLUMN IF EXISTS recorded_xid;
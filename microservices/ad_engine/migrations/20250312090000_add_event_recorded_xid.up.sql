@@ -0,0 +1,12 @@
+-- Add up migration script here
+
+-- Transaction which recorded the event, the CTR learner aggregates only events of transactions finished before its
+-- cursor, so an event committed late is counted by a later refresh
+
+ALTER TABLE views_clients ADD COLUMN IF NOT EXISTS recorded_xid XID8 NOT NULL DEFAULT pg_current_xact_id();
+
+ALTER TABLE clicks_clients ADD COLUMN IF NOT EXISTS recorded_xid XID8 NOT NULL DEFAULT pg_current_xact_id();
+
+CREATE INDEX IF NOT EXISTS idx_views_clients_recorded_xid ON views_clients (recorded_xid);
+
+CREATE INDEX IF NOT EXISTS idx_clicks_clients_recorded_xid ON clicks_clients (recorded_xid);
@@ -1,4 +1,4 @@
-use crate::infrastructure;
+use crate::{domain, infrastructure};
 
 /// Configuration state for the application's core functionality
 ///
@@ -8,12 +8,21 @@ use crate::infrastructure;
 ///
 /// # Configuration Categories
 ///
-/// ## Yandex GPT Integration
+/// ## LLM Integration
+/// * `llm_provider` - Provider of the LLM (`yandex`, `openai` or `mock`)
 /// * `yandex_api_key` - API authentication key for Yandex services
 /// * `yandex_folder_id` - Resource folder identifier in Yandex cloud
+/// * `yandex_iam_url` - URL of the Yandex IAM tokens endpoint
+/// * `yandex_completion_url` - URL of the Yandex GPT completion endpoint
+/// * `yandex_iam_token_refresh_before` - How long before the expiry the IAM
+///   token is refreshed
+/// * `openai_base_url` - Base URL of the OpenAI-compatible API
+/// * `openai_api_key` - Bearer token of the OpenAI-compatible API
+/// * `openai_model` - Model name for the OpenAI-compatible API
 /// * `gpt_temperature` - Controls output randomness (0.0 = deterministic, 1.0 =
 ///   creative)
 /// * `gpt_max_tokens` - Response length limit in tokens
+/// * `llm_retry_policy` - Timeout, retries and circuit breaker of LLM requests
 ///
 /// ## Content Generation
 /// * `system_prompt_for_generate_title` - Template prompt for AI title
@@ -27,19 +36,58 @@ use crate::infrastructure;
 /// * `ads_weight_relevance` - Content relevance factor
 /// * `ads_weight_fulfillment` - Delivery success factor
 /// * `ads_weight_time_left` - Time urgency factor
+/// * `ads_weights_sum_to_one` - Whether weights set through the API must sum
+///   to 1
+/// * `ads_strategy` - Selection strategy (`weighted` or `second_price`)
+/// * `ads_pacing_enabled` - Whether the pacing controller is enabled
+/// * `ads_pacing_curve_exponent` - Exponent of the ideal delivery curve
+/// * `ads_pacing_boost` - Score bonus for campaigns behind the curve
+/// * `ads_ctr_learning_enabled` - Whether the online CTR learner is enabled
+/// * `ads_ctr_learning_weight` - Share of the learned CTR in relevance
+/// * `ads_ctr_prior_alpha` - Prior clicks of the CTR learner
+/// * `ads_ctr_prior_beta` - Prior impressions without a click
+/// * `ads_ctr_refresh_interval` - Interval between CTR learner refreshes
+/// * `ads_fallback_scores_refresh_interval` - Interval between refreshes of
+///   the fallback relevance scores
+/// * `ads_targeting_index_resync_interval` - Interval between rebuilds of the
+///   targeting index from Redis
+/// * `ads_exploration_enabled` - Whether exploration is enabled in ranking
+/// * `ads_exploration_policy` - Exploration policy (`epsilon_greedy` or
+///   `thompson`)
+/// * `ads_exploration_epsilon` - Probability of exploration for
+///   `epsilon_greedy`
+/// * `ads_exploration_seed` - Seed of the exploration random generator
+/// * `ads_exploration_rng` - Exploration random generator, shared by all
+///   requests and all states of the process
+/// * `ads_shadow_enabled` - Whether the shadow ranking is computed
+/// * `ads_shadow_weight_*` - Weights of the shadow ranking
+/// * `ads_shadow_strategy` - Selection strategy of the shadow ranking
 ///
 /// ## Media Handling
 /// * `media_support_mime` - List of supported MIME types
 /// * `media_max_size` - Maximum allowed file size in bytes
 /// * `media_max_image_on_campaign` - Image limit per campaign
 ///
+/// ## Generation Jobs
+/// * `generation_job_lease` - How long a running job stays leased to its task
+///   without a renewal
+/// * `generation_job_permits` - Limits the number of jobs the process runs at
+///   once, shared by all states of the process
+///
 /// ## Content Moderation
 /// * `auto_moderating_sensitivity` - Sensitivity threshold for auto-moderation
 ///   (0.0 to 1.0)
 #[derive(Clone)]
 pub struct AppState {
+    pub llm_provider: domain::services::LlmProvider,
     pub yandex_api_key: String,
     pub yandex_folder_id: String,
+    pub yandex_iam_url: String,
+    pub yandex_completion_url: String,
+    pub yandex_iam_token_refresh_before: std::time::Duration,
+    pub openai_base_url: String,
+    pub openai_api_key: String,
+    pub openai_model: String,
     pub system_prompt_for_generate_title: String,
     pub system_prompt_for_generate_body: String,
 
@@ -47,15 +95,42 @@ pub struct AppState {
     pub ads_weight_relevance: f64,
     pub ads_weight_fulfillment: f64,
     pub ads_weight_time_left: f64,
+    pub ads_weights_sum_to_one: bool,
+    pub ads_strategy: domain::services::AdsStrategy,
+    pub ads_pacing_enabled: bool,
+    pub ads_pacing_curve_exponent: f64,
+    pub ads_pacing_boost: f64,
+    pub ads_ctr_learning_enabled: bool,
+    pub ads_ctr_learning_weight: f64,
+    pub ads_ctr_prior_alpha: f64,
+    pub ads_ctr_prior_beta: f64,
+    pub ads_ctr_refresh_interval: std::time::Duration,
+    pub ads_fallback_scores_refresh_interval: std::time::Duration,
+    pub ads_targeting_index_resync_interval: std::time::Duration,
+    pub ads_exploration_enabled: bool,
+    pub ads_exploration_policy: domain::services::ExplorationPolicy,
+    pub ads_exploration_epsilon: f64,
+    pub ads_exploration_seed: Option<u64>,
+    pub ads_exploration_rng: domain::services::ExplorationRng,
+    pub ads_shadow_enabled: bool,
+    pub ads_shadow_weight_profit: f64,
+    pub ads_shadow_weight_relevance: f64,
+    pub ads_shadow_weight_fulfillment: f64,
+    pub ads_shadow_weight_time_left: f64,
+    pub ads_shadow_strategy: domain::services::AdsStrategy,
 
     pub gpt_temperature: f32,
     pub gpt_max_tokens: u32,
+    pub llm_retry_policy: infrastructure::gpt::RetryPolicy,
 
     pub media_support_mime: Vec<String>,
     pub media_max_size: usize,
     pub media_max_image_on_campaign: usize,
 
     pub auto_moderating_sensitivity: f32,
+
+    pub generation_job_lease: std::time::Duration,
+    pub generation_job_permits: std::sync::Arc<tokio::sync::Semaphore>,
 }
 
 /// Provides conversion from infrastructure Config to AppState
@@ -79,20 +154,114 @@ pub struct AppState {
 impl From<&infrastructure::configurate::Config> for AppState {
     fn from(config: &infrastructure::configurate::Config) -> Self {
         Self {
+            llm_provider: config.llm.provider,
             yandex_api_key: config.yandex.api_key.clone(),
             yandex_folder_id: config.yandex.folder_id.clone(),
+            yandex_iam_url: config.yandex.iam_url.clone(),
+            yandex_completion_url: config.yandex.completion_url.clone(),
+            yandex_iam_token_refresh_before: config.yandex.iam_token_refresh_before,
+            openai_base_url: config.llm.openai.base_url.clone(),
+            openai_api_key: config.llm.openai.api_key.clone(),
+            openai_model: config.llm.openai.model.clone(),
             ads_weight_profit: config.ads_recommendation.weight_profit,
             ads_weight_relevance: config.ads_recommendation.weight_relevance,
             ads_weight_fulfillment: config.ads_recommendation.weight_fulfillment,
             ads_weight_time_left: config.ads_recommendation.weight_time_left,
-            gpt_temperature: config.yandex.gpt.temperature,
-            gpt_max_tokens: config.yandex.gpt.max_tokens,
-            system_prompt_for_generate_title: config.yandex.gpt.system_prompt_for_generate_title.clone(),
-            system_prompt_for_generate_body: config.yandex.gpt.system_prompt_for_generate_body.clone(),
+            ads_weights_sum_to_one: config.ads_recommendation.weights_sum_to_one,
+            ads_strategy: config.ads_recommendation.strategy,
+            ads_pacing_enabled: config.ads_recommendation.pacing.enabled,
+            ads_pacing_curve_exponent: config.ads_recommendation.pacing.curve_exponent,
+            ads_pacing_boost: config.ads_recommendation.pacing.boost,
+            ads_ctr_learning_enabled: config.ads_recommendation.ctr_learning.enabled,
+            ads_ctr_learning_weight: config.ads_recommendation.ctr_learning.weight,
+            ads_ctr_prior_alpha: config.ads_recommendation.ctr_learning.prior_alpha,
+            ads_ctr_prior_beta: config.ads_recommendation.ctr_learning.prior_beta,
+            ads_ctr_refresh_interval: config.ads_recommendation.ctr_learning.refresh_interval,
+            ads_fallback_scores_refresh_interval: config.ads_recommendation.fallback_scores.refresh_interval,
+            ads_targeting_index_resync_interval: config.ads_recommendation.targeting_index.resync_interval,
+            ads_exploration_enabled: config.ads_recommendation.exploration.enabled,
+            ads_exploration_policy: config.ads_recommendation.exploration.policy,
+            ads_exploration_epsilon: config.ads_recommendation.exploration.epsilon,
+            ads_exploration_seed: config.ads_recommendation.exploration.seed,
+            ads_exploration_rng: domain::services::ExplorationService::create_rng(
+                config.ads_recommendation.exploration.seed,
+            ),
+            ads_shadow_enabled: config.ads_recommendation.shadow.enabled,
+            ads_shadow_weight_profit: config.ads_recommendation.shadow.weight_profit,
+            ads_shadow_weight_relevance: config.ads_recommendation.shadow.weight_relevance,
+            ads_shadow_weight_fulfillment: config.ads_recommendation.shadow.weight_fulfillment,
+            ads_shadow_weight_time_left: config.ads_recommendation.shadow.weight_time_left,
+            ads_shadow_strategy: config.ads_recommendation.shadow.strategy,
+            gpt_temperature: config.llm.temperature,
+            gpt_max_tokens: config.llm.max_tokens,
+            llm_retry_policy: infrastructure::gpt::RetryPolicy {
+                timeout: config.llm.request_timeout,
+                max_attempts: config.llm.retry.max_attempts,
+                base_delay: config.llm.retry.base_delay,
+                max_delay: config.llm.retry.max_delay,
+                failure_threshold: config.llm.circuit_breaker.failure_threshold,
+                open_duration: config.llm.circuit_breaker.open_duration,
+            },
+            system_prompt_for_generate_title: config.llm.system_prompt_for_generate_title.clone(),
+            system_prompt_for_generate_body: config.llm.system_prompt_for_generate_body.clone(),
             media_support_mime: config.upload_content.support_mime.clone(),
             media_max_size: config.upload_content.max_size,
             media_max_image_on_campaign: config.upload_content.max_image_on_campaign,
             auto_moderating_sensitivity: config.auto_moderating.sensitivity,
+            generation_job_lease: config.generation_jobs.lease,
+            generation_job_permits: std::sync::Arc::new(tokio::sync::Semaphore::new(
+                config.generation_jobs.max_concurrent,
+            )),
+        }
+    }
+}
+
+impl AppState {
+    /// Takes the state shared by the whole process from the current state,
+    /// so a reloaded state keeps limiting the jobs started before the reload
+    /// and continues the exploration random sequence unless its seed changed
+    ///
+    /// # Arguments
+    /// * `current` - State the reloaded state replaces
+    pub fn keep_process_state(mut self, current: &AppState) -> Self {
+        self.generation_job_permits = current.generation_job_permits.clone();
+        if self.ads_exploration_seed == current.ads_exploration_seed {
+            self.ads_exploration_rng = current.ads_exploration_rng.clone();
+        }
+        self
+    }
+}
+
+/// Application state shared between the HTTP workers and the config watcher
+///
+/// Handlers take a snapshot of the current state with [`SharedAppState::load`]
+/// at the start of a request, a reload replaces the whole state at once, so a
+/// request never sees a mix of the old and the new configuration.
+pub struct SharedAppState {
+    current: std::sync::RwLock<std::sync::Arc<AppState>>,
+}
+
+impl SharedAppState {
+    /// Creates the shared state with the initial application state
+    pub fn new(app_state: AppState) -> Self {
+        Self {
+            current: std::sync::RwLock::new(std::sync::Arc::new(app_state)),
+        }
+    }
+
+    /// Returns the current application state
+    pub fn load(&self) -> std::sync::Arc<AppState> {
+        match self.current.read() {
+            Ok(current) => current.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Replaces the application state for all following requests
+    pub fn store(&self, app_state: AppState) {
+        match self.current.write() {
+            Ok(mut current) => *current = std::sync::Arc::new(app_state),
+            Err(poisoned) => *poisoned.into_inner() = std::sync::Arc::new(app_state),
         }
     }
 }
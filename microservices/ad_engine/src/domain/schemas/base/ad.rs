@@ -31,3 +31,68 @@ pub struct AdSchema {
     #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
     pub advertiser_id: uuid::Uuid,
 }
+
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema, Debug, Clone, PartialEq)]
+#[schema(
+    title = "Ranked advertisement",
+    description = "Advertisement selected for a client together with the combined score it was ranked by",
+    example = json!({
+        "ad_id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        "ad_title": "Mega Ad",
+        "ad_text": "His omega must be Ad",
+        "advertiser_id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        "creative_id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        "image_names": ["banner.png"],
+        "score": 0.87
+    })
+)]
+/// Represents an advertisement in a ranked feed
+///
+/// Same payload as [`AdSchema`] plus the combined score used for ordering, so
+/// a feed with several slots can show the ads best first. The impression price
+/// is kept for recording the view and is not serialized.
+pub struct RankedAdSchema {
+    /// Unique identifier for the advertisement
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub ad_id: uuid::Uuid,
+
+    /// Title of the advertisement
+    #[schema(example = "Mega Ad")]
+    pub ad_title: String,
+
+    /// Main content text of the advertisement
+    #[schema(example = "His omega must be Ad")]
+    pub ad_text: String,
+
+    /// Unique identifier of the advertiser who created this ad
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub advertiser_id: uuid::Uuid,
+
+    /// Unique identifier of the served creative, absent when the campaign has
+    /// no creatives
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub creative_id: Option<uuid::Uuid>,
+
+    /// Names of the campaign images of the served creative
+    #[schema(example = json!(["banner.png"]))]
+    pub image_names: Vec<String>,
+
+    /// Combined weighted score of the advertisement
+    #[schema(example = 0.87)]
+    pub score: f64,
+
+    /// Price of the impression charged to the advertiser
+    #[serde(skip)]
+    pub price: f64,
+}
+
+impl std::convert::From<RankedAdSchema> for AdSchema {
+    fn from(ad: RankedAdSchema) -> Self {
+        Self {
+            ad_id: ad.ad_id,
+            ad_title: ad.ad_title,
+            ad_text: ad.ad_text,
+            advertiser_id: ad.advertiser_id,
+        }
+    }
+}
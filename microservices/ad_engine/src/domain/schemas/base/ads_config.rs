@@ -0,0 +1,47 @@
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize, utoipa::ToSchema, validator::Validate)]
+#[schema(
+    title = "Ads ranking weights",
+    description = "Weights of the score components used to rank campaigns",
+    example = json!({
+        "weight_profit": 0.5,
+        "weight_relevance": 0.3,
+        "weight_fulfillment": 0.1,
+        "weight_time_left": 0.1
+    })
+)]
+/// Represents the runtime weights of the `weighted` ranking strategy
+///
+/// The weights are stored in Redis and read on every ads request, the
+/// `ads_recommendation` configuration is used until they are set.
+pub struct AdsConfigSchema {
+    /// Weight factor for profit scoring
+    #[schema(example = 0.5, minimum = 0)]
+    #[validate(range(min = 0.0, message = "weight_profit must be more or equal 0"))]
+    pub weight_profit: f64,
+
+    /// Weight factor for relevance scoring
+    #[schema(example = 0.3, minimum = 0)]
+    #[validate(range(min = 0.0, message = "weight_relevance must be more or equal 0"))]
+    pub weight_relevance: f64,
+
+    /// Weight factor for campaign fulfillment
+    #[schema(example = 0.1, minimum = 0)]
+    #[validate(range(min = 0.0, message = "weight_fulfillment must be more or equal 0"))]
+    pub weight_fulfillment: f64,
+
+    /// Weight factor for remaining campaign time
+    #[schema(example = 0.1, minimum = 0)]
+    #[validate(range(min = 0.0, message = "weight_time_left must be more or equal 0"))]
+    pub weight_time_left: f64,
+}
+
+impl From<&crate::domain::configurate::AppState> for AdsConfigSchema {
+    fn from(app_state: &crate::domain::configurate::AppState) -> Self {
+        Self {
+            weight_profit: app_state.ads_weight_profit,
+            weight_relevance: app_state.ads_weight_relevance,
+            weight_fulfillment: app_state.ads_weight_fulfillment,
+            weight_time_left: app_state.ads_weight_time_left,
+        }
+    }
+}
@@ -0,0 +1,36 @@
+#[derive(serde::Serialize, utoipa::ToSchema, Debug, Clone, PartialEq)]
+#[schema(
+    title = "Brand voice",
+    description = "Style of the texts generated for an advertiser",
+    example = json!({
+        "advertiser_id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        "tone": "friendly",
+        "language": "Russian",
+        "guidelines": "Never use exclamation marks",
+        "template_id": "3fa85f64-5717-4562-b3fc-2c963f66afa6"
+    })
+)]
+/// Represents the brand voice profile of an advertiser
+///
+/// The profile fills the variables of the prompt templates.
+pub struct BrandVoiceSchema {
+    /// Unique identifier of the advertiser
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub advertiser_id: uuid::Uuid,
+
+    /// Tone of the texts
+    #[schema(example = "friendly")]
+    pub tone: String,
+
+    /// Language of the texts
+    #[schema(example = "Russian")]
+    pub language: String,
+
+    /// Additional rules of the brand
+    #[schema(example = "Never use exclamation marks")]
+    pub guidelines: String,
+
+    /// Template used for the advertiser, the default template when omitted
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub template_id: Option<uuid::Uuid>,
+}
@@ -9,6 +9,8 @@
         "clicks_limit": 105,
         "cost_per_impression": 100.0,
         "cost_per_click": 150.0,
+        "budget": 50000.0,
+        "daily_budget": 10000.0,
         "ad_title": "Mega Ad",
         "ad_text": "His omega must be Ad",
         "start_date": 3,
@@ -18,7 +20,12 @@
             "age_from": 18,
             "age_to": 18,
             "location": "Moscow, mcad"
-        }
+        },
+        "frequency_cap": {
+            "impressions": 3,
+            "period": 7
+        },
+        "prompt_version_id": null
     })
 )]
 /// Campaign configuration schema
@@ -50,6 +57,16 @@ pub struct CampaignSchema {
     #[schema(example = 150.0, minimum = 0)]
     pub cost_per_click: f64,
 
+    /// Total budget of the campaign in campaign currency, unlimited when
+    /// omitted
+    #[schema(example = 50000.0, minimum = 0)]
+    pub budget: Option<f64>,
+
+    /// Budget of the campaign for one day in campaign currency, unlimited
+    /// when omitted
+    #[schema(example = 10000.0, minimum = 0)]
+    pub daily_budget: Option<f64>,
+
     /// Title of the advertisement
     #[schema(example = "Mega Ad")]
     pub ad_title: String,
@@ -70,6 +87,41 @@ pub struct CampaignSchema {
 
     /// Targeting criteria for the campaign
     pub targeting: TargetingCampaignSchema,
+
+    /// Limit of impressions for one client, one impression for the whole
+    /// campaign when omitted
+    pub frequency_cap: Option<FrequencyCapSchema>,
+
+    /// Version of the prompt template which generated the title and the text,
+    /// `None` when they are written by the advertiser or generated with the
+    /// prompts from config
+    #[schema(example = json!(null), format = "uuid v4")]
+    pub prompt_version_id: Option<uuid::Uuid>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, validator::Validate, utoipa::ToSchema, Debug, Clone, PartialEq)]
+#[schema(
+    title = "Campaign frequency cap",
+    description = "Limits how many times one client can see the campaign within a period",
+    example = json!({
+        "impressions": 3,
+        "period": 7
+    })
+)]
+/// Campaign frequency cap
+///
+/// Allows at most `impressions` impressions of the campaign to one client
+/// within the last `period` days
+pub struct FrequencyCapSchema {
+    /// Maximum number of impressions for one client within the period
+    #[schema(example = 3, minimum = 1)]
+    #[validate(range(min = 1, message = "impressions must be more or equal 1"))]
+    pub impressions: u32,
+
+    /// Length of the period in days, the whole campaign when omitted
+    #[schema(example = 7, minimum = 1)]
+    #[validate(range(min = 1, message = "period must be more or equal 1"))]
+    pub period: Option<u32>,
 }
 
 #[derive(
@@ -80,14 +132,28 @@ pub struct CampaignSchema {
     description = "Defines the demographic and geographic targeting parameters for a campaign",
     example = json!({
         "gender": "MALE",
+        "genders": ["MALE", "FEMALE"],
         "age_from": 18,
         "age_to": 18,
-        "location": "Moscow, mcad"
+        "location": "Moscow, mcad",
+        "locations": ["Moscow", "Saint Petersburg"],
+        "exclude_locations": ["Moscow, zelenograd"],
+        "expression": "age >= 18 AND interest IN [\"sport\", \"travel\"]"
     })
 )]
 /// Campaign targeting configuration
 ///
-/// Defines the demographic and geographic targeting parameters for a campaign
+/// Defines the demographic and geographic targeting parameters for a campaign.
+///
+/// Locations are hierarchical, parts are separated by commas: targeting
+/// `Moscow` matches clients from `Moscow` and from `Moscow, mcad`. A client
+/// matches when their location is under `location` or one of `locations` (any
+/// location when both are omitted) and not under any of `exclude_locations`.
+/// A client matches when their gender is `gender` or one of `genders` (any
+/// gender when both are omitted).
+///
+/// `expression` is a boolean expression over the client fields and attributes,
+/// see [`crate::domain::services::TargetingExpression`] for the syntax.
 pub struct TargetingCampaignSchema {
     /// Target gender (MALE or FEMALE)
     #[schema(example = "MALE")]
@@ -100,9 +166,13 @@ pub struct TargetingCampaignSchema {
     )]
     pub gender: Option<String>,
 
+    /// Target genders, in addition to `gender`
+    #[schema(example = json!(["MALE", "FEMALE"]))]
+    pub genders: Option<Vec<String>>,
+
     /// Minimum age for targeting
     #[schema(example = 18, minimum = 0, maximum = 100)]
-    #[validate(range(min = 0, max =100, message = "age must be under or equal 0 and under 100"))]
+    #[validate(range(min = 0, max = 100, message = "age must be under or equal 0 and under 100"))]
     pub age_from: Option<u8>,
 
     /// Maximum age for targeting
@@ -113,6 +183,18 @@ pub struct TargetingCampaignSchema {
     /// Geographic location for targeting
     #[schema(example = "Moscow, mcad")]
     pub location: Option<String>,
+
+    /// Geographic locations for targeting, in addition to `location`
+    #[schema(example = json!(["Moscow", "Saint Petersburg"]))]
+    pub locations: Option<Vec<String>>,
+
+    /// Geographic locations excluded from targeting
+    #[schema(example = json!(["Moscow, zelenograd"]))]
+    pub exclude_locations: Option<Vec<String>>,
+
+    /// Boolean expression over the client fields and attributes
+    #[schema(example = "age >= 18 AND interest IN [\"sport\", \"travel\"]")]
+    pub expression: Option<String>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
@@ -139,6 +221,22 @@ pub struct ActiveCampaignSchema {
     /// Cost per click (CPC)
     pub cost_per_click: f64,
 
+    /// Total budget of the campaign
+    pub budget: Option<f64>,
+
+    /// Budget of the campaign for one day
+    pub daily_budget: Option<f64>,
+
+    /// Money spent by the campaign on impressions and clicks, kept in a
+    /// separate Redis counter
+    #[serde(skip)]
+    pub spent: f64,
+
+    /// Money spent by the campaign on the current day, kept in a separate
+    /// Redis counter
+    #[serde(skip)]
+    pub spent_today: f64,
+
     /// Advertisement title
     pub ad_title: String,
 
@@ -151,20 +249,39 @@ pub struct ActiveCampaignSchema {
     /// Campaign end date
     pub end_date: u32,
 
-    /// List of client IDs who viewed the ad
-    pub view_clients_id: Vec<uuid::Uuid>,
+    /// Total number of impressions of the ad, kept in a separate Redis
+    /// counter
+    #[serde(skip)]
+    pub views_count: u32,
 
-    /// List of client IDs who clicked the ad
-    pub click_clients_id: Vec<uuid::Uuid>,
+    /// Number of impressions of the ad per client and per day, only the
+    /// clients the campaign is loaded for
+    #[serde(skip)]
+    pub view_clients: std::collections::HashMap<uuid::Uuid, std::collections::BTreeMap<u32, u32>>,
+
+    /// Number of clients who clicked the ad, kept in a separate Redis set
+    #[serde(skip)]
+    pub clicks_count: u32,
 
     /// Campaign targeting parameters
     pub targeting: TargetingCampaignSchema,
-}
 
-impl std::convert::From<(CampaignSchema, Vec<uuid::Uuid>, Vec<uuid::Uuid>)> for ActiveCampaignSchema {
-    fn from(data: (CampaignSchema, Vec<uuid::Uuid>, Vec<uuid::Uuid>)) -> Self {
-        let campaign = data.0;
+    /// Limit of impressions for one client
+    pub frequency_cap: Option<FrequencyCapSchema>,
 
+    /// Creatives served in rotation, the campaign title and text are served
+    /// when there are none
+    #[serde(default)]
+    pub creatives: Vec<crate::domain::schemas::CreativeSchema>,
+
+    /// Targeting expression parsed once when the campaign is activated, so
+    /// serving doesn't parse it on every request
+    #[serde(default)]
+    pub targeting_expression: Option<crate::domain::services::TargetingExpression>,
+}
+
+impl std::convert::From<CampaignSchema> for ActiveCampaignSchema {
+    fn from(campaign: CampaignSchema) -> Self {
         Self {
             campaign_id: campaign.campaign_id,
             advertiser_id: campaign.advertiser_id,
@@ -172,13 +289,56 @@ impl std::convert::From<(CampaignSchema, Vec<uuid::Uuid>, Vec<uuid::Uuid>)> for
             clicks_limit: campaign.clicks_limit,
             cost_per_impression: campaign.cost_per_impression,
             cost_per_click: campaign.cost_per_click,
+            budget: campaign.budget,
+            daily_budget: campaign.daily_budget,
+            spent: 0.0,
+            spent_today: 0.0,
             ad_title: campaign.ad_title,
             ad_text: campaign.ad_text,
             start_date: campaign.start_date,
             end_date: campaign.end_date,
-            view_clients_id: data.1,
-            click_clients_id: data.2,
+            views_count: 0,
+            view_clients: std::collections::HashMap::new(),
+            clicks_count: 0,
+            targeting_expression: campaign
+                .targeting
+                .expression
+                .as_deref()
+                .and_then(|expression| crate::domain::services::TargetingExpression::parse(expression).ok()),
             targeting: campaign.targeting,
+            frequency_cap: campaign.frequency_cap,
+            creatives: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+impl ActiveCampaignSchema {
+    /// Creates an active campaign for tests: 100 impressions and 50 clicks on
+    /// days 0 to 100 without budgets, targeting, frequency cap and creatives
+    pub fn test_fixture() -> Self {
+        Self {
+            campaign_id: uuid::Uuid::new_v4(),
+            advertiser_id: uuid::Uuid::new_v4(),
+            impressions_limit: 100,
+            clicks_limit: 50,
+            cost_per_impression: 1.,
+            cost_per_click: 2.,
+            budget: None,
+            daily_budget: None,
+            spent: 0.,
+            spent_today: 0.,
+            ad_title: "Test Ad".into(),
+            ad_text: "Test Content".into(),
+            start_date: 0,
+            end_date: 100,
+            views_count: 0,
+            view_clients: std::collections::HashMap::new(),
+            clicks_count: 0,
+            targeting: TargetingCampaignSchema::default(),
+            frequency_cap: None,
+            creatives: Vec::new(),
+            targeting_expression: None,
         }
     }
 }
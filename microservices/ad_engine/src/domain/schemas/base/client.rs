@@ -5,7 +5,12 @@
         "login": "my_login", 
         "location": "Moscow, mcad",
         "gender": "MALE",
-        "age": 34
+        "age": 34,
+        "attributes": {
+            "interest": ["sport", "travel"],
+            "device": "android",
+            "income": 3
+        }
     }),
     title = "Client Profile",
     description = "Schema containing core client profile information including identification, demographics and location",
@@ -38,4 +43,9 @@ pub struct ClientProfileSchema {
     #[schema(example = 34, maximum = 160, minimum = 1)]
     #[validate(range(min = 1, max = 160, message = "Age not must be over 160"))]
     pub age: u8,
+
+    /// Custom client attributes used by targeting expressions
+    #[schema(value_type = Object, example = json!({"interest": ["sport", "travel"], "device": "android", "income": 3}))]
+    #[serde(default)]
+    pub attributes: serde_json::Map<String, serde_json::Value>,
 }
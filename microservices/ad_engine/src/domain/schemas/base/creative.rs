@@ -0,0 +1,44 @@
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema, Debug, Clone, PartialEq)]
+#[schema(
+    title = "Creative",
+    description = "Variant of the advertisement copy served under a campaign",
+    example = json!({
+        "creative_id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        "campaign_id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        "ad_title": "Mega Ad",
+        "ad_text": "His omega must be Ad",
+        "image_names": ["banner.png"],
+        "prompt_version_id": null
+    })
+)]
+/// Represents a creative of a campaign
+///
+/// A campaign with creatives serves them in rotation instead of its own title
+/// and text.
+pub struct CreativeSchema {
+    /// Unique identifier of the creative
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub creative_id: uuid::Uuid,
+
+    /// Unique identifier of the campaign
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub campaign_id: uuid::Uuid,
+
+    /// Title of the advertisement
+    #[schema(example = "Mega Ad")]
+    pub ad_title: String,
+
+    /// Main text content of the advertisement
+    #[schema(example = "His omega must be Ad")]
+    pub ad_text: String,
+
+    /// Names of the campaign images shown with the creative
+    #[schema(example = json!(["banner.png"]))]
+    pub image_names: Vec<String>,
+
+    /// Version of the prompt template which generated the creative, `None`
+    /// for creatives written by the advertiser
+    #[schema(example = json!(null), format = "uuid v4")]
+    #[serde(default)]
+    pub prompt_version_id: Option<uuid::Uuid>,
+}
@@ -0,0 +1,125 @@
+#[derive(serde::Serialize, serde::Deserialize, validator::Validate, utoipa::ToSchema, Debug, Clone, PartialEq)]
+#[schema(
+    title = "Experiment arm",
+    description = "Ranking configuration served to a part of clients in an A/B experiment",
+    example = json!({
+        "name": "relevance",
+        "weight_profit": 0.2,
+        "weight_relevance": 0.6,
+        "weight_fulfillment": 0.1,
+        "weight_time_left": 0.1,
+        "strategy": "weighted",
+        "traffic": 1
+    })
+)]
+/// Represents one arm of an A/B experiment
+///
+/// Omitted weights are taken from the runtime weights
+/// ([`crate::domain::schemas::AdsConfigSchema`]) and an omitted strategy from
+/// the `ads_recommendation` configuration, so an arm may change only the
+/// weights or only the strategy.
+pub struct ExperimentArmSchema {
+    /// Name of the arm, unique within the experiment
+    #[schema(example = "relevance")]
+    #[validate(length(min = 1, max = 64, message = "name must be from 1 to 64 characters"))]
+    pub name: String,
+
+    /// Weight factor for profit scoring
+    #[schema(example = 0.2, minimum = 0)]
+    #[validate(range(min = 0.0, message = "weight_profit must be more or equal 0"))]
+    pub weight_profit: Option<f64>,
+
+    /// Weight factor for relevance scoring
+    #[schema(example = 0.6, minimum = 0)]
+    #[validate(range(min = 0.0, message = "weight_relevance must be more or equal 0"))]
+    pub weight_relevance: Option<f64>,
+
+    /// Weight factor for campaign fulfillment
+    #[schema(example = 0.1, minimum = 0)]
+    #[validate(range(min = 0.0, message = "weight_fulfillment must be more or equal 0"))]
+    pub weight_fulfillment: Option<f64>,
+
+    /// Weight factor for remaining campaign time
+    #[schema(example = 0.1, minimum = 0)]
+    #[validate(range(min = 0.0, message = "weight_time_left must be more or equal 0"))]
+    pub weight_time_left: Option<f64>,
+
+    /// Strategy used to rank and price campaigns
+    pub strategy: Option<crate::domain::services::AdsStrategy>,
+
+    /// Share of the traffic of the arm relative to other arms
+    #[schema(example = 1, minimum = 1)]
+    #[serde(default = "ExperimentArmSchema::default_traffic")]
+    #[validate(range(min = 1, max = 100, message = "traffic must be from 1 to 100"))]
+    pub traffic: u32,
+}
+
+impl ExperimentArmSchema {
+    fn default_traffic() -> u32 {
+        1
+    }
+
+    /// Returns the ranking weights of the arm, the weights the arm doesn't
+    /// override are taken from the runtime weights
+    pub fn weights(
+        &self,
+        runtime: &crate::domain::schemas::AdsConfigSchema,
+    ) -> crate::domain::schemas::AdsConfigSchema {
+        crate::domain::schemas::AdsConfigSchema {
+            weight_profit: self.weight_profit.unwrap_or(runtime.weight_profit),
+            weight_relevance: self.weight_relevance.unwrap_or(runtime.weight_relevance),
+            weight_fulfillment: self.weight_fulfillment.unwrap_or(runtime.weight_fulfillment),
+            weight_time_left: self.weight_time_left.unwrap_or(runtime.weight_time_left),
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema, Debug, Clone, PartialEq)]
+#[schema(
+    title = "Experiment",
+    description = "A/B experiment comparing ranking configurations",
+    example = json!({
+        "experiment_id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        "name": "Relevance weight",
+        "arms": [
+            {"name": "control", "traffic": 1},
+            {"name": "relevance", "weight_relevance": 0.6, "traffic": 1}
+        ],
+        "start_date": 3,
+        "end_date": null
+    })
+)]
+/// Represents an A/B experiment
+///
+/// Clients are assigned to arms by a hash of the experiment and client ids, so
+/// a client always sees the same arm while the experiment is running.
+pub struct ExperimentSchema {
+    /// Unique identifier of the experiment
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub experiment_id: uuid::Uuid,
+
+    /// Name of the experiment
+    #[schema(example = "Relevance weight")]
+    pub name: String,
+
+    /// Arms of the experiment
+    pub arms: Vec<ExperimentArmSchema>,
+
+    /// Day the experiment was started
+    #[schema(example = 3)]
+    pub start_date: u32,
+
+    /// Day the experiment was stopped, absent for the running experiment
+    #[schema(example = json!(null))]
+    pub end_date: Option<u32>,
+}
+
+/// Arm of the running experiment a client is assigned to
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExperimentAssignmentSchema {
+    /// Unique identifier of the experiment
+    pub experiment_id: uuid::Uuid,
+
+    /// Name of the arm
+    pub arm: String,
+}
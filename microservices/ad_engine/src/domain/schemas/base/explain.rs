@@ -0,0 +1,126 @@
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+/// Targeting rule or limit that excluded a campaign from the ranking
+pub enum ExclusionReason {
+    /// Client gender is not targeted
+    Gender,
+
+    /// Client location is not targeted or is excluded
+    Location,
+
+    /// Client age is out of the targeted range
+    Age,
+
+    /// Client doesn't match the targeting expression
+    Expression,
+
+    /// Campaign has reached its impressions limit
+    ImpressionsLimit,
+
+    /// Total or daily budget of the campaign is exhausted
+    Budget,
+
+    /// Pacing controller throttles the campaign today
+    Pacing,
+
+    /// Client has reached the frequency cap of the campaign
+    FrequencyCap,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, utoipa::ToSchema)]
+#[schema(
+    title = "Score breakdown",
+    description = "Components of the score a campaign was ranked by",
+    example = json!({
+        "profit": 0.8,
+        "relevance": 0.5,
+        "fulfillment": 1.7,
+        "time_left": 0.02,
+        "pacing_multiplier": 1.0,
+        "score": 0.62
+    })
+)]
+/// Represents the components of the score of a suitable campaign
+///
+/// Profit, relevance and time left are normalized among the suitable
+/// campaigns. With the `weighted` strategy the score is their weighted sum
+/// multiplied by the pacing multiplier, with the `second_price` strategy the
+/// score is the eCPM of the campaign.
+pub struct ScoreBreakdownSchema {
+    /// Normalized potential profit
+    #[schema(example = 0.8)]
+    pub profit: f64,
+
+    /// Normalized relevance for the client
+    #[schema(example = 0.5)]
+    pub relevance: f64,
+
+    /// Part of the impressions and clicks limits not yet fulfilled
+    #[schema(example = 1.7)]
+    pub fulfillment: f64,
+
+    /// Normalized time left until the end of the campaign
+    #[schema(example = 0.02)]
+    pub time_left: f64,
+
+    /// Score multiplier of the pacing controller
+    #[schema(example = 1.0)]
+    pub pacing_multiplier: f64,
+
+    /// Final score the campaign is ranked by
+    #[schema(example = 0.62)]
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, utoipa::ToSchema)]
+#[schema(
+    title = "Campaign explanation",
+    description = "Why an active campaign was or wasn't selected for a client",
+    example = json!({
+        "campaign_id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        "advertiser_id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        "ad_title": "Mega Ad",
+        "excluded_by": null,
+        "rank": 1,
+        "price": 10.0,
+        "breakdown": {
+            "profit": 0.8,
+            "relevance": 0.5,
+            "fulfillment": 1.7,
+            "time_left": 0.02,
+            "pacing_multiplier": 1.0,
+            "score": 0.62
+        }
+    })
+)]
+/// Represents the decision about one active campaign for a client
+///
+/// An excluded campaign has only `excluded_by`, a suitable campaign has its
+/// rank, impression price and score breakdown.
+pub struct CampaignExplanationSchema {
+    /// Unique identifier of the campaign
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub campaign_id: uuid::Uuid,
+
+    /// Unique identifier of the campaign advertiser
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub advertiser_id: uuid::Uuid,
+
+    /// Title of the advertisement
+    #[schema(example = "Mega Ad")]
+    pub ad_title: String,
+
+    /// Rule or limit that excluded the campaign
+    pub excluded_by: Option<ExclusionReason>,
+
+    /// Position of the campaign in the ranking, starting from 1
+    #[schema(example = 1)]
+    pub rank: Option<usize>,
+
+    /// Price of an impression at this position
+    #[schema(example = 10.0)]
+    pub price: Option<f64>,
+
+    /// Components of the campaign score
+    pub breakdown: Option<ScoreBreakdownSchema>,
+}
@@ -0,0 +1,80 @@
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+/// State of a text generation job
+pub enum GenerationJobStatus {
+    /// Job is waiting for the LLM
+    Pending,
+
+    /// Texts are being generated
+    Running,
+
+    /// Texts are generated and wait for a decision of the advertiser
+    Done,
+
+    /// LLM failed to generate texts, see `error`
+    Failed,
+
+    /// Generated texts are applied to the campaign
+    Accepted,
+
+    /// Generated texts are discarded
+    Rejected,
+}
+
+#[derive(serde::Serialize, utoipa::ToSchema, Debug, Clone, PartialEq)]
+#[schema(
+    title = "Generation job",
+    description = "Asynchronous generation of the campaign texts",
+    example = json!({
+        "job_id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        "advertiser_id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        "campaign_id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        "generate_type": "ALL",
+        "status": "DONE",
+        "ad_title": "Mega Ad",
+        "ad_text": "His omega must be Ad",
+        "error": null,
+        "prompt_version_id": "3fa85f64-5717-4562-b3fc-2c963f66afa6"
+    })
+)]
+/// Represents a text generation job of a campaign
+///
+/// Generated texts are candidates: the campaign is changed only when the job
+/// is accepted.
+pub struct GenerationJobSchema {
+    /// Unique identifier of the job
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub job_id: uuid::Uuid,
+
+    /// Unique identifier of the campaign advertiser
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub advertiser_id: uuid::Uuid,
+
+    /// Unique identifier of the campaign
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub campaign_id: uuid::Uuid,
+
+    /// Type of text generation (TITLE, TEXT, or ALL)
+    #[schema(example = "ALL")]
+    pub generate_type: String,
+
+    /// State of the job
+    pub status: GenerationJobStatus,
+
+    /// Generated title, only for TITLE and ALL
+    #[schema(example = "Mega Ad")]
+    pub ad_title: Option<String>,
+
+    /// Generated text, only for TEXT and ALL
+    #[schema(example = "His omega must be Ad")]
+    pub ad_text: Option<String>,
+
+    /// Reason of the failure
+    #[schema(example = json!(null))]
+    pub error: Option<String>,
+
+    /// Version of the prompt template which generated the texts, `None` for
+    /// the prompts from config
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub prompt_version_id: Option<uuid::Uuid>,
+}
@@ -1,10 +1,26 @@
 mod ad;
+mod ads_config;
 mod advertiser;
+mod brand_voice;
 mod campaign;
 mod client;
+mod creative;
+mod experiment;
+mod explain;
+mod generation_job;
 mod moderate;
-pub use ad::AdSchema;
+mod pacing;
+mod prompt_template;
+pub use ad::{AdSchema, RankedAdSchema};
+pub use ads_config::AdsConfigSchema;
 pub use advertiser::AdvertiserProfileSchema;
-pub use campaign::{ActiveCampaignSchema, CampaignSchema, TargetingCampaignSchema};
+pub use brand_voice::BrandVoiceSchema;
+pub use campaign::{ActiveCampaignSchema, CampaignSchema, FrequencyCapSchema, TargetingCampaignSchema};
 pub use client::ClientProfileSchema;
+pub use creative::CreativeSchema;
+pub use experiment::{ExperimentArmSchema, ExperimentAssignmentSchema, ExperimentSchema};
+pub use explain::{CampaignExplanationSchema, ExclusionReason, ScoreBreakdownSchema};
+pub use generation_job::{GenerationJobSchema, GenerationJobStatus};
 pub use moderate::ModerateSchema;
+pub use pacing::{PacingSchema, PacingStatus};
+pub use prompt_template::{PromptTemplateSchema, PromptTemplateVersionSchema};
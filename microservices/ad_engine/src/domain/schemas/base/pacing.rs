@@ -0,0 +1,41 @@
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+/// Decision of the pacing controller for a campaign on the current day
+pub enum PacingStatus {
+    /// Delivery for the current day is done, the campaign is not shown
+    Throttled,
+
+    /// Delivery follows the ideal curve
+    OnTrack,
+
+    /// Delivery lags behind the ideal curve, the campaign is ranked higher
+    Boosted,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, utoipa::ToSchema)]
+#[schema(
+    title = "Campaign pacing",
+    description = "Actual delivery of a campaign compared to its ideal delivery curve",
+    example = json!({
+        "status": "ON_TRACK",
+        "expected_delivery": 0.4,
+        "actual_delivery": 0.35
+    })
+)]
+/// Represents the pacing state of an active campaign
+///
+/// Deliveries are fractions of the campaign budget (or of the impressions
+/// limit for campaigns without a budget) from 0 to 1.
+pub struct PacingSchema {
+    /// Decision of the pacing controller
+    #[schema(example = "ON_TRACK")]
+    pub status: PacingStatus,
+
+    /// Part of the delivery that should be done by the end of the current day
+    #[schema(example = 0.4, minimum = 0, maximum = 1)]
+    pub expected_delivery: f64,
+
+    /// Part of the delivery that is already done
+    #[schema(example = 0.35, minimum = 0)]
+    pub actual_delivery: f64,
+}
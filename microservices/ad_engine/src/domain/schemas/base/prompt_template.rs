@@ -0,0 +1,82 @@
+#[derive(serde::Serialize, utoipa::ToSchema, Debug, Clone, PartialEq)]
+#[schema(
+    title = "Prompt template",
+    description = "System prompts for the generation of campaign texts with their latest version",
+    example = json!({
+        "template_id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        "name": "Short and bold",
+        "is_default": true,
+        "version_id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        "version": 2,
+        "title_prompt": "Write a title for {{advertiser_name}} in {{language}}, tone: {{tone}}",
+        "text_prompt": "Write an ad for {{audience}} in {{language}}, tone: {{tone}}. {{guidelines}}"
+    })
+)]
+/// Represents a prompt template with its latest version
+///
+/// The latest version is used for the generation, older versions are kept to
+/// compare the texts they generated.
+pub struct PromptTemplateSchema {
+    /// Unique identifier of the template
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub template_id: uuid::Uuid,
+
+    /// Unique name of the template
+    #[schema(example = "Short and bold")]
+    pub name: String,
+
+    /// Whether the template is used for advertisers without a chosen template
+    #[schema(example = true)]
+    pub is_default: bool,
+
+    /// Unique identifier of the latest version
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub version_id: uuid::Uuid,
+
+    /// Number of the latest version, starting from 1
+    #[schema(example = 2, minimum = 1)]
+    pub version: u32,
+
+    /// System prompt for title generation
+    #[schema(example = "Write a title for {{advertiser_name}} in {{language}}, tone: {{tone}}")]
+    pub title_prompt: String,
+
+    /// System prompt for body text generation
+    #[schema(example = "Write an ad for {{audience}} in {{language}}, tone: {{tone}}. {{guidelines}}")]
+    pub text_prompt: String,
+}
+
+#[derive(serde::Serialize, utoipa::ToSchema, Debug, Clone, PartialEq)]
+#[schema(
+    title = "Prompt template version",
+    description = "Immutable version of the system prompts of a template",
+    example = json!({
+        "version_id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        "template_id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        "version": 2,
+        "title_prompt": "Write a title for {{advertiser_name}} in {{language}}, tone: {{tone}}",
+        "text_prompt": "Write an ad for {{audience}} in {{language}}, tone: {{tone}}. {{guidelines}}"
+    })
+)]
+/// Represents a version of a prompt template
+pub struct PromptTemplateVersionSchema {
+    /// Unique identifier of the version, recorded on the generated texts
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub version_id: uuid::Uuid,
+
+    /// Unique identifier of the template
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub template_id: uuid::Uuid,
+
+    /// Number of the version, starting from 1
+    #[schema(example = 2, minimum = 1)]
+    pub version: u32,
+
+    /// System prompt for title generation
+    #[schema(example = "Write a title for {{advertiser_name}} in {{language}}, tone: {{tone}}")]
+    pub title_prompt: String,
+
+    /// System prompt for body text generation
+    #[schema(example = "Write an ad for {{audience}} in {{language}}, tone: {{tone}}. {{guidelines}}")]
+    pub text_prompt: String,
+}
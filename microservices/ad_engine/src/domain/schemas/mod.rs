@@ -3,11 +3,18 @@ mod request;
 mod response;
 
 pub use base::{
-    ActiveCampaignSchema, AdSchema, AdvertiserProfileSchema, CampaignSchema, ClientProfileSchema, ModerateSchema,
-    TargetingCampaignSchema,
+    ActiveCampaignSchema, AdSchema, AdsConfigSchema, AdvertiserProfileSchema, BrandVoiceSchema,
+    CampaignExplanationSchema, CampaignSchema, ClientProfileSchema, CreativeSchema, ExclusionReason,
+    ExperimentArmSchema, ExperimentAssignmentSchema, ExperimentSchema, FrequencyCapSchema, GenerationJobSchema,
+    GenerationJobStatus, ModerateSchema, PacingSchema, PacingStatus, PromptTemplateSchema, PromptTemplateVersionSchema,
+    RankedAdSchema, ScoreBreakdownSchema, TargetingCampaignSchema,
 };
 pub use request::{
-    AdClickRequest, CampaignsCreateRequest, CampaignsGenerateTextRequest, CampaignsUpdateRequest, MlScoreRequest,
-    TimeAdvanceRequest,
+    AdClickRequest, BrandVoiceUpdateRequest, CampaignsCreateRequest, CampaignsGenerateTextRequest,
+    CampaignsUpdateRequest, CreativeCreateRequest, CreativesGenerateRequest, ExperimentCreateRequest, MlScoreRequest,
+    PromptTemplateCreateRequest, PromptTemplateVersionCreateRequest, TimeAdvanceRequest,
+};
+pub use response::{
+    AdsExplainResponse, AdsResponse, CreativeStatSchema, ExperimentArmStatSchema, ExperimentStatResponse,
+    PromptVersionStatSchema, StatDailyResponse, StatResponse, TimeAdvanceResponse,
 };
-pub use response::{StatDailyResponse, StatResponse, TimeAdvanceResponse};
@@ -0,0 +1,32 @@
+#[derive(serde::Serialize, serde::Deserialize, validator::Validate, utoipa::ToSchema, Debug, Clone, PartialEq)]
+#[schema(
+    title = "Update Brand Voice Request",
+    description = "Request payload for setting the brand voice profile of an advertiser",
+    example = json!({
+        "tone": "friendly",
+        "language": "Russian",
+        "guidelines": "Never use exclamation marks",
+        "template_id": "3fa85f64-5717-4562-b3fc-2c963f66afa6"
+    })
+)]
+/// Represents a request to set the brand voice profile of an advertiser
+pub struct BrandVoiceUpdateRequest {
+    /// Tone of the texts
+    #[schema(example = "friendly")]
+    #[validate(length(min = 1, max = 256, message = "tone must be from 1 to 256 characters"))]
+    pub tone: String,
+
+    /// Language of the texts
+    #[schema(example = "Russian")]
+    #[validate(length(min = 1, max = 64, message = "language must be from 1 to 64 characters"))]
+    pub language: String,
+
+    /// Additional rules of the brand
+    #[schema(example = "Never use exclamation marks")]
+    #[validate(length(max = 2048, message = "guidelines must be at most 2048 characters"))]
+    pub guidelines: Option<String>,
+
+    /// Template used for the advertiser, the default template when omitted
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub template_id: Option<uuid::Uuid>,
+}
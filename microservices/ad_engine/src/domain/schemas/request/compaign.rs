@@ -1,3 +1,5 @@
+use validator::Validate;
+
 use crate::domain;
 
 #[derive(serde::Serialize, serde::Deserialize, validator::Validate, utoipa::ToSchema, Debug, Clone, PartialEq)]
@@ -9,6 +11,8 @@ use crate::domain;
         "clicks_limit": 25,
         "cost_per_impression": 100.0,
         "cost_per_click": 150.0,
+        "budget": 50000.0,
+        "daily_budget": 10000.0,
         "ad_title": "Mega Ad",
         "ad_text": "His omega must be Ad",
         "start_date": 3,
@@ -31,6 +35,12 @@ pub struct CampaignsCreateRequest {
     /// Cost per click (CPC)
     #[schema(example = 150.0, minimum = 0)]
     pub cost_per_click: f64,
+    /// Total budget of the campaign, unlimited when omitted
+    #[schema(example = 50000.0, minimum = 0)]
+    pub budget: Option<f64>,
+    /// Budget of the campaign for one day, unlimited when omitted
+    #[schema(example = 10000.0, minimum = 0)]
+    pub daily_budget: Option<f64>,
 
     /// Title of the advertisement
     #[schema(example = "Mega Ad")]
@@ -51,6 +61,11 @@ pub struct CampaignsCreateRequest {
 
     /// Targeting criteria for the campaign
     pub targeting: domain::schemas::TargetingCampaignSchema,
+
+    /// Limit of impressions for one client, one impression for the whole
+    /// campaign when omitted
+    #[validate]
+    pub frequency_cap: Option<domain::schemas::FrequencyCapSchema>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, validator::Validate, utoipa::ToSchema, Debug, Clone, PartialEq)]
@@ -62,6 +77,8 @@ pub struct CampaignsCreateRequest {
         "clicks_limit": 205,
         "cost_per_impression": 100.0,
         "cost_per_click": 150.0,
+        "budget": 50000.0,
+        "daily_budget": 10000.0,
         "ad_title": "Mega Ad",
         "ad_text": "His omega must be Ad",
         "start_date": 3,
@@ -85,6 +102,12 @@ pub struct CampaignsUpdateRequest {
     /// Cost per click (CPC)
     #[schema(example = 150.0, minimum = 0)]
     pub cost_per_click: f64,
+    /// Total budget of the campaign, unlimited when omitted
+    #[schema(example = 50000.0, minimum = 0)]
+    pub budget: Option<f64>,
+    /// Budget of the campaign for one day, unlimited when omitted
+    #[schema(example = 10000.0, minimum = 0)]
+    pub daily_budget: Option<f64>,
 
     /// Title of the advertisement
     #[schema(example = "Mega Ad")]
@@ -105,6 +128,11 @@ pub struct CampaignsUpdateRequest {
 
     /// Targeting criteria for the campaign
     pub targeting: domain::schemas::TargetingCampaignSchema,
+
+    /// Limit of impressions for one client, one impression for the whole
+    /// campaign when omitted
+    #[validate]
+    pub frequency_cap: Option<domain::schemas::FrequencyCapSchema>,
 }
 
 #[derive(serde::Deserialize, validator::Validate, utoipa::ToSchema, Debug)]
@@ -142,11 +170,14 @@ impl std::convert::From<domain::schemas::CampaignSchema> for CampaignsUpdateRequ
             clicks_limit: campaign.clicks_limit,
             cost_per_impression: campaign.cost_per_impression,
             cost_per_click: campaign.cost_per_click,
+            budget: campaign.budget,
+            daily_budget: campaign.daily_budget,
             ad_title: campaign.ad_title,
             ad_text: campaign.ad_text,
             start_date: campaign.start_date,
             end_date: campaign.end_date,
             targeting: campaign.targeting,
+            frequency_cap: campaign.frequency_cap,
         }
     }
 }
@@ -0,0 +1,52 @@
+#[derive(serde::Serialize, serde::Deserialize, validator::Validate, utoipa::ToSchema, Debug, Clone, PartialEq)]
+#[schema(
+    title = "Create Creative Request",
+    description = "Request payload for adding a creative to a campaign",
+    example = json!({
+        "ad_title": "Mega Ad",
+        "ad_text": "His omega must be Ad",
+        "image_names": ["banner.png"]
+    })
+)]
+/// Represents a request to add a creative to a campaign
+pub struct CreativeCreateRequest {
+    /// Title of the advertisement
+    #[schema(example = "Mega Ad")]
+    #[validate(length(min = 1, message = "ad_title must not be empty"))]
+    pub ad_title: String,
+
+    /// Main text content of the advertisement
+    #[schema(example = "His omega must be Ad")]
+    #[validate(length(min = 1, message = "ad_text must not be empty"))]
+    pub ad_text: String,
+
+    /// Names of the uploaded campaign images shown with the creative
+    #[schema(example = json!(["banner.png"]))]
+    pub image_names: Option<Vec<String>>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, validator::Validate, utoipa::ToSchema, Debug, Clone, PartialEq)]
+#[schema(
+    title = "Generate Creatives Request",
+    description = "Request payload for generating creatives of a campaign with GPT",
+    example = json!({
+        "ad_title": "Mega Ad",
+        "ad_text": "His omega must be Ad",
+        "variants": 3
+    })
+)]
+/// Represents a request to generate several creatives of a campaign
+pub struct CreativesGenerateRequest {
+    /// Optional keywords for the titles, the campaign title when omitted
+    #[schema(example = "Mega Ad")]
+    pub ad_title: Option<String>,
+
+    /// Optional keywords for the texts, the campaign text when omitted
+    #[schema(example = "His omega must be Ad")]
+    pub ad_text: Option<String>,
+
+    /// Number of creatives to generate
+    #[schema(example = 3, minimum = 1, maximum = 10)]
+    #[validate(range(min = 1, max = 10, message = "variants must be from 1 to 10"))]
+    pub variants: u32,
+}
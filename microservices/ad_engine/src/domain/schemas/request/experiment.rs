@@ -0,0 +1,29 @@
+use validator::Validate;
+
+use crate::domain;
+
+#[derive(serde::Serialize, serde::Deserialize, validator::Validate, utoipa::ToSchema, Debug, Clone, PartialEq)]
+#[schema(
+    title = "Create Experiment Request",
+    description = "Request payload for starting a new A/B experiment",
+    example = json!({
+        "name": "Relevance weight",
+        "arms": [
+            {"name": "control"},
+            {"name": "relevance", "weight_relevance": 0.6},
+            {"name": "second_price", "strategy": "second_price", "traffic": 2}
+        ]
+    })
+)]
+/// Represents a request to start a new A/B experiment
+pub struct ExperimentCreateRequest {
+    /// Name of the experiment
+    #[schema(example = "Relevance weight")]
+    #[validate(length(min = 1, max = 256, message = "name must be from 1 to 256 characters"))]
+    pub name: String,
+
+    /// Arms of the experiment, at least two
+    #[validate(length(min = 2, max = 16, message = "arms must contain from 2 to 16 arms"))]
+    #[validate]
+    pub arms: Vec<domain::schemas::ExperimentArmSchema>,
+}
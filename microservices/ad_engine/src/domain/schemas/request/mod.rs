@@ -1,8 +1,16 @@
 mod ads;
+mod brand_voice;
 mod compaign;
+mod creative;
+mod experiment;
 mod ml_score;
+mod prompt_template;
 mod time;
 pub use ads::AdClickRequest;
+pub use brand_voice::BrandVoiceUpdateRequest;
 pub use compaign::{CampaignsCreateRequest, CampaignsGenerateTextRequest, CampaignsUpdateRequest};
+pub use creative::{CreativeCreateRequest, CreativesGenerateRequest};
+pub use experiment::ExperimentCreateRequest;
 pub use ml_score::MlScoreRequest;
+pub use prompt_template::{PromptTemplateCreateRequest, PromptTemplateVersionCreateRequest};
 pub use time::TimeAdvanceRequest;
@@ -0,0 +1,54 @@
+#[derive(serde::Serialize, serde::Deserialize, validator::Validate, utoipa::ToSchema, Debug, Clone, PartialEq)]
+#[schema(
+    title = "Create Prompt Template Request",
+    description = "Request payload for creating a prompt template with its first version",
+    example = json!({
+        "name": "Short and bold",
+        "is_default": true,
+        "title_prompt": "Write a title for {{advertiser_name}} in {{language}}, tone: {{tone}}",
+        "text_prompt": "Write an ad for {{audience}} in {{language}}, tone: {{tone}}. {{guidelines}}"
+    })
+)]
+/// Represents a request to create a prompt template
+pub struct PromptTemplateCreateRequest {
+    /// Unique name of the template
+    #[schema(example = "Short and bold")]
+    #[validate(length(min = 1, max = 256, message = "name must be from 1 to 256 characters"))]
+    pub name: String,
+
+    /// Whether the template replaces the current default template
+    #[schema(example = true)]
+    pub is_default: Option<bool>,
+
+    /// System prompt for title generation
+    #[schema(example = "Write a title for {{advertiser_name}} in {{language}}, tone: {{tone}}")]
+    #[validate(length(min = 1, message = "title_prompt must not be empty"))]
+    pub title_prompt: String,
+
+    /// System prompt for body text generation
+    #[schema(example = "Write an ad for {{audience}} in {{language}}, tone: {{tone}}. {{guidelines}}")]
+    #[validate(length(min = 1, message = "text_prompt must not be empty"))]
+    pub text_prompt: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, validator::Validate, utoipa::ToSchema, Debug, Clone, PartialEq)]
+#[schema(
+    title = "Create Prompt Template Version Request",
+    description = "Request payload for adding a version to a prompt template",
+    example = json!({
+        "title_prompt": "Write a title for {{advertiser_name}} in {{language}}, tone: {{tone}}",
+        "text_prompt": "Write an ad for {{audience}} in {{language}}, tone: {{tone}}. {{guidelines}}"
+    })
+)]
+/// Represents a request to add a version to a prompt template
+pub struct PromptTemplateVersionCreateRequest {
+    /// System prompt for title generation
+    #[schema(example = "Write a title for {{advertiser_name}} in {{language}}, tone: {{tone}}")]
+    #[validate(length(min = 1, message = "title_prompt must not be empty"))]
+    pub title_prompt: String,
+
+    /// System prompt for body text generation
+    #[schema(example = "Write an ad for {{audience}} in {{language}}, tone: {{tone}}. {{guidelines}}")]
+    #[validate(length(min = 1, message = "text_prompt must not be empty"))]
+    pub text_prompt: String,
+}
@@ -0,0 +1,44 @@
+use crate::domain;
+
+#[derive(Debug, serde::Serialize, utoipa::ToSchema)]
+#[serde(untagged)]
+#[schema(
+    title = "Ads Response",
+    description = "Single best ad, or a ranked list of ads when the `limit` query parameter is set"
+)]
+/// Represents the body returned by the ads endpoint
+pub enum AdsResponse {
+    /// The single best ad for the client
+    Single(domain::schemas::AdSchema),
+
+    /// Ads ordered by their combined score, best first
+    Ranked(Vec<domain::schemas::RankedAdSchema>),
+}
+
+#[derive(Debug, serde::Serialize, utoipa::ToSchema)]
+#[schema(
+    title = "Ads Explain Response",
+    description = "Decision about every active campaign for a client, ranked campaigns first"
+)]
+/// Represents the body returned by the ads explain endpoint
+pub struct AdsExplainResponse {
+    /// Unique identifier of the client
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub client_id: uuid::Uuid,
+
+    /// Current day the decision is made for
+    #[schema(example = 1, minimum = 0)]
+    pub current_date: u32,
+
+    /// Unique identifier of the running experiment the client is assigned in
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub experiment_id: Option<uuid::Uuid>,
+
+    /// Experiment arm whose weights and strategy rank the campaigns, absent
+    /// without a running experiment
+    #[schema(example = "relevance")]
+    pub arm: Option<String>,
+
+    /// Active campaigns, ranked ones best first, then excluded ones
+    pub campaigns: Vec<domain::schemas::CampaignExplanationSchema>,
+}
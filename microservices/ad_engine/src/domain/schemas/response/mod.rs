@@ -1,4 +1,9 @@
+mod ads;
 mod stats;
 mod time;
-pub use stats::{StatDailyResponse, StatResponse};
+pub use ads::{AdsExplainResponse, AdsResponse};
+pub use stats::{
+    CreativeStatSchema, ExperimentArmStatSchema, ExperimentStatResponse, PromptVersionStatSchema, StatDailyResponse,
+    StatResponse,
+};
 pub use time::TimeAdvanceResponse;
@@ -1,3 +1,5 @@
+use crate::domain;
+
 #[derive(Debug, serde::Serialize, utoipa::ToSchema, validator::Validate)]
 #[schema(
     title = "Campaign Statistics",
@@ -29,6 +31,15 @@ pub struct StatResponse {
     /// Total campaign spend (impressions + clicks) in campaign currency
     #[schema(example = 9100.0)]
     pub spent_total: f64,
+
+    /// Remaining budget in campaign currency, absent for campaigns without a
+    /// budget
+    #[schema(example = 900.0)]
+    pub budget_remaining: Option<f64>,
+
+    /// Pacing state of the campaign, absent for inactive campaigns and
+    /// advertiser statistics
+    pub pacing: Option<domain::schemas::PacingSchema>,
 }
 
 #[derive(Debug, Default, Clone, serde::Serialize, utoipa::ToSchema, validator::Validate)]
@@ -66,3 +77,146 @@ pub struct StatDailyResponse {
     #[schema(example = 1)]
     pub date: u32,
 }
+
+#[derive(Debug, Default, Clone, serde::Serialize, utoipa::ToSchema)]
+#[schema(
+    title = "Experiment Arm Statistics",
+    description = "Impressions, clicks and spend recorded for the clients of one experiment arm"
+)]
+/// Represents statistics of one arm of an A/B experiment
+pub struct ExperimentArmStatSchema {
+    /// Name of the arm
+    #[schema(example = "relevance")]
+    pub arm: String,
+
+    /// Number of ad impressions served to the clients of the arm
+    #[schema(example = 75)]
+    pub impressions_count: u32,
+
+    /// Number of clicks of the clients of the arm
+    #[schema(example = 25)]
+    pub clicks_count: u32,
+
+    /// Click-through rate (CTR) as percentage of impressions that resulted in
+    /// clicks
+    #[schema(example = 33.3)]
+    pub conversion: f64,
+
+    /// Revenue from impressions in campaign currency
+    #[schema(example = 5550.0)]
+    pub spent_impressions: f64,
+
+    /// Revenue from clicks in campaign currency
+    #[schema(example = 3550.0)]
+    pub spent_clicks: f64,
+
+    /// Total revenue (impressions + clicks) in campaign currency
+    #[schema(example = 9100.0)]
+    pub spent_total: f64,
+}
+
+#[derive(Debug, Clone, serde::Serialize, utoipa::ToSchema)]
+#[schema(
+    title = "Experiment Statistics",
+    description = "Comparison of the arms of an A/B experiment"
+)]
+/// Represents statistics of an A/B experiment by arm
+pub struct ExperimentStatResponse {
+    /// Unique identifier of the experiment
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub experiment_id: uuid::Uuid,
+
+    /// Name of the experiment
+    #[schema(example = "Relevance weight")]
+    pub name: String,
+
+    /// Statistics of every arm in the order of the experiment arms
+    pub arms: Vec<ExperimentArmStatSchema>,
+}
+
+#[derive(Debug, Default, Clone, serde::Serialize, utoipa::ToSchema)]
+#[schema(
+    title = "Creative Statistics",
+    description = "Impressions, clicks and spend recorded for one creative of a campaign"
+)]
+/// Represents statistics of one creative of a campaign
+pub struct CreativeStatSchema {
+    /// Unique identifier of the creative
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub creative_id: uuid::Uuid,
+
+    /// Title of the creative
+    #[schema(example = "Mega Ad")]
+    pub ad_title: String,
+
+    /// Whether the creative was deleted and isn't served anymore
+    #[schema(example = false)]
+    pub deleted: bool,
+
+    /// Number of impressions of the creative
+    #[schema(example = 75)]
+    pub impressions_count: u32,
+
+    /// Number of clicks on the creative
+    #[schema(example = 25)]
+    pub clicks_count: u32,
+
+    /// Click-through rate (CTR) as percentage of impressions that resulted in
+    /// clicks
+    #[schema(example = 33.3)]
+    pub conversion: f64,
+
+    /// Total cost spent on impressions in campaign currency
+    #[schema(example = 5550.0)]
+    pub spent_impressions: f64,
+
+    /// Total cost spent on clicks in campaign currency
+    #[schema(example = 3550.0)]
+    pub spent_clicks: f64,
+
+    /// Total spend (impressions + clicks) in campaign currency
+    #[schema(example = 9100.0)]
+    pub spent_total: f64,
+}
+
+#[derive(Debug, Default, Clone, serde::Serialize, utoipa::ToSchema)]
+#[schema(
+    title = "Prompt Version Statistics",
+    description = "Impressions and clicks of the creatives and the campaign texts generated by one version of a \
+                   prompt template"
+)]
+/// Represents statistics of the creatives and the campaign texts generated by
+/// a prompt template version
+///
+/// Events of a campaign served without a creative count for the version which
+/// generated the current texts of the campaign.
+pub struct PromptVersionStatSchema {
+    /// Unique identifier of the version
+    #[schema(example = "3fa85f64-5717-4562-b3fc-2c963f66afa6", format = "uuid v4")]
+    pub version_id: uuid::Uuid,
+
+    /// Number of the version
+    #[schema(example = 2)]
+    pub version: u32,
+
+    /// Number of creatives generated by the version
+    #[schema(example = 6)]
+    pub creatives_count: u32,
+
+    /// Number of campaigns whose current texts are generated by the version
+    #[schema(example = 2)]
+    pub campaigns_count: u32,
+
+    /// Number of impressions of the creatives and the campaign texts
+    #[schema(example = 75)]
+    pub impressions_count: u32,
+
+    /// Number of clicks on the creatives and the campaign texts
+    #[schema(example = 25)]
+    pub clicks_count: u32,
+
+    /// Click-through rate (CTR) as percentage of impressions that resulted in
+    /// clicks
+    #[schema(example = 33.3)]
+    pub conversion: f64,
+}
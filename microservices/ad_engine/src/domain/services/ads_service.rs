@@ -14,13 +14,98 @@ pub trait IGetMlScores {
     /// * `advertisers_id` - Vector of advertiser UUIDs to get scores for
     ///
     /// # Returns
-    /// * `RepoResult<Vec<f64>>` - Vector of scores between 0 and 1 for each
-    ///   advertiser
+    /// * `RepoResult<Vec<Option<f64>>>` - Vector of scores between 0 and 1 for
+    ///   each advertiser, `None` when the pair has no score
     async fn get_ml_scores(
         &self,
         client_id: uuid::Uuid,
         advertisers_id: Vec<uuid::Uuid>,
-    ) -> infrastructure::repository::RepoResult<Vec<f64>>;
+    ) -> infrastructure::repository::RepoResult<Vec<Option<f64>>>;
+}
+
+/// ML scores repository remembering the loaded scores, so several rankings
+/// for the same request load every score only once
+///
+/// The repository is used by reference: `&MlScoresCache<R>` implements
+/// [`IGetMlScores`] and queries the wrapped repository only for the pairs that
+/// weren't loaded before.
+#[derive(Debug)]
+pub struct MlScoresCache<R> {
+    repo: R,
+    scores: std::sync::Mutex<std::collections::HashMap<(uuid::Uuid, uuid::Uuid), Option<f64>>>,
+}
+
+impl<R> MlScoresCache<R> {
+    /// Creates an empty cache over a repository
+    pub fn new(repo: R) -> Self {
+        Self {
+            repo,
+            scores: std::sync::Mutex::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl<R> IGetMlScores for &MlScoresCache<R>
+where
+    R: IGetMlScores + Send + Sync,
+{
+    async fn get_ml_scores(
+        &self,
+        client_id: uuid::Uuid,
+        advertisers_id: Vec<uuid::Uuid>,
+    ) -> infrastructure::repository::RepoResult<Vec<Option<f64>>> {
+        let missing: Vec<uuid::Uuid> = {
+            let scores = self.scores.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+            advertisers_id
+                .iter()
+                .filter(|advertiser_id| !scores.contains_key(&(client_id, **advertiser_id)))
+                .copied()
+                .collect::<std::collections::HashSet<_>>()
+                .into_iter()
+                .collect()
+        };
+
+        let loaded = if missing.is_empty() {
+            Vec::new()
+        } else {
+            self.repo.get_ml_scores(client_id, missing.clone()).await?
+        };
+
+        let mut scores = self.scores.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        scores.extend(
+            missing
+                .into_iter()
+                .zip(loaded)
+                .map(|(advertiser_id, score)| ((client_id, advertiser_id), score)),
+        );
+
+        Ok(advertisers_id
+            .iter()
+            .map(|advertiser_id| scores.get(&(client_id, *advertiser_id)).copied().flatten())
+            .collect())
+    }
+}
+
+/// Strategy used to select and price ads for a client
+///
+/// * `weighted` - campaigns are ranked by the weighted blend of profit,
+///   relevance, fulfillment and time left, an impression costs
+///   `cost_per_impression`
+/// * `second_price` - campaigns are ranked by eCPM (`cost_per_impression +
+///   cost_per_click * pCTR`, pCTR taken from ml scores), an impression costs
+///   the eCPM of the next campaign in the ranking minus the expected click
+///   value `cost_per_click * pCTR` of the campaign
+///
+/// With both strategies a click costs `cost_per_click`, so with
+/// `second_price` an impression together with its expected clicks costs the
+/// eCPM of the next campaign.
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AdsStrategy {
+    #[default]
+    Weighted,
+    SecondPrice,
 }
 
 /// Service for managing and scoring advertising campaigns
@@ -34,6 +119,11 @@ pub struct AdsService {
     weight_relevance: f64,
     weight_fulfillment: f64,
     weight_time_left: f64,
+    strategy: AdsStrategy,
+    pacing: Option<domain::services::PacingService>,
+    ctr_learner: Option<domain::services::CtrLearnerService>,
+    exploration: Option<domain::services::ExplorationService>,
+    fallback_scores: domain::services::FallbackScoreService,
 }
 
 impl AdsService {
@@ -45,70 +135,255 @@ impl AdsService {
     /// * `weight_relevance` - Weight factor for relevance scoring (0-1)
     /// * `weight_fulfillment` - Weight factor for campaign fulfillment (0-1)
     /// * `weight_time_left` - Weight factor for remaining campaign time (0-1)
-    pub fn new(
-        weight_profit: f64,
-        weight_relevance: f64,
-        weight_fulfillment: f64,
-        weight_time_left: f64,
-    ) -> Self {
+    pub fn new(weight_profit: f64, weight_relevance: f64, weight_fulfillment: f64, weight_time_left: f64) -> Self {
         Self {
             weight_profit,
             weight_relevance,
             weight_fulfillment,
             weight_time_left,
+            strategy: AdsStrategy::default(),
+            pacing: None,
+            ctr_learner: None,
+            exploration: None,
+            fallback_scores: domain::services::FallbackScoreService::new(),
         }
     }
+
+    /// Sets the strategy used to rank and price campaigns
+    ///
+    /// # Arguments
+    /// * `strategy` - Selection strategy, `weighted` by default
+    pub fn with_strategy(mut self, strategy: AdsStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Sets the pacing controller, campaigns are not paced without it
+    ///
+    /// # Arguments
+    /// * `pacing` - Pacing controller
+    pub fn with_pacing(mut self, pacing: Option<domain::services::PacingService>) -> Self {
+        self.pacing = pacing;
+        self
+    }
+
+    /// Sets the online CTR learner, relevance scores are taken from ml scores
+    /// only without it
+    ///
+    /// # Arguments
+    /// * `ctr_learner` - CTR learner blended into relevance scores
+    pub fn with_ctr_learner(mut self, ctr_learner: Option<domain::services::CtrLearnerService>) -> Self {
+        self.ctr_learner = ctr_learner;
+        self
+    }
+
+    /// Sets the exploration policy, the ranking is deterministic without it
+    ///
+    /// # Arguments
+    /// * `exploration` - Exploration of campaigns without enough history
+    pub fn with_exploration(mut self, exploration: Option<domain::services::ExplorationService>) -> Self {
+        self.exploration = exploration;
+        self
+    }
+
+    /// Replaces the cold-start scores of advertisers without an ML score,
+    /// the process-wide scores are used by default
+    #[cfg(test)]
+    pub fn with_fallback_scores(mut self, fallback_scores: domain::services::FallbackScoreService) -> Self {
+        self.fallback_scores = fallback_scores;
+        self
+    }
 }
 
 impl AdsService {
-    /// Recommends the most suitable ad for a client based on multiple factors
+    /// Recommends up to `limit` most suitable ads for a client based on
+    /// multiple factors, ordered by their combined score
     ///
     /// # Arguments
     /// * `active_campaigns` - List of currently active ad campaigns
-    /// * `client_id` - UUID of the target client
+    /// * `client` - Target client, loaded with [`Self::get_client`]
     /// * `advanced_time` - Current timestamp for time-based calculations
-    /// * `repo_client` - Repository for accessing client data
+    /// * `limit` - Maximum number of ads to return
     /// * `repo_score` - Repository for accessing ML scores
     ///
     /// # Returns
-    /// * `ServiceResult<AdSchema>` - The recommended ad if found
+    /// * `ServiceResult<Vec<RankedAdSchema>>` - Non-empty list of ads, best
+    ///   first
     ///
     /// # Type Parameters
-    /// * `R1` - Type implementing IGetClientById trait
-    /// * `R2` - Type implementing IGetMlScores trait
-    pub async fn recommendation_ads<R1, R2>(
+    /// * `R` - Type implementing IGetMlScores trait
+    pub async fn recommendation_ads<R>(
+        &self,
+        active_campaigns: Vec<domain::schemas::ActiveCampaignSchema>,
+        client: &infrastructure::repository::sqlx_lib::ClientReturningSchema,
+        advanced_time: u32,
+        limit: usize,
+        repo_score: R,
+    ) -> domain::services::ServiceResult<Vec<domain::schemas::RankedAdSchema>>
+    where
+        R: super::repository::IGetMlScores,
+    {
+        let suitable_campaigns = self
+            .get_suitable_campaigns(active_campaigns, client, advanced_time)
+            .await?;
+        let scored_campaigns = self
+            .score_campaigns(suitable_campaigns, client, advanced_time, &repo_score)
+            .await?;
+        let top_campaigns = self.get_top_campaigns(&scored_campaigns, limit).await?;
+
+        Ok(top_campaigns
+            .iter()
+            .enumerate()
+            .map(|(i, (score, _, campaign))| {
+                let creative = domain::services::CreativeService.rotate(campaign);
+
+                domain::schemas::RankedAdSchema {
+                    ad_id: campaign.campaign_id,
+                    ad_title: creative
+                        .map_or(&campaign.ad_title, |creative| &creative.ad_title)
+                        .clone(),
+                    ad_text: creative.map_or(&campaign.ad_text, |creative| &creative.ad_text).clone(),
+                    advertiser_id: campaign.advertiser_id,
+                    creative_id: creative.map(|creative| creative.creative_id),
+                    image_names: creative
+                        .map(|creative| creative.image_names.clone())
+                        .unwrap_or_default(),
+                    score: *score,
+                    price: self.calculate_price(&scored_campaigns, i),
+                }
+            })
+            .collect())
+    }
+
+    /// Explains the decision about every active campaign for a client
+    ///
+    /// Excluded campaigns get the targeting rule or the limit that excluded
+    /// them, suitable campaigns get their rank, impression price and score
+    /// components. Nothing is recorded.
+    ///
+    /// # Arguments
+    /// * `active_campaigns` - List of currently active ad campaigns
+    /// * `client_id` - UUID of the target client
+    /// * `advanced_time` - Current timestamp
+    /// * `repo_client` - Repository for accessing client data
+    /// * `repo_score` - Repository for accessing ML scores
+    ///
+    /// # Returns
+    /// * `ServiceResult<Vec<CampaignExplanationSchema>>` - Ranked campaigns
+    ///   best first, then excluded campaigns
+    pub async fn explain_ads<R1, R2>(
         &self,
         active_campaigns: Vec<domain::schemas::ActiveCampaignSchema>,
         client_id: uuid::Uuid,
         advanced_time: u32,
         repo_client: R1,
         repo_score: R2,
-    ) -> domain::services::ServiceResult<domain::schemas::AdSchema>
+    ) -> domain::services::ServiceResult<Vec<domain::schemas::CampaignExplanationSchema>>
     where
         R1: super::repository::IGetClientById,
         R2: super::repository::IGetMlScores,
     {
         let client = self.get_client(repo_client, client_id).await?;
-        let suitable_campaigns = self.get_suitable_campaigns(active_campaigns, &client).await?;
-        let scored_campaigns = self
-            .score_campaigns(suitable_campaigns, client_id, advanced_time, &repo_score)
-            .await?;
-        let top_campaign = self.get_top_campaign(&scored_campaigns).await?;
+        let age = client.age_u8();
+        let fields = Self::targeting_fields(age, &client.gender, &client.location, client.attributes.clone());
 
-        Ok(domain::schemas::AdSchema {
-            ad_id: top_campaign.campaign_id,
-            ad_title: top_campaign.ad_title.clone(),
-            ad_text: top_campaign.ad_text.clone(),
-            advertiser_id: top_campaign.advertiser_id,
-        })
+        let (suitable_campaigns, excluded_campaigns): (Vec<_>, Vec<_>) = active_campaigns
+            .into_iter()
+            .map(|campaign| {
+                let reason = self
+                    .get_targeting_exclusion(&campaign, age, &client.gender, &client.location, &fields)
+                    .or_else(|| self.get_limit_exclusion(&campaign, client_id, advanced_time));
+                (reason, campaign)
+            })
+            .partition(|(reason, _)| reason.is_none());
+        let suitable_campaigns: Vec<domain::schemas::ActiveCampaignSchema> =
+            suitable_campaigns.into_iter().map(|(_, campaign)| campaign).collect();
+
+        let breakdowns = if suitable_campaigns.is_empty() {
+            Vec::new()
+        } else {
+            self.get_score_breakdowns(&suitable_campaigns, &client, advanced_time, &repo_score)
+                .await?
+        };
+        let mut ranked: Vec<(
+            (f64, u32, domain::schemas::ActiveCampaignSchema),
+            domain::schemas::ScoreBreakdownSchema,
+        )> = suitable_campaigns
+            .into_iter()
+            .zip(breakdowns)
+            .map(|(campaign, breakdown)| ((breakdown.score, campaign.end_date, campaign), breakdown))
+            .collect();
+        ranked.sort_by(|(a, _), (b, _)| Self::compare_scored(a, b));
+        let (scored_campaigns, breakdowns): (Vec<_>, Vec<_>) = ranked.into_iter().unzip();
+
+        let mut explanations: Vec<domain::schemas::CampaignExplanationSchema> = breakdowns
+            .into_iter()
+            .enumerate()
+            .map(|(i, breakdown)| {
+                let (_, _, campaign) = &scored_campaigns[i];
+                domain::schemas::CampaignExplanationSchema {
+                    campaign_id: campaign.campaign_id,
+                    advertiser_id: campaign.advertiser_id,
+                    ad_title: campaign.ad_title.clone(),
+                    excluded_by: None,
+                    rank: Some(i + 1),
+                    price: Some(self.calculate_price(&scored_campaigns, i)),
+                    breakdown: Some(breakdown),
+                }
+            })
+            .collect();
+        explanations.extend(excluded_campaigns.into_iter().map(|(reason, campaign)| {
+            domain::schemas::CampaignExplanationSchema {
+                campaign_id: campaign.campaign_id,
+                advertiser_id: campaign.advertiser_id,
+                ad_title: campaign.ad_title,
+                excluded_by: reason,
+                rank: None,
+                price: None,
+                breakdown: None,
+            }
+        }));
+
+        Ok(explanations)
+    }
+
+    /// Calculates the price of one impression of a ranked campaign
+    ///
+    /// For the `second_price` strategy the campaign pays the eCPM of the
+    /// campaign ranked right after it, but never more than its own eCPM. Clicks
+    /// are billed separately, so the expected click value of the campaign is
+    /// taken off the impression price, which never drops below zero. The last
+    /// campaign in the ranking has no competitor and pays its
+    /// `cost_per_impression`.
+    ///
+    /// # Arguments
+    /// * `scored_campaigns` - List of campaigns with their scores, best first
+    /// * `position` - Position of the campaign in the ranking
+    fn calculate_price(
+        &self,
+        scored_campaigns: &[(f64, u32, domain::schemas::ActiveCampaignSchema)],
+        position: usize,
+    ) -> f64 {
+        let (score, _, campaign) = &scored_campaigns[position];
+        match self.strategy {
+            AdsStrategy::Weighted => campaign.cost_per_impression as f64,
+            AdsStrategy::SecondPrice => match scored_campaigns.get(position + 1) {
+                Some((next_score, _, _)) => {
+                    let expected_click_value = score - campaign.cost_per_impression as f64;
+                    (next_score.min(*score) - expected_click_value).max(0.)
+                },
+                None => campaign.cost_per_impression as f64,
+            },
+        }
     }
 
-    /// Retrieves client information from the repository
+    /// Retrieves client information from the repository, the client is
+    /// loaded once per request and passed to [`Self::recommendation_ads`]
     ///
     /// # Arguments
     /// * `repo_client` - Repository implementing IGetClientById
     /// * `client_id` - UUID of the client to retrieve
-    async fn get_client<R>(
+    pub async fn get_client<R>(
         &self,
         repo_client: R,
         client_id: uuid::Uuid,
@@ -122,11 +397,13 @@ impl AdsService {
             .map_err(|e| domain::services::ServiceError::Repository(e))
     }
 
-    /// Filters campaigns based on targeting criteria and campaign limits
+    /// Filters campaigns based on targeting criteria, campaign limits, budgets,
+    /// pacing and frequency caps
     ///
     /// # Arguments
     /// * `active_campaigns` - List of active campaigns to filter
     /// * `client` - Client information for targeting matching
+    /// * `advanced_time` - Current timestamp for frequency capping
     ///
     /// # Returns
     /// * List of campaigns that match targeting criteria and have available
@@ -135,23 +412,19 @@ impl AdsService {
         &self,
         active_campaigns: Vec<domain::schemas::ActiveCampaignSchema>,
         client: &infrastructure::repository::sqlx_lib::ClientReturningSchema,
+        advanced_time: u32,
     ) -> domain::services::ServiceResult<Vec<domain::schemas::ActiveCampaignSchema>> {
         let filtered_campaigns = self
             .filter_targeted_campaigns(
                 active_campaigns,
-                client.age as u8,
+                client.age_u8(),
                 client.gender.clone(),
                 client.location.clone(),
+                client.attributes.clone(),
             )
             .await
             .into_iter()
-            .filter(|c| {
-                c.view_clients_id.len() <= (c.impressions_limit as f64 * 1.05).floor() as usize && !c.view_clients_id.contains(&client.client_id)
-                // && (
-                //     (c.view_clients_id.contains(&client.client_id) && !c.click_clients_id.contains(&client.client_id)) 
-                //     || !c.view_clients_id.contains(&client.client_id)
-                // )
-            })
+            .filter(|c| self.get_limit_exclusion(c, client.client_id, advanced_time).is_none())
             .collect::<Vec<_>>();
 
         if filtered_campaigns.is_empty() {
@@ -163,11 +436,88 @@ impl AdsService {
         Ok(filtered_campaigns)
     }
 
-    /// Scores campaigns based on multiple weighted factors
+    /// Checks whether the pacing controller throttles a campaign today
+    ///
+    /// # Arguments
+    /// * `campaign` - Campaign to check
+    /// * `advanced_time` - Current timestamp
+    fn is_throttled(&self, campaign: &domain::schemas::ActiveCampaignSchema, advanced_time: u32) -> bool {
+        self.pacing.as_ref().is_some_and(|pacing| {
+            pacing.calculate(campaign, advanced_time).status == domain::schemas::PacingStatus::Throttled
+        })
+    }
+
+    /// Returns the pacing score multiplier of a campaign
+    ///
+    /// # Arguments
+    /// * `campaign` - Campaign to calculate the multiplier for
+    /// * `advanced_time` - Current timestamp
+    fn pacing_multiplier(&self, campaign: &domain::schemas::ActiveCampaignSchema, advanced_time: u32) -> f64 {
+        self.pacing.as_ref().map_or(1.0, |pacing| {
+            pacing.multiplier(&pacing.calculate(campaign, advanced_time))
+        })
+    }
+
+    /// Checks that the client has not reached the frequency cap of a campaign
+    ///
+    /// Without a cap the client can see the campaign only once. With a cap the
+    /// impressions of the last `period` days (of the whole campaign when no
+    /// period is set) are counted.
+    ///
+    /// # Arguments
+    /// * `campaign` - Campaign to check
+    /// * `client_id` - UUID of the client
+    /// * `advanced_time` - Current timestamp
+    fn is_under_frequency_cap(
+        &self,
+        campaign: &domain::schemas::ActiveCampaignSchema,
+        client_id: uuid::Uuid,
+        advanced_time: u32,
+    ) -> bool {
+        let Some(client_views) = campaign.view_clients.get(&client_id) else {
+            return true;
+        };
+
+        let (impressions, since) = Self::frequency_cap_window(&campaign.frequency_cap, advanced_time);
+
+        client_views.range(since..).map(|(_, count)| count).sum::<u32>() < impressions
+    }
+
+    /// Returns the number of impressions allowed to one client and the first
+    /// day of the window they are counted in
+    ///
+    /// # Arguments
+    /// * `frequency_cap` - Frequency cap of the campaign
+    /// * `advanced_time` - Current timestamp
+    pub fn frequency_cap_window(
+        frequency_cap: &Option<domain::schemas::FrequencyCapSchema>,
+        advanced_time: u32,
+    ) -> (u32, u32) {
+        match frequency_cap {
+            Some(frequency_cap) => (
+                frequency_cap.impressions,
+                frequency_cap
+                    .period
+                    .map_or(0, |period| (advanced_time + 1).saturating_sub(period)),
+            ),
+            None => (1, 0),
+        }
+    }
+
+    /// Returns the number of impressions after which a campaign isn't shown,
+    /// 5% over its impressions limit
+    ///
+    /// # Arguments
+    /// * `campaign` - Campaign to check
+    pub fn max_views(campaign: &domain::schemas::ActiveCampaignSchema) -> u32 {
+        (campaign.impressions_limit as f64 * 1.05).floor() as u32
+    }
+
+    /// Scores campaigns according to the selected strategy
     ///
     /// # Arguments
     /// * `suitable_campaigns` - Pre-filtered list of suitable campaigns
-    /// * `client_id` - Target client UUID
+    /// * `client` - Target client
     /// * `advanced_time` - Current timestamp
     /// * `repo_score` - Repository for ML scores
     ///
@@ -176,68 +526,206 @@ impl AdsService {
     async fn score_campaigns<R>(
         &self,
         suitable_campaigns: Vec<domain::schemas::ActiveCampaignSchema>,
-        client_id: uuid::Uuid,
+        client: &infrastructure::repository::sqlx_lib::ClientReturningSchema,
         advanced_time: u32,
         repo_score: &R,
     ) -> domain::services::ServiceResult<Vec<(f64, u32, domain::schemas::ActiveCampaignSchema)>>
     where
         R: super::repository::IGetMlScores,
     {
-        let profits: Vec<f64> = suitable_campaigns
+        let breakdowns = self
+            .get_score_breakdowns(&suitable_campaigns, client, advanced_time, repo_score)
+            .await?;
+
+        let mut scored_campaigns: Vec<(f64, u32, domain::schemas::ActiveCampaignSchema)> = breakdowns
+            .into_iter()
+            .zip(suitable_campaigns)
+            .map(|(breakdown, campaign)| (breakdown.score, campaign.end_date, campaign))
+            .collect();
+        scored_campaigns.sort_by(Self::compare_scored);
+        if let Some(exploration) = &self.exploration {
+            exploration.explore(&mut scored_campaigns);
+        }
+
+        Ok(scored_campaigns)
+    }
+
+    /// Orders scored campaigns by score, best first, campaigns ending earlier
+    /// win ties
+    fn compare_scored(
+        (score_a, end_date_a, _): &(f64, u32, domain::schemas::ActiveCampaignSchema),
+        (score_b, end_date_b, _): &(f64, u32, domain::schemas::ActiveCampaignSchema),
+    ) -> std::cmp::Ordering {
+        score_b
+            .partial_cmp(score_a)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(end_date_a.cmp(end_date_b))
+    }
+
+    /// Calculates the score components of campaigns according to the selected
+    /// strategy
+    ///
+    /// With the `weighted` strategy the score is the weighted blend of
+    /// normalized profit, relevance, fulfillment and time left, campaigns
+    /// behind their pacing curve get a score bonus. With the `second_price`
+    /// strategy the score is the eCPM of the campaign.
+    ///
+    /// # Arguments
+    /// * `campaigns` - Suitable campaigns
+    /// * `client` - Target client
+    /// * `advanced_time` - Current timestamp
+    /// * `repo_score` - Repository for ML scores
+    ///
+    /// # Returns
+    /// * Score components of the campaigns in the same order
+    async fn get_score_breakdowns<R>(
+        &self,
+        campaigns: &[domain::schemas::ActiveCampaignSchema],
+        client: &infrastructure::repository::sqlx_lib::ClientReturningSchema,
+        advanced_time: u32,
+        repo_score: &R,
+    ) -> domain::services::ServiceResult<Vec<domain::schemas::ScoreBreakdownSchema>>
+    where
+        R: super::repository::IGetMlScores,
+    {
+        let advertisers_id: Vec<uuid::Uuid> = campaigns.iter().map(|campaign| campaign.advertiser_id).collect();
+        let scores = self.get_relevance_scores(client, advertisers_id, repo_score).await?;
+        let scores = self.blend_learned_ctr(campaigns, client, scores);
+
+        let profits: Vec<f64> = campaigns
             .par_iter()
             .map(|campaign| {
-                let remaining_impressions = campaign.impressions_limit as f64 - campaign.view_clients_id.len() as f64;
-                let remaining_clicks = campaign.clicks_limit as f64 - campaign.click_clients_id.len() as f64;
+                let remaining_impressions = campaign.impressions_limit as f64 - campaign.views_count as f64;
+                let remaining_clicks = campaign.clicks_limit as f64 - campaign.clicks_count as f64;
                 (remaining_impressions * campaign.cost_per_impression as f64)
                     + (remaining_clicks * campaign.cost_per_click as f64)
             })
             .collect();
 
         let (min_profit, max_profit) = self.calculate_min_max(&profits);
-        let advertisers_id: Vec<uuid::Uuid> = suitable_campaigns
+        let (min_score, max_score) = self.calculate_min_max(&scores);
+
+        Ok(campaigns
             .iter()
-            .map(|campaign| campaign.advertiser_id)
-            .collect();
+            .zip(profits.iter().zip(scores.iter()))
+            .map(|(campaign, (profit, score))| {
+                let profit = self.normalize_value(*profit, min_profit, max_profit);
+                let relevance = self.normalize_value(*score, min_score, max_score);
+                let fulfillment = self.calculate_fulfillment(campaign);
+                let time_left = self.calculate_time_left(campaign.end_date, advanced_time);
+                let pacing_multiplier = self.pacing_multiplier(campaign, advanced_time);
+
+                let combined_score = match self.strategy {
+                    AdsStrategy::Weighted => {
+                        (self.weight_profit * profit
+                            + self.weight_relevance * relevance
+                            + self.weight_fulfillment * fulfillment
+                            + self.weight_time_left * time_left)
+                            * pacing_multiplier
+                    },
+                    AdsStrategy::SecondPrice => self.calculate_ecpm(campaign, *score),
+                };
+
+                domain::schemas::ScoreBreakdownSchema {
+                    profit,
+                    relevance,
+                    fulfillment,
+                    time_left,
+                    pacing_multiplier,
+                    score: combined_score,
+                }
+            })
+            .collect())
+    }
+
+    /// Gets relevance scores of advertisers for a client
+    ///
+    /// Advertisers without an ML score for the client get the precomputed
+    /// cold-start score of [`domain::services::FallbackScoreService`].
+    ///
+    /// # Arguments
+    /// * `client` - Target client
+    /// * `advertisers_id` - Advertisers to score
+    /// * `repo_score` - Repository for ML scores
+    async fn get_relevance_scores<R>(
+        &self,
+        client: &infrastructure::repository::sqlx_lib::ClientReturningSchema,
+        advertisers_id: Vec<uuid::Uuid>,
+        repo_score: &R,
+    ) -> domain::services::ServiceResult<Vec<f64>>
+    where
+        R: super::repository::IGetMlScores,
+    {
         let scores = repo_score
-            .get_ml_scores(client_id, advertisers_id)
+            .get_ml_scores(client.client_id, advertisers_id.clone())
             .await
             .map_err(|e| domain::services::ServiceError::Repository(e.into()))?;
-        let (min_score, max_score) = self.calculate_min_max(&scores);
+        let segment = domain::services::CtrLearnerService::segment(client.age_u8(), &client.gender, &client.location);
 
-        let mut scored_campaigns = self
-            .calculate_scores(
-                suitable_campaigns,
-                &profits,
-                &scores,
-                min_profit,
-                max_profit,
-                min_score,
-                max_score,
-                advanced_time,
-            )
-            .await;
-        scored_campaigns.sort_by(|(score_a, end_date_a, _), (score_b, end_date_b, _)| {
-            score_b
-                .partial_cmp(score_a)
-                .unwrap_or(std::cmp::Ordering::Equal)
-                .then(end_date_a.cmp(end_date_b))
-        });
+        Ok(advertisers_id
+            .iter()
+            .zip(scores)
+            .map(|(advertiser_id, score)| {
+                score.unwrap_or_else(|| self.fallback_scores.score(advertiser_id, &segment))
+            })
+            .collect())
+    }
 
-        Ok(scored_campaigns)
+    /// Blends relevance scores of campaigns with the CTR learned for the
+    /// client segment, scores are returned as is without a CTR learner
+    ///
+    /// With the `thompson` exploration policy the CTR is sampled from the
+    /// posterior of the campaign instead of taking its mean.
+    ///
+    /// # Arguments
+    /// * `campaigns` - Scored campaigns
+    /// * `client` - Target client
+    /// * `scores` - Relevance scores of the campaigns in the same order
+    fn blend_learned_ctr(
+        &self,
+        campaigns: &[domain::schemas::ActiveCampaignSchema],
+        client: &infrastructure::repository::sqlx_lib::ClientReturningSchema,
+        scores: Vec<f64>,
+    ) -> Vec<f64> {
+        let Some(ctr_learner) = &self.ctr_learner else {
+            return scores;
+        };
+
+        let segment = domain::services::CtrLearnerService::segment(client.age_u8(), &client.gender, &client.location);
+        campaigns
+            .iter()
+            .zip(scores)
+            .map(|(campaign, score)| {
+                let ctr = match &self.exploration {
+                    Some(exploration) if exploration.policy() == domain::services::ExplorationPolicy::Thompson => {
+                        let (alpha, beta) =
+                            ctr_learner.posterior(&campaign.campaign_id, &campaign.advertiser_id, &segment);
+                        exploration.sample_beta(alpha, beta)
+                    },
+                    _ => ctr_learner.estimate(&campaign.campaign_id, &campaign.advertiser_id, &segment),
+                };
+                ctr_learner.blend(score, ctr)
+            })
+            .collect()
     }
 
-    /// Gets the highest scoring campaign from scored campaigns list
+    /// Gets the highest scoring campaigns from scored campaigns list
     ///
     /// # Arguments
-    /// * `scored_campaigns` - List of campaigns with their scores
-    async fn get_top_campaign<'a>(
+    /// * `scored_campaigns` - List of campaigns with their scores, best first
+    /// * `limit` - Maximum number of campaigns to take
+    async fn get_top_campaigns<'a>(
         &self,
         scored_campaigns: &'a [(f64, u32, domain::schemas::ActiveCampaignSchema)],
-    ) -> domain::services::ServiceResult<&'a domain::schemas::ActiveCampaignSchema> {
-        scored_campaigns
-            .get(0)
-            .map(|(_, _, campaign)| campaign)
-            .ok_or_else(|| domain::services::ServiceError::Validation("No top campaign found".into()))
+        limit: usize,
+    ) -> domain::services::ServiceResult<&'a [(f64, u32, domain::schemas::ActiveCampaignSchema)]> {
+        if scored_campaigns.is_empty() || limit == 0 {
+            return Err(domain::services::ServiceError::Validation(
+                "No top campaign found".into(),
+            ));
+        }
+
+        Ok(&scored_campaigns[..limit.min(scored_campaigns.len())])
     }
 
     /// Filters campaigns based on targeting criteria
@@ -247,26 +735,193 @@ impl AdsService {
     /// * `age` - Client age for targeting
     /// * `gender` - Client gender for targeting
     /// * `location` - Client location for targeting
+    /// * `attributes` - Custom client attributes for targeting expressions
     async fn filter_targeted_campaigns(
         &self,
         active_campaigns: Vec<domain::schemas::ActiveCampaignSchema>,
         age: u8,
         gender: String,
         location: String,
+        attributes: serde_json::Value,
     ) -> Vec<domain::schemas::ActiveCampaignSchema> {
+        let fields = Self::targeting_fields(age, &gender, &location, attributes);
+
         active_campaigns
             .into_iter()
             .filter(|c| {
-                (c.targeting.location == Some(location.clone()) || c.targeting.location.is_none())
-                    && (c.targeting.gender == Some(gender.clone())
-                        || c.targeting.gender == Some("ALL".to_string())
-                        || c.targeting.gender.is_none())
-                    && (c.targeting.age_from <= Some(age) || c.targeting.age_from.is_none())
-                    && (c.targeting.age_to >= Some(age) || c.targeting.age_to.is_none())
+                self.get_targeting_exclusion(c, age, &gender, &location, &fields)
+                    .is_none()
             })
             .collect()
     }
 
+    /// Collects client fields and attributes available in targeting
+    /// expressions
+    ///
+    /// # Arguments
+    /// * `age` - Client age
+    /// * `gender` - Client gender
+    /// * `location` - Client location
+    /// * `attributes` - Custom client attributes
+    fn targeting_fields(
+        age: u8,
+        gender: &str,
+        location: &str,
+        attributes: serde_json::Value,
+    ) -> serde_json::Map<String, serde_json::Value> {
+        let mut fields = match attributes {
+            serde_json::Value::Object(attributes) => attributes,
+            _ => serde_json::Map::new(),
+        };
+        fields.insert("age".into(), age.into());
+        fields.insert("gender".into(), gender.into());
+        fields.insert("location".into(), location.into());
+        fields
+    }
+
+    /// Finds the targeting rule that excludes a client
+    ///
+    /// # Arguments
+    /// * `campaign` - Campaign to check
+    /// * `age` - Client age
+    /// * `gender` - Client gender
+    /// * `location` - Client location
+    /// * `fields` - Client fields and attributes by name
+    ///
+    /// # Returns
+    /// * The first failed rule, `None` if the client is targeted
+    fn get_targeting_exclusion(
+        &self,
+        campaign: &domain::schemas::ActiveCampaignSchema,
+        age: u8,
+        gender: &str,
+        location: &str,
+        fields: &serde_json::Map<String, serde_json::Value>,
+    ) -> Option<domain::schemas::ExclusionReason> {
+        let targeting = &campaign.targeting;
+        if !self.is_targeted_gender(targeting, gender) {
+            Some(domain::schemas::ExclusionReason::Gender)
+        } else if !self.is_targeted_location(targeting, location) {
+            Some(domain::schemas::ExclusionReason::Location)
+        } else if targeting.age_from.is_some_and(|age_from| age_from > age)
+            || targeting.age_to.is_some_and(|age_to| age_to < age)
+        {
+            Some(domain::schemas::ExclusionReason::Age)
+        } else if !self.is_targeted_expression(campaign, fields) {
+            Some(domain::schemas::ExclusionReason::Expression)
+        } else {
+            None
+        }
+    }
+
+    /// Finds the campaign limit that doesn't allow to show it to a client
+    ///
+    /// # Arguments
+    /// * `campaign` - Campaign to check
+    /// * `client_id` - UUID of the client
+    /// * `advanced_time` - Current timestamp
+    ///
+    /// # Returns
+    /// * The first exceeded limit, `None` if the campaign can be shown
+    fn get_limit_exclusion(
+        &self,
+        campaign: &domain::schemas::ActiveCampaignSchema,
+        client_id: uuid::Uuid,
+        advanced_time: u32,
+    ) -> Option<domain::schemas::ExclusionReason> {
+        if campaign.views_count > Self::max_views(campaign) {
+            Some(domain::schemas::ExclusionReason::ImpressionsLimit)
+        } else if domain::services::BudgetService.is_exhausted(campaign) {
+            Some(domain::schemas::ExclusionReason::Budget)
+        } else if self.is_throttled(campaign, advanced_time) {
+            Some(domain::schemas::ExclusionReason::Pacing)
+        } else if !self.is_under_frequency_cap(campaign, client_id, advanced_time) {
+            Some(domain::schemas::ExclusionReason::FrequencyCap)
+        } else {
+            None
+        }
+    }
+
+    /// Checks that the client matches the targeting expression
+    ///
+    /// Uses the expression parsed on campaign activation. Campaigns stored
+    /// before it was kept parse the source text. Expressions are validated on
+    /// campaign creation, an expression that can't be parsed anyway matches
+    /// nobody.
+    ///
+    /// # Arguments
+    /// * `campaign` - Campaign to check
+    /// * `fields` - Client fields and attributes by name
+    fn is_targeted_expression(
+        &self,
+        campaign: &domain::schemas::ActiveCampaignSchema,
+        fields: &serde_json::Map<String, serde_json::Value>,
+    ) -> bool {
+        match (&campaign.targeting_expression, &campaign.targeting.expression) {
+            (Some(expression), _) => expression.evaluate(fields),
+            (None, Some(expression)) => domain::services::TargetingExpression::parse(expression)
+                .is_ok_and(|expression| expression.evaluate(fields)),
+            (None, None) => true,
+        }
+    }
+
+    /// Checks that the client gender is one of the targeted genders
+    ///
+    /// # Arguments
+    /// * `targeting` - Campaign targeting
+    /// * `gender` - Client gender
+    fn is_targeted_gender(&self, targeting: &domain::schemas::TargetingCampaignSchema, gender: &str) -> bool {
+        let mut genders = targeting
+            .gender
+            .iter()
+            .chain(targeting.genders.iter().flatten())
+            .peekable();
+
+        genders.peek().is_none() || genders.any(|g| g == "ALL" || g.eq_ignore_ascii_case(gender))
+    }
+
+    /// Checks that the client location is under one of the included
+    /// locations and not under any of the excluded ones
+    ///
+    /// # Arguments
+    /// * `targeting` - Campaign targeting
+    /// * `location` - Client location
+    fn is_targeted_location(&self, targeting: &domain::schemas::TargetingCampaignSchema, location: &str) -> bool {
+        let mut included = targeting
+            .location
+            .iter()
+            .chain(targeting.locations.iter().flatten())
+            .peekable();
+
+        (included.peek().is_none() || included.any(|l| Self::is_sublocation(location, l)))
+            && !targeting
+                .exclude_locations
+                .iter()
+                .flatten()
+                .any(|l| Self::is_sublocation(location, l))
+    }
+
+    /// Checks that a location lies inside a parent location
+    ///
+    /// Locations are compared by comma separated parts, case insensitive:
+    /// `Moscow, mcad` lies inside `Moscow` and inside itself, but not inside
+    /// `Moscow, zelenograd`.
+    ///
+    /// # Arguments
+    /// * `location` - Location to check
+    /// * `parent` - Parent location
+    pub fn is_sublocation(location: &str, parent: &str) -> bool {
+        let split = |l: &str| {
+            l.split(',')
+                .map(|part| part.trim().to_lowercase())
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<String>>()
+        };
+        let parent = split(parent);
+
+        !parent.is_empty() && split(location).starts_with(&parent)
+    }
+
     /// Calculates minimum and maximum values from a slice of f64
     ///
     /// # Arguments
@@ -280,44 +935,13 @@ impl AdsService {
         (min, max)
     }
 
-    /// Calculates final scores for campaigns considering all weighted factors
+    /// Calculates eCPM of a campaign for the `second_price` strategy
     ///
     /// # Arguments
-    /// * `campaigns` - List of campaigns to score
-    /// * `profits` - Pre-calculated profit values
-    /// * `scores` - ML relevance scores
-    /// * `min_profit` - Minimum profit value for normalization
-    /// * `max_profit` - Maximum profit value for normalization
-    /// * `min_score` - Minimum ML score for normalization
-    /// * `max_score` - Maximum ML score for normalization
-    /// * `advanced_time` - Current timestamp
-    async fn calculate_scores(
-        &self,
-        campaigns: Vec<domain::schemas::ActiveCampaignSchema>,
-        profits: &[f64],
-        scores: &[f64],
-        min_profit: f64,
-        max_profit: f64,
-        min_score: f64,
-        max_score: f64,
-        advanced_time: u32,
-    ) -> Vec<(f64, u32, domain::schemas::ActiveCampaignSchema)> {
-        futures::future::join_all(campaigns.into_iter().enumerate().map(|(i, campaign)| {
-            let profit = profits[i];
-            let score = scores[i];
-            async move {
-                let normalized_profit = self.normalize_value(profit, min_profit, max_profit);
-                let normalized_relevance = self.normalize_value(score, min_score, max_score);
-                let fulfillment = self.calculate_fulfillment(&campaign);
-                let normalized_time_left = self.calculate_time_left(campaign.end_date, advanced_time);
-                let combined_score: f64 = self.weight_profit * normalized_profit
-                    + self.weight_relevance * normalized_relevance
-                    + self.weight_fulfillment * fulfillment
-                    + self.weight_time_left * normalized_time_left;
-                (combined_score, campaign.end_date, campaign)
-            }
-        }))
-        .await
+    /// * `campaign` - Campaign to score
+    /// * `score` - ML relevance score, used as predicted CTR
+    fn calculate_ecpm(&self, campaign: &domain::schemas::ActiveCampaignSchema, score: f64) -> f64 {
+        campaign.cost_per_impression as f64 + campaign.cost_per_click as f64 * score.clamp(0.0, 1.0)
     }
 
     /// Normalizes a value using natural logarithm scaling
@@ -339,8 +963,8 @@ impl AdsService {
     /// # Arguments
     /// * `campaign` - Campaign to calculate fulfillment for
     fn calculate_fulfillment(&self, campaign: &domain::schemas::ActiveCampaignSchema) -> f64 {
-        let remaining_impressions = campaign.impressions_limit as f64 - campaign.view_clients_id.len() as f64;
-        let remaining_clicks = campaign.clicks_limit as f64 - campaign.click_clients_id.len() as f64;
+        let remaining_impressions = campaign.impressions_limit as f64 - campaign.views_count as f64;
+        let remaining_clicks = campaign.clicks_limit as f64 - campaign.clicks_count as f64;
         (remaining_impressions / campaign.impressions_limit as f64) + (remaining_clicks / campaign.clicks_limit as f64)
     }
 
@@ -362,7 +986,28 @@ mod tests {
     use uuid::Uuid;
 
     use super::*;
-    use crate::domain::services::repository::IGetClientById;
+
+    impl AdsService {
+        /// Loads the client and ranks the campaigns for it, as `/ads` does
+        async fn recommendation_ads_by_id<R1, R2>(
+            &self,
+            active_campaigns: Vec<domain::schemas::ActiveCampaignSchema>,
+            client_id: Uuid,
+            advanced_time: u32,
+            limit: usize,
+            repo_client: R1,
+            repo_score: R2,
+        ) -> domain::services::ServiceResult<Vec<domain::schemas::RankedAdSchema>>
+        where
+            R1: domain::services::repository::IGetClientById,
+            R2: domain::services::repository::IGetMlScores,
+        {
+            let client = self.get_client(repo_client, client_id).await?;
+            self.recommendation_ads(active_campaigns, &client, advanced_time, limit, repo_score)
+                .await
+        }
+    }
+    use crate::domain::services::repository::IGetClientById;
 
     mock! {
         pub ClientRepo {}
@@ -376,34 +1021,28 @@ mod tests {
         pub MlScoreRepo {}
         #[async_trait]
         impl IGetMlScores for MlScoreRepo {
-            async fn get_ml_scores(&self, client_id: Uuid, advertisers_id: Vec<Uuid>) -> infrastructure::repository::RepoResult<Vec<f64>>;
+            async fn get_ml_scores(&self, client_id: Uuid, advertisers_id: Vec<Uuid>) -> infrastructure::repository::RepoResult<Vec<Option<f64>>>;
         }
     }
 
     fn create_test_service() -> AdsService {
-        AdsService::new(0.4, 0.3, 0.2, 0.1)
+        AdsService::new(0.4, 0.3, 0.2, 0.1).with_fallback_scores(
+            domain::services::FallbackScoreService::new().with_scores(std::sync::Arc::default()),
+        )
     }
 
     fn create_test_campaign(id: Uuid, advertiser_id: Uuid) -> domain::schemas::ActiveCampaignSchema {
         domain::schemas::ActiveCampaignSchema {
             campaign_id: id,
             advertiser_id,
-            ad_title: "Test Ad".into(),
-            ad_text: "Test Content".into(),
-            cost_per_impression: 1.,
-            cost_per_click: 2.,
-            impressions_limit: 100,
-            clicks_limit: 50,
-            start_date: 0,
-            end_date: 100,
             targeting: domain::schemas::TargetingCampaignSchema {
                 age_from: Some(18),
                 age_to: Some(35),
                 gender: Some("Male".into()),
                 location: Some("NY".into()),
+                ..Default::default()
             },
-            view_clients_id: vec![],
-            click_clients_id: vec![],
+            ..domain::schemas::ActiveCampaignSchema::test_fixture()
         }
     }
 
@@ -421,25 +1060,29 @@ mod tests {
                 age: 25,
                 gender: "Male".into(),
                 location: "NY".into(),
+                attributes: serde_json::json!({}),
             })
         });
 
         let mut mock_ml_repo = MockMlScoreRepo::new();
-        mock_ml_repo.expect_get_ml_scores().returning(|_, _| Ok(vec![0.8]));
+        mock_ml_repo
+            .expect_get_ml_scores()
+            .returning(|_, _| Ok(vec![Some(0.8)]));
 
         let service = create_test_service();
         let result = service
-            .recommendation_ads(
+            .recommendation_ads_by_id(
                 vec![create_test_campaign(campaign_id, advertiser_id)],
                 client_id,
                 50,
+                1,
                 mock_client_repo,
                 mock_ml_repo,
             )
             .await;
 
         assert!(result.is_ok());
-        let ad = result.unwrap();
+        let ad = result.unwrap().remove(0);
         assert_eq!(ad.ad_id, campaign_id);
     }
 
@@ -455,15 +1098,17 @@ mod tests {
                 age: 40,
                 gender: "Female".into(),
                 location: "LA".into(),
+                attributes: serde_json::json!({}),
             })
         });
 
         let service = create_test_service();
         let result = service
-            .recommendation_ads(
+            .recommendation_ads_by_id(
                 vec![create_test_campaign(Uuid::new_v4(), Uuid::new_v4())],
                 client_id,
                 50,
+                1,
                 mock_client_repo,
                 MockMlScoreRepo::new(),
             )
@@ -486,6 +1131,7 @@ mod tests {
                 age: 25,
                 gender: "Male".into(),
                 location: "NY".into(),
+                attributes: serde_json::json!({}),
             })
         });
 
@@ -496,10 +1142,11 @@ mod tests {
 
         let service = create_test_service();
         let result = service
-            .recommendation_ads(
+            .recommendation_ads_by_id(
                 vec![create_test_campaign(Uuid::new_v4(), Uuid::new_v4())],
                 client_id,
                 50,
+                1,
                 mock_client_repo,
                 mock_ml_repo,
             )
@@ -522,11 +1169,14 @@ mod tests {
                 age: 25,
                 gender: "Male".into(),
                 location: "NY".into(),
+                attributes: serde_json::json!({}),
             })
         });
 
         let mut mock_ml_repo = MockMlScoreRepo::new();
-        mock_ml_repo.expect_get_ml_scores().returning(|_, _| Ok(vec![0.5, 0.5]));
+        mock_ml_repo
+            .expect_get_ml_scores()
+            .returning(|_, _| Ok(vec![Some(0.5), Some(0.5)]));
 
         let service = AdsService::new(1.0, 0.0, 0.0, 0.0);
         let mut campaign1 = create_test_campaign(campaign1_id, Uuid::new_v4());
@@ -536,17 +1186,18 @@ mod tests {
         campaign2.cost_per_impression = 20.;
 
         let result = service
-            .recommendation_ads(
+            .recommendation_ads_by_id(
                 vec![campaign1, campaign2],
                 client_id,
                 50,
+                1,
                 mock_client_repo,
                 mock_ml_repo,
             )
             .await;
 
         assert!(result.is_ok());
-        assert_eq!(result.unwrap().ad_id, campaign2_id);
+        assert_eq!(result.unwrap()[0].ad_id, campaign2_id);
     }
 
     #[tokio::test]
@@ -563,11 +1214,14 @@ mod tests {
                 age: 25,
                 gender: "Male".into(),
                 location: "NY".into(),
+                attributes: serde_json::json!({}),
             })
         });
 
         let mut mock_ml_repo = MockMlScoreRepo::new();
-        mock_ml_repo.expect_get_ml_scores().returning(|_, _| Ok(vec![0.5, 0.5]));
+        mock_ml_repo
+            .expect_get_ml_scores()
+            .returning(|_, _| Ok(vec![Some(0.5), Some(0.5)]));
 
         let service = AdsService::new(0.0, 0.0, 0.0, 1.0);
         let mut campaign1 = create_test_campaign(campaign1_id, Uuid::new_v4());
@@ -577,17 +1231,657 @@ mod tests {
         campaign2.end_date = 200;
 
         let result = service
-            .recommendation_ads(
+            .recommendation_ads_by_id(
                 vec![campaign1, campaign2],
                 client_id,
                 50,
+                1,
                 mock_client_repo,
                 mock_ml_repo,
             )
             .await;
 
         assert!(result.is_ok());
-        assert_eq!(result.unwrap().ad_id, campaign1_id);
+        assert_eq!(result.unwrap()[0].ad_id, campaign1_id);
+    }
+
+    #[tokio::test]
+    async fn recommendation_ads_returns_ordered_list_within_limit() {
+        let client_id = Uuid::new_v4();
+        let campaign_ids = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+
+        let mut mock_client_repo = MockClientRepo::new();
+        mock_client_repo.expect_get_by_id().returning(move |_| {
+            Ok(infrastructure::repository::sqlx_lib::ClientReturningSchema {
+                client_id,
+                login: "my_name".into(),
+                age: 25,
+                gender: "Male".into(),
+                location: "NY".into(),
+                attributes: serde_json::json!({}),
+            })
+        });
+
+        let mut mock_ml_repo = MockMlScoreRepo::new();
+        mock_ml_repo
+            .expect_get_ml_scores()
+            .returning(|_, _| Ok(vec![Some(0.5), Some(0.5), Some(0.5)]));
+
+        let service = AdsService::new(1.0, 0.0, 0.0, 0.0);
+        let campaigns = campaign_ids
+            .iter()
+            .zip([10., 30., 20.])
+            .map(|(id, cost)| {
+                let mut campaign = create_test_campaign(*id, Uuid::new_v4());
+                campaign.cost_per_impression = cost;
+                campaign
+            })
+            .collect();
+
+        let result = service
+            .recommendation_ads_by_id(campaigns, client_id, 50, 2, mock_client_repo, mock_ml_repo)
+            .await
+            .unwrap();
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].ad_id, campaign_ids[1]);
+        assert_eq!(result[1].ad_id, campaign_ids[2]);
+        assert!(result[0].score >= result[1].score);
+    }
+
+    #[tokio::test]
+    async fn explain_ads_lists_exclusions_and_score_components() {
+        let client_id = Uuid::new_v4();
+
+        let mut mock_client_repo = MockClientRepo::new();
+        mock_client_repo.expect_get_by_id().returning(move |_| {
+            Ok(infrastructure::repository::sqlx_lib::ClientReturningSchema {
+                client_id,
+                login: "my_name".into(),
+                age: 25,
+                gender: "Male".into(),
+                location: "NY".into(),
+                attributes: serde_json::json!({}),
+            })
+        });
+
+        let mut mock_ml_repo = MockMlScoreRepo::new();
+        mock_ml_repo
+            .expect_get_ml_scores()
+            .returning(|_, _| Ok(vec![Some(0.5), Some(0.5)]));
+
+        let mut cheap = create_test_campaign(Uuid::new_v4(), Uuid::new_v4());
+        cheap.cost_per_impression = 10.;
+        let mut expensive = create_test_campaign(Uuid::new_v4(), Uuid::new_v4());
+        expensive.cost_per_impression = 30.;
+        let mut female = create_test_campaign(Uuid::new_v4(), Uuid::new_v4());
+        female.targeting.gender = Some("Female".into());
+        let mut old = create_test_campaign(Uuid::new_v4(), Uuid::new_v4());
+        old.targeting.age_from = Some(30);
+        let mut exhausted = create_test_campaign(Uuid::new_v4(), Uuid::new_v4());
+        exhausted.views_count = 200;
+
+        let service = AdsService::new(1.0, 0.0, 0.0, 0.0);
+        let result = service
+            .explain_ads(
+                vec![
+                    cheap.clone(),
+                    female.clone(),
+                    expensive.clone(),
+                    old.clone(),
+                    exhausted.clone(),
+                ],
+                client_id,
+                50,
+                mock_client_repo,
+                mock_ml_repo,
+            )
+            .await
+            .unwrap();
+
+        let decisions: Vec<(Uuid, Option<domain::schemas::ExclusionReason>, Option<usize>)> = result
+            .iter()
+            .map(|explanation| (explanation.campaign_id, explanation.excluded_by, explanation.rank))
+            .collect();
+        assert_eq!(
+            decisions,
+            vec![
+                (expensive.campaign_id, None, Some(1)),
+                (cheap.campaign_id, None, Some(2)),
+                (female.campaign_id, Some(domain::schemas::ExclusionReason::Gender), None),
+                (old.campaign_id, Some(domain::schemas::ExclusionReason::Age), None),
+                (
+                    exhausted.campaign_id,
+                    Some(domain::schemas::ExclusionReason::ImpressionsLimit),
+                    None
+                ),
+            ]
+        );
+
+        let breakdown = result[0].breakdown.as_ref().unwrap();
+        assert_eq!(breakdown.profit, 1.0);
+        assert_eq!(breakdown.score, 1.0);
+        assert_eq!(result[1].breakdown.as_ref().unwrap().profit, 0.0);
+        assert_eq!(result[0].price, Some(30.));
+        assert!(result[2].breakdown.is_none());
+    }
+
+    #[tokio::test]
+    async fn epsilon_greedy_exploration_is_reproducible_with_seed() {
+        let client_id = Uuid::new_v4();
+        let campaigns: Vec<domain::schemas::ActiveCampaignSchema> = [10., 30., 20.]
+            .into_iter()
+            .map(|cost| {
+                let mut campaign = create_test_campaign(Uuid::new_v4(), Uuid::new_v4());
+                campaign.cost_per_impression = cost;
+                campaign
+            })
+            .collect();
+
+        async fn winners(
+            service: &AdsService,
+            campaigns: &[domain::schemas::ActiveCampaignSchema],
+            client_id: Uuid,
+        ) -> Vec<Uuid> {
+            let mut result = Vec::new();
+            for _ in 0..20 {
+                let mut mock_client_repo = MockClientRepo::new();
+                mock_client_repo.expect_get_by_id().returning(move |_| {
+                    Ok(infrastructure::repository::sqlx_lib::ClientReturningSchema {
+                        client_id,
+                        login: "my_name".into(),
+                        age: 25,
+                        gender: "Male".into(),
+                        location: "NY".into(),
+                        attributes: serde_json::json!({}),
+                    })
+                });
+                let mut mock_ml_repo = MockMlScoreRepo::new();
+                mock_ml_repo
+                    .expect_get_ml_scores()
+                    .returning(|_, _| Ok(vec![Some(0.5), Some(0.5), Some(0.5)]));
+
+                let ads = service
+                    .recommendation_ads_by_id(campaigns.to_vec(), client_id, 50, 1, mock_client_repo, mock_ml_repo)
+                    .await
+                    .unwrap();
+                result.push(ads[0].ad_id);
+            }
+            result
+        }
+
+        let create_service = || {
+            AdsService::new(1.0, 0.0, 0.0, 0.0).with_exploration(Some(domain::services::ExplorationService::new(
+                domain::services::ExplorationPolicy::EpsilonGreedy,
+                0.5,
+                domain::services::ExplorationService::create_rng(Some(42)),
+            )))
+        };
+
+        let first = winners(&create_service(), &campaigns, client_id).await;
+        let second = winners(&create_service(), &campaigns, client_id).await;
+
+        assert_eq!(first, second);
+        assert!(first.contains(&campaigns[1].campaign_id));
+        assert!(first.iter().any(|id| *id != campaigns[1].campaign_id));
+    }
+
+    #[tokio::test]
+    async fn second_price_ranks_by_ecpm_and_charges_next_bid() {
+        let client_id = Uuid::new_v4();
+        let campaign_ids = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+
+        let mut mock_client_repo = MockClientRepo::new();
+        mock_client_repo.expect_get_by_id().returning(move |_| {
+            Ok(infrastructure::repository::sqlx_lib::ClientReturningSchema {
+                client_id,
+                login: "my_name".into(),
+                age: 25,
+                gender: "Male".into(),
+                location: "NY".into(),
+                attributes: serde_json::json!({}),
+            })
+        });
+
+        let mut mock_ml_repo = MockMlScoreRepo::new();
+        mock_ml_repo
+            .expect_get_ml_scores()
+            .returning(|_, _| Ok(vec![Some(0.125), Some(0.5), Some(0.125)]));
+
+        let service = create_test_service().with_strategy(AdsStrategy::SecondPrice);
+        let campaigns = campaign_ids
+            .iter()
+            .zip([(5., 8.), (1., 8.), (2., 8.)])
+            .map(|(id, (cost_per_impression, cost_per_click))| {
+                let mut campaign = create_test_campaign(*id, Uuid::new_v4());
+                campaign.cost_per_impression = cost_per_impression;
+                campaign.cost_per_click = cost_per_click;
+                campaign
+            })
+            .collect();
+
+        let result = service
+            .recommendation_ads_by_id(campaigns, client_id, 50, 3, mock_client_repo, mock_ml_repo)
+            .await
+            .unwrap();
+
+        let ids: Vec<Uuid> = result.iter().map(|ad| ad.ad_id).collect();
+        assert_eq!(ids, vec![campaign_ids[0], campaign_ids[1], campaign_ids[2]]);
+        assert_eq!(result[0].score, 6.);
+        assert_eq!(result[0].price, 4.);
+        assert_eq!(result[1].price, 0.);
+        assert_eq!(result[2].price, 2.);
+    }
+
+    #[tokio::test]
+    async fn second_price_bills_click_value_once() {
+        let client_id = Uuid::new_v4();
+
+        let mut mock_client_repo = MockClientRepo::new();
+        mock_client_repo.expect_get_by_id().returning(move |_| {
+            Ok(infrastructure::repository::sqlx_lib::ClientReturningSchema {
+                client_id,
+                login: "my_name".into(),
+                age: 25,
+                gender: "Male".into(),
+                location: "NY".into(),
+                attributes: serde_json::json!({}),
+            })
+        });
+
+        let mut mock_ml_repo = MockMlScoreRepo::new();
+        mock_ml_repo
+            .expect_get_ml_scores()
+            .returning(|_, _| Ok(vec![Some(0.25), Some(0.25)]));
+
+        let service = create_test_service().with_strategy(AdsStrategy::SecondPrice);
+        let campaigns = [(6., 8.), (3., 8.)]
+            .into_iter()
+            .map(|(cost_per_impression, cost_per_click)| {
+                let mut campaign = create_test_campaign(Uuid::new_v4(), Uuid::new_v4());
+                campaign.cost_per_impression = cost_per_impression;
+                campaign.cost_per_click = cost_per_click;
+                campaign
+            })
+            .collect();
+
+        let result = service
+            .recommendation_ads_by_id(campaigns, client_id, 50, 1, mock_client_repo, mock_ml_repo)
+            .await
+            .unwrap();
+
+        // eCPM 8 wins against eCPM 5, the expected click value 8 * 0.25 is
+        // billed on click and taken off the impression price
+        let winner = &result[0];
+        assert_eq!(winner.score, 8.);
+        assert_eq!(winner.price, 3.);
+    }
+
+    #[tokio::test]
+    async fn second_price_single_campaign_pays_cost_per_impression() {
+        let client_id = Uuid::new_v4();
+
+        let mut mock_client_repo = MockClientRepo::new();
+        mock_client_repo.expect_get_by_id().returning(move |_| {
+            Ok(infrastructure::repository::sqlx_lib::ClientReturningSchema {
+                client_id,
+                login: "my_name".into(),
+                age: 25,
+                gender: "Male".into(),
+                location: "NY".into(),
+                attributes: serde_json::json!({}),
+            })
+        });
+
+        let mut mock_ml_repo = MockMlScoreRepo::new();
+        mock_ml_repo
+            .expect_get_ml_scores()
+            .returning(|_, _| Ok(vec![Some(0.9)]));
+
+        let service = create_test_service().with_strategy(AdsStrategy::SecondPrice);
+        let result = service
+            .recommendation_ads_by_id(
+                vec![create_test_campaign(Uuid::new_v4(), Uuid::new_v4())],
+                client_id,
+                50,
+                1,
+                mock_client_repo,
+                mock_ml_repo,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result[0].price, 1.);
+    }
+
+    #[tokio::test]
+    async fn ctr_learner_blends_learned_ctr_into_ml_score() {
+        let client_id = Uuid::new_v4();
+
+        let mut mock_client_repo = MockClientRepo::new();
+        mock_client_repo.expect_get_by_id().returning(move |_| {
+            Ok(infrastructure::repository::sqlx_lib::ClientReturningSchema {
+                client_id,
+                login: "my_name".into(),
+                age: 25,
+                gender: "Male".into(),
+                location: "NY".into(),
+                attributes: serde_json::json!({}),
+            })
+        });
+
+        let mut mock_ml_repo = MockMlScoreRepo::new();
+        mock_ml_repo
+            .expect_get_ml_scores()
+            .returning(|_, _| Ok(vec![Some(0.9)]));
+
+        let service = create_test_service()
+            .with_strategy(AdsStrategy::SecondPrice)
+            .with_ctr_learner(Some(
+                domain::services::CtrLearnerService::new(1.0, 9.0, 0.5).with_estimates(std::sync::Arc::default()),
+            ));
+        let result = service
+            .recommendation_ads_by_id(
+                vec![create_test_campaign(Uuid::new_v4(), Uuid::new_v4())],
+                client_id,
+                50,
+                1,
+                mock_client_repo,
+                mock_ml_repo,
+            )
+            .await
+            .unwrap();
+
+        // Nothing is learned for a new campaign: 0.5 * 0.9 + 0.5 * 0.1 (prior)
+        assert!((result[0].score - 2.0).abs() < 1e-9);
+    }
+
+    struct FallbackStatisticsRepo {
+        advertiser_id: Uuid,
+    }
+
+    #[async_trait]
+    impl domain::services::repository::IGetFallbackScoreStatistics for FallbackStatisticsRepo {
+        async fn get_segment_scores(
+            &self,
+        ) -> infrastructure::repository::RepoResult<
+            Vec<infrastructure::repository::sqlx_lib::SegmentScoreReturningSchema>,
+        > {
+            Ok(vec![
+                infrastructure::repository::sqlx_lib::SegmentScoreReturningSchema {
+                    advertiser_id: self.advertiser_id,
+                    age_bucket: 2,
+                    gender: "MALE".into(),
+                    region: "ny".into(),
+                    score: 0.6,
+                },
+            ])
+        }
+
+        async fn get_advertiser_scores(
+            &self,
+        ) -> infrastructure::repository::RepoResult<
+            Vec<infrastructure::repository::sqlx_lib::AdvertiserScoreReturningSchema>,
+        > {
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn missing_ml_scores_fall_back_to_precomputed_scores() {
+        let advertisers_id: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        let fallback_scores = domain::services::FallbackScoreService::new().with_scores(std::sync::Arc::default());
+        fallback_scores
+            .refresh(FallbackStatisticsRepo {
+                advertiser_id: advertisers_id[1],
+            })
+            .await
+            .unwrap();
+
+        let mut mock_ml_repo = MockMlScoreRepo::new();
+        mock_ml_repo
+            .expect_get_ml_scores()
+            .returning(|_, _| Ok(vec![Some(0.9), None, None]));
+
+        let client = infrastructure::repository::sqlx_lib::ClientReturningSchema {
+            client_id: Uuid::new_v4(),
+            login: "my_name".into(),
+            age: 25,
+            gender: "Male".into(),
+            location: "NY".into(),
+            attributes: serde_json::json!({}),
+        };
+        let service = create_test_service().with_fallback_scores(fallback_scores);
+        let scores = service
+            .get_relevance_scores(&client, advertisers_id, &mock_ml_repo)
+            .await
+            .unwrap();
+
+        assert_eq!(scores, vec![0.9, 0.6, 0.0]);
+    }
+
+    #[tokio::test]
+    async fn viewed_campaign_excluded_without_frequency_cap() {
+        let client_id = Uuid::new_v4();
+
+        let mut mock_client_repo = MockClientRepo::new();
+        mock_client_repo.expect_get_by_id().returning(move |_| {
+            Ok(infrastructure::repository::sqlx_lib::ClientReturningSchema {
+                client_id,
+                login: "my_name".into(),
+                age: 25,
+                gender: "Male".into(),
+                location: "NY".into(),
+                attributes: serde_json::json!({}),
+            })
+        });
+
+        let mut campaign = create_test_campaign(Uuid::new_v4(), Uuid::new_v4());
+        campaign.views_count = 1;
+        campaign.view_clients.insert(client_id, [(10, 1)].into());
+
+        let service = create_test_service();
+        let result = service
+            .recommendation_ads_by_id(
+                vec![campaign],
+                client_id,
+                50,
+                1,
+                mock_client_repo,
+                MockMlScoreRepo::new(),
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ServiceError::Repository(RepoError::ObjDoesNotExists(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn pacing_throttles_campaign_ahead_of_curve() {
+        let client_id = Uuid::new_v4();
+
+        let mut mock_client_repo = MockClientRepo::new();
+        mock_client_repo.expect_get_by_id().returning(move |_| {
+            Ok(infrastructure::repository::sqlx_lib::ClientReturningSchema {
+                client_id,
+                login: "my_name".into(),
+                age: 25,
+                gender: "Male".into(),
+                location: "NY".into(),
+                attributes: serde_json::json!({}),
+            })
+        });
+
+        let mut campaign = create_test_campaign(Uuid::new_v4(), Uuid::new_v4());
+        campaign.views_count = 60;
+
+        let service = create_test_service().with_pacing(Some(domain::services::PacingService::new(1.0, 0.5)));
+        let result = service
+            .recommendation_ads_by_id(
+                vec![campaign],
+                client_id,
+                50,
+                1,
+                mock_client_repo,
+                MockMlScoreRepo::new(),
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ServiceError::Repository(RepoError::ObjDoesNotExists(_)))
+        ));
+    }
+
+    #[test]
+    fn frequency_cap_counts_impressions_within_period() {
+        let client_id = Uuid::new_v4();
+        let service = create_test_service();
+
+        let mut campaign = create_test_campaign(Uuid::new_v4(), Uuid::new_v4());
+        campaign.frequency_cap = Some(domain::schemas::FrequencyCapSchema {
+            impressions: 2,
+            period: Some(3),
+        });
+        campaign.view_clients.insert(client_id, [(46, 2), (49, 1)].into());
+
+        assert!(service.is_under_frequency_cap(&campaign, Uuid::new_v4(), 50));
+        assert!(service.is_under_frequency_cap(&campaign, client_id, 50));
+
+        campaign
+            .view_clients
+            .insert(client_id, [(46, 2), (48, 1), (50, 1)].into());
+        assert!(!service.is_under_frequency_cap(&campaign, client_id, 50));
+        assert!(service.is_under_frequency_cap(&campaign, client_id, 51));
+
+        campaign.frequency_cap = Some(domain::schemas::FrequencyCapSchema {
+            impressions: 4,
+            period: None,
+        });
+        assert!(!service.is_under_frequency_cap(&campaign, client_id, 100));
+    }
+
+    #[tokio::test]
+    async fn rich_targeting_filters_genders_and_locations() {
+        let service = create_test_service();
+        let targeted = |targeting: domain::schemas::TargetingCampaignSchema| {
+            let mut campaign = create_test_campaign(Uuid::new_v4(), Uuid::new_v4());
+            campaign.targeting = targeting;
+            campaign
+        };
+
+        let campaigns = vec![
+            targeted(domain::schemas::TargetingCampaignSchema {
+                genders: Some(vec!["FEMALE".into(), "MALE".into()]),
+                location: Some("Moscow".into()),
+                ..Default::default()
+            }),
+            targeted(domain::schemas::TargetingCampaignSchema {
+                locations: Some(vec!["Saint Petersburg".into(), "Moscow, mcad".into()]),
+                exclude_locations: Some(vec!["Moscow, mcad, arbat".into()]),
+                ..Default::default()
+            }),
+            targeted(domain::schemas::TargetingCampaignSchema {
+                locations: Some(vec!["Moscow".into()]),
+                exclude_locations: Some(vec!["Moscow, mcad".into()]),
+                ..Default::default()
+            }),
+            targeted(domain::schemas::TargetingCampaignSchema {
+                genders: Some(vec!["FEMALE".into()]),
+                ..Default::default()
+            }),
+        ];
+        let expected: Vec<Uuid> = campaigns[..2].iter().map(|c| c.campaign_id).collect();
+
+        let result: Vec<Uuid> = service
+            .filter_targeted_campaigns(
+                campaigns,
+                25,
+                "MALE".into(),
+                "moscow, MCAD".into(),
+                serde_json::json!({}),
+            )
+            .await
+            .into_iter()
+            .map(|c| c.campaign_id)
+            .collect();
+
+        assert_eq!(result, expected);
+    }
+
+    #[tokio::test]
+    async fn expression_targeting_uses_client_attributes() {
+        let service = create_test_service();
+        let targeted = |expression: &str| {
+            let mut campaign = create_test_campaign(Uuid::new_v4(), Uuid::new_v4());
+            campaign.targeting = domain::schemas::TargetingCampaignSchema {
+                expression: Some(expression.into()),
+                ..Default::default()
+            };
+            campaign
+        };
+
+        let campaigns = vec![
+            targeted(r#"age >= 18 AND interest IN ["sport", "travel"] AND NOT location = "Kazan""#),
+            targeted(r#"device = "ios" OR income > 5"#),
+            targeted(r#"NOT location = "Moscow""#),
+        ];
+        let expected = vec![campaigns[0].campaign_id];
+
+        let result: Vec<Uuid> = service
+            .filter_targeted_campaigns(
+                campaigns,
+                25,
+                "MALE".into(),
+                "Moscow, mcad".into(),
+                serde_json::json!({"interest": ["travel"], "device": "android", "income": 3}),
+            )
+            .await
+            .into_iter()
+            .map(|c| c.campaign_id)
+            .collect();
+
+        assert_eq!(result, expected);
+    }
+
+    #[tokio::test]
+    async fn parsed_expression_is_used_over_source() {
+        let service = create_test_service();
+        let mut parsed = create_test_campaign(Uuid::new_v4(), Uuid::new_v4());
+        parsed.targeting = domain::schemas::TargetingCampaignSchema {
+            expression: Some("age > 100".into()),
+            ..Default::default()
+        };
+        parsed.targeting_expression = Some(domain::services::TargetingExpression::parse("age > 18").unwrap());
+        let mut stale = parsed.clone();
+        stale.campaign_id = Uuid::new_v4();
+        stale.targeting_expression = None;
+
+        let result: Vec<Uuid> = service
+            .filter_targeted_campaigns(
+                vec![parsed.clone(), stale],
+                25,
+                "MALE".into(),
+                "Moscow".into(),
+                serde_json::json!({}),
+            )
+            .await
+            .into_iter()
+            .map(|c| c.campaign_id)
+            .collect();
+
+        assert_eq!(result, vec![parsed.campaign_id]);
+    }
+
+    #[test]
+    fn sublocation_matches_by_parts() {
+        assert!(AdsService::is_sublocation("Moscow, mcad", "Moscow"));
+        assert!(AdsService::is_sublocation("Moscow, mcad", "Moscow, mcad"));
+        assert!(!AdsService::is_sublocation("Moscow", "Moscow, mcad"));
+        assert!(!AdsService::is_sublocation("Moscowskaya oblast", "Moscow"));
+        assert!(!AdsService::is_sublocation("Moscow", ""));
     }
 
     #[tokio::test]
@@ -600,10 +1894,11 @@ mod tests {
 
         let service = create_test_service();
         let result = service
-            .recommendation_ads(
+            .recommendation_ads_by_id(
                 vec![create_test_campaign(Uuid::new_v4(), Uuid::new_v4())],
                 client_id,
                 50,
+                1,
                 mock_client_repo,
                 MockMlScoreRepo::new(),
             )
@@ -614,4 +1909,30 @@ mod tests {
             Err(ServiceError::Repository(RepoError::ObjDoesNotExists(_)))
         ));
     }
+
+    #[tokio::test]
+    async fn ml_scores_cache_loads_each_score_once() {
+        let client_id = Uuid::new_v4();
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let mut mock_score_repo = MockMlScoreRepo::new();
+        mock_score_repo
+            .expect_get_ml_scores()
+            .withf(move |_, advertisers_id| advertisers_id == &vec![first])
+            .times(1)
+            .returning(|_, _| Ok(vec![Some(0.7)]));
+        mock_score_repo
+            .expect_get_ml_scores()
+            .withf(move |_, advertisers_id| advertisers_id == &vec![second])
+            .times(1)
+            .returning(|_, _| Ok(vec![None]));
+        let cache = MlScoresCache::new(mock_score_repo);
+
+        let scores = (&cache).get_ml_scores(client_id, vec![first, first]).await.unwrap();
+        assert_eq!(scores, vec![Some(0.7), Some(0.7)]);
+        let scores = (&cache).get_ml_scores(client_id, vec![second, first]).await.unwrap();
+        assert_eq!(scores, vec![None, Some(0.7)]);
+        let scores = (&cache).get_ml_scores(client_id, vec![first, second]).await.unwrap();
+        assert_eq!(scores, vec![Some(0.7), None]);
+    }
 }
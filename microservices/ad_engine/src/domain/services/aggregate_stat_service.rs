@@ -90,6 +90,7 @@ impl AggregateStatService {
     /// * `clicks` - Number of clicks
     /// * `spent_imp` - Amount spent on impressions
     /// * `spent_clk` - Amount spent on clicks
+    /// * `budget_remaining` - Remaining budget, `None` when unlimited
     ///
     /// # Returns
     /// New StatResponse object with calculated totals and conversion rate
@@ -99,6 +100,7 @@ impl AggregateStatService {
         clicks: u32,
         spent_imp: f64,
         spent_clk: f64,
+        budget_remaining: Option<f64>,
     ) -> domain::schemas::StatResponse {
         domain::schemas::StatResponse {
             impressions_count: impressions,
@@ -107,6 +109,8 @@ impl AggregateStatService {
             spent_clicks: spent_clk,
             spent_total: spent_clk + spent_imp,
             conversion: self.calculate_conversion(impressions, clicks),
+            budget_remaining,
+            pacing: None,
         }
     }
 }
@@ -213,7 +217,7 @@ mod tests {
     fn test_create_stat_response() {
         let service = AggregateStatService;
 
-        let response = service.create_stat_response(100, 10, 5.0, 1.0);
+        let response = service.create_stat_response(100, 10, 5.0, 1.0, Some(4.0));
 
         assert_eq!(response.impressions_count, 100);
         assert_eq!(response.clicks_count, 10);
@@ -221,5 +225,6 @@ mod tests {
         assert_eq!(response.spent_clicks, 1.0);
         assert_eq!(response.spent_total, 6.0);
         assert_eq!(response.conversion, 10.0);
+        assert_eq!(response.budget_remaining, Some(4.0));
     }
 }
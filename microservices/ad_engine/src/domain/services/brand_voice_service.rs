@@ -0,0 +1,150 @@
+use async_trait::async_trait;
+
+use crate::{domain, infrastructure};
+
+/// Trait for setting the brand voice profile of an advertiser
+#[async_trait]
+pub trait IUpsertBrandVoice {
+    /// Creates or replaces the brand voice profile of an advertiser
+    ///
+    /// # Arguments
+    /// * `advertiser_id` - UUID of the advertiser
+    /// * `brand_voice` - Tone, language, guidelines and template of the
+    ///   profile
+    ///
+    /// # Returns
+    /// * `RepoResult<BrandVoiceReturningSchema>` - Stored profile,
+    ///   `ObjDoesNotExists` when there is no such advertiser or template
+    async fn upsert(
+        &self,
+        advertiser_id: uuid::Uuid,
+        brand_voice: domain::schemas::BrandVoiceUpdateRequest,
+    ) -> infrastructure::repository::RepoResult<infrastructure::repository::sqlx_lib::BrandVoiceReturningSchema>;
+}
+
+/// Trait for retrieving the brand voice profile of an advertiser
+#[async_trait]
+pub trait IGetBrandVoice {
+    /// Gets the brand voice profile of an advertiser
+    ///
+    /// # Arguments
+    /// * `advertiser_id` - UUID of the advertiser
+    ///
+    /// # Returns
+    /// * `RepoResult<Option<BrandVoiceReturningSchema>>` - Profile, `None`
+    ///   when the advertiser has not set it
+    async fn get(
+        &self,
+        advertiser_id: uuid::Uuid,
+    ) -> infrastructure::repository::RepoResult<Option<infrastructure::repository::sqlx_lib::BrandVoiceReturningSchema>>;
+}
+
+/// Service for the brand voice profiles of advertisers
+///
+/// A profile sets the tone, the language, the additional guidelines and
+/// optionally the prompt template of the texts generated for the advertiser.
+#[derive(Debug)]
+pub struct BrandVoiceService;
+
+impl BrandVoiceService {
+    /// Creates or replaces the brand voice profile of an advertiser
+    ///
+    /// # Arguments
+    /// * `advertiser_id` - UUID of the advertiser
+    /// * `brand_voice` - Tone, language, guidelines and template of the
+    ///   profile
+    /// * `repo` - Repository implementation for data access
+    #[tracing::instrument(name = "`BrandVoiceService` update brand voice", skip(repo))]
+    pub async fn update<R: IUpsertBrandVoice>(
+        &self,
+        advertiser_id: uuid::Uuid,
+        brand_voice: domain::schemas::BrandVoiceUpdateRequest,
+        repo: R,
+    ) -> domain::services::ServiceResult<domain::schemas::BrandVoiceSchema> {
+        repo.upsert(advertiser_id, brand_voice)
+            .await
+            .map(|brand_voice| brand_voice.into())
+            .map_err(|e| domain::services::ServiceError::Repository(e))
+    }
+
+    /// Gets the brand voice profile of an advertiser
+    ///
+    /// # Arguments
+    /// * `advertiser_id` - UUID of the advertiser
+    /// * `repo` - Repository implementation for data access
+    ///
+    /// # Returns
+    /// The profile, `None` when the advertiser has not set it
+    #[tracing::instrument(name = "`BrandVoiceService` get brand voice", skip(repo))]
+    pub async fn get<R: IGetBrandVoice>(
+        &self,
+        advertiser_id: uuid::Uuid,
+        repo: R,
+    ) -> domain::services::ServiceResult<Option<domain::schemas::BrandVoiceSchema>> {
+        repo.get(advertiser_id)
+            .await
+            .map(|brand_voice| brand_voice.map(|brand_voice| brand_voice.into()))
+            .map_err(|e| domain::services::ServiceError::Repository(e))
+    }
+}
+
+impl From<infrastructure::repository::sqlx_lib::BrandVoiceReturningSchema> for domain::schemas::BrandVoiceSchema {
+    fn from(brand_voice: infrastructure::repository::sqlx_lib::BrandVoiceReturningSchema) -> Self {
+        Self {
+            advertiser_id: brand_voice.advertiser_id,
+            tone: brand_voice.tone,
+            language: brand_voice.language,
+            guidelines: brand_voice.guidelines,
+            template_id: brand_voice.template_id,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    struct MockBrandVoiceRepo {
+        brand_voice: Option<infrastructure::repository::sqlx_lib::BrandVoiceReturningSchema>,
+    }
+
+    #[async_trait]
+    impl IGetBrandVoice for MockBrandVoiceRepo {
+        async fn get(
+            &self,
+            _advertiser_id: Uuid,
+        ) -> infrastructure::repository::RepoResult<
+            Option<infrastructure::repository::sqlx_lib::BrandVoiceReturningSchema>,
+        > {
+            Ok(self.brand_voice.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn get_brand_voice() {
+        let advertiser_id = Uuid::new_v4();
+        let repo = MockBrandVoiceRepo {
+            brand_voice: Some(infrastructure::repository::sqlx_lib::BrandVoiceReturningSchema {
+                advertiser_id,
+                tone: "friendly".into(),
+                language: "English".into(),
+                guidelines: String::new(),
+                template_id: None,
+            }),
+        };
+
+        let brand_voice = BrandVoiceService.get(advertiser_id, repo).await.unwrap().unwrap();
+
+        assert_eq!(brand_voice.advertiser_id, advertiser_id);
+        assert_eq!(brand_voice.tone, "friendly");
+    }
+
+    #[tokio::test]
+    async fn get_missing_brand_voice() {
+        let repo = MockBrandVoiceRepo { brand_voice: None };
+
+        assert_eq!(BrandVoiceService.get(Uuid::new_v4(), repo).await.unwrap(), None);
+    }
+}
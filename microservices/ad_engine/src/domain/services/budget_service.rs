@@ -0,0 +1,91 @@
+use crate::domain;
+
+/// Service for tracking campaign budgets
+///
+/// Decides when a campaign has to leave the active set because its total or
+/// daily budget is exhausted. The spend itself is counted in Redis.
+#[derive(Debug)]
+pub struct BudgetService;
+
+impl BudgetService {
+    /// Checks whether the total or the daily budget of a campaign is exhausted
+    ///
+    /// # Arguments
+    /// * `campaign` - Active campaign to check
+    ///
+    /// # Returns
+    /// `true` if the campaign can't spend money anymore
+    pub fn is_exhausted(&self, campaign: &domain::schemas::ActiveCampaignSchema) -> bool {
+        campaign.budget.is_some_and(|budget| campaign.spent >= budget)
+            || campaign
+                .daily_budget
+                .is_some_and(|daily_budget| campaign.spent_today >= daily_budget)
+    }
+
+    /// Calculates the remaining budget of a campaign
+    ///
+    /// # Arguments
+    /// * `budget` - Total budget, `None` for an unlimited campaign
+    /// * `spent` - Money already spent
+    ///
+    /// # Returns
+    /// Remaining budget, never less than zero, or `None` for an unlimited
+    /// campaign
+    pub fn remaining(&self, budget: Option<f64>, spent: f64) -> Option<f64> {
+        budget.map(|budget| (budget - spent).max(0.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_campaign(budget: Option<f64>, daily_budget: Option<f64>) -> domain::schemas::ActiveCampaignSchema {
+        domain::schemas::ActiveCampaignSchema {
+            budget,
+            daily_budget,
+            ..domain::schemas::ActiveCampaignSchema::test_fixture()
+        }
+    }
+
+    #[test]
+    fn unlimited_campaign_never_exhausted() {
+        let service = BudgetService;
+        let mut campaign = create_test_campaign(None, None);
+        campaign.spent = 1_000_000.;
+        campaign.spent_today = 1_000_000.;
+
+        assert!(!service.is_exhausted(&campaign));
+    }
+
+    #[test]
+    fn total_budget_exhausted() {
+        let service = BudgetService;
+        let mut campaign = create_test_campaign(Some(10.), None);
+
+        campaign.spent = 6.;
+        assert!(!service.is_exhausted(&campaign));
+
+        campaign.spent = 10.;
+        assert!(service.is_exhausted(&campaign));
+    }
+
+    #[test]
+    fn daily_budget_exhausted() {
+        let service = BudgetService;
+        let mut campaign = create_test_campaign(Some(100.), Some(5.));
+        campaign.spent = 55.;
+        campaign.spent_today = 5.;
+
+        assert!(service.is_exhausted(&campaign));
+    }
+
+    #[test]
+    fn remaining_budget_not_negative() {
+        let service = BudgetService;
+
+        assert_eq!(service.remaining(Some(10.), 4.), Some(6.));
+        assert_eq!(service.remaining(Some(10.), 12.), Some(0.));
+        assert_eq!(service.remaining(None, 12.), None);
+    }
+}
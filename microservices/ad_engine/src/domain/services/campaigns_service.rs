@@ -43,6 +43,32 @@ pub trait IUpdateCampaign {
     ) -> infrastructure::repository::RepoResult<infrastructure::repository::sqlx_lib::CampaignReturningSchema>;
 }
 
+/// Defines the interface for saving generated campaign texts.
+#[async_trait]
+pub trait IUpdateCampaignTexts {
+    /// Updates the title, the text and the prompt template version of the
+    /// campaign texts in one statement.
+    ///
+    /// # Arguments
+    /// * `advertiser_id` - ID of the advertiser who owns the campaign
+    /// * `campaign_id` - Unique identifier of the campaign
+    /// * `ad_title` - Generated title
+    /// * `ad_text` - Generated text
+    /// * `prompt_version_id` - Version which generated the texts, `None` for
+    ///   the prompts from config
+    ///
+    /// # Returns
+    /// A Result containing the updated campaign details or a repository error
+    async fn update_texts(
+        &self,
+        advertiser_id: uuid::Uuid,
+        campaign_id: uuid::Uuid,
+        ad_title: String,
+        ad_text: String,
+        prompt_version_id: Option<uuid::Uuid>,
+    ) -> infrastructure::repository::RepoResult<infrastructure::repository::sqlx_lib::CampaignReturningSchema>;
+}
+
 /// Defines the interface for retrieving individual campaigns by ID.
 #[async_trait]
 pub trait IGetCampaignById {
@@ -228,6 +254,36 @@ impl<'p> CampaignService {
         Ok(repo_campaign.into())
     }
 
+    /// Saves generated campaign texts together with the prompt template
+    /// version which generated them.
+    ///
+    /// The texts and the version are written by one statement, so the texts
+    /// are never saved with a stale version.
+    ///
+    /// # Arguments
+    /// * `advertiser_id` - ID of the advertiser who owns the campaign
+    /// * `campaign_id` - Unique identifier of the campaign
+    /// * `ad_title` - Generated title
+    /// * `ad_text` - Generated text
+    /// * `prompt_version_id` - Version which generated the texts
+    /// * `repo` - Repository implementation for campaign operations
+
+    #[tracing::instrument(name = "`CampaignService` update texts of campaign", skip(repo))]
+    pub async fn update_texts<R: IUpdateCampaignTexts>(
+        &self,
+        advertiser_id: uuid::Uuid,
+        campaign_id: uuid::Uuid,
+        ad_title: String,
+        ad_text: String,
+        prompt_version_id: Option<uuid::Uuid>,
+        repo: R,
+    ) -> domain::services::ServiceResult<domain::schemas::CampaignSchema> {
+        repo.update_texts(advertiser_id, campaign_id, ad_title, ad_text, prompt_version_id)
+            .await
+            .map(|campaign| campaign.into())
+            .map_err(|e| domain::services::ServiceError::Repository(e))
+    }
+
     /// Deletes a campaign after verifying ownership.
     ///
     /// # Arguments
@@ -391,12 +447,18 @@ impl From<infrastructure::repository::sqlx_lib::CampaignReturningSchema> for dom
             clicks_limit: campaign.clicks_limit as u32,
             cost_per_impression: campaign.cost_per_impressions.to_f64().unwrap_or(0.0),
             cost_per_click: campaign.cost_per_clicks.to_f64().unwrap_or(0.0),
+            budget: campaign.budget.and_then(|budget| budget.to_f64()),
+            daily_budget: campaign.daily_budget.and_then(|daily_budget| daily_budget.to_f64()),
             ad_title: campaign.ad_title,
             ad_text: campaign.ad_text,
             start_date: campaign.start_date as u32,
             end_date: campaign.end_date as u32,
             targeting: serde_json::from_value(campaign.targeting.unwrap_or(serde_json::json!({})))
                 .unwrap_or(domain::schemas::TargetingCampaignSchema::default()),
+            frequency_cap: campaign
+                .frequency_cap
+                .and_then(|frequency_cap| serde_json::from_value(frequency_cap).ok()),
+            prompt_version_id: campaign.prompt_version_id,
         }
     }
 }
@@ -15,11 +15,12 @@ pub trait IGetOrCreateUniqIdForStatCampaign {
     /// * `campaign_id` - UUID of the campaign to get/create IDs for
     ///
     /// # Returns
-    /// A tuple containing two vectors of UUIDs representing different ID sets
+    /// A tuple of the (client, time) pairs of every impression and the IDs of
+    /// clients who clicked the campaign
     async fn get_or_create_uniq_id(
         &self,
         campaign_id: uuid::Uuid,
-    ) -> infrastructure::repository::RepoResult<(Vec<uuid::Uuid>, Vec<uuid::Uuid>)>;
+    ) -> infrastructure::repository::RepoResult<(Vec<(uuid::Uuid, u32)>, Vec<uuid::Uuid>)>;
 }
 
 /// Trait for recording campaign view events
@@ -27,19 +28,24 @@ pub trait IGetOrCreateUniqIdForStatCampaign {
 /// Provides functionality to track when a campaign is viewed by a client.
 #[async_trait]
 pub trait IViewCampaign {
-    /// Records a campaign view event
+    /// Records a campaign view event, the view gets the prompt version which
+    /// generated the served creative, or the campaign texts without a creative
     ///
     /// # Arguments
     /// * `campaign_id` - UUID of the viewed campaign
     /// * `client_id` - UUID of the client viewing the campaign
     /// * `cost` - Cost associated with this view
     /// * `advanced_time` - Time spent viewing in seconds
+    /// * `creative_id` - UUID of the served creative
+    /// * `experiment` - Experiment arm the client is assigned to
     async fn view_campaign(
         &self,
         campaign_id: uuid::Uuid,
         client_id: uuid::Uuid,
         cost: f64,
         advanced_time: u32,
+        creative_id: Option<uuid::Uuid>,
+        experiment: Option<domain::schemas::ExperimentAssignmentSchema>,
     ) -> infrastructure::repository::RepoResult<()>;
 }
 
@@ -48,7 +54,9 @@ pub trait IViewCampaign {
 /// Provides functionality to track when a campaign is clicked by a client.
 #[async_trait]
 pub trait IClickCampaign {
-    /// Records a campaign click event
+    /// Records a campaign click event, the click gets the experiment arm, the
+    /// creative and the prompt version of the last impression of the campaign
+    /// to the client
     ///
     /// # Arguments
     /// * `campaign_id` - UUID of the clicked campaign
@@ -64,6 +72,46 @@ pub trait IClickCampaign {
     ) -> infrastructure::repository::RepoResult<()>;
 }
 
+/// Trait for retrieving money spent by a campaign
+///
+/// Provides functionality to sum the cost of campaign views and clicks.
+#[async_trait]
+pub trait IGetSpentCampaign {
+    /// Retrieves money spent by a campaign
+    ///
+    /// # Arguments
+    /// * `campaign_id` - UUID of the campaign
+    /// * `advanced_time` - Day to calculate the daily spend for
+    ///
+    /// # Returns
+    /// A tuple of the total spend and the spend on the given day
+    async fn get_spent(
+        &self,
+        campaign_id: uuid::Uuid,
+        advanced_time: u32,
+    ) -> infrastructure::repository::RepoResult<(f64, f64)>;
+}
+
+/// Trait for retrieving the budget of a campaign
+#[async_trait]
+pub trait IGetBudgetCampaign {
+    /// Retrieves the total budget of a campaign
+    ///
+    /// # Arguments
+    /// * `campaign_id` - UUID of the campaign
+    async fn get_budget(&self, campaign_id: uuid::Uuid) -> infrastructure::repository::RepoResult<Option<f64>>;
+
+    /// Retrieves the total budget and the total spend of every campaign of an
+    /// advertiser
+    ///
+    /// # Arguments
+    /// * `advertiser_id` - UUID of the advertiser
+    async fn get_budgets_by_advertiser(
+        &self,
+        advertiser_id: uuid::Uuid,
+    ) -> infrastructure::repository::RepoResult<Vec<(Option<f64>, f64)>>;
+}
+
 /// Trait for retrieving daily campaign statistics
 ///
 /// Provides functionality to fetch aggregated daily statistics for campaigns.
@@ -94,13 +142,14 @@ impl<'p> CampaignStatService {
     /// * `repo` - Repository implementation for data access
     ///
     /// # Returns
-    /// A tuple of UUID vectors representing different ID sets for the campaign
+    /// A tuple of the (client, time) pairs of every impression and the IDs of
+    /// clients who clicked the campaign
     #[tracing::instrument(name = "`CampaignStatService` get ot create uniq id for stats campaign", skip(repo))]
     pub async fn get_or_create_uniq_id<R: IGetOrCreateUniqIdForStatCampaign>(
         &self,
         campaign_id: uuid::Uuid,
         repo: R,
-    ) -> domain::services::ServiceResult<(Vec<uuid::Uuid>, Vec<uuid::Uuid>)> {
+    ) -> domain::services::ServiceResult<(Vec<(uuid::Uuid, u32)>, Vec<uuid::Uuid>)> {
         repo.get_or_create_uniq_id(campaign_id)
             .await
             .map_err(|e| domain::services::ServiceError::Repository(e))
@@ -113,6 +162,8 @@ impl<'p> CampaignStatService {
     /// * `client_id` - UUID of the viewing client
     /// * `cost` - Associated view cost
     /// * `advanced_time` - View duration in seconds
+    /// * `creative_id` - UUID of the served creative
+    /// * `experiment` - Experiment arm the client is assigned to
     /// * `repo` - Repository implementation for data access
     #[tracing::instrument(name = "`CampaignStatService` add view to campaign", skip(repo))]
     pub async fn view_campaign<R: IViewCampaign>(
@@ -121,9 +172,11 @@ impl<'p> CampaignStatService {
         client_id: uuid::Uuid,
         cost: f64,
         advanced_time: u32,
+        creative_id: Option<uuid::Uuid>,
+        experiment: Option<domain::schemas::ExperimentAssignmentSchema>,
         repo: R,
     ) -> domain::services::ServiceResult<()> {
-        repo.view_campaign(campaign_id, client_id, cost, advanced_time)
+        repo.view_campaign(campaign_id, client_id, cost, advanced_time, creative_id, experiment)
             .await
             .map_err(|e| domain::services::ServiceError::Repository(e))
     }
@@ -150,6 +203,60 @@ impl<'p> CampaignStatService {
             .map_err(|e| domain::services::ServiceError::Repository(e))
     }
 
+    /// Retrieves money spent by a campaign
+    ///
+    /// # Arguments
+    /// * `campaign_id` - UUID of the target campaign
+    /// * `advanced_time` - Day to calculate the daily spend for
+    /// * `repo` - Repository implementation for data access
+    ///
+    /// # Returns
+    /// A tuple of the total spend and the spend on the given day
+    #[tracing::instrument(name = "`CampaignStatService` get spent of campaign", skip(repo))]
+    pub async fn get_spent<R: IGetSpentCampaign>(
+        &self,
+        campaign_id: uuid::Uuid,
+        advanced_time: u32,
+        repo: R,
+    ) -> domain::services::ServiceResult<(f64, f64)> {
+        repo.get_spent(campaign_id, advanced_time)
+            .await
+            .map_err(|e| domain::services::ServiceError::Repository(e))
+    }
+
+    /// Retrieves the total budget of a campaign
+    ///
+    /// # Arguments
+    /// * `campaign_id` - UUID of the target campaign
+    /// * `repo` - Repository implementation for data access
+    #[tracing::instrument(name = "`CampaignStatService` get budget of campaign", skip(repo))]
+    pub async fn get_budget<R: IGetBudgetCampaign>(
+        &self,
+        campaign_id: uuid::Uuid,
+        repo: R,
+    ) -> domain::services::ServiceResult<Option<f64>> {
+        repo.get_budget(campaign_id)
+            .await
+            .map_err(|e| domain::services::ServiceError::Repository(e))
+    }
+
+    /// Retrieves the total budget and the total spend of every campaign of an
+    /// advertiser in one query
+    ///
+    /// # Arguments
+    /// * `advertiser_id` - UUID of the advertiser
+    /// * `repo` - Repository implementation for data access
+    #[tracing::instrument(name = "`CampaignStatService` get budgets of advertiser", skip(repo))]
+    pub async fn get_budgets_by_advertiser<R: IGetBudgetCampaign>(
+        &self,
+        advertiser_id: uuid::Uuid,
+        repo: R,
+    ) -> domain::services::ServiceResult<Vec<(Option<f64>, f64)>> {
+        repo.get_budgets_by_advertiser(advertiser_id)
+            .await
+            .map_err(|e| domain::services::ServiceError::Repository(e))
+    }
+
     /// Retrieves daily statistics for a campaign with gap filling
     ///
     /// Gets daily stats and fills any missing dates with default values to
@@ -240,7 +347,7 @@ mod tests {
     use super::*;
 
     struct MockGetOrCreateUniqIdRepo {
-        result: Result<(Vec<Uuid>, Vec<Uuid>), infrastructure::repository::RepoError>,
+        result: Result<(Vec<(Uuid, u32)>, Vec<Uuid>), infrastructure::repository::RepoError>,
     }
 
     #[async_trait]
@@ -248,7 +355,7 @@ mod tests {
         async fn get_or_create_uniq_id(
             &self,
             _campaign_id: Uuid,
-        ) -> infrastructure::repository::RepoResult<(Vec<Uuid>, Vec<Uuid>)> {
+        ) -> infrastructure::repository::RepoResult<(Vec<(Uuid, u32)>, Vec<Uuid>)> {
             self.result.clone()
         }
     }
@@ -265,6 +372,8 @@ mod tests {
             _client_id: Uuid,
             _cost: f64,
             _advanced_time: u32,
+            _creative_id: Option<Uuid>,
+            _experiment: Option<domain::schemas::ExperimentAssignmentSchema>,
         ) -> infrastructure::repository::RepoResult<()> {
             self.result.clone()
         }
@@ -309,7 +418,7 @@ mod tests {
     async fn test_get_or_create_uniq_id_success() {
         let campaign_id = Uuid::new_v4();
         let mock_repo = MockGetOrCreateUniqIdRepo {
-            result: Ok((vec![Uuid::new_v4()], vec![Uuid::new_v4()])),
+            result: Ok((vec![(Uuid::new_v4(), 1)], vec![Uuid::new_v4()])),
         };
         let service = CampaignStatService;
 
@@ -329,7 +438,7 @@ mod tests {
         let service = CampaignStatService;
 
         let result = service
-            .view_campaign(campaign_id, client_id, cost, advanced_time, mock_repo)
+            .view_campaign(campaign_id, client_id, cost, advanced_time, None, None, mock_repo)
             .await;
 
         assert!(result.is_ok());
@@ -15,6 +15,7 @@ pub trait IRegisterBulkClient {
     /// * `locations` - Vector of location strings for each client
     /// * `genders` - Vector of gender strings for each client
     /// * `ages` - Vector of ages as integers for each client
+    /// * `attributes` - Vector of custom attribute objects for each client
     ///
     /// # Returns
     /// A Result containing a vector of registered client data on success,
@@ -26,6 +27,7 @@ pub trait IRegisterBulkClient {
         locations: Vec<String>,
         genders: Vec<String>,
         ages: Vec<i32>,
+        attributes: Vec<serde_json::Value>,
     ) -> infrastructure::repository::RepoResult<Vec<infrastructure::repository::sqlx_lib::ClientReturningSchema>>;
 }
 
@@ -80,20 +82,21 @@ impl<'p> ClientService {
 
         let unique_clients: Vec<domain::schemas::ClientProfileSchema> = clients_map.into_values().collect();
 
-        let (client_ids, logins, locations, genders, ages) = unique_clients.into_iter().fold(
-            (Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new()),
-            |(mut uuids, mut names, mut emails, mut phones, mut ages), client| {
+        let (client_ids, logins, locations, genders, ages, attributes) = unique_clients.into_iter().fold(
+            (Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new()),
+            |(mut uuids, mut names, mut emails, mut phones, mut ages, mut attributes), client| {
                 uuids.push(client.client_id);
                 names.push(client.login);
                 emails.push(client.location);
                 phones.push(client.gender);
                 ages.push(client.age as i32);
-                (uuids, names, emails, phones, ages)
+                attributes.push(serde_json::Value::Object(client.attributes));
+                (uuids, names, emails, phones, ages, attributes)
             },
         );
 
         let repo_user = repo
-            .register(client_ids, logins, locations, genders, ages)
+            .register(client_ids, logins, locations, genders, ages, attributes)
             .await
             .map_err(|e| domain::services::ServiceError::Repository(e))?;
 
@@ -144,6 +147,7 @@ mod tests {
                 locations: Vec<String>,
                 genders: Vec<String>,
                 ages: Vec<i32>,
+                attributes: Vec<serde_json::Value>,
             ) -> infrastructure::repository::RepoResult
             <Vec<infrastructure::repository::sqlx_lib::ClientReturningSchema>>;
         }
@@ -173,6 +177,7 @@ mod tests {
             location: "Moscow".to_string(),
             gender: "MALE".to_string(),
             age: 25,
+            attributes: serde_json::Map::new(),
         };
 
         let client_2 = domain::schemas::ClientProfileSchema {
@@ -181,6 +186,7 @@ mod tests {
             location: "St. Petersburg".to_string(),
             gender: "FEMALE".to_string(),
             age: 30,
+            attributes: serde_json::Map::new(),
         };
 
         let client_3 = domain::schemas::ClientProfileSchema {
@@ -189,13 +195,14 @@ mod tests {
             location: "Moscow".to_string(),
             gender: "MALE".to_string(),
             age: 25,
+            attributes: serde_json::Map::new(),
         };
 
         let input_data = vec![client_1.clone(), client_2.clone(), client_3.clone()];
 
         mock_repo
             .expect_register()
-            .withf(move |ids, logins, locations, genders, ages| {
+            .withf(move |ids, logins, locations, genders, ages, _| {
                 ids.len() == 2
                     && logins.len() == 2
                     && locations.len() == 2
@@ -212,7 +219,7 @@ mod tests {
                     && ages.contains(&25)
                     && ages.contains(&30)
             })
-            .returning(move |_, _, _, _, _| {
+            .returning(move |_, _, _, _, _, _| {
                 Ok(vec![
                     infrastructure::repository::sqlx_lib::ClientReturningSchema {
                         client_id: client_id_1,
@@ -220,6 +227,7 @@ mod tests {
                         location: "Moscow".to_string(),
                         gender: "MALE".to_string(),
                         age: 25,
+                        attributes: serde_json::json!({}),
                     },
                     infrastructure::repository::sqlx_lib::ClientReturningSchema {
                         client_id: client_id_2,
@@ -227,6 +235,7 @@ mod tests {
                         location: "St. Petersburg".to_string(),
                         gender: "FEMALE".to_string(),
                         age: 30,
+                        attributes: serde_json::json!({}),
                     },
                 ])
             });
@@ -244,6 +253,7 @@ mod tests {
             location: "Moscow".to_string(),
             gender: "MALE".to_string(),
             age: 25,
+            attributes: serde_json::Map::new(),
         };
 
         assert!(returned_clients.contains(&expected_client_3));
@@ -261,6 +271,7 @@ mod tests {
             location: "Moscow".to_string(),
             gender: "MALE".to_string(),
             age: 25,
+            attributes: serde_json::Map::new(),
         };
 
         mock_repo.expect_get_by_id().with(eq(client_id)).returning(move |_| {
@@ -270,6 +281,7 @@ mod tests {
                 location: "Moscow".to_string(),
                 gender: "MALE".to_string(),
                 age: 25,
+                attributes: serde_json::json!({}),
             })
         });
 
@@ -292,13 +304,14 @@ mod tests {
             location: "Moscow".to_string(),
             gender: "MALE".to_string(),
             age: 25,
+            attributes: serde_json::Map::new(),
         };
 
         let input_data = vec![client];
 
         mock_repo
             .expect_register()
-            .returning(|_, _, _, _, _| Err(infrastructure::repository::RepoError::UniqueConstraint("err".into())));
+            .returning(|_, _, _, _, _, _| Err(infrastructure::repository::RepoError::UniqueConstraint("err".into())));
 
         let service = ClientService;
         let result = service.register(input_data, mock_repo).await;
@@ -329,4 +342,25 @@ mod tests {
             _ => panic!("Expected Repository error"),
         }
     }
+
+    #[tokio::test]
+    async fn out_of_range_age_is_clamped() {
+        for (age, expected) in [(300, u8::MAX), (-1, 0), (25, 25)] {
+            let mut mock_repo = MockGetClientById::new();
+            mock_repo.expect_get_by_id().returning(move |client_id| {
+                Ok(infrastructure::repository::sqlx_lib::ClientReturningSchema {
+                    client_id,
+                    login: "client".into(),
+                    age,
+                    location: "Moscow".into(),
+                    gender: "MALE".into(),
+                    attributes: serde_json::json!({}),
+                })
+            });
+
+            let client = ClientService.get_by_id(Uuid::new_v4(), mock_repo).await.unwrap();
+
+            assert_eq!(client.age, expected);
+        }
+    }
 }
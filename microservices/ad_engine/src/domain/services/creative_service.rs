@@ -0,0 +1,419 @@
+use async_trait::async_trait;
+use bigdecimal::ToPrimitive;
+
+use crate::{domain, infrastructure};
+
+/// Trait for creating creatives of a campaign
+#[async_trait]
+pub trait ICreateCreatives {
+    /// Creates creatives of a campaign in one transaction
+    ///
+    /// # Arguments
+    /// * `campaign_id` - UUID of the campaign
+    /// * `creatives` - Titles, texts and image names of the creatives
+    /// * `prompt_version_id` - Version of the prompt template which generated
+    ///   the creatives, `None` for creatives written by the advertiser
+    async fn create(
+        &self,
+        campaign_id: uuid::Uuid,
+        creatives: Vec<domain::schemas::CreativeCreateRequest>,
+        prompt_version_id: Option<uuid::Uuid>,
+    ) -> infrastructure::repository::RepoResult<Vec<infrastructure::repository::sqlx_lib::CreativeReturningSchema>>;
+}
+
+/// Trait for retrieving creatives of a campaign
+#[async_trait]
+pub trait IGetCreativeList {
+    /// Gets all creatives of a campaign, the oldest first
+    ///
+    /// # Arguments
+    /// * `campaign_id` - UUID of the campaign
+    async fn get_list(
+        &self,
+        campaign_id: uuid::Uuid,
+    ) -> infrastructure::repository::RepoResult<Vec<infrastructure::repository::sqlx_lib::CreativeReturningSchema>>;
+}
+
+/// Trait for retrieving creatives of several campaigns
+#[async_trait]
+pub trait IGetCreativesByCampaigns {
+    /// Gets all creatives of the campaigns in one query, the oldest first
+    ///
+    /// # Arguments
+    /// * `campaign_ids` - UUIDs of the campaigns
+    async fn get_by_campaigns(
+        &self,
+        campaign_ids: &[uuid::Uuid],
+    ) -> infrastructure::repository::RepoResult<Vec<infrastructure::repository::sqlx_lib::CreativeReturningSchema>>;
+}
+
+/// Trait for deleting a creative of a campaign
+#[async_trait]
+pub trait IDeleteCreative {
+    /// Deletes a creative softly, so its recorded events keep referencing it
+    /// and stay in the statistics of the campaign and of the creatives
+    ///
+    /// # Arguments
+    /// * `campaign_id` - UUID of the campaign
+    /// * `creative_id` - UUID of the creative
+    ///
+    /// # Returns
+    /// * `RepoResult<()>` - `ObjDoesNotExists` when the campaign has no such
+    ///   creative
+    async fn delete(
+        &self,
+        campaign_id: uuid::Uuid,
+        creative_id: uuid::Uuid,
+    ) -> infrastructure::repository::RepoResult<()>;
+}
+
+/// Trait for retrieving statistics of the creatives of a campaign
+#[async_trait]
+pub trait IGetCreativeStat {
+    /// Gets impressions, clicks and spend of every creative of a campaign,
+    /// deleted creatives included
+    ///
+    /// # Arguments
+    /// * `campaign_id` - UUID of the campaign
+    async fn get_stat(
+        &self,
+        campaign_id: uuid::Uuid,
+    ) -> infrastructure::repository::RepoResult<Vec<infrastructure::repository::sqlx_lib::CreativeStatReturningSchema>>;
+}
+
+/// Service for creatives, the variants of the advertisement copy of a campaign
+///
+/// Creatives of an active campaign are kept in its Redis copy and served in
+/// rotation by the number of impressions of the campaign, so every creative
+/// gets an equal share of impressions. The served creative is recorded on the
+/// impression and on the following click, so creatives are compared by the
+/// recorded events.
+#[derive(Debug)]
+pub struct CreativeService;
+
+impl CreativeService {
+    /// Creates creatives of a campaign
+    ///
+    /// # Arguments
+    /// * `campaign_id` - UUID of the campaign
+    /// * `creatives` - Titles, texts and image names of the creatives
+    /// * `prompt_version_id` - Version of the prompt template which generated
+    ///   the creatives
+    /// * `repo` - Repository implementation for data access
+    #[tracing::instrument(name = "`CreativeService` create creatives", skip(repo))]
+    pub async fn create<R: ICreateCreatives>(
+        &self,
+        campaign_id: uuid::Uuid,
+        creatives: Vec<domain::schemas::CreativeCreateRequest>,
+        prompt_version_id: Option<uuid::Uuid>,
+        repo: R,
+    ) -> domain::services::ServiceResult<Vec<domain::schemas::CreativeSchema>> {
+        repo.create(campaign_id, creatives, prompt_version_id)
+            .await
+            .map(|creatives| creatives.into_iter().map(|creative| creative.into()).collect())
+            .map_err(|e| domain::services::ServiceError::Repository(e))
+    }
+
+    /// Gets all creatives of a campaign
+    ///
+    /// # Arguments
+    /// * `campaign_id` - UUID of the campaign
+    /// * `repo` - Repository implementation for data access
+    #[tracing::instrument(name = "`CreativeService` get list of creatives", skip(repo))]
+    pub async fn get_list<R: IGetCreativeList>(
+        &self,
+        campaign_id: uuid::Uuid,
+        repo: R,
+    ) -> domain::services::ServiceResult<Vec<domain::schemas::CreativeSchema>> {
+        repo.get_list(campaign_id)
+            .await
+            .map(|creatives| creatives.into_iter().map(|creative| creative.into()).collect())
+            .map_err(|e| domain::services::ServiceError::Repository(e))
+    }
+
+    /// Gets all creatives of several campaigns, used to activate many
+    /// campaigns at once
+    ///
+    /// # Arguments
+    /// * `campaign_ids` - UUIDs of the campaigns
+    /// * `repo` - Repository implementation for data access
+    ///
+    /// # Returns
+    /// Creatives grouped by campaign, campaigns without creatives are missing
+    #[tracing::instrument(name = "`CreativeService` get creatives of campaigns", skip(repo))]
+    pub async fn get_by_campaigns<R: IGetCreativesByCampaigns>(
+        &self,
+        campaign_ids: &[uuid::Uuid],
+        repo: R,
+    ) -> domain::services::ServiceResult<std::collections::HashMap<uuid::Uuid, Vec<domain::schemas::CreativeSchema>>>
+    {
+        let creatives = repo
+            .get_by_campaigns(campaign_ids)
+            .await
+            .map_err(|e| domain::services::ServiceError::Repository(e))?;
+
+        let mut grouped: std::collections::HashMap<uuid::Uuid, Vec<domain::schemas::CreativeSchema>> =
+            std::collections::HashMap::new();
+        for creative in creatives {
+            grouped.entry(creative.campaign_id).or_default().push(creative.into());
+        }
+
+        Ok(grouped)
+    }
+
+    /// Deletes a creative of a campaign
+    ///
+    /// # Arguments
+    /// * `campaign_id` - UUID of the campaign
+    /// * `creative_id` - UUID of the creative
+    /// * `repo` - Repository implementation for data access
+    #[tracing::instrument(name = "`CreativeService` delete creative", skip(repo))]
+    pub async fn delete<R: IDeleteCreative>(
+        &self,
+        campaign_id: uuid::Uuid,
+        creative_id: uuid::Uuid,
+        repo: R,
+    ) -> domain::services::ServiceResult<()> {
+        repo.delete(campaign_id, creative_id)
+            .await
+            .map_err(|e| domain::services::ServiceError::Repository(e))
+    }
+
+    /// Gets statistics of every creative of a campaign
+    ///
+    /// # Arguments
+    /// * `campaign_id` - UUID of the campaign
+    /// * `repo` - Repository implementation for data access
+    ///
+    /// # Returns
+    /// Statistics of every creative, creatives without events have zero
+    /// counters
+    #[tracing::instrument(name = "`CreativeService` get stat of creatives", skip(repo))]
+    pub async fn get_stat<R: IGetCreativeStat>(
+        &self,
+        campaign_id: uuid::Uuid,
+        repo: R,
+    ) -> domain::services::ServiceResult<Vec<domain::schemas::CreativeStatSchema>> {
+        repo.get_stat(campaign_id)
+            .await
+            .map(|stats| stats.into_iter().map(|stat| stat.into()).collect())
+            .map_err(|e| domain::services::ServiceError::Repository(e))
+    }
+
+    /// Selects the creative of an active campaign for the next impression
+    ///
+    /// # Returns
+    /// The creative following the last served one, `None` when the campaign
+    /// has no creatives
+    pub fn rotate<'c>(
+        &self,
+        campaign: &'c domain::schemas::ActiveCampaignSchema,
+    ) -> Option<&'c domain::schemas::CreativeSchema> {
+        if campaign.creatives.is_empty() {
+            return None;
+        }
+
+        campaign
+            .creatives
+            .get(campaign.views_count as usize % campaign.creatives.len())
+    }
+}
+
+impl From<infrastructure::repository::sqlx_lib::CreativeReturningSchema> for domain::schemas::CreativeSchema {
+    fn from(creative: infrastructure::repository::sqlx_lib::CreativeReturningSchema) -> Self {
+        Self {
+            creative_id: creative.id,
+            campaign_id: creative.campaign_id,
+            ad_title: creative.ad_title,
+            ad_text: creative.ad_text,
+            image_names: creative.image_names,
+            prompt_version_id: creative.prompt_version_id,
+        }
+    }
+}
+
+impl From<infrastructure::repository::sqlx_lib::CreativeStatReturningSchema> for domain::schemas::CreativeStatSchema {
+    fn from(stat: infrastructure::repository::sqlx_lib::CreativeStatReturningSchema) -> Self {
+        let impressions_count = stat.impressions_count.unwrap_or(0);
+        let clicks_count = stat.clicks_count.unwrap_or(0);
+        let spent_impressions = stat.spent_impressions.and_then(|v| v.to_f64()).unwrap_or(0.0);
+        let spent_clicks = stat.spent_clicks.and_then(|v| v.to_f64()).unwrap_or(0.0);
+
+        Self {
+            creative_id: stat.creative_id,
+            ad_title: stat.ad_title,
+            deleted: stat.deleted,
+            impressions_count: impressions_count as u32,
+            clicks_count: clicks_count as u32,
+            conversion: domain::services::AggregateStatService
+                .calculate_conversion(impressions_count as u32, clicks_count as u32),
+            spent_impressions,
+            spent_clicks,
+            spent_total: spent_impressions + spent_clicks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn create_test_campaign(creatives_count: usize, views_count: u32) -> domain::schemas::ActiveCampaignSchema {
+        let campaign_id = Uuid::new_v4();
+
+        domain::schemas::ActiveCampaignSchema {
+            campaign_id,
+            clicks_limit: 10,
+            end_date: 10,
+            views_count,
+            creatives: (0..creatives_count)
+                .map(|i| domain::schemas::CreativeSchema {
+                    creative_id: Uuid::new_v4(),
+                    campaign_id,
+                    ad_title: format!("Title {}", i),
+                    ad_text: format!("Text {}", i),
+                    image_names: Vec::new(),
+                    prompt_version_id: None,
+                })
+                .collect(),
+            ..domain::schemas::ActiveCampaignSchema::test_fixture()
+        }
+    }
+
+    struct MockCreativesByCampaignsRepo {
+        creatives: Vec<infrastructure::repository::sqlx_lib::CreativeReturningSchema>,
+    }
+
+    #[async_trait]
+    impl IGetCreativesByCampaigns for MockCreativesByCampaignsRepo {
+        async fn get_by_campaigns(
+            &self,
+            campaign_ids: &[Uuid],
+        ) -> infrastructure::repository::RepoResult<Vec<infrastructure::repository::sqlx_lib::CreativeReturningSchema>>
+        {
+            Ok(self
+                .creatives
+                .iter()
+                .filter(|creative| campaign_ids.contains(&creative.campaign_id))
+                .cloned()
+                .collect())
+        }
+    }
+
+    fn create_test_creative(
+        campaign_id: Uuid,
+        ad_title: &str,
+    ) -> infrastructure::repository::sqlx_lib::CreativeReturningSchema {
+        infrastructure::repository::sqlx_lib::CreativeReturningSchema {
+            id: Uuid::new_v4(),
+            campaign_id,
+            ad_title: ad_title.into(),
+            ad_text: "Text".into(),
+            image_names: Vec::new(),
+            prompt_version_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn creatives_of_campaigns_are_grouped_in_order() {
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+        let without_creatives = Uuid::new_v4();
+        let repo = MockCreativesByCampaignsRepo {
+            creatives: vec![
+                create_test_creative(first, "First 1"),
+                create_test_creative(first, "First 2"),
+                create_test_creative(second, "Second 1"),
+            ],
+        };
+
+        let grouped = CreativeService
+            .get_by_campaigns(&[first, second, without_creatives], repo)
+            .await
+            .unwrap();
+
+        let titles = |campaign_id| {
+            grouped[&campaign_id]
+                .iter()
+                .map(|c| c.ad_title.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(titles(first), vec!["First 1", "First 2"]);
+        assert_eq!(titles(second), vec!["Second 1"]);
+        assert!(!grouped.contains_key(&without_creatives));
+    }
+
+    struct MockCreativeStatRepo {
+        stats: Vec<infrastructure::repository::sqlx_lib::CreativeStatReturningSchema>,
+    }
+
+    #[async_trait]
+    impl IGetCreativeStat for MockCreativeStatRepo {
+        async fn get_stat(
+            &self,
+            _campaign_id: Uuid,
+        ) -> infrastructure::repository::RepoResult<
+            Vec<infrastructure::repository::sqlx_lib::CreativeStatReturningSchema>,
+        > {
+            Ok(self.stats.clone())
+        }
+    }
+
+    #[test]
+    fn rotate_without_creatives() {
+        let campaign = create_test_campaign(0, 5);
+
+        assert!(CreativeService.rotate(&campaign).is_none());
+    }
+
+    #[test]
+    fn rotate_cycles_through_creatives() {
+        let titles: Vec<String> = (0..6)
+            .map(|views_count| {
+                let campaign = create_test_campaign(3, views_count);
+                let title = CreativeService.rotate(&campaign).unwrap().ad_title.clone();
+                title
+            })
+            .collect();
+
+        assert_eq!(
+            titles,
+            vec!["Title 0", "Title 1", "Title 2", "Title 0", "Title 1", "Title 2"]
+        );
+    }
+
+    #[tokio::test]
+    async fn stat_of_creative_without_events_is_zero() {
+        let repo = MockCreativeStatRepo {
+            stats: vec![
+                infrastructure::repository::sqlx_lib::CreativeStatReturningSchema {
+                    creative_id: Uuid::new_v4(),
+                    ad_title: "Clicked".into(),
+                    deleted: false,
+                    impressions_count: Some(4),
+                    clicks_count: Some(1),
+                    spent_impressions: Some(bigdecimal::BigDecimal::from(4)),
+                    spent_clicks: Some(bigdecimal::BigDecimal::from(2)),
+                },
+                infrastructure::repository::sqlx_lib::CreativeStatReturningSchema {
+                    creative_id: Uuid::new_v4(),
+                    ad_title: "New".into(),
+                    deleted: false,
+                    impressions_count: None,
+                    clicks_count: None,
+                    spent_impressions: None,
+                    spent_clicks: None,
+                },
+            ],
+        };
+
+        let stats = CreativeService.get_stat(Uuid::new_v4(), repo).await.unwrap();
+
+        assert_eq!(stats[0].conversion, 25.0);
+        assert_eq!(stats[0].spent_total, 6.0);
+        assert_eq!(stats[1].impressions_count, 0);
+        assert_eq!(stats[1].conversion, 0.0);
+    }
+}
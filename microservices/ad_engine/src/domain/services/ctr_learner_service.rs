@@ -0,0 +1,404 @@
+use async_trait::async_trait;
+
+use crate::{domain, infrastructure};
+
+lazy_static::lazy_static! {
+    static ref CTR_ESTIMATES: std::sync::Arc<std::sync::RwLock<CtrEstimates>> =
+        std::sync::Arc::new(std::sync::RwLock::new(CtrEstimates::default()));
+}
+
+/// Trait for retrieving observed impressions and clicks of campaigns
+#[async_trait]
+pub trait IGetCtrStatistics {
+    /// Gets the transaction id up to which recorded events are complete
+    ///
+    /// Every transaction with a lower id is finished, so no event recorded by a
+    /// lower transaction id can appear later. The id never decreases.
+    async fn get_ctr_watermark(&self) -> infrastructure::repository::RepoResult<i64>;
+
+    /// Gets the number of impressions and clicks of every campaign recorded by
+    /// a range of transaction ids
+    ///
+    /// # Arguments
+    /// * `since` - Inclusive start of the range, `None` for all earlier events
+    /// * `until` - Exclusive end of the range
+    ///
+    /// # Returns
+    /// * `RepoResult<Vec<CampaignCtrReturningSchema>>` - Counters of campaigns
+    ///   with at least one event in the range
+    async fn get_campaign_ctr_statistics(
+        &self,
+        since: Option<i64>,
+        until: i64,
+    ) -> infrastructure::repository::RepoResult<Vec<infrastructure::repository::sqlx_lib::CampaignCtrReturningSchema>>;
+
+    /// Gets the number of impressions and clicks of every advertiser in every
+    /// client segment (age bucket, gender and region) recorded by a range of
+    /// transaction ids
+    ///
+    /// # Arguments
+    /// * `since` - Inclusive start of the range, `None` for all earlier events
+    /// * `until` - Exclusive end of the range
+    ///
+    /// # Returns
+    /// * `RepoResult<Vec<SegmentCtrReturningSchema>>` - Counters of segments
+    ///   with at least one event in the range
+    async fn get_segment_ctr_statistics(
+        &self,
+        since: Option<i64>,
+        until: i64,
+    ) -> infrastructure::repository::RepoResult<Vec<infrastructure::repository::sqlx_lib::SegmentCtrReturningSchema>>;
+}
+
+/// Observed impressions and clicks used by the CTR learner
+#[derive(Debug, Default)]
+pub struct CtrEstimates {
+    campaigns: std::collections::HashMap<uuid::Uuid, (u64, u64)>,
+    segments: std::collections::HashMap<(uuid::Uuid, String), (u64, u64)>,
+    /// Transaction id up to which the recorded events are counted
+    watermark: Option<i64>,
+}
+
+impl CtrEstimates {
+    /// Returns the process-wide estimates
+    pub fn global() -> std::sync::Arc<std::sync::RwLock<Self>> {
+        CTR_ESTIMATES.clone()
+    }
+
+    /// Adds campaign and segment counters of new events to the estimates
+    pub fn add(
+        &mut self,
+        campaigns: Vec<infrastructure::repository::sqlx_lib::CampaignCtrReturningSchema>,
+        segments: Vec<infrastructure::repository::sqlx_lib::SegmentCtrReturningSchema>,
+    ) {
+        for stat in campaigns {
+            let counters = self.campaigns.entry(stat.campaign_id).or_default();
+            counters.0 += stat.views as u64;
+            counters.1 += stat.clicks as u64;
+        }
+        for stat in segments {
+            let key = CtrLearnerService::segment_key(stat.age_bucket, &stat.gender, &stat.region);
+            let counters = self.segments.entry((stat.advertiser_id, key)).or_default();
+            counters.0 += stat.views as u64;
+            counters.1 += stat.clicks as u64;
+        }
+    }
+}
+
+/// Service for the online CTR learner
+///
+/// CTR is modelled as a Bernoulli variable with a `Beta(prior_alpha,
+/// prior_beta)` prior. The posterior mean of an advertiser in a client segment
+/// is `(clicks + prior_alpha) / (views + prior_alpha + prior_beta)`. The
+/// posterior of a campaign uses the segment estimate as its prior mean with the
+/// same prior strength, so a new campaign starts from the CTR of its advertiser
+/// in the segment and moves to its own CTR as impressions come in.
+///
+/// The counters live in the memory of the process and are refreshed by a
+/// background task, every refresh adds only the impressions and clicks
+/// recorded since the previous one.
+#[derive(Debug, Clone)]
+pub struct CtrLearnerService {
+    prior_alpha: f64,
+    prior_beta: f64,
+    weight: f64,
+    estimates: std::sync::Arc<std::sync::RwLock<CtrEstimates>>,
+}
+
+impl CtrLearnerService {
+    /// Creates a new CtrLearnerService instance with the process-wide
+    /// estimates
+    ///
+    /// # Arguments
+    /// * `prior_alpha` - Prior number of clicks
+    /// * `prior_beta` - Prior number of impressions without a click
+    /// * `weight` - Weight of the learned CTR in the relevance score (0-1)
+    pub fn new(prior_alpha: f64, prior_beta: f64, weight: f64) -> Self {
+        Self {
+            prior_alpha,
+            prior_beta,
+            weight,
+            estimates: CtrEstimates::global(),
+        }
+    }
+
+    /// Replaces the estimates of the service
+    #[cfg(test)]
+    pub fn with_estimates(mut self, estimates: std::sync::Arc<std::sync::RwLock<CtrEstimates>>) -> Self {
+        self.estimates = estimates;
+        self
+    }
+
+    /// Adds the impressions and clicks recorded since the previous refresh to
+    /// the learned counters
+    ///
+    /// The counters and the watermark are updated at once, so a poisoned lock
+    /// can't hold partially updated counters and is taken over. A refresh that
+    /// raced with another one is dropped, so no event is counted twice.
+    ///
+    /// # Arguments
+    /// * `estimates` - Estimates to update, [`CtrEstimates::global`] outside
+    ///   of tests
+    /// * `repo` - Repository of recorded impressions and clicks
+    pub async fn learn<R>(estimates: &std::sync::RwLock<CtrEstimates>, repo: R) -> domain::services::ServiceResult<()>
+    where
+        R: IGetCtrStatistics,
+    {
+        let since = estimates
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .watermark;
+        let until = repo
+            .get_ctr_watermark()
+            .await
+            .map_err(domain::services::ServiceError::Repository)?;
+        let campaigns = repo
+            .get_campaign_ctr_statistics(since, until)
+            .await
+            .map_err(domain::services::ServiceError::Repository)?;
+        let segments = repo
+            .get_segment_ctr_statistics(since, until)
+            .await
+            .map_err(domain::services::ServiceError::Repository)?;
+
+        let mut estimates = estimates.write().unwrap_or_else(std::sync::PoisonError::into_inner);
+        if estimates.watermark == since {
+            estimates.add(campaigns, segments);
+            estimates.watermark = Some(until);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the key of a client segment, the same as in the fallback
+    /// relevance scores: age bucket, gender and region
+    ///
+    /// # Arguments
+    /// * `age` - Client age
+    /// * `gender` - Client gender
+    /// * `location` - Client location, only the top level is used
+    pub fn segment(age: u8, gender: &str, location: &str) -> String {
+        Self::segment_key(
+            (age / 10).into(),
+            gender,
+            location.split(',').next().unwrap_or_default(),
+        )
+    }
+
+    pub(super) fn segment_key(age_bucket: i32, gender: &str, region: &str) -> String {
+        format!(
+            "{}:{}:{}",
+            age_bucket,
+            gender.to_uppercase(),
+            region.trim().to_lowercase()
+        )
+    }
+
+    /// Estimates the CTR of a campaign for a client segment with the learned
+    /// counters
+    ///
+    /// # Arguments
+    /// * `campaign_id` - UUID of the campaign
+    /// * `advertiser_id` - UUID of the campaign advertiser
+    /// * `segment` - Key of the client segment
+    pub fn estimate(&self, campaign_id: &uuid::Uuid, advertiser_id: &uuid::Uuid, segment: &str) -> f64 {
+        let (alpha, beta) = self.posterior(campaign_id, advertiser_id, segment);
+        alpha / (alpha + beta)
+    }
+
+    /// Returns the `(alpha, beta)` parameters of the Beta posterior of a
+    /// campaign CTR for a client segment with the learned counters
+    ///
+    /// # Arguments
+    /// * `campaign_id` - UUID of the campaign
+    /// * `advertiser_id` - UUID of the campaign advertiser
+    /// * `segment` - Key of the client segment
+    pub fn posterior(&self, campaign_id: &uuid::Uuid, advertiser_id: &uuid::Uuid, segment: &str) -> (f64, f64) {
+        let estimates = self.estimates.read().unwrap_or_else(std::sync::PoisonError::into_inner);
+        self.posterior_with(&estimates, campaign_id, advertiser_id, segment)
+    }
+
+    /// Blends a relevance score with the learned CTR
+    ///
+    /// # Arguments
+    /// * `score` - Relevance score from ml scores
+    /// * `estimate` - Learned CTR
+    pub fn blend(&self, score: f64, estimate: f64) -> f64 {
+        (1.0 - self.weight) * score + self.weight * estimate
+    }
+
+    fn posterior_with(
+        &self,
+        estimates: &CtrEstimates,
+        campaign_id: &uuid::Uuid,
+        advertiser_id: &uuid::Uuid,
+        segment: &str,
+    ) -> (f64, f64) {
+        let strength = self.prior_alpha + self.prior_beta;
+
+        let (views, clicks) = estimates
+            .segments
+            .get(&(*advertiser_id, segment.to_string()))
+            .copied()
+            .unwrap_or_default();
+        let (alpha, beta) = Self::update(views, clicks, self.prior_alpha, self.prior_beta);
+        let segment_ctr = alpha / (alpha + beta);
+
+        let (views, clicks) = estimates.campaigns.get(campaign_id).copied().unwrap_or_default();
+        Self::update(views, clicks, segment_ctr * strength, (1.0 - segment_ctr) * strength)
+    }
+
+    /// Updates a `Beta(alpha, beta)` prior with observed impressions and clicks
+    fn update(views: u64, clicks: u64, alpha: f64, beta: f64) -> (f64, f64) {
+        let clicks = clicks.min(views);
+        (alpha + clicks as f64, beta + (views - clicks) as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::infrastructure::repository::sqlx_lib::{CampaignCtrReturningSchema, SegmentCtrReturningSchema};
+
+    fn create_test_estimates(
+        campaign_id: Uuid,
+        advertiser_id: Uuid,
+        campaign: (i64, i64),
+        segment: (i64, i64),
+    ) -> CtrEstimates {
+        let mut estimates = CtrEstimates::default();
+        estimates.add(
+            vec![CampaignCtrReturningSchema {
+                campaign_id,
+                views: campaign.0,
+                clicks: campaign.1,
+            }],
+            vec![SegmentCtrReturningSchema {
+                advertiser_id,
+                age_bucket: 2,
+                gender: "MALE".into(),
+                region: "moscow".into(),
+                views: segment.0,
+                clicks: segment.1,
+            }],
+        );
+        estimates
+    }
+
+    fn posterior_mean(
+        service: &CtrLearnerService,
+        estimates: &CtrEstimates,
+        campaign_id: &Uuid,
+        advertiser_id: &Uuid,
+        segment: &str,
+    ) -> f64 {
+        let (alpha, beta) = service.posterior_with(estimates, campaign_id, advertiser_id, segment);
+        alpha / (alpha + beta)
+    }
+
+    #[test]
+    fn segment_uses_age_bucket_gender_and_region() {
+        assert_eq!(
+            CtrLearnerService::segment(27, "male", " Moscow , mcad"),
+            "2:MALE:moscow"
+        );
+    }
+
+    #[test]
+    fn estimate_without_data_is_prior_mean() {
+        let service = CtrLearnerService::new(1.0, 9.0, 0.5);
+
+        let estimate = posterior_mean(
+            &service,
+            &CtrEstimates::default(),
+            &Uuid::new_v4(),
+            &Uuid::new_v4(),
+            "2:MALE:moscow",
+        );
+
+        assert!((estimate - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn new_campaign_starts_from_segment_ctr() {
+        let service = CtrLearnerService::new(1.0, 9.0, 0.5);
+        let (campaign_id, advertiser_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let estimates = create_test_estimates(campaign_id, advertiser_id, (0, 0), (990, 499));
+
+        let estimate = posterior_mean(&service, &estimates, &campaign_id, &advertiser_id, "2:MALE:moscow");
+        assert!((estimate - 0.5).abs() < 1e-9);
+
+        let other_segment = posterior_mean(&service, &estimates, &campaign_id, &advertiser_id, "3:MALE:moscow");
+        assert!((other_segment - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn campaign_ctr_moves_estimate_away_from_segment() {
+        let service = CtrLearnerService::new(1.0, 9.0, 0.5);
+        let (campaign_id, advertiser_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let estimates = create_test_estimates(campaign_id, advertiser_id, (90, 81), (990, 499));
+
+        let estimate = posterior_mean(&service, &estimates, &campaign_id, &advertiser_id, "2:MALE:moscow");
+
+        assert!((estimate - 0.86).abs() < 1e-9);
+        assert!((service.blend(0.2, estimate) - 0.53).abs() < 1e-9);
+    }
+
+    struct WindowedCtrRepo {
+        campaign_id: Uuid,
+        watermarks: Vec<i64>,
+        requested_since: std::sync::Mutex<Vec<Option<i64>>>,
+    }
+
+    #[async_trait]
+    impl IGetCtrStatistics for &WindowedCtrRepo {
+        async fn get_ctr_watermark(&self) -> infrastructure::repository::RepoResult<i64> {
+            let calls = self.requested_since.lock().unwrap().len();
+            Ok(self.watermarks[calls])
+        }
+
+        async fn get_campaign_ctr_statistics(
+            &self,
+            since: Option<i64>,
+            _until: i64,
+        ) -> infrastructure::repository::RepoResult<Vec<CampaignCtrReturningSchema>> {
+            self.requested_since.lock().unwrap().push(since);
+            Ok(vec![CampaignCtrReturningSchema {
+                campaign_id: self.campaign_id,
+                views: 10,
+                clicks: 1,
+            }])
+        }
+
+        async fn get_segment_ctr_statistics(
+            &self,
+            _since: Option<i64>,
+            _until: i64,
+        ) -> infrastructure::repository::RepoResult<Vec<SegmentCtrReturningSchema>> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn learn_adds_only_events_after_watermark() {
+        let (first, second) = (100, 160);
+        let repo = WindowedCtrRepo {
+            campaign_id: Uuid::new_v4(),
+            watermarks: vec![first, second],
+            requested_since: std::sync::Mutex::new(Vec::new()),
+        };
+
+        let estimates = std::sync::RwLock::new(CtrEstimates::default());
+
+        CtrLearnerService::learn(&estimates, &repo).await.unwrap();
+        CtrLearnerService::learn(&estimates, &repo).await.unwrap();
+
+        let requested_since = repo.requested_since.lock().unwrap().clone();
+        assert_eq!(requested_since, vec![None, Some(first)]);
+        let estimates = estimates.read().unwrap();
+        assert_eq!(estimates.campaigns[&repo.campaign_id], (20, 2));
+        assert_eq!(estimates.watermark, Some(second));
+    }
+}
@@ -22,9 +22,9 @@ pub enum ServiceError {
     Cash(String),
 
     /// Error that occurs when GPT service fails to provide a response.
-    /// Contains information about why the GPT request failed.
+    /// Wraps the LLM error with the status and message of the provider.
     #[error("Gpt not response - {0}")]
-    GptNotResponse(String),
+    GptNotResponse(infrastructure::gpt::LlmError),
 
     /// Error that occurs when content is flagged by content
     /// moderation/censorship rules. Contains details about which content
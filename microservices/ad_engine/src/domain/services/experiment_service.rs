@@ -0,0 +1,417 @@
+use async_trait::async_trait;
+use bigdecimal::ToPrimitive;
+
+use crate::{domain, infrastructure};
+
+/// Trait for creating an A/B experiment
+#[async_trait]
+pub trait ICreateExperiment {
+    /// Creates a running experiment
+    ///
+    /// # Arguments
+    /// * `experiment` - Name and arms of the experiment
+    /// * `start_date` - Day the experiment is started
+    ///
+    /// # Returns
+    /// * `RepoResult<ExperimentReturningSchema>` - Created experiment,
+    ///   `UniqueConstraint` when another experiment is running
+    async fn create(
+        &self,
+        experiment: domain::schemas::ExperimentCreateRequest,
+        start_date: u32,
+    ) -> infrastructure::repository::RepoResult<infrastructure::repository::sqlx_lib::ExperimentReturningSchema>;
+}
+
+/// Trait for stopping an A/B experiment
+#[async_trait]
+pub trait IStopExperiment {
+    /// Stops a running experiment
+    ///
+    /// # Arguments
+    /// * `experiment_id` - UUID of the experiment
+    /// * `end_date` - Day the experiment is stopped
+    ///
+    /// # Returns
+    /// * `RepoResult<ExperimentReturningSchema>` - Stopped experiment,
+    ///   `ObjDoesNotExists` when there is no such running experiment
+    async fn stop(
+        &self,
+        experiment_id: uuid::Uuid,
+        end_date: u32,
+    ) -> infrastructure::repository::RepoResult<infrastructure::repository::sqlx_lib::ExperimentReturningSchema>;
+}
+
+/// Trait for retrieving all A/B experiments
+#[async_trait]
+pub trait IGetExperimentList {
+    /// Gets all experiments, the latest first
+    async fn get_list(
+        &self,
+    ) -> infrastructure::repository::RepoResult<Vec<infrastructure::repository::sqlx_lib::ExperimentReturningSchema>>;
+}
+
+/// Trait for retrieving the running A/B experiment
+#[async_trait]
+pub trait IGetActiveExperiment {
+    /// Gets the running experiment, `None` when no experiment is running
+    async fn get_active(
+        &self,
+    ) -> infrastructure::repository::RepoResult<Option<infrastructure::repository::sqlx_lib::ExperimentReturningSchema>>;
+}
+
+/// Trait for retrieving an A/B experiment by ID
+#[async_trait]
+pub trait IGetExperimentById {
+    /// Gets an experiment
+    ///
+    /// # Arguments
+    /// * `experiment_id` - UUID of the experiment
+    async fn get_by_id(
+        &self,
+        experiment_id: uuid::Uuid,
+    ) -> infrastructure::repository::RepoResult<infrastructure::repository::sqlx_lib::ExperimentReturningSchema>;
+}
+
+/// Trait for retrieving statistics of an A/B experiment
+#[async_trait]
+pub trait IGetExperimentStat {
+    /// Gets impressions, clicks and spend of every arm with recorded events
+    ///
+    /// # Arguments
+    /// * `experiment_id` - UUID of the experiment
+    async fn get_stat(
+        &self,
+        experiment_id: uuid::Uuid,
+    ) -> infrastructure::repository::RepoResult<Vec<infrastructure::repository::sqlx_lib::ExperimentStatReturningSchema>>;
+}
+
+/// Service for A/B experiments of the ranking configuration
+///
+/// Every arm of an experiment overrides the weights or the strategy of
+/// [`domain::services::AdsService`]. Clients are assigned to arms
+/// deterministically by a hash of the experiment and client ids, weighted by
+/// the traffic of the arms. The arm is recorded on every impression and click,
+/// so arms are compared by the recorded events.
+#[derive(Debug)]
+pub struct ExperimentService;
+
+impl ExperimentService {
+    /// Starts a new experiment
+    ///
+    /// # Arguments
+    /// * `experiment` - Name and arms of the experiment
+    /// * `start_date` - Current day
+    /// * `repo` - Repository implementation for data access
+    #[tracing::instrument(name = "`ExperimentService` create experiment", skip(repo))]
+    pub async fn create<R: ICreateExperiment>(
+        &self,
+        experiment: domain::schemas::ExperimentCreateRequest,
+        start_date: u32,
+        repo: R,
+    ) -> domain::services::ServiceResult<domain::schemas::ExperimentSchema> {
+        repo.create(experiment, start_date)
+            .await
+            .map(|experiment| experiment.into())
+            .map_err(|e| domain::services::ServiceError::Repository(e))
+    }
+
+    /// Stops a running experiment, its statistics stay available
+    ///
+    /// # Arguments
+    /// * `experiment_id` - UUID of the experiment
+    /// * `end_date` - Current day
+    /// * `repo` - Repository implementation for data access
+    #[tracing::instrument(name = "`ExperimentService` stop experiment", skip(repo))]
+    pub async fn stop<R: IStopExperiment>(
+        &self,
+        experiment_id: uuid::Uuid,
+        end_date: u32,
+        repo: R,
+    ) -> domain::services::ServiceResult<domain::schemas::ExperimentSchema> {
+        repo.stop(experiment_id, end_date)
+            .await
+            .map(|experiment| experiment.into())
+            .map_err(|e| domain::services::ServiceError::Repository(e))
+    }
+
+    /// Gets all experiments, the latest first
+    ///
+    /// # Arguments
+    /// * `repo` - Repository implementation for data access
+    #[tracing::instrument(name = "`ExperimentService` get list of experiments", skip(repo))]
+    pub async fn get_list<R: IGetExperimentList>(
+        &self,
+        repo: R,
+    ) -> domain::services::ServiceResult<Vec<domain::schemas::ExperimentSchema>> {
+        repo.get_list()
+            .await
+            .map(|experiments| experiments.into_iter().map(|experiment| experiment.into()).collect())
+            .map_err(|e| domain::services::ServiceError::Repository(e))
+    }
+
+    /// Gets the running experiment
+    ///
+    /// # Arguments
+    /// * `repo` - Repository implementation for data access
+    #[tracing::instrument(name = "`ExperimentService` get active experiment", skip(repo))]
+    pub async fn get_active<R: IGetActiveExperiment>(
+        &self,
+        repo: R,
+    ) -> domain::services::ServiceResult<Option<domain::schemas::ExperimentSchema>> {
+        repo.get_active()
+            .await
+            .map(|experiment| experiment.map(|experiment| experiment.into()))
+            .map_err(|e| domain::services::ServiceError::Repository(e))
+    }
+
+    /// Assigns a client to an arm of an experiment
+    ///
+    /// The FNV-1a hash of the experiment and client ids is taken modulo the
+    /// total traffic of the arms, so the assignment doesn't depend on the
+    /// process or the order of requests.
+    ///
+    /// # Arguments
+    /// * `experiment` - Experiment to assign the client in
+    /// * `client_id` - UUID of the client
+    ///
+    /// # Returns
+    /// The arm of the client, `None` for an experiment without arms
+    pub fn assign<'e>(
+        &self,
+        experiment: &'e domain::schemas::ExperimentSchema,
+        client_id: &uuid::Uuid,
+    ) -> Option<&'e domain::schemas::ExperimentArmSchema> {
+        let total_traffic: u64 = experiment.arms.iter().map(|arm| arm.traffic as u64).sum();
+        if total_traffic == 0 {
+            return None;
+        }
+
+        let hash = experiment
+            .experiment_id
+            .as_bytes()
+            .iter()
+            .chain(client_id.as_bytes())
+            .fold(0xcbf29ce484222325_u64, |hash, byte| {
+                (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+            });
+
+        let mut bucket = hash % total_traffic;
+        experiment.arms.iter().find(|arm| {
+            if bucket < arm.traffic as u64 {
+                return true;
+            }
+            bucket -= arm.traffic as u64;
+            false
+        })
+    }
+
+    /// Compares the arms of an experiment by impressions, clicks, CTR and
+    /// revenue
+    ///
+    /// # Arguments
+    /// * `experiment_id` - UUID of the experiment
+    /// * `repo` - Repository implementation for data access
+    ///
+    /// # Returns
+    /// Statistics of every arm, arms without events have zero counters
+    #[tracing::instrument(name = "`ExperimentService` get stat of experiment", skip(repo))]
+    pub async fn get_stat<R: IGetExperimentById + IGetExperimentStat>(
+        &self,
+        experiment_id: uuid::Uuid,
+        repo: R,
+    ) -> domain::services::ServiceResult<domain::schemas::ExperimentStatResponse> {
+        let experiment: domain::schemas::ExperimentSchema = repo
+            .get_by_id(experiment_id)
+            .await
+            .map_err(|e| domain::services::ServiceError::Repository(e))?
+            .into();
+        let mut stats: std::collections::HashMap<String, domain::schemas::ExperimentArmStatSchema> = repo
+            .get_stat(experiment_id)
+            .await
+            .map_err(|e| domain::services::ServiceError::Repository(e))?
+            .into_iter()
+            .map(|stat| {
+                let stat: domain::schemas::ExperimentArmStatSchema = stat.into();
+                (stat.arm.clone(), stat)
+            })
+            .collect();
+
+        Ok(domain::schemas::ExperimentStatResponse {
+            experiment_id: experiment.experiment_id,
+            name: experiment.name,
+            arms: experiment
+                .arms
+                .into_iter()
+                .map(|arm| {
+                    stats
+                        .remove(&arm.name)
+                        .unwrap_or(domain::schemas::ExperimentArmStatSchema {
+                            arm: arm.name,
+                            ..Default::default()
+                        })
+                })
+                .collect(),
+        })
+    }
+}
+
+impl From<infrastructure::repository::sqlx_lib::ExperimentReturningSchema> for domain::schemas::ExperimentSchema {
+    fn from(experiment: infrastructure::repository::sqlx_lib::ExperimentReturningSchema) -> Self {
+        Self {
+            experiment_id: experiment.id,
+            name: experiment.name,
+            arms: serde_json::from_value(experiment.arms).unwrap_or_default(),
+            start_date: experiment.start_date as u32,
+            end_date: experiment.end_date.map(|end_date| end_date as u32),
+        }
+    }
+}
+
+impl From<infrastructure::repository::sqlx_lib::ExperimentStatReturningSchema>
+    for domain::schemas::ExperimentArmStatSchema
+{
+    fn from(stat: infrastructure::repository::sqlx_lib::ExperimentStatReturningSchema) -> Self {
+        let impressions_count = stat.impressions_count.unwrap_or(0);
+        let clicks_count = stat.clicks_count.unwrap_or(0);
+        let spent_impressions = stat.spent_impressions.and_then(|v| v.to_f64()).unwrap_or(0.0);
+        let spent_clicks = stat.spent_clicks.and_then(|v| v.to_f64()).unwrap_or(0.0);
+
+        Self {
+            arm: stat.arm.unwrap_or_default(),
+            impressions_count: impressions_count as u32,
+            clicks_count: clicks_count as u32,
+            conversion: domain::services::AggregateStatService
+                .calculate_conversion(impressions_count as u32, clicks_count as u32),
+            spent_impressions,
+            spent_clicks,
+            spent_total: spent_impressions + spent_clicks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn create_test_arm(name: &str, traffic: u32) -> domain::schemas::ExperimentArmSchema {
+        domain::schemas::ExperimentArmSchema {
+            name: name.into(),
+            weight_profit: None,
+            weight_relevance: None,
+            weight_fulfillment: None,
+            weight_time_left: None,
+            strategy: None,
+            traffic,
+        }
+    }
+
+    fn create_test_experiment(arms: Vec<domain::schemas::ExperimentArmSchema>) -> domain::schemas::ExperimentSchema {
+        domain::schemas::ExperimentSchema {
+            experiment_id: Uuid::new_v4(),
+            name: "Test experiment".into(),
+            arms,
+            start_date: 0,
+            end_date: None,
+        }
+    }
+
+    struct MockExperimentStatRepo {
+        experiment_id: Uuid,
+        stats: Vec<infrastructure::repository::sqlx_lib::ExperimentStatReturningSchema>,
+    }
+
+    #[async_trait]
+    impl IGetExperimentById for MockExperimentStatRepo {
+        async fn get_by_id(
+            &self,
+            experiment_id: Uuid,
+        ) -> infrastructure::repository::RepoResult<infrastructure::repository::sqlx_lib::ExperimentReturningSchema>
+        {
+            if experiment_id != self.experiment_id {
+                return Err(infrastructure::repository::RepoError::ObjDoesNotExists(
+                    "experiment".into(),
+                ));
+            }
+
+            Ok(infrastructure::repository::sqlx_lib::ExperimentReturningSchema {
+                id: experiment_id,
+                name: "Test experiment".into(),
+                arms: serde_json::json!([{"name": "control"}, {"name": "relevance", "weight_relevance": 0.6}]),
+                start_date: 1,
+                end_date: None,
+            })
+        }
+    }
+
+    #[async_trait]
+    impl IGetExperimentStat for MockExperimentStatRepo {
+        async fn get_stat(
+            &self,
+            _experiment_id: Uuid,
+        ) -> infrastructure::repository::RepoResult<
+            Vec<infrastructure::repository::sqlx_lib::ExperimentStatReturningSchema>,
+        > {
+            Ok(self.stats.clone())
+        }
+    }
+
+    #[test]
+    fn assignment_is_deterministic() {
+        let service = ExperimentService;
+        let experiment = create_test_experiment(vec![create_test_arm("a", 1), create_test_arm("b", 1)]);
+        let client_id = Uuid::new_v4();
+
+        let arm = service.assign(&experiment, &client_id).unwrap();
+        for _ in 0..10 {
+            assert_eq!(service.assign(&experiment, &client_id).unwrap().name, arm.name);
+        }
+    }
+
+    #[test]
+    fn assignment_follows_traffic_split() {
+        let service = ExperimentService;
+        let experiment = create_test_experiment(vec![create_test_arm("a", 1), create_test_arm("b", 3)]);
+
+        let clients = 20_000;
+        let in_b = (0..clients)
+            .filter(|_| service.assign(&experiment, &Uuid::new_v4()).unwrap().name == "b")
+            .count();
+
+        let share = in_b as f64 / clients as f64;
+        assert!((share - 0.75).abs() < 0.02, "{share}");
+    }
+
+    #[test]
+    fn assignment_without_arms_is_none() {
+        let experiment = create_test_experiment(vec![]);
+
+        assert!(ExperimentService.assign(&experiment, &Uuid::new_v4()).is_none());
+    }
+
+    #[tokio::test]
+    async fn stat_lists_every_arm_in_experiment_order() {
+        let experiment_id = Uuid::new_v4();
+        let repo = MockExperimentStatRepo {
+            experiment_id,
+            stats: vec![infrastructure::repository::sqlx_lib::ExperimentStatReturningSchema {
+                arm: Some("relevance".into()),
+                impressions_count: Some(4),
+                clicks_count: Some(1),
+                spent_impressions: Some(bigdecimal::BigDecimal::from(8)),
+                spent_clicks: Some(bigdecimal::BigDecimal::from(5)),
+            }],
+        };
+
+        let stat = ExperimentService.get_stat(experiment_id, repo).await.unwrap();
+
+        assert_eq!(stat.arms.len(), 2);
+        assert_eq!(stat.arms[0].arm, "control");
+        assert_eq!(stat.arms[0].impressions_count, 0);
+        assert_eq!(stat.arms[1].arm, "relevance");
+        assert_eq!(stat.arms[1].clicks_count, 1);
+        assert!((stat.arms[1].conversion - 25.0).abs() < 1e-9);
+        assert!((stat.arms[1].spent_total - 13.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,202 @@
+use rand::{Rng, SeedableRng};
+use rand_distr::Distribution;
+
+/// Exploration policy used in the ranking of campaigns
+///
+/// * `epsilon_greedy` - with probability `epsilon` a random campaign is moved
+///   to the top of the ranking
+/// * `thompson` - the learned CTR of every campaign is sampled from its Beta
+///   posterior instead of taking the posterior mean, requires the CTR learner
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExplorationPolicy {
+    #[default]
+    EpsilonGreedy,
+    Thompson,
+}
+
+/// Random generator of the exploration shared by all requests of the process
+pub type ExplorationRng = std::sync::Arc<std::sync::Mutex<rand::rngs::StdRng>>;
+
+/// Service for exploring campaigns without enough history
+///
+/// The ranking is deterministic without exploration, so new campaigns never
+/// beat campaigns with a good history. The service randomizes the ranking
+/// according to its policy with a seedable random generator. The generator is
+/// created once for the process and shared by the services of all requests,
+/// so a fixed seed makes the sequence of rankings since the start
+/// reproducible.
+#[derive(Debug)]
+pub struct ExplorationService {
+    policy: ExplorationPolicy,
+    epsilon: f64,
+    rng: ExplorationRng,
+}
+
+impl ExplorationService {
+    /// Creates a new ExplorationService instance
+    ///
+    /// # Arguments
+    /// * `policy` - Exploration policy
+    /// * `epsilon` - Probability of exploration for the `epsilon_greedy` policy
+    ///   (0-1)
+    /// * `rng` - Random generator of the process, see [`Self::create_rng`]
+    pub fn new(policy: ExplorationPolicy, epsilon: f64, rng: ExplorationRng) -> Self {
+        Self {
+            policy,
+            epsilon: epsilon.clamp(0.0, 1.0),
+            rng,
+        }
+    }
+
+    /// Creates the random generator of the process
+    ///
+    /// # Arguments
+    /// * `seed` - Seed of the random generator, taken from the OS when `None`
+    pub fn create_rng(seed: Option<u64>) -> ExplorationRng {
+        let rng = match seed {
+            Some(seed) => rand::rngs::StdRng::seed_from_u64(seed),
+            None => rand::rngs::StdRng::from_entropy(),
+        };
+        std::sync::Arc::new(std::sync::Mutex::new(rng))
+    }
+
+    /// Returns the exploration policy
+    pub fn policy(&self) -> ExplorationPolicy {
+        self.policy
+    }
+
+    /// Moves a random item to the top of a ranking with probability `epsilon`
+    /// for the `epsilon_greedy` policy, other policies keep the ranking
+    ///
+    /// # Arguments
+    /// * `ranking` - Items ordered from the best
+    ///
+    /// # Returns
+    /// `true` if the ranking was changed
+    pub fn explore<T>(&self, ranking: &mut [T]) -> bool {
+        if self.policy != ExplorationPolicy::EpsilonGreedy || ranking.len() < 2 {
+            return false;
+        }
+
+        let mut rng = self.lock_rng();
+        if !rng.gen_bool(self.epsilon) {
+            return false;
+        }
+
+        let position = rng.gen_range(0..ranking.len());
+        ranking[..=position].rotate_right(1);
+        position != 0
+    }
+
+    /// Samples a value from the `Beta(alpha, beta)` distribution, the mean
+    /// for invalid parameters
+    ///
+    /// # Arguments
+    /// * `alpha` - First shape parameter, more than 0
+    /// * `beta` - Second shape parameter, more than 0
+    pub fn sample_beta(&self, alpha: f64, beta: f64) -> f64 {
+        match rand_distr::Beta::new(alpha, beta) {
+            Ok(distribution) => distribution.sample(&mut *self.lock_rng()),
+            Err(_) => alpha / (alpha + beta),
+        }
+    }
+
+    /// Locks the random generator, a panic while it was locked can't leave it
+    /// in an invalid state, so a poisoned lock is taken over
+    fn lock_rng(&self) -> std::sync::MutexGuard<'_, rand::rngs::StdRng> {
+        self.rng.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_gives_same_samples() {
+        let first = ExplorationService::new(
+            ExplorationPolicy::Thompson,
+            0.0,
+            ExplorationService::create_rng(Some(42)),
+        );
+        let second = ExplorationService::new(
+            ExplorationPolicy::Thompson,
+            0.0,
+            ExplorationService::create_rng(Some(42)),
+        );
+
+        let first_samples: Vec<f64> = (0..10).map(|_| first.sample_beta(2.0, 5.0)).collect();
+        let second_samples: Vec<f64> = (0..10).map(|_| second.sample_beta(2.0, 5.0)).collect();
+
+        assert_eq!(first_samples, second_samples);
+    }
+
+    #[test]
+    fn services_sharing_rng_continue_its_sequence() {
+        let single = ExplorationService::new(
+            ExplorationPolicy::Thompson,
+            0.0,
+            ExplorationService::create_rng(Some(42)),
+        );
+        let expected: Vec<f64> = (0..4).map(|_| single.sample_beta(2.0, 5.0)).collect();
+
+        let rng = ExplorationService::create_rng(Some(42));
+        let samples: Vec<f64> = (0..4)
+            .map(|_| ExplorationService::new(ExplorationPolicy::Thompson, 0.0, rng.clone()).sample_beta(2.0, 5.0))
+            .collect();
+
+        assert_eq!(samples, expected);
+        assert_ne!(samples[0], samples[1]);
+    }
+
+    #[test]
+    fn beta_samples_converge_to_mean() {
+        let service = ExplorationService::new(
+            ExplorationPolicy::Thompson,
+            0.0,
+            ExplorationService::create_rng(Some(7)),
+        );
+
+        for (alpha, beta) in [(2.0, 8.0), (0.5, 0.5), (30.0, 10.0)] {
+            let samples: Vec<f64> = (0..20_000).map(|_| service.sample_beta(alpha, beta)).collect();
+            let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+
+            assert!(samples.iter().all(|sample| (0.0..=1.0).contains(sample)));
+            assert!((mean - alpha / (alpha + beta)).abs() < 0.01, "{alpha} {beta} {mean}");
+        }
+    }
+
+    #[test]
+    fn epsilon_controls_exploration() {
+        let never = ExplorationService::new(
+            ExplorationPolicy::EpsilonGreedy,
+            0.0,
+            ExplorationService::create_rng(Some(1)),
+        );
+        let always = ExplorationService::new(
+            ExplorationPolicy::EpsilonGreedy,
+            1.0,
+            ExplorationService::create_rng(Some(1)),
+        );
+        let thompson = ExplorationService::new(
+            ExplorationPolicy::Thompson,
+            1.0,
+            ExplorationService::create_rng(Some(1)),
+        );
+
+        let mut ranking = [1, 2, 3, 4];
+        assert!(!never.explore(&mut ranking));
+        assert!(!thompson.explore(&mut ranking));
+        assert_eq!(ranking, [1, 2, 3, 4]);
+
+        let explored = (0..100).filter(|_| always.explore(&mut [1, 2, 3, 4])).count();
+        assert!(explored > 50);
+
+        let mut ranking = [1, 2, 3, 4];
+        always.explore(&mut ranking);
+        let mut sorted = ranking;
+        sorted.sort();
+        assert_eq!(sorted, [1, 2, 3, 4]);
+    }
+}
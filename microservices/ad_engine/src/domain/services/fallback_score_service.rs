@@ -0,0 +1,204 @@
+use async_trait::async_trait;
+
+use crate::{domain, infrastructure};
+
+lazy_static::lazy_static! {
+    static ref FALLBACK_SCORES: std::sync::Arc<std::sync::RwLock<FallbackScores>> =
+        std::sync::Arc::new(std::sync::RwLock::new(FallbackScores::default()));
+}
+
+/// Trait for retrieving statistics of the fallback relevance of advertisers
+#[async_trait]
+pub trait IGetFallbackScoreStatistics {
+    /// Gets the average ML score of every advertiser in every client segment
+    /// (age bucket, gender and region)
+    ///
+    /// # Returns
+    /// * `RepoResult<Vec<SegmentScoreReturningSchema>>` - Scores of segments
+    ///   with at least one ML score
+    async fn get_segment_scores(
+        &self,
+    ) -> infrastructure::repository::RepoResult<Vec<infrastructure::repository::sqlx_lib::SegmentScoreReturningSchema>>;
+
+    /// Gets the average ML score and the observed CTR of every advertiser
+    ///
+    /// # Returns
+    /// * `RepoResult<Vec<AdvertiserScoreReturningSchema>>` - Scores of
+    ///   advertisers with at least one ML score or impression
+    async fn get_advertiser_scores(
+        &self,
+    ) -> infrastructure::repository::RepoResult<Vec<infrastructure::repository::sqlx_lib::AdvertiserScoreReturningSchema>>;
+}
+
+/// Precomputed fallback relevance of advertisers
+#[derive(Debug, Default)]
+pub struct FallbackScores {
+    segments: std::collections::HashMap<(uuid::Uuid, String), f64>,
+    advertisers: std::collections::HashMap<uuid::Uuid, f64>,
+}
+
+impl FallbackScores {
+    /// Returns the process-wide fallback scores
+    pub fn global() -> std::sync::Arc<std::sync::RwLock<Self>> {
+        FALLBACK_SCORES.clone()
+    }
+
+    /// Builds the fallback scores from segment and advertiser statistics, an
+    /// advertiser without ML scores falls back to its observed CTR
+    pub fn new(
+        segments: Vec<infrastructure::repository::sqlx_lib::SegmentScoreReturningSchema>,
+        advertisers: Vec<infrastructure::repository::sqlx_lib::AdvertiserScoreReturningSchema>,
+    ) -> Self {
+        Self {
+            segments: segments
+                .into_iter()
+                .map(|stat| {
+                    (
+                        (
+                            stat.advertiser_id,
+                            domain::services::CtrLearnerService::segment_key(
+                                stat.age_bucket,
+                                &stat.gender,
+                                &stat.region,
+                            ),
+                        ),
+                        stat.score,
+                    )
+                })
+                .collect(),
+            advertisers: advertisers
+                .into_iter()
+                .filter_map(|stat| stat.score.or(stat.ctr).map(|score| (stat.advertiser_id, score)))
+                .collect(),
+        }
+    }
+
+    fn score(&self, advertiser_id: &uuid::Uuid, segment: &str) -> Option<f64> {
+        self.segments
+            .get(&(*advertiser_id, segment.to_string()))
+            .or_else(|| self.advertisers.get(advertiser_id))
+            .copied()
+    }
+}
+
+/// Service for the cold-start relevance of advertisers without an ML score
+/// for a client
+///
+/// The fallback is the average score of the advertiser in the client's segment
+/// (age bucket, gender and region), then the average score of the advertiser,
+/// then the observed CTR of the advertiser. The aggregates are too expensive
+/// for every request, so they live in the memory of the process and are
+/// refreshed by a background task.
+#[derive(Debug, Clone)]
+pub struct FallbackScoreService {
+    scores: std::sync::Arc<std::sync::RwLock<FallbackScores>>,
+}
+
+impl Default for FallbackScoreService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FallbackScoreService {
+    /// Creates a new FallbackScoreService instance with the process-wide
+    /// fallback scores
+    pub fn new() -> Self {
+        Self {
+            scores: FallbackScores::global(),
+        }
+    }
+
+    /// Replaces the fallback scores of the service
+    #[cfg(test)]
+    pub fn with_scores(mut self, scores: std::sync::Arc<std::sync::RwLock<FallbackScores>>) -> Self {
+        self.scores = scores;
+        self
+    }
+
+    /// Replaces the fallback scores with fresh statistics from the repository
+    ///
+    /// # Arguments
+    /// * `repo` - Repository of ML scores and recorded events
+    pub async fn refresh<R>(&self, repo: R) -> domain::services::ServiceResult<()>
+    where
+        R: IGetFallbackScoreStatistics,
+    {
+        let segments = repo
+            .get_segment_scores()
+            .await
+            .map_err(domain::services::ServiceError::Repository)?;
+        let advertisers = repo
+            .get_advertiser_scores()
+            .await
+            .map_err(domain::services::ServiceError::Repository)?;
+
+        let scores = FallbackScores::new(segments, advertisers);
+        *self
+            .scores
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = scores;
+
+        Ok(())
+    }
+
+    /// Returns the fallback relevance of an advertiser for a client segment,
+    /// zero when there is no data at all
+    ///
+    /// # Arguments
+    /// * `advertiser_id` - UUID of the advertiser
+    /// * `segment` - Key of the client segment, see
+    ///   [`domain::services::CtrLearnerService::segment`]
+    pub fn score(&self, advertiser_id: &uuid::Uuid, segment: &str) -> f64 {
+        self.scores
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .score(advertiser_id, segment)
+            .unwrap_or(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::infrastructure::repository::sqlx_lib::{AdvertiserScoreReturningSchema, SegmentScoreReturningSchema};
+
+    #[test]
+    fn segment_score_then_advertiser_score_then_ctr() {
+        let advertisers_id: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        let scores = FallbackScores::new(
+            vec![SegmentScoreReturningSchema {
+                advertiser_id: advertisers_id[0],
+                age_bucket: 2,
+                gender: "MALE".into(),
+                region: "moscow".into(),
+                score: 0.6,
+            }],
+            vec![
+                AdvertiserScoreReturningSchema {
+                    advertiser_id: advertisers_id[0],
+                    score: Some(0.4),
+                    ctr: Some(0.1),
+                },
+                AdvertiserScoreReturningSchema {
+                    advertiser_id: advertisers_id[1],
+                    score: Some(0.4),
+                    ctr: Some(0.1),
+                },
+                AdvertiserScoreReturningSchema {
+                    advertiser_id: advertisers_id[2],
+                    score: None,
+                    ctr: Some(0.1),
+                },
+            ],
+        );
+        let segment = domain::services::CtrLearnerService::segment(27, "male", "Moscow, mcad");
+
+        let fallbacks: Vec<Option<f64>> = advertisers_id.iter().map(|id| scores.score(id, &segment)).collect();
+
+        assert_eq!(fallbacks, vec![Some(0.6), Some(0.4), Some(0.1), None]);
+        assert_eq!(scores.score(&advertisers_id[0], "2:FEMALE:moscow"), Some(0.4));
+    }
+}
@@ -0,0 +1,730 @@
+use async_trait::async_trait;
+
+use crate::{domain, infrastructure};
+
+/// Trait for creating a text generation job
+#[async_trait]
+pub trait ICreateGenerationJob {
+    /// Creates a pending job
+    ///
+    /// # Arguments
+    /// * `advertiser_id` - UUID of the campaign advertiser
+    /// * `campaign_id` - UUID of the campaign
+    /// * `generate_type` - Type of text generation (TITLE, TEXT, or ALL)
+    /// * `source_title` - Input text for title generation
+    /// * `source_text` - Input text for body generation
+    async fn create(
+        &self,
+        advertiser_id: uuid::Uuid,
+        campaign_id: uuid::Uuid,
+        generate_type: &str,
+        source_title: &str,
+        source_text: &str,
+    ) -> infrastructure::repository::RepoResult<infrastructure::repository::sqlx_lib::GenerationJobReturningSchema>;
+}
+
+/// Trait for retrieving a text generation job by ID
+#[async_trait]
+pub trait IGetGenerationJobById {
+    /// Gets a job
+    ///
+    /// # Arguments
+    /// * `job_id` - UUID of the job
+    async fn get_by_id(
+        &self,
+        job_id: uuid::Uuid,
+    ) -> infrastructure::repository::RepoResult<infrastructure::repository::sqlx_lib::GenerationJobReturningSchema>;
+}
+
+/// Trait for recording the progress of a text generation job
+#[async_trait]
+pub trait IRunGenerationJob {
+    /// Marks a pending job as running under a lease
+    ///
+    /// # Arguments
+    /// * `job_id` - UUID of the job
+    /// * `lease_id` - Lease of the task running the job
+    /// * `lease` - How long the lease lasts without a renewal
+    ///
+    /// # Returns
+    /// * `RepoResult<Option<GenerationJobReturningSchema>>` - Started job,
+    ///   `None` when the job isn't pending, for example it's already taken by
+    ///   another task
+    async fn start(
+        &self,
+        job_id: uuid::Uuid,
+        lease_id: uuid::Uuid,
+        lease: std::time::Duration,
+    ) -> infrastructure::repository::RepoResult<
+        Option<infrastructure::repository::sqlx_lib::GenerationJobReturningSchema>,
+    >;
+
+    /// Extends the lease of a running job
+    ///
+    /// # Arguments
+    /// * `job_id` - UUID of the job
+    /// * `lease_id` - Lease of the task running the job
+    /// * `lease` - How long the lease lasts from now
+    ///
+    /// # Returns
+    /// * `RepoResult<bool>` - Whether the task still holds the lease
+    async fn renew_lease(
+        &self,
+        job_id: uuid::Uuid,
+        lease_id: uuid::Uuid,
+        lease: std::time::Duration,
+    ) -> infrastructure::repository::RepoResult<bool>;
+
+    /// Stores the generated texts of a running job
+    ///
+    /// # Arguments
+    /// * `job_id` - UUID of the job
+    /// * `lease_id` - Lease of the task running the job
+    /// * `ad_title` - Generated title
+    /// * `ad_text` - Generated body text
+    /// * `prompt_version_id` - Version of the prompt template which generated
+    ///   the texts
+    ///
+    /// # Returns
+    /// * `RepoResult<Option<GenerationJobReturningSchema>>` - Finished job,
+    ///   `None` when the job isn't running under the lease anymore
+    async fn complete(
+        &self,
+        job_id: uuid::Uuid,
+        lease_id: uuid::Uuid,
+        ad_title: Option<String>,
+        ad_text: Option<String>,
+        prompt_version_id: Option<uuid::Uuid>,
+    ) -> infrastructure::repository::RepoResult<
+        Option<infrastructure::repository::sqlx_lib::GenerationJobReturningSchema>,
+    >;
+
+    /// Stores the reason a running job failed
+    ///
+    /// # Arguments
+    /// * `job_id` - UUID of the job
+    /// * `lease_id` - Lease of the task running the job
+    /// * `error` - Reason of the failure
+    ///
+    /// # Returns
+    /// * `RepoResult<Option<GenerationJobReturningSchema>>` - Failed job,
+    ///   `None` when the job isn't running under the lease anymore
+    async fn fail(
+        &self,
+        job_id: uuid::Uuid,
+        lease_id: uuid::Uuid,
+        error: String,
+    ) -> infrastructure::repository::RepoResult<
+        Option<infrastructure::repository::sqlx_lib::GenerationJobReturningSchema>,
+    >;
+}
+
+/// Trait for rejecting generated texts
+#[async_trait]
+pub trait IRejectGenerationJob {
+    /// Marks a done job as rejected
+    ///
+    /// # Arguments
+    /// * `job_id` - UUID of the job
+    ///
+    /// # Returns
+    /// * `RepoResult<GenerationJobReturningSchema>` - Rejected job,
+    ///   `ObjDoesNotExists` when there is no such done job
+    async fn reject(
+        &self,
+        job_id: uuid::Uuid,
+    ) -> infrastructure::repository::RepoResult<infrastructure::repository::sqlx_lib::GenerationJobReturningSchema>;
+}
+
+/// Trait for applying generated texts to the campaign
+#[async_trait]
+pub trait IAcceptGenerationJob {
+    /// Marks a done job as accepted and applies its texts and prompt version
+    /// to the campaign in one transaction
+    ///
+    /// # Arguments
+    /// * `job_id` - UUID of the job
+    ///
+    /// # Returns
+    /// * `RepoResult<GenerationJobReturningSchema>` - Accepted job,
+    ///   `ObjDoesNotExists` when there is no such done job
+    async fn accept(
+        &self,
+        job_id: uuid::Uuid,
+    ) -> infrastructure::repository::RepoResult<infrastructure::repository::sqlx_lib::GenerationJobReturningSchema>;
+}
+
+/// Trait for resuming text generation jobs after a restart
+#[async_trait]
+pub trait IRequeueGenerationJobs {
+    /// Marks running jobs whose lease expired as pending again
+    ///
+    /// # Returns
+    /// * `RepoResult<Vec<uuid::Uuid>>` - Ids of all pending jobs
+    async fn requeue_unfinished(&self) -> infrastructure::repository::RepoResult<Vec<uuid::Uuid>>;
+}
+
+/// Service for asynchronous generation of campaign texts
+///
+/// A job is created as `PENDING` and processed in the background: `RUNNING`
+/// while the LLM generates texts, then `DONE` with the candidates or `FAILED`
+/// with the reason. The advertiser accepts the candidates, which applies them
+/// to the campaign, or rejects them. Jobs are stored in the database, so
+/// unfinished jobs are resumed after a restart.
+///
+/// A running job is leased to the task running it, the task renews the lease
+/// while the LLM generates texts. A job whose lease expired, because its
+/// replica stopped, is resumed by the next replica that starts, and the
+/// stopped task can't overwrite its result anymore.
+#[derive(Debug)]
+pub struct GenerationJobService;
+
+impl GenerationJobService {
+    /// Creates a pending job for a campaign
+    ///
+    /// # Arguments
+    /// * `campaign` - Campaign to generate texts for
+    /// * `generate_schema` - Schema containing generation parameters, omitted
+    ///   texts are taken from the campaign
+    /// * `repo` - Repository implementation for data access
+    #[tracing::instrument(name = "`GenerationJobService` create job", skip(repo))]
+    pub async fn create<R: ICreateGenerationJob>(
+        &self,
+        campaign: &domain::schemas::CampaignSchema,
+        generate_schema: domain::schemas::CampaignsGenerateTextRequest,
+        repo: R,
+    ) -> domain::services::ServiceResult<domain::schemas::GenerationJobSchema> {
+        repo.create(
+            campaign.advertiser_id,
+            campaign.campaign_id,
+            &generate_schema.generate_type,
+            &generate_schema.ad_title.unwrap_or(campaign.ad_title.clone()),
+            &generate_schema.ad_text.unwrap_or(campaign.ad_text.clone()),
+        )
+        .await
+        .map_err(|e| domain::services::ServiceError::Repository(e))?
+        .try_into()
+    }
+
+    /// Gets a job
+    ///
+    /// # Arguments
+    /// * `job_id` - UUID of the job
+    /// * `repo` - Repository implementation for data access
+    #[tracing::instrument(name = "`GenerationJobService` get job by id", skip(repo))]
+    pub async fn get_by_id<R: IGetGenerationJobById>(
+        &self,
+        job_id: uuid::Uuid,
+        repo: R,
+    ) -> domain::services::ServiceResult<domain::schemas::GenerationJobSchema> {
+        repo.get_by_id(job_id)
+            .await
+            .map_err(|e| domain::services::ServiceError::Repository(e))?
+            .try_into()
+    }
+
+    /// Generates the texts of a pending job
+    ///
+    /// The job is started before anything else, so any failure after that,
+    /// of loading the prompts or of the LLM, is stored in the job, not
+    /// returned. The lease of the job is renewed every third of its duration
+    /// until the texts are generated.
+    ///
+    /// # Arguments
+    /// * `job_id` - UUID of the job
+    /// * `lease` - How long the job stays leased to this task without a
+    ///   renewal
+    /// * `gpt_service` - Service generating the texts
+    /// * `load_prompts` - Loads the system prompts of the generation by the
+    ///   advertiser and the campaign of the job, their version is recorded on
+    ///   the job
+    /// * `repo` - Repository implementation for data access
+    ///
+    /// # Returns
+    /// The finished job, `None` when the job isn't pending or stopped running
+    /// under this task before it's finished
+    #[tracing::instrument(name = "`GenerationJobService` run job", skip(gpt_service, load_prompts, repo))]
+    pub async fn run<R, F, Fut>(
+        &self,
+        job_id: uuid::Uuid,
+        lease: std::time::Duration,
+        gpt_service: &domain::services::GptService,
+        load_prompts: F,
+        repo: R,
+    ) -> domain::services::ServiceResult<Option<domain::schemas::GenerationJobSchema>>
+    where
+        R: IRunGenerationJob,
+        F: FnOnce(uuid::Uuid, uuid::Uuid) -> Fut,
+        Fut: std::future::Future<Output = domain::services::ServiceResult<domain::services::GenerationPrompts>>,
+    {
+        let lease_id = uuid::Uuid::new_v4();
+        let Some(job) = repo
+            .start(job_id, lease_id, lease)
+            .await
+            .map_err(|e| domain::services::ServiceError::Repository(e))?
+        else {
+            return Ok(None);
+        };
+
+        let generation = async {
+            let prompts = load_prompts(job.advertiser_id, job.campaign_id).await?;
+            let (ad_title, ad_text) = gpt_service
+                .generate_texts(&job.generate_type, &job.source_title, &job.source_text, &prompts)
+                .await?;
+            Ok::<_, domain::services::ServiceError>((ad_title, ad_text, prompts.prompt_version_id))
+        };
+        tokio::pin!(generation);
+        let mut heartbeat = tokio::time::interval(lease / 3);
+        heartbeat.tick().await;
+        let generated = loop {
+            tokio::select! {
+                generated = &mut generation => break generated,
+                _ = heartbeat.tick() => match repo.renew_lease(job_id, lease_id, lease).await {
+                    Ok(true) => {},
+                    Ok(false) => {
+                        tracing::warn!("Generation job {} lost its lease", job_id);
+                        return Ok(None);
+                    },
+                    Err(e) => tracing::warn!("Failed to renew the lease of generation job {}: {}", job_id, e),
+                },
+            }
+        };
+
+        let job = match generated {
+            Ok((ad_title, ad_text, prompt_version_id)) => {
+                repo.complete(job_id, lease_id, ad_title, ad_text, prompt_version_id)
+                    .await
+            },
+            Err(e) => {
+                tracing::warn!("Generation job {} failed: {}", job_id, e);
+                repo.fail(job_id, lease_id, e.to_string()).await
+            },
+        }
+        .map_err(|e| domain::services::ServiceError::Repository(e))?;
+
+        let Some(job) = job else {
+            tracing::warn!("Generation job {} stopped running before it's finished", job_id);
+            return Ok(None);
+        };
+
+        Ok(Some(job.try_into()?))
+    }
+
+    /// Rejects a done job, the campaign keeps its texts
+    ///
+    /// # Arguments
+    /// * `job_id` - UUID of the job
+    /// * `repo` - Repository implementation for data access
+    #[tracing::instrument(name = "`GenerationJobService` reject job", skip(repo))]
+    pub async fn reject<R: IRejectGenerationJob>(
+        &self,
+        job_id: uuid::Uuid,
+        repo: R,
+    ) -> domain::services::ServiceResult<domain::schemas::GenerationJobSchema> {
+        repo.reject(job_id)
+            .await
+            .map_err(|e| domain::services::ServiceError::Repository(e))?
+            .try_into()
+    }
+
+    /// Accepts a done job and applies its texts to the campaign
+    ///
+    /// # Arguments
+    /// * `job_id` - UUID of the job
+    /// * `repo` - Repository implementation for data access
+    #[tracing::instrument(name = "`GenerationJobService` accept job", skip(repo))]
+    pub async fn accept<R: IAcceptGenerationJob>(
+        &self,
+        job_id: uuid::Uuid,
+        repo: R,
+    ) -> domain::services::ServiceResult<domain::schemas::GenerationJobSchema> {
+        repo.accept(job_id)
+            .await
+            .map_err(|e| domain::services::ServiceError::Repository(e))?
+            .try_into()
+    }
+
+    /// Marks running jobs whose lease expired as pending
+    ///
+    /// # Arguments
+    /// * `repo` - Repository implementation for data access
+    ///
+    /// # Returns
+    /// Ids of all pending jobs
+    #[tracing::instrument(name = "`GenerationJobService` requeue jobs", skip(repo))]
+    pub async fn requeue_unfinished<R: IRequeueGenerationJobs>(
+        &self,
+        repo: R,
+    ) -> domain::services::ServiceResult<Vec<uuid::Uuid>> {
+        repo.requeue_unfinished()
+            .await
+            .map_err(|e| domain::services::ServiceError::Repository(e))
+    }
+}
+
+impl TryFrom<infrastructure::repository::sqlx_lib::GenerationJobReturningSchema>
+    for domain::schemas::GenerationJobSchema
+{
+    type Error = domain::services::ServiceError;
+
+    /// Fails on a status unknown to the service instead of reporting a job
+    /// in a state it isn't in
+    fn try_from(job: infrastructure::repository::sqlx_lib::GenerationJobReturningSchema) -> Result<Self, Self::Error> {
+        let status = serde_json::from_value(serde_json::Value::String(job.status.clone())).map_err(|_| {
+            tracing::error!("Generation job {} has unknown status `{}`", job.id, job.status);
+            domain::services::ServiceError::Repository(infrastructure::repository::RepoError::Unknown)
+        })?;
+
+        Ok(Self {
+            job_id: job.id,
+            advertiser_id: job.advertiser_id,
+            campaign_id: job.campaign_id,
+            generate_type: job.generate_type,
+            status,
+            ad_title: job.ad_title,
+            ad_text: job.ad_text,
+            error: job.error,
+            prompt_version_id: job.prompt_version_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    const TEST_LEASE: std::time::Duration = std::time::Duration::from_secs(60);
+
+    /// Repository keeping one job and its lease in memory
+    struct MockGenerationJobRepo {
+        job: std::sync::Arc<std::sync::Mutex<infrastructure::repository::sqlx_lib::GenerationJobReturningSchema>>,
+        lease_id: std::sync::Mutex<Option<Uuid>>,
+        renewals: std::sync::atomic::AtomicUsize,
+    }
+
+    impl MockGenerationJobRepo {
+        fn new(generate_type: &str, status: &str) -> Self {
+            Self {
+                job: std::sync::Arc::new(std::sync::Mutex::new(
+                    infrastructure::repository::sqlx_lib::GenerationJobReturningSchema {
+                        id: Uuid::new_v4(),
+                        advertiser_id: Uuid::new_v4(),
+                        campaign_id: Uuid::new_v4(),
+                        generate_type: generate_type.into(),
+                        source_title: "Coffee".into(),
+                        source_text: "Fresh coffee".into(),
+                        status: status.into(),
+                        ad_title: None,
+                        ad_text: None,
+                        error: None,
+                        prompt_version_id: None,
+                    },
+                )),
+                lease_id: std::sync::Mutex::new(None),
+                renewals: std::sync::atomic::AtomicUsize::new(0),
+            }
+        }
+
+        fn holds_lease(&self, lease_id: Uuid) -> bool {
+            self.job.lock().unwrap().status == "RUNNING" && *self.lease_id.lock().unwrap() == Some(lease_id)
+        }
+
+        fn job_id(&self) -> Uuid {
+            self.job.lock().unwrap().id
+        }
+    }
+
+    #[async_trait]
+    impl IRunGenerationJob for &MockGenerationJobRepo {
+        async fn start(
+            &self,
+            _job_id: Uuid,
+            lease_id: Uuid,
+            _lease: std::time::Duration,
+        ) -> infrastructure::repository::RepoResult<
+            Option<infrastructure::repository::sqlx_lib::GenerationJobReturningSchema>,
+        > {
+            let mut job = self.job.lock().unwrap();
+            if job.status != "PENDING" {
+                return Ok(None);
+            }
+            job.status = "RUNNING".into();
+            *self.lease_id.lock().unwrap() = Some(lease_id);
+            Ok(Some(job.clone()))
+        }
+
+        async fn renew_lease(
+            &self,
+            _job_id: Uuid,
+            lease_id: Uuid,
+            _lease: std::time::Duration,
+        ) -> infrastructure::repository::RepoResult<bool> {
+            self.renewals.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(self.holds_lease(lease_id))
+        }
+
+        async fn complete(
+            &self,
+            _job_id: Uuid,
+            lease_id: Uuid,
+            ad_title: Option<String>,
+            ad_text: Option<String>,
+            prompt_version_id: Option<Uuid>,
+        ) -> infrastructure::repository::RepoResult<
+            Option<infrastructure::repository::sqlx_lib::GenerationJobReturningSchema>,
+        > {
+            if !self.holds_lease(lease_id) {
+                return Ok(None);
+            }
+            let mut job = self.job.lock().unwrap();
+            job.status = "DONE".into();
+            job.ad_title = ad_title;
+            job.ad_text = ad_text;
+            job.prompt_version_id = prompt_version_id;
+            Ok(Some(job.clone()))
+        }
+
+        async fn fail(
+            &self,
+            _job_id: Uuid,
+            lease_id: Uuid,
+            error: String,
+        ) -> infrastructure::repository::RepoResult<
+            Option<infrastructure::repository::sqlx_lib::GenerationJobReturningSchema>,
+        > {
+            if !self.holds_lease(lease_id) {
+                return Ok(None);
+            }
+            let mut job = self.job.lock().unwrap();
+            job.status = "FAILED".into();
+            job.error = Some(error);
+            Ok(Some(job.clone()))
+        }
+    }
+
+    struct UnavailableLlmClient;
+
+    #[async_trait]
+    impl domain::services::ILlmClient for UnavailableLlmClient {
+        async fn ask_gpt(&self, _: &str, _: &str) -> infrastructure::gpt::LlmResult<String> {
+            Err(infrastructure::gpt::LlmError::CircuitOpen)
+        }
+    }
+
+    /// LLM client that marks the job failed by another task while it answers
+    struct InterruptingLlmClient {
+        job: std::sync::Arc<std::sync::Mutex<infrastructure::repository::sqlx_lib::GenerationJobReturningSchema>>,
+    }
+
+    #[async_trait]
+    impl domain::services::ILlmClient for InterruptingLlmClient {
+        async fn ask_gpt(&self, _: &str, _: &str) -> infrastructure::gpt::LlmResult<String> {
+            self.job.lock().unwrap().status = "FAILED".into();
+            Ok("Generated".into())
+        }
+    }
+
+    /// LLM client that answers after a delay
+    struct SlowLlmClient {
+        delay: std::time::Duration,
+    }
+
+    #[async_trait]
+    impl domain::services::ILlmClient for SlowLlmClient {
+        async fn ask_gpt(&self, _: &str, _: &str) -> infrastructure::gpt::LlmResult<String> {
+            tokio::time::sleep(self.delay).await;
+            Ok("Generated".into())
+        }
+    }
+
+    fn create_test_gpt_service(
+        llm_client: Box<dyn domain::services::ILlmClient + Send + Sync>,
+    ) -> domain::services::GptService {
+        domain::services::GptService::new(llm_client)
+    }
+
+    fn create_test_prompts() -> domain::services::GenerationPrompts {
+        domain::services::GenerationPrompts {
+            prompt_version_id: Some(Uuid::new_v4()),
+            title_prompt: "title prompt".into(),
+            text_prompt: "body prompt".into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn run_stores_generated_texts() {
+        let repo = MockGenerationJobRepo::new("TITLE", "PENDING");
+        let gpt_service = create_test_gpt_service(Box::new(infrastructure::gpt::mock::MockLlmClient));
+        let prompts = create_test_prompts();
+
+        let job = GenerationJobService
+            .run(
+                repo.job_id(),
+                TEST_LEASE,
+                &gpt_service,
+                |_, _| async { Ok(prompts.clone()) },
+                &repo,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(job.status, domain::schemas::GenerationJobStatus::Done);
+        assert_eq!(
+            job.ad_title,
+            Some(gpt_service.generate_title("Coffee", &prompts).await.unwrap())
+        );
+        assert_eq!(job.ad_text, None);
+        assert_eq!(job.prompt_version_id, prompts.prompt_version_id);
+    }
+
+    #[tokio::test]
+    async fn run_stores_llm_failure() {
+        let repo = MockGenerationJobRepo::new("ALL", "PENDING");
+        let gpt_service = create_test_gpt_service(Box::new(UnavailableLlmClient));
+
+        let job = GenerationJobService
+            .run(
+                repo.job_id(),
+                TEST_LEASE,
+                &gpt_service,
+                |_, _| async { Ok(create_test_prompts()) },
+                &repo,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(job.status, domain::schemas::GenerationJobStatus::Failed);
+        assert!(job.error.unwrap().contains("circuit breaker"));
+    }
+
+    #[tokio::test]
+    async fn run_skips_job_that_is_not_pending() {
+        let repo = MockGenerationJobRepo::new("ALL", "DONE");
+        let gpt_service = create_test_gpt_service(Box::new(UnavailableLlmClient));
+
+        let job = GenerationJobService
+            .run(
+                repo.job_id(),
+                TEST_LEASE,
+                &gpt_service,
+                |_, _| async { Ok(create_test_prompts()) },
+                &repo,
+            )
+            .await
+            .unwrap();
+
+        assert!(job.is_none());
+        assert_eq!(repo.job.lock().unwrap().status, "DONE");
+    }
+
+    #[test]
+    fn unknown_status_is_an_error() {
+        let repo = MockGenerationJobRepo::new("ALL", "PAUSED");
+        let job = repo.job.lock().unwrap().clone();
+
+        assert_eq!(
+            domain::schemas::GenerationJobSchema::try_from(job),
+            Err(domain::services::ServiceError::Repository(
+                infrastructure::repository::RepoError::Unknown
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn run_keeps_job_finished_by_another_task() {
+        let repo = MockGenerationJobRepo::new("TITLE", "PENDING");
+        let gpt_service = create_test_gpt_service(Box::new(InterruptingLlmClient { job: repo.job.clone() }));
+
+        let job = GenerationJobService
+            .run(
+                repo.job_id(),
+                TEST_LEASE,
+                &gpt_service,
+                |_, _| async { Ok(create_test_prompts()) },
+                &repo,
+            )
+            .await
+            .unwrap();
+
+        assert!(job.is_none());
+        let stored = repo.job.lock().unwrap();
+        assert_eq!(stored.status, "FAILED");
+        assert_eq!(stored.ad_title, None);
+    }
+
+    #[tokio::test]
+    async fn run_renews_lease_while_generating() {
+        let repo = MockGenerationJobRepo::new("TITLE", "PENDING");
+        let gpt_service = create_test_gpt_service(Box::new(SlowLlmClient {
+            delay: std::time::Duration::from_millis(100),
+        }));
+
+        let job = GenerationJobService
+            .run(
+                repo.job_id(),
+                std::time::Duration::from_millis(30),
+                &gpt_service,
+                |_, _| async { Ok(create_test_prompts()) },
+                &repo,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(job.status, domain::schemas::GenerationJobStatus::Done);
+        assert!(repo.renewals.load(std::sync::atomic::Ordering::SeqCst) >= 2);
+    }
+
+    #[tokio::test]
+    async fn run_stops_when_lease_is_taken_over() {
+        let repo = MockGenerationJobRepo::new("TITLE", "PENDING");
+        let gpt_service = create_test_gpt_service(Box::new(SlowLlmClient {
+            delay: std::time::Duration::from_millis(100),
+        }));
+
+        let run = GenerationJobService.run(
+            repo.job_id(),
+            std::time::Duration::from_millis(30),
+            &gpt_service,
+            |_, _| async { Ok(create_test_prompts()) },
+            &repo,
+        );
+        let take_over = async {
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            *repo.lease_id.lock().unwrap() = Some(Uuid::new_v4());
+        };
+        let (job, _) = tokio::join!(run, take_over);
+
+        assert!(job.unwrap().is_none());
+        assert_eq!(repo.job.lock().unwrap().ad_title, None);
+    }
+
+    #[tokio::test]
+    async fn run_stores_prompts_failure() {
+        let repo = MockGenerationJobRepo::new("ALL", "PENDING");
+        let gpt_service = create_test_gpt_service(Box::new(infrastructure::gpt::mock::MockLlmClient));
+
+        let job = GenerationJobService
+            .run(
+                repo.job_id(),
+                TEST_LEASE,
+                &gpt_service,
+                |_, _| async {
+                    Err(domain::services::ServiceError::Repository(
+                        infrastructure::repository::RepoError::ObjDoesNotExists("Campaign".into()),
+                    ))
+                },
+                &repo,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(job.status, domain::schemas::GenerationJobStatus::Failed);
+        assert!(job.error.unwrap().contains("Campaign"));
+    }
+}
@@ -0,0 +1,333 @@
+use async_trait::async_trait;
+
+use crate::{domain, infrastructure};
+
+/// Provider of the LLM used to generate campaign texts
+///
+/// * `yandex` - Yandex GPT in Yandex Cloud
+/// * `openai` - any server with the OpenAI-compatible chat completions API,
+///   including local servers like llama.cpp or vLLM
+/// * `mock` - deterministic local responses without network, for development
+///   and tests
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LlmProvider {
+    #[default]
+    Yandex,
+    Openai,
+    Mock,
+}
+
+/// Trait for sending prompts to an LLM
+#[async_trait]
+pub trait ILlmClient {
+    /// Sends a prompt to the LLM and returns the generated response
+    ///
+    /// # Arguments
+    /// * `user_prompt` - The main prompt text to send to the model
+    /// * `system_prompt` - System-level instructions that guide the model's
+    ///   behavior
+    ///
+    /// # Returns
+    /// * `LlmResult<String>` - Generated response text or error
+    async fn ask_gpt(&self, user_prompt: &str, system_prompt: &str) -> infrastructure::gpt::LlmResult<String>;
+}
+
+/// System prompts for the generation of campaign texts
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerationPrompts {
+    /// Version of the prompt template, `None` for the prompts from config
+    pub prompt_version_id: Option<uuid::Uuid>,
+
+    /// System prompt for title generation
+    pub title_prompt: String,
+
+    /// System prompt for body text generation
+    pub text_prompt: String,
+}
+
+/// GptService handles text generation for campaign titles and body content
+/// with the LLM provider selected in config
+pub struct GptService {
+    llm_client: Box<dyn ILlmClient + Send + Sync>,
+}
+
+impl GptService {
+    /// Creates a new GptService instance
+    ///
+    /// # Arguments
+    /// * `llm_client` - Client of the LLM provider
+    pub fn new(llm_client: Box<dyn ILlmClient + Send + Sync>) -> Self {
+        Self { llm_client }
+    }
+
+    /// Generates text content for a campaign based on specified generation type
+    ///
+    /// # Arguments
+    /// * `campaign` - Mutable reference to campaign schema to update, the
+    ///   prompt version is recorded on it
+    /// * `generate_schema` - Schema containing generation parameters
+    /// * `prompts` - System prompts of the generation
+    ///
+    /// # Returns
+    /// * `ServiceResult<()>` - Result indicating success or error
+    pub async fn generate_text_for_campaign(
+        &self,
+        campaign: &mut domain::schemas::CampaignSchema,
+        generate_schema: domain::schemas::CampaignsGenerateTextRequest,
+        prompts: &GenerationPrompts,
+    ) -> domain::services::ServiceResult<()> {
+        let (ad_title, ad_text) = self
+            .generate_texts(
+                &generate_schema.generate_type,
+                &generate_schema.ad_title.unwrap_or(campaign.ad_title.clone()),
+                &generate_schema.ad_text.unwrap_or(campaign.ad_text.clone()),
+                prompts,
+            )
+            .await?;
+
+        if let Some(ad_title) = ad_title {
+            campaign.ad_title = ad_title;
+        }
+        if let Some(ad_text) = ad_text {
+            campaign.ad_text = ad_text;
+        }
+        campaign.prompt_version_id = prompts.prompt_version_id;
+        Ok(())
+    }
+
+    /// Generates the title and the body text according to the generation type
+    ///
+    /// # Arguments
+    /// * `generate_type` - Type of text generation (TITLE, TEXT, or ALL)
+    /// * `source_title` - Input text for title generation
+    /// * `source_text` - Input text for body generation
+    /// * `prompts` - System prompts of the generation
+    ///
+    /// # Returns
+    /// * `ServiceResult<(Option<String>, Option<String>)>` - Generated title
+    ///   and body text, `None` for the part not requested by the type
+    pub async fn generate_texts(
+        &self,
+        generate_type: &str,
+        source_title: &str,
+        source_text: &str,
+        prompts: &GenerationPrompts,
+    ) -> domain::services::ServiceResult<(Option<String>, Option<String>)> {
+        match generate_type {
+            "ALL" => {
+                let ad_text = self.generate_body(source_text, prompts).await?;
+                let ad_title = self.generate_title(source_title, prompts).await?;
+                Ok((Some(ad_title), Some(ad_text)))
+            },
+            "TITLE" => Ok((Some(self.generate_title(source_title, prompts).await?), None)),
+            "TEXT" => Ok((None, Some(self.generate_body(source_text, prompts).await?))),
+            _ => Err(domain::services::ServiceError::Validation(
+                "Invalid generate type".to_string(),
+            )),
+        }
+    }
+
+    /// Generates several variants of the title and the body text, the
+    /// variants are requested concurrently
+    ///
+    /// # Arguments
+    /// * `source_title` - Input text for title generation
+    /// * `source_text` - Input text for body generation
+    /// * `variants` - Number of variants
+    /// * `prompts` - System prompts of the generation
+    ///
+    /// # Returns
+    /// * `ServiceResult<Vec<(String, String)>>` - Generated titles and body
+    ///   texts, an error when any variant failed
+    pub async fn generate_variants(
+        &self,
+        source_title: &str,
+        source_text: &str,
+        variants: u32,
+        prompts: &GenerationPrompts,
+    ) -> domain::services::ServiceResult<Vec<(String, String)>> {
+        futures::future::try_join_all((0..variants).map(|_| async {
+            futures::try_join!(
+                self.generate_title(source_title, prompts),
+                self.generate_body(source_text, prompts)
+            )
+        }))
+        .await
+    }
+
+    /// Generates campaign title using the LLM
+    ///
+    /// # Arguments
+    /// * `text` - Input text for title generation
+    /// * `prompts` - System prompts of the generation
+    ///
+    /// # Returns
+    /// * `ServiceResult<String>` - Generated title or error
+    pub async fn generate_title(
+        &self,
+        text: &str,
+        prompts: &GenerationPrompts,
+    ) -> domain::services::ServiceResult<String> {
+        self.llm_client
+            .ask_gpt(text, &prompts.title_prompt)
+            .await
+            .map_err(domain::services::ServiceError::GptNotResponse)
+    }
+
+    /// Generates campaign body text using the LLM
+    ///
+    /// # Arguments  
+    /// * `text` - Input text for body generation
+    /// * `prompts` - System prompts of the generation
+    ///
+    /// # Returns
+    /// * `ServiceResult<String>` - Generated body text or error
+    pub async fn generate_body(
+        &self,
+        text: &str,
+        prompts: &GenerationPrompts,
+    ) -> domain::services::ServiceResult<String> {
+        self.llm_client
+            .ask_gpt(text, &prompts.text_prompt)
+            .await
+            .map_err(domain::services::ServiceError::GptNotResponse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::gpt::mock::MockLlmClient;
+
+    fn create_test_service() -> GptService {
+        GptService::new(Box::new(MockLlmClient))
+    }
+
+    fn create_test_prompts() -> GenerationPrompts {
+        GenerationPrompts {
+            prompt_version_id: Some(uuid::Uuid::new_v4()),
+            title_prompt: "title prompt".into(),
+            text_prompt: "body prompt".into(),
+        }
+    }
+
+    fn create_test_campaign() -> domain::schemas::CampaignSchema {
+        domain::schemas::CampaignSchema {
+            campaign_id: uuid::Uuid::new_v4(),
+            advertiser_id: uuid::Uuid::new_v4(),
+            impressions_limit: 100,
+            clicks_limit: 10,
+            cost_per_impression: 1.0,
+            cost_per_click: 2.0,
+            budget: None,
+            daily_budget: None,
+            ad_title: "Old title".into(),
+            ad_text: "Old text".into(),
+            start_date: 1,
+            end_date: 10,
+            targeting: Default::default(),
+            frequency_cap: None,
+            prompt_version_id: None,
+        }
+    }
+
+    fn create_test_request(
+        generate_type: &str,
+        ad_title: Option<&str>,
+    ) -> domain::schemas::CampaignsGenerateTextRequest {
+        domain::schemas::CampaignsGenerateTextRequest {
+            generate_type: generate_type.into(),
+            ad_title: ad_title.map(Into::into),
+            ad_text: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn mock_generates_deterministic_texts() {
+        let service = create_test_service();
+        let prompts = create_test_prompts();
+
+        let first = service.generate_title("Coffee", &prompts).await.unwrap();
+        let second = service.generate_title("Coffee", &prompts).await.unwrap();
+
+        assert_eq!(first, second);
+        assert_ne!(first, service.generate_body("Coffee", &prompts).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn generate_title_keeps_text() {
+        let service = create_test_service();
+        let prompts = create_test_prompts();
+        let mut campaign = create_test_campaign();
+
+        service
+            .generate_text_for_campaign(&mut campaign, create_test_request("TITLE", Some("Coffee")), &prompts)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            campaign.ad_title,
+            service.generate_title("Coffee", &prompts).await.unwrap()
+        );
+        assert_eq!(campaign.ad_text, "Old text");
+        assert_eq!(campaign.prompt_version_id, prompts.prompt_version_id);
+    }
+
+    #[tokio::test]
+    async fn generate_all_uses_campaign_texts_by_default() {
+        let service = create_test_service();
+        let prompts = create_test_prompts();
+        let mut campaign = create_test_campaign();
+
+        service
+            .generate_text_for_campaign(&mut campaign, create_test_request("ALL", None), &prompts)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            campaign.ad_title,
+            service.generate_title("Old title", &prompts).await.unwrap()
+        );
+        assert_eq!(
+            campaign.ad_text,
+            service.generate_body("Old text", &prompts).await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn generate_variants_returns_requested_number() {
+        let service = create_test_service();
+        let prompts = create_test_prompts();
+
+        let variants = service
+            .generate_variants("Coffee", "Fresh coffee", 3, &prompts)
+            .await
+            .unwrap();
+
+        assert_eq!(variants.len(), 3);
+        assert_eq!(
+            variants[0],
+            (
+                service.generate_title("Coffee", &prompts).await.unwrap(),
+                service.generate_body("Fresh coffee", &prompts).await.unwrap()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_generate_type_is_rejected() {
+        let service = create_test_service();
+        let prompts = create_test_prompts();
+
+        let result = service
+            .generate_text_for_campaign(
+                &mut create_test_campaign(),
+                create_test_request("IMAGE", None),
+                &prompts,
+            )
+            .await;
+
+        assert!(matches!(result, Err(domain::services::ServiceError::Validation(_))));
+    }
+}
@@ -6,49 +6,94 @@
 mod ads_service;
 mod advertiser_service;
 mod aggregate_stat_service;
+mod brand_voice_service;
+mod budget_service;
 mod campaign_image;
 mod campaigns_service;
 mod campaigns_stat_service;
 mod client_service;
+mod creative_service;
+mod ctr_learner_service;
 mod error;
+mod experiment_service;
+mod exploration_service;
+mod fallback_score_service;
+mod generation_job_service;
+mod gpt_service;
 mod ml_score_service;
 mod moderate_list_service;
 mod moderate_text_service;
+mod pacing_service;
 mod prometheus_service;
+mod prompt_template_service;
 mod redis_service;
+mod targeting_expression;
+mod targeting_index_service;
 mod upload_image_service;
-mod yandex_gpt_service;
 
-pub use ads_service::AdsService;
+pub use ads_service::{AdsService, AdsStrategy, MlScoresCache};
 pub use advertiser_service::AdvertiserService;
 pub use aggregate_stat_service::AggregateStatService;
+pub use brand_voice_service::BrandVoiceService;
+pub use budget_service::BudgetService;
 pub use campaign_image::CampaignImageService;
 pub use campaigns_service::CampaignService;
 pub use campaigns_stat_service::CampaignStatService;
 pub use client_service::ClientService;
+pub use creative_service::CreativeService;
+pub use ctr_learner_service::{CtrEstimates, CtrLearnerService};
 pub use error::ServiceError;
+pub use experiment_service::ExperimentService;
+pub use exploration_service::{ExplorationPolicy, ExplorationRng, ExplorationService};
+pub use fallback_score_service::FallbackScoreService;
+pub use generation_job_service::GenerationJobService;
+pub use gpt_service::{GenerationPrompts, GptService, ILlmClient, LlmProvider};
 pub use ml_score_service::MlScoreService;
 pub use moderate_list_service::ModerateListService;
 pub use moderate_text_service::ModerateTextService;
+pub use pacing_service::PacingService;
 pub use prometheus_service::PrometheusService;
+pub use prompt_template_service::{PromptTemplateService, PromptVariables};
 pub use redis_service::RedisService;
+pub use targeting_expression::TargetingExpression;
+pub use targeting_index_service::TargetingIndexService;
 pub use upload_image_service::UploadImageService;
-pub use yandex_gpt_service::YandexGptService;
 
 pub mod repository {
     pub use super::{
         ads_service::IGetMlScores,
         advertiser_service::{IGetAdvertiserById, IRegisterBulkAdvertiser},
+        brand_voice_service::{IGetBrandVoice, IUpsertBrandVoice},
         campaign_image::{IDeleteCampaignImage, IGetCampaignImage, IGetCampaignNamesImage},
         campaigns_service::{
             ICreateCampaign, IDeleteCampaign, IGetActiveCampaignList, IGetCampaignById, IGetCampaignList,
-            IGetIdsCampaign, ISearchCampaign, IUpdateCampaign,
+            IGetIdsCampaign, ISearchCampaign, IUpdateCampaign, IUpdateCampaignTexts,
+        },
+        campaigns_stat_service::{
+            IClickCampaign, IGetBudgetCampaign, IGetDailyStat, IGetOrCreateUniqIdForStatCampaign, IGetSpentCampaign,
+            IViewCampaign,
         },
-        campaigns_stat_service::{IClickCampaign, IGetDailyStat, IGetOrCreateUniqIdForStatCampaign, IViewCampaign},
         client_service::{IGetClientById, IRegisterBulkClient},
+        creative_service::{
+            ICreateCreatives, IDeleteCreative, IGetCreativeList, IGetCreativeStat, IGetCreativesByCampaigns,
+        },
+        ctr_learner_service::IGetCtrStatistics,
+        experiment_service::{
+            ICreateExperiment, IGetActiveExperiment, IGetExperimentById, IGetExperimentList, IGetExperimentStat,
+            IStopExperiment,
+        },
+        fallback_score_service::IGetFallbackScoreStatistics,
+        generation_job_service::{
+            IAcceptGenerationJob, ICreateGenerationJob, IGetGenerationJobById, IRejectGenerationJob,
+            IRequeueGenerationJobs, IRunGenerationJob,
+        },
         ml_score_service::ISetMlScore,
         moderate_list_service::{IAddModerateList, IDeleteModerateList},
         moderate_text_service::IGetAbusiveWords,
+        prompt_template_service::{
+            ICreatePromptTemplate, ICreatePromptTemplateVersion, IGetPromptTemplateList, IGetPromptTemplateVersions,
+            IGetPromptVersionStat,
+        },
         upload_image_service::IUploadCampaignImage,
     };
 }
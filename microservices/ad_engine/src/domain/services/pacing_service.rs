@@ -0,0 +1,137 @@
+use crate::domain;
+
+/// Service for pacing the delivery of campaigns
+///
+/// Compares the actual delivery of a campaign with the ideal delivery curve
+/// over its `start_date..=end_date` range. The ideal part of the delivery done
+/// by the end of a day is `(elapsed_days / total_days) ^ curve_exponent`, so
+/// an exponent of 1 gives an even (linear) delivery, a bigger exponent moves
+/// the delivery to the end of the campaign and a smaller one to its start.
+#[derive(Debug, Clone)]
+pub struct PacingService {
+    curve_exponent: f64,
+    boost: f64,
+}
+
+impl PacingService {
+    /// Creates a new PacingService instance
+    ///
+    /// # Arguments
+    /// * `curve_exponent` - Exponent of the ideal delivery curve, 1 is linear
+    /// * `boost` - Score bonus for campaigns that lag behind the curve
+    pub fn new(curve_exponent: f64, boost: f64) -> Self {
+        Self { curve_exponent, boost }
+    }
+
+    /// Calculates the pacing state of a campaign on the current day
+    ///
+    /// Delivery is measured by spend when the campaign has a budget and by
+    /// impressions otherwise. A campaign that has already done the delivery
+    /// expected by the end of the day is throttled, a campaign that hasn't
+    /// done the delivery expected by the end of the previous day is boosted.
+    ///
+    /// # Arguments
+    /// * `campaign` - Active campaign to calculate pacing for
+    /// * `advanced_time` - Current day
+    pub fn calculate(
+        &self,
+        campaign: &domain::schemas::ActiveCampaignSchema,
+        advanced_time: u32,
+    ) -> domain::schemas::PacingSchema {
+        let total_days = campaign.end_date.saturating_sub(campaign.start_date) + 1;
+        let elapsed_days = (advanced_time + 1).saturating_sub(campaign.start_date).min(total_days);
+
+        let expected_delivery = self.ideal_delivery(elapsed_days, total_days);
+        let previous_expected_delivery = self.ideal_delivery(elapsed_days.saturating_sub(1), total_days);
+
+        let actual_delivery = match campaign.budget {
+            Some(budget) if budget > 0.0 => campaign.spent / budget,
+            _ if campaign.impressions_limit > 0 => campaign.views_count as f64 / campaign.impressions_limit as f64,
+            _ => 1.0,
+        };
+
+        let status = if actual_delivery >= expected_delivery {
+            domain::schemas::PacingStatus::Throttled
+        } else if actual_delivery < previous_expected_delivery {
+            domain::schemas::PacingStatus::Boosted
+        } else {
+            domain::schemas::PacingStatus::OnTrack
+        };
+
+        domain::schemas::PacingSchema {
+            status,
+            expected_delivery,
+            actual_delivery,
+        }
+    }
+
+    /// Returns the score multiplier for a pacing state
+    ///
+    /// # Arguments
+    /// * `pacing` - Pacing state of a campaign
+    pub fn multiplier(&self, pacing: &domain::schemas::PacingSchema) -> f64 {
+        match pacing.status {
+            domain::schemas::PacingStatus::Boosted => 1.0 + self.boost,
+            _ => 1.0,
+        }
+    }
+
+    /// Calculates the ideal part of the delivery after `elapsed_days`
+    fn ideal_delivery(&self, elapsed_days: u32, total_days: u32) -> f64 {
+        (elapsed_days as f64 / total_days as f64).powf(self.curve_exponent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::schemas::PacingStatus;
+
+    fn create_test_campaign(views_count: u32) -> domain::schemas::ActiveCampaignSchema {
+        domain::schemas::ActiveCampaignSchema {
+            end_date: 9,
+            views_count,
+            ..domain::schemas::ActiveCampaignSchema::test_fixture()
+        }
+    }
+
+    #[test]
+    fn linear_curve_statuses() {
+        let service = PacingService::new(1.0, 0.5);
+
+        let pacing = service.calculate(&create_test_campaign(45), 4);
+        assert_eq!(pacing.expected_delivery, 0.5);
+        assert_eq!(pacing.status, PacingStatus::OnTrack);
+
+        let pacing = service.calculate(&create_test_campaign(50), 4);
+        assert_eq!(pacing.status, PacingStatus::Throttled);
+        assert_eq!(service.multiplier(&pacing), 1.0);
+
+        let pacing = service.calculate(&create_test_campaign(10), 4);
+        assert_eq!(pacing.status, PacingStatus::Boosted);
+        assert_eq!(service.multiplier(&pacing), 1.5);
+    }
+
+    #[test]
+    fn budget_is_used_for_delivery_when_set() {
+        let service = PacingService::new(1.0, 0.5);
+        let mut campaign = create_test_campaign(0);
+        campaign.budget = Some(200.);
+        campaign.spent = 100.;
+
+        let pacing = service.calculate(&campaign, 4);
+
+        assert_eq!(pacing.actual_delivery, 0.5);
+        assert_eq!(pacing.status, PacingStatus::Throttled);
+    }
+
+    #[test]
+    fn curve_exponent_shifts_delivery_to_the_end() {
+        let service = PacingService::new(2.0, 0.5);
+
+        let pacing = service.calculate(&create_test_campaign(20), 4);
+
+        assert_eq!(pacing.expected_delivery, 0.25);
+        assert_eq!(pacing.status, PacingStatus::OnTrack);
+    }
+}
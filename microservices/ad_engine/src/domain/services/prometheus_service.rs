@@ -93,6 +93,22 @@ impl PrometheusService {
         }
     }
 
+    /// Records the comparison of a served winner with the shadow one
+    ///
+    /// # Arguments
+    /// * `is_differs` - Whether the shadow winner differs from the served one
+    /// * `revenue_delta` - Impression price of the shadow winner minus the
+    ///   served one
+    pub fn shadow_ranking(is_differs: bool, revenue_delta: f64) {
+        if let Ok(metrics) = infrastructure::metrics::prometheus::APP_METRICS.lock() {
+            metrics.shadow_rankings.inc();
+            if is_differs {
+                metrics.shadow_winner_differs.inc();
+            }
+            metrics.shadow_revenue_delta.add(revenue_delta);
+        }
+    }
+
     /// Adds to total client counter
     pub fn add_total_clients(value: i64) {
         if let Ok(metrics) = infrastructure::metrics::prometheus::APP_METRICS.lock() {
@@ -0,0 +1,595 @@
+use async_trait::async_trait;
+
+use crate::{domain, infrastructure};
+
+/// Variables available in the prompt templates
+const PROMPT_VARIABLES: [&str; 5] = ["advertiser_name", "audience", "language", "tone", "guidelines"];
+
+/// Language of the texts for advertisers without a brand voice profile
+const DEFAULT_LANGUAGE: &str = "Russian";
+
+/// Tone of the texts for advertisers without a brand voice profile
+const DEFAULT_TONE: &str = "neutral";
+
+/// Trait for creating prompt templates
+#[async_trait]
+pub trait ICreatePromptTemplate {
+    /// Creates a template with its first version, a default template replaces
+    /// the previous default one
+    ///
+    /// # Arguments
+    /// * `template` - Name and prompts of the template
+    async fn create(
+        &self,
+        template: domain::schemas::PromptTemplateCreateRequest,
+    ) -> infrastructure::repository::RepoResult<infrastructure::repository::sqlx_lib::PromptTemplateReturningSchema>;
+}
+
+/// Trait for retrieving prompt templates
+#[async_trait]
+pub trait IGetPromptTemplateList {
+    /// Gets all templates with their latest versions
+    async fn get_list(
+        &self,
+    ) -> infrastructure::repository::RepoResult<Vec<infrastructure::repository::sqlx_lib::PromptTemplateReturningSchema>>;
+}
+
+/// Trait for adding versions to prompt templates
+#[async_trait]
+pub trait ICreatePromptTemplateVersion {
+    /// Adds the next version to a template
+    ///
+    /// # Arguments
+    /// * `template_id` - UUID of the template
+    /// * `version` - Prompts of the version
+    ///
+    /// # Returns
+    /// * `RepoResult<PromptTemplateVersionReturningSchema>` - Created version,
+    ///   `ObjDoesNotExists` when there is no such template
+    async fn create_version(
+        &self,
+        template_id: uuid::Uuid,
+        version: domain::schemas::PromptTemplateVersionCreateRequest,
+    ) -> infrastructure::repository::RepoResult<
+        infrastructure::repository::sqlx_lib::PromptTemplateVersionReturningSchema,
+    >;
+}
+
+/// Trait for retrieving versions of prompt templates
+#[async_trait]
+pub trait IGetPromptTemplateVersions {
+    /// Gets all versions of a template, the latest first
+    ///
+    /// # Arguments
+    /// * `template_id` - UUID of the template
+    ///
+    /// # Returns
+    /// * `RepoResult<Vec<PromptTemplateVersionReturningSchema>>` - Versions of
+    ///   the template, `ObjDoesNotExists` when there is no such template
+    async fn get_versions(
+        &self,
+        template_id: uuid::Uuid,
+    ) -> infrastructure::repository::RepoResult<
+        Vec<infrastructure::repository::sqlx_lib::PromptTemplateVersionReturningSchema>,
+    >;
+
+    /// Gets the latest version of a template
+    ///
+    /// # Arguments
+    /// * `template_id` - UUID of the template, the default template when
+    ///   `None`
+    ///
+    /// # Returns
+    /// * `RepoResult<Option<PromptTemplateVersionReturningSchema>>` - Latest
+    ///   version, `None` when there is no such template
+    async fn get_latest_version(
+        &self,
+        template_id: Option<uuid::Uuid>,
+    ) -> infrastructure::repository::RepoResult<
+        Option<infrastructure::repository::sqlx_lib::PromptTemplateVersionReturningSchema>,
+    >;
+}
+
+/// Trait for retrieving statistics of the versions of a prompt template
+#[async_trait]
+pub trait IGetPromptVersionStat {
+    /// Gets impressions and clicks of the creatives and the campaign texts
+    /// generated by every version of a template
+    ///
+    /// # Arguments
+    /// * `template_id` - UUID of the template
+    async fn get_version_stat(
+        &self,
+        template_id: uuid::Uuid,
+    ) -> infrastructure::repository::RepoResult<
+        Vec<infrastructure::repository::sqlx_lib::PromptVersionStatReturningSchema>,
+    >;
+}
+
+/// Values of the variables of the prompt templates
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptVariables {
+    /// Name of the advertiser
+    pub advertiser_name: String,
+
+    /// Description of the campaign targeting
+    pub audience: String,
+
+    /// Language of the texts
+    pub language: String,
+
+    /// Tone of the texts
+    pub tone: String,
+
+    /// Additional rules of the brand
+    pub guidelines: String,
+}
+
+impl PromptVariables {
+    /// Collects the variables for a campaign
+    ///
+    /// # Arguments
+    /// * `advertiser_name` - Name of the campaign advertiser
+    /// * `targeting` - Targeting of the campaign
+    /// * `brand_voice` - Brand voice profile of the advertiser, the default
+    ///   language and tone when `None`
+    pub fn new(
+        advertiser_name: String,
+        targeting: &domain::schemas::TargetingCampaignSchema,
+        brand_voice: Option<domain::schemas::BrandVoiceSchema>,
+    ) -> Self {
+        let (language, tone, guidelines) = match brand_voice {
+            Some(brand_voice) => (brand_voice.language, brand_voice.tone, brand_voice.guidelines),
+            None => (DEFAULT_LANGUAGE.into(), DEFAULT_TONE.into(), String::new()),
+        };
+
+        Self {
+            advertiser_name,
+            audience: Self::describe_audience(targeting),
+            language,
+            tone,
+            guidelines,
+        }
+    }
+
+    /// Describes the targeting of a campaign for the LLM
+    fn describe_audience(targeting: &domain::schemas::TargetingCampaignSchema) -> String {
+        let mut parts = Vec::new();
+
+        let genders = targeting
+            .genders
+            .clone()
+            .or_else(|| targeting.gender.clone().map(|gender| vec![gender]))
+            .unwrap_or_default();
+        if !genders.is_empty() {
+            parts.push(format!("gender: {}", genders.join(", ")));
+        }
+
+        match (targeting.age_from, targeting.age_to) {
+            (Some(age_from), Some(age_to)) => parts.push(format!("age: {}-{}", age_from, age_to)),
+            (Some(age_from), None) => parts.push(format!("age: from {}", age_from)),
+            (None, Some(age_to)) => parts.push(format!("age: up to {}", age_to)),
+            (None, None) => {},
+        }
+
+        let locations = targeting
+            .locations
+            .clone()
+            .or_else(|| targeting.location.clone().map(|location| vec![location]))
+            .unwrap_or_default();
+        if !locations.is_empty() {
+            parts.push(format!("locations: {}", locations.join(", ")));
+        }
+
+        if parts.is_empty() {
+            return "everyone".into();
+        }
+        parts.join("; ")
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        match name {
+            "advertiser_name" => Some(&self.advertiser_name),
+            "audience" => Some(&self.audience),
+            "language" => Some(&self.language),
+            "tone" => Some(&self.tone),
+            "guidelines" => Some(&self.guidelines),
+            _ => None,
+        }
+    }
+}
+
+/// Service for the prompt templates of the text generation
+///
+/// A template holds the system prompts for title and body text generation with
+/// `{{variable}}` placeholders filled from the advertiser, the campaign
+/// targeting and the brand voice profile. Templates are versioned: a change
+/// adds a version, the latest version is used, and the version is recorded on
+/// the generated texts. Without templates the prompts from config are used.
+#[derive(Debug)]
+pub struct PromptTemplateService;
+
+impl PromptTemplateService {
+    /// Creates a template with its first version
+    ///
+    /// # Arguments
+    /// * `template` - Name and prompts of the template
+    /// * `repo` - Repository implementation for data access
+    #[tracing::instrument(name = "`PromptTemplateService` create template", skip(repo))]
+    pub async fn create<R: ICreatePromptTemplate>(
+        &self,
+        template: domain::schemas::PromptTemplateCreateRequest,
+        repo: R,
+    ) -> domain::services::ServiceResult<domain::schemas::PromptTemplateSchema> {
+        self.validate_prompt(&template.title_prompt)?;
+        self.validate_prompt(&template.text_prompt)?;
+
+        repo.create(template)
+            .await
+            .map(|template| template.into())
+            .map_err(|e| domain::services::ServiceError::Repository(e))
+    }
+
+    /// Gets all templates with their latest versions
+    ///
+    /// # Arguments
+    /// * `repo` - Repository implementation for data access
+    #[tracing::instrument(name = "`PromptTemplateService` get list of templates", skip(repo))]
+    pub async fn get_list<R: IGetPromptTemplateList>(
+        &self,
+        repo: R,
+    ) -> domain::services::ServiceResult<Vec<domain::schemas::PromptTemplateSchema>> {
+        repo.get_list()
+            .await
+            .map(|templates| templates.into_iter().map(|template| template.into()).collect())
+            .map_err(|e| domain::services::ServiceError::Repository(e))
+    }
+
+    /// Adds the next version to a template
+    ///
+    /// # Arguments
+    /// * `template_id` - UUID of the template
+    /// * `version` - Prompts of the version
+    /// * `repo` - Repository implementation for data access
+    #[tracing::instrument(name = "`PromptTemplateService` create template version", skip(repo))]
+    pub async fn create_version<R: ICreatePromptTemplateVersion>(
+        &self,
+        template_id: uuid::Uuid,
+        version: domain::schemas::PromptTemplateVersionCreateRequest,
+        repo: R,
+    ) -> domain::services::ServiceResult<domain::schemas::PromptTemplateVersionSchema> {
+        self.validate_prompt(&version.title_prompt)?;
+        self.validate_prompt(&version.text_prompt)?;
+
+        repo.create_version(template_id, version)
+            .await
+            .map(|version| version.into())
+            .map_err(|e| domain::services::ServiceError::Repository(e))
+    }
+
+    /// Gets all versions of a template, the latest first
+    ///
+    /// # Arguments
+    /// * `template_id` - UUID of the template
+    /// * `repo` - Repository implementation for data access
+    #[tracing::instrument(name = "`PromptTemplateService` get template versions", skip(repo))]
+    pub async fn get_versions<R: IGetPromptTemplateVersions>(
+        &self,
+        template_id: uuid::Uuid,
+        repo: R,
+    ) -> domain::services::ServiceResult<Vec<domain::schemas::PromptTemplateVersionSchema>> {
+        repo.get_versions(template_id)
+            .await
+            .map(|versions| versions.into_iter().map(|version| version.into()).collect())
+            .map_err(|e| domain::services::ServiceError::Repository(e))
+    }
+
+    /// Gets the prompts for the generation of campaign texts
+    ///
+    /// # Arguments
+    /// * `template_id` - Template chosen by the advertiser, the default
+    ///   template when `None`
+    /// * `variables` - Values of the template variables
+    /// * `fallback` - Prompts from config, used when there is no template
+    /// * `repo` - Repository implementation for data access
+    ///
+    /// # Returns
+    /// Prompts of the latest template version with filled variables and the
+    /// version id, or the prompts from config without a version id
+    #[tracing::instrument(name = "`PromptTemplateService` get generation prompts", skip(fallback, repo))]
+    pub async fn get_generation_prompts<R: IGetPromptTemplateVersions>(
+        &self,
+        template_id: Option<uuid::Uuid>,
+        variables: &PromptVariables,
+        fallback: domain::services::GenerationPrompts,
+        repo: R,
+    ) -> domain::services::ServiceResult<domain::services::GenerationPrompts> {
+        let version = repo
+            .get_latest_version(template_id)
+            .await
+            .map_err(|e| domain::services::ServiceError::Repository(e))?;
+
+        Ok(match version {
+            Some(version) => domain::services::GenerationPrompts {
+                prompt_version_id: Some(version.id),
+                title_prompt: self.render(&version.title_prompt, variables),
+                text_prompt: self.render(&version.text_prompt, variables),
+            },
+            None => domain::services::GenerationPrompts {
+                prompt_version_id: None,
+                title_prompt: self.render(&fallback.title_prompt, variables),
+                text_prompt: self.render(&fallback.text_prompt, variables),
+            },
+        })
+    }
+
+    /// Gets statistics of the creatives and the campaign texts generated by
+    /// every version of a template
+    ///
+    /// Impressions and clicks are attributed to the version which generated
+    /// the served texts when the event was recorded, so regenerating the texts
+    /// of a campaign doesn't move earlier events to the new version.
+    ///
+    /// # Arguments
+    /// * `template_id` - UUID of the template
+    /// * `repo` - Repository implementation for data access
+    #[tracing::instrument(name = "`PromptTemplateService` get stat of template versions", skip(repo))]
+    pub async fn get_stat<R: IGetPromptVersionStat>(
+        &self,
+        template_id: uuid::Uuid,
+        repo: R,
+    ) -> domain::services::ServiceResult<Vec<domain::schemas::PromptVersionStatSchema>> {
+        repo.get_version_stat(template_id)
+            .await
+            .map(|stats| stats.into_iter().map(|stat| stat.into()).collect())
+            .map_err(|e| domain::services::ServiceError::Repository(e))
+    }
+
+    /// Checks that a prompt uses only known variables and closes every
+    /// placeholder
+    ///
+    /// # Arguments
+    /// * `prompt` - Prompt of a template
+    pub fn validate_prompt(&self, prompt: &str) -> domain::services::ServiceResult<()> {
+        let mut rest = prompt;
+        while let Some(start) = rest.find("{{") {
+            let Some(end) = rest[start..].find("}}") else {
+                return Err(domain::services::ServiceError::Validation(
+                    "prompt has an unclosed variable, expected }}".into(),
+                ));
+            };
+
+            let name = rest[start + 2..start + end].trim();
+            if !PROMPT_VARIABLES.contains(&name) {
+                return Err(domain::services::ServiceError::Validation(format!(
+                    "unknown variable {} in prompt, available variables: {}",
+                    name,
+                    PROMPT_VARIABLES.join(", ")
+                )));
+            }
+            rest = &rest[start + end + 2..];
+        }
+
+        Ok(())
+    }
+
+    /// Fills the variables of a prompt, unknown placeholders are kept as is
+    ///
+    /// # Arguments
+    /// * `prompt` - Prompt of a template
+    /// * `variables` - Values of the template variables
+    pub fn render(&self, prompt: &str, variables: &PromptVariables) -> String {
+        let mut rendered = String::with_capacity(prompt.len());
+        let mut rest = prompt;
+        while let Some(start) = rest.find("{{") {
+            let Some(end) = rest[start..].find("}}") else {
+                break;
+            };
+
+            rendered.push_str(&rest[..start]);
+            match variables.get(rest[start + 2..start + end].trim()) {
+                Some(value) => rendered.push_str(value),
+                None => rendered.push_str(&rest[start..start + end + 2]),
+            }
+            rest = &rest[start + end + 2..];
+        }
+        rendered.push_str(rest);
+
+        rendered
+    }
+}
+
+impl From<infrastructure::repository::sqlx_lib::PromptTemplateReturningSchema>
+    for domain::schemas::PromptTemplateSchema
+{
+    fn from(template: infrastructure::repository::sqlx_lib::PromptTemplateReturningSchema) -> Self {
+        Self {
+            template_id: template.id,
+            name: template.name,
+            is_default: template.is_default,
+            version_id: template.version_id,
+            version: template.version as u32,
+            title_prompt: template.title_prompt,
+            text_prompt: template.text_prompt,
+        }
+    }
+}
+
+impl From<infrastructure::repository::sqlx_lib::PromptTemplateVersionReturningSchema>
+    for domain::schemas::PromptTemplateVersionSchema
+{
+    fn from(version: infrastructure::repository::sqlx_lib::PromptTemplateVersionReturningSchema) -> Self {
+        Self {
+            version_id: version.id,
+            template_id: version.template_id,
+            version: version.version as u32,
+            title_prompt: version.title_prompt,
+            text_prompt: version.text_prompt,
+        }
+    }
+}
+
+impl From<infrastructure::repository::sqlx_lib::PromptVersionStatReturningSchema>
+    for domain::schemas::PromptVersionStatSchema
+{
+    fn from(stat: infrastructure::repository::sqlx_lib::PromptVersionStatReturningSchema) -> Self {
+        let impressions_count = stat.impressions_count.unwrap_or(0) as u32;
+        let clicks_count = stat.clicks_count.unwrap_or(0) as u32;
+
+        Self {
+            version_id: stat.version_id,
+            version: stat.version as u32,
+            creatives_count: stat.creatives_count.unwrap_or(0) as u32,
+            campaigns_count: stat.campaigns_count.unwrap_or(0) as u32,
+            impressions_count,
+            clicks_count,
+            conversion: domain::services::AggregateStatService.calculate_conversion(impressions_count, clicks_count),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    struct MockVersionsRepo {
+        latest_version: Option<infrastructure::repository::sqlx_lib::PromptTemplateVersionReturningSchema>,
+    }
+
+    #[async_trait]
+    impl IGetPromptTemplateVersions for MockVersionsRepo {
+        async fn get_versions(
+            &self,
+            _template_id: Uuid,
+        ) -> infrastructure::repository::RepoResult<
+            Vec<infrastructure::repository::sqlx_lib::PromptTemplateVersionReturningSchema>,
+        > {
+            Ok(self.latest_version.clone().into_iter().collect())
+        }
+
+        async fn get_latest_version(
+            &self,
+            _template_id: Option<Uuid>,
+        ) -> infrastructure::repository::RepoResult<
+            Option<infrastructure::repository::sqlx_lib::PromptTemplateVersionReturningSchema>,
+        > {
+            Ok(self.latest_version.clone())
+        }
+    }
+
+    fn create_test_variables() -> PromptVariables {
+        PromptVariables {
+            advertiser_name: "Coffee House".into(),
+            audience: "everyone".into(),
+            language: "English".into(),
+            tone: "friendly".into(),
+            guidelines: String::new(),
+        }
+    }
+
+    fn create_test_fallback() -> domain::services::GenerationPrompts {
+        domain::services::GenerationPrompts {
+            prompt_version_id: None,
+            title_prompt: "config title prompt".into(),
+            text_prompt: "config text prompt in {{language}}".into(),
+        }
+    }
+
+    #[test]
+    fn render_fills_variables() {
+        let rendered = PromptTemplateService.render(
+            "Write for {{ advertiser_name }} in {{language}}, tone: {{tone}}.",
+            &create_test_variables(),
+        );
+
+        assert_eq!(rendered, "Write for Coffee House in English, tone: friendly.");
+    }
+
+    #[test]
+    fn render_keeps_unknown_and_unclosed_placeholders() {
+        let rendered = PromptTemplateService.render("{{brand}} for {{audience}} {{tone", &create_test_variables());
+
+        assert_eq!(rendered, "{{brand}} for everyone {{tone");
+    }
+
+    #[test]
+    fn validate_rejects_unknown_variable() {
+        let result = PromptTemplateService.validate_prompt("Write for {{brand}}");
+
+        assert!(matches!(result, Err(domain::services::ServiceError::Validation(_))));
+    }
+
+    #[test]
+    fn validate_rejects_unclosed_variable() {
+        assert!(PromptTemplateService.validate_prompt("Write in {{language}}").is_ok());
+        assert!(matches!(
+            PromptTemplateService.validate_prompt("Write in {{language"),
+            Err(domain::services::ServiceError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn variables_describe_targeting_and_default_brand_voice() {
+        let targeting = domain::schemas::TargetingCampaignSchema {
+            gender: Some("FEMALE".into()),
+            age_from: Some(18),
+            age_to: Some(30),
+            locations: Some(vec!["Moscow".into(), "Kazan".into()]),
+            ..Default::default()
+        };
+
+        let variables = PromptVariables::new("Coffee House".into(), &targeting, None);
+
+        assert_eq!(
+            variables.audience,
+            "gender: FEMALE; age: 18-30; locations: Moscow, Kazan"
+        );
+        assert_eq!(variables.language, DEFAULT_LANGUAGE);
+        assert_eq!(variables.tone, DEFAULT_TONE);
+        assert_eq!(
+            PromptVariables::new("Coffee House".into(), &Default::default(), None).audience,
+            "everyone"
+        );
+    }
+
+    #[tokio::test]
+    async fn generation_prompts_use_latest_version() {
+        let version_id = Uuid::new_v4();
+        let repo = MockVersionsRepo {
+            latest_version: Some(
+                infrastructure::repository::sqlx_lib::PromptTemplateVersionReturningSchema {
+                    id: version_id,
+                    template_id: Uuid::new_v4(),
+                    version: 3,
+                    title_prompt: "Title for {{advertiser_name}}".into(),
+                    text_prompt: "Text, tone: {{tone}}".into(),
+                },
+            ),
+        };
+
+        let prompts = PromptTemplateService
+            .get_generation_prompts(None, &create_test_variables(), create_test_fallback(), repo)
+            .await
+            .unwrap();
+
+        assert_eq!(prompts.prompt_version_id, Some(version_id));
+        assert_eq!(prompts.title_prompt, "Title for Coffee House");
+        assert_eq!(prompts.text_prompt, "Text, tone: friendly");
+    }
+
+    #[tokio::test]
+    async fn generation_prompts_fall_back_to_config() {
+        let repo = MockVersionsRepo { latest_version: None };
+
+        let prompts = PromptTemplateService
+            .get_generation_prompts(None, &create_test_variables(), create_test_fallback(), repo)
+            .await
+            .unwrap();
+
+        assert_eq!(prompts.prompt_version_id, None);
+        assert_eq!(prompts.title_prompt, "config title prompt");
+        assert_eq!(prompts.text_prompt, "config text prompt in English");
+    }
+}
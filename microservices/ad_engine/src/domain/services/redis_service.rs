@@ -2,6 +2,145 @@ use serde::Serialize;
 
 use crate::{domain, infrastructure};
 
+lazy_static::lazy_static! {
+    /// Checks the impressions limit, the budgets and the frequency cap of a
+    /// campaign and records an impression
+    ///
+    /// KEYS: campaign, views, client views, spent, spent by day. ARGV: client
+    /// id, day, cost, max views, budget, daily budget (empty when unlimited),
+    /// frequency cap impressions, first day of the cap window.
+    ///
+    /// Impressions of a client are kept in one field of the client views hash
+    /// as a JSON object of impressions by day. Nothing is recorded once the
+    /// campaign record is deleted, so a script running after the deletion
+    /// can't recreate its counters.
+    ///
+    /// Daily spend is kept in a hash by day, so a new day starts from zero
+    /// without resetting the counter. Days before the recorded one are
+    /// dropped from the hash.
+    static ref RECORD_VIEW_SCRIPT: redis::Script = redis::Script::new(
+        r#"
+        if redis.call('EXISTS', KEYS[1]) == 0 then
+            return false
+        end
+        if tonumber(redis.call('GET', KEYS[2]) or '0') > tonumber(ARGV[4]) then
+            return false
+        end
+        if ARGV[5] ~= '' and tonumber(redis.call('GET', KEYS[4]) or '0') >= tonumber(ARGV[5]) then
+            return false
+        end
+        if ARGV[6] ~= '' and tonumber(redis.call('HGET', KEYS[5], ARGV[2]) or '0') >= tonumber(ARGV[6]) then
+            return false
+        end
+
+        local client_views = cjson.decode(redis.call('HGET', KEYS[3], ARGV[1]) or '{}')
+        local seen = 0
+        for day, count in pairs(client_views) do
+            if tonumber(day) >= tonumber(ARGV[8]) then
+                seen = seen + count
+            end
+        end
+        if seen >= tonumber(ARGV[7]) then
+            return false
+        end
+
+        client_views[ARGV[2]] = (client_views[ARGV[2]] or 0) + 1
+        redis.call('INCR', KEYS[2])
+        redis.call('HSET', KEYS[3], ARGV[1], cjson.encode(client_views))
+        for _, day in ipairs(redis.call('HKEYS', KEYS[5])) do
+            if tonumber(day) < tonumber(ARGV[2]) then
+                redis.call('HDEL', KEYS[5], day)
+            end
+        end
+        return {redis.call('INCRBYFLOAT', KEYS[4], ARGV[3]), redis.call('HINCRBYFLOAT', KEYS[5], ARGV[2], ARGV[3])}
+        "#
+    );
+
+    /// Records the first click of a client who has seen a campaign
+    ///
+    /// KEYS: campaign, client views, clickers, spent, spent by day. ARGV:
+    /// client id, day, cost. Returns -2 when the campaign record is deleted,
+    /// -1 when the client hasn't seen the campaign and 0 when the client has
+    /// already clicked it.
+    static ref RECORD_CLICK_SCRIPT: redis::Script = redis::Script::new(
+        r#"
+        if redis.call('EXISTS', KEYS[1]) == 0 then
+            return -2
+        end
+        if redis.call('HEXISTS', KEYS[2], ARGV[1]) == 0 then
+            return -1
+        end
+        if redis.call('SADD', KEYS[3], ARGV[1]) == 0 then
+            return 0
+        end
+        for _, day in ipairs(redis.call('HKEYS', KEYS[5])) do
+            if tonumber(day) < tonumber(ARGV[2]) then
+                redis.call('HDEL', KEYS[5], day)
+            end
+        end
+        return {redis.call('INCRBYFLOAT', KEYS[4], ARGV[3]), redis.call('HINCRBYFLOAT', KEYS[5], ARGV[2], ARGV[3])}
+        "#
+    );
+
+    /// Reverts an impression recorded by `RECORD_VIEW_SCRIPT`
+    ///
+    /// KEYS: campaign, views, client views, spent, spent by day. ARGV: client
+    /// id, day, cost. Nothing is changed when the campaign record is deleted
+    /// or the client has no impression on that day, the daily spend only when
+    /// that day is still kept.
+    static ref UNDO_VIEW_SCRIPT: redis::Script = redis::Script::new(
+        r#"
+        if redis.call('EXISTS', KEYS[1]) == 0 then
+            return 0
+        end
+        local client_views = cjson.decode(redis.call('HGET', KEYS[3], ARGV[1]) or '{}')
+        local count = client_views[ARGV[2]]
+        if count == nil then
+            return 0
+        end
+
+        if count > 1 then
+            client_views[ARGV[2]] = count - 1
+        else
+            client_views[ARGV[2]] = nil
+        end
+        if next(client_views) == nil then
+            redis.call('HDEL', KEYS[3], ARGV[1])
+        else
+            redis.call('HSET', KEYS[3], ARGV[1], cjson.encode(client_views))
+        end
+        redis.call('DECR', KEYS[2])
+        redis.call('INCRBYFLOAT', KEYS[4], -tonumber(ARGV[3]))
+        if redis.call('HEXISTS', KEYS[5], ARGV[2]) == 1 then
+            redis.call('HINCRBYFLOAT', KEYS[5], ARGV[2], -tonumber(ARGV[3]))
+        end
+        return 1
+        "#
+    );
+
+    /// Reverts a click recorded by `RECORD_CLICK_SCRIPT`
+    ///
+    /// KEYS: campaign, clickers, spent, spent by day. ARGV: client id, day,
+    /// cost. Nothing is changed when the campaign record is deleted or the
+    /// client hasn't clicked the campaign, the daily spend only when that day
+    /// is still kept.
+    static ref UNDO_CLICK_SCRIPT: redis::Script = redis::Script::new(
+        r#"
+        if redis.call('EXISTS', KEYS[1]) == 0 then
+            return 0
+        end
+        if redis.call('SREM', KEYS[2], ARGV[1]) == 0 then
+            return 0
+        end
+        redis.call('INCRBYFLOAT', KEYS[3], -tonumber(ARGV[3]))
+        if redis.call('HEXISTS', KEYS[4], ARGV[2]) == 1 then
+            redis.call('HINCRBYFLOAT', KEYS[4], ARGV[2], -tonumber(ARGV[3]))
+        end
+        return 1
+        "#
+    );
+}
+
 /// Redis service for managing campaign data and moderation settings
 pub struct RedisService<'p> {
     repo: infrastructure::cash::redis::RedisExecutor<'p>,
@@ -16,12 +155,18 @@ impl<'p> RedisService<'p> {
     }
 
     /// Retrieves all active campaigns from Redis
+    ///
+    /// Records written in a layout this version can't read, such as the
+    /// positional records of earlier versions, are deleted together with
+    /// their counters. The campaign is activated again from the database by
+    /// the next `/time/advance` or update of the campaign.
     pub async fn get_all_active_campaigns(
         &self,
     ) -> domain::services::ServiceResult<Vec<domain::schemas::ActiveCampaignSchema>> {
         let mut conn = self.repo.get_conn().await?;
         let mut cursor: isize = 0;
         let mut active_campaigns = Vec::new();
+        let mut undecodable_keys = Vec::new();
         let pattern = "active_campaign:*";
 
         loop {
@@ -34,8 +179,13 @@ impl<'p> RedisService<'p> {
             match result {
                 Ok((new_cursor, keys)) => {
                     for key in keys {
-                        let campaign: domain::schemas::ActiveCampaignSchema = self.repo.get(&key).await?;
-                        active_campaigns.push(campaign);
+                        let Some(data) = self.repo.get::<Option<Vec<u8>>>(&key).await? else {
+                            continue;
+                        };
+                        match Self::decode_active_campaign(&key, &data) {
+                            Some(campaign) => active_campaigns.push(campaign),
+                            None => undecodable_keys.push(key),
+                        }
                     }
 
                     cursor = new_cursor;
@@ -50,9 +200,26 @@ impl<'p> RedisService<'p> {
             }
         }
 
+        for key in undecodable_keys {
+            let random_id = key.trim_start_matches("active_campaign:");
+            self.repo.delete(&key).await?;
+            redis::cmd("DEL")
+                .arg(Self::campaign_stats_keys(random_id))
+                .query::<()>(&mut conn)
+                .map_err(|_| domain::services::ServiceError::Cash("Redis delete campaign stats error".to_string()))?;
+        }
+
         Ok(active_campaigns)
     }
 
+    /// Decodes an active campaign record, `None` when it was written in a
+    /// layout this version can't read
+    fn decode_active_campaign(key: &str, data: &[u8]) -> Option<domain::schemas::ActiveCampaignSchema> {
+        rmp_serde::from_slice(data)
+            .inspect_err(|e| tracing::warn!("Skipping undecodable active campaign record {}: {}", key, e))
+            .ok()
+    }
+
     /// Extracts a 24 character random chunk from a UUID
     #[inline]
     async fn get_random_chunk_from_uuid(&self, id: &uuid::Uuid) -> String {
@@ -64,10 +231,380 @@ impl<'p> RedisService<'p> {
             .collect::<String>()
     }
 
-    /// Deletes an active campaign by ID
+    /// Retrieves active campaigns by IDs, skipping campaigns that are no
+    /// longer active or whose records can't be decoded
+    pub async fn get_active_campaigns_by_ids(
+        &self,
+        ids: &[uuid::Uuid],
+    ) -> domain::services::ServiceResult<Vec<domain::schemas::ActiveCampaignSchema>> {
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut keys = Vec::with_capacity(ids.len());
+        for id in ids {
+            keys.push(format!("active_campaign:{}", self.get_random_chunk_from_uuid(id).await));
+        }
+
+        let mut conn = self.repo.get_conn().await?;
+        let campaigns: Vec<Option<Vec<u8>>> = redis::cmd("MGET")
+            .arg(&keys)
+            .query(&mut conn)
+            .map_err(|_| domain::services::ServiceError::Cash("Redis MGET error".to_string()))?;
+
+        Ok(keys
+            .iter()
+            .zip(campaigns)
+            .filter_map(|(key, data)| Self::decode_active_campaign(key, &data?))
+            .collect())
+    }
+
+    /// Deletes an active campaign and its counters by ID
+    ///
+    /// The campaign record goes first. The recording scripts don't touch the
+    /// counters of a campaign without its record, so the counters are no
+    /// longer live when they are deleted and a script still in flight can't
+    /// recreate them.
     pub async fn del_active_campaigns(&self, id: &uuid::Uuid) -> domain::services::ServiceResult<()> {
         let random_id = self.get_random_chunk_from_uuid(id).await;
-        self.repo.delete(&format!("active_campaign:{random_id}")).await
+        self.repo.delete(&format!("active_campaign:{random_id}")).await?;
+        self.del_campaign_stats(id).await?;
+        domain::services::TargetingIndexService::remove(id);
+        Ok(())
+    }
+
+    /// Checks whether a campaign is active
+    pub async fn is_active_campaign(&self, id: &uuid::Uuid) -> domain::services::ServiceResult<bool> {
+        let random_id = self.get_random_chunk_from_uuid(id).await;
+        let mut conn = self.repo.get_conn().await?;
+
+        redis::cmd("EXISTS")
+            .arg(format!("active_campaign:{random_id}"))
+            .query(&mut conn)
+            .map_err(|_| domain::services::ServiceError::Cash("Redis EXISTS error".to_string()))
+    }
+
+    /// Loads impression, click and spend counters into active campaigns
+    ///
+    /// Counters are kept in separate Redis keys next to the campaign record,
+    /// only impressions of `client_id` are loaded into `view_clients` and only
+    /// the spend of `advanced_time` into `spent_today`.
+    pub async fn load_campaign_stats(
+        &self,
+        campaigns: &mut [domain::schemas::ActiveCampaignSchema],
+        client_id: Option<uuid::Uuid>,
+        advanced_time: u32,
+    ) -> domain::services::ServiceResult<()> {
+        if campaigns.is_empty() {
+            return Ok(());
+        }
+
+        let mut pipe = redis::pipe();
+        for campaign in campaigns.iter() {
+            let random_id = self.get_random_chunk_from_uuid(&campaign.campaign_id).await;
+            pipe.cmd("GET")
+                .arg(format!("campaign_views:{random_id}"))
+                .cmd("SCARD")
+                .arg(format!("campaign_clickers:{random_id}"))
+                .cmd("GET")
+                .arg(format!("campaign_spent:{random_id}"))
+                .cmd("HGET")
+                .arg(format!("campaign_spent_by_day:{random_id}"))
+                .arg(advanced_time);
+            if let Some(client_id) = client_id {
+                pipe.cmd("HGET")
+                    .arg(format!("campaign_client_views:{random_id}"))
+                    .arg(client_id.to_string());
+            }
+        }
+
+        let mut conn = self.repo.get_conn().await?;
+        let values: Vec<redis::Value> = pipe
+            .query(&mut conn)
+            .map_err(|_| domain::services::ServiceError::Cash("Redis load campaign stats error".to_string()))?;
+
+        let chunk_size = if client_id.is_some() { 5 } else { 4 };
+        for (campaign, values) in campaigns.iter_mut().zip(values.chunks(chunk_size)) {
+            let parse_error = |_| domain::services::ServiceError::Cash("Redis campaign stats type error".to_string());
+
+            campaign.views_count = redis::from_redis_value::<Option<u32>>(&values[0])
+                .map_err(parse_error)?
+                .unwrap_or(0);
+            campaign.clicks_count = redis::from_redis_value(&values[1]).map_err(parse_error)?;
+            campaign.spent = redis::from_redis_value::<Option<f64>>(&values[2])
+                .map_err(parse_error)?
+                .unwrap_or(0.0);
+            campaign.spent_today = redis::from_redis_value::<Option<f64>>(&values[3])
+                .map_err(parse_error)?
+                .unwrap_or(0.0);
+            campaign.view_clients.clear();
+            if let Some(client_id) = client_id {
+                if let Some(client_views) =
+                    redis::from_redis_value::<Option<String>>(&values[4]).map_err(parse_error)?
+                {
+                    let client_views: std::collections::BTreeMap<u32, u32> = serde_json::from_str(&client_views)
+                        .map_err(|_| {
+                            domain::services::ServiceError::Cash("Redis campaign stats type error".to_string())
+                        })?;
+                    campaign.view_clients.insert(client_id, client_views);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replaces impression and click counters of a campaign
+    ///
+    /// Old counters are deleted and new ones are written in one transaction,
+    /// so readers never see a campaign without its counters.
+    ///
+    /// # Arguments
+    /// * `id` - Campaign id
+    /// * `views` - Client id and day of every impression
+    /// * `click_clients_id` - Ids of clients who clicked the campaign
+    pub async fn set_campaign_stats(
+        &self,
+        id: &uuid::Uuid,
+        views: Vec<(uuid::Uuid, u32)>,
+        click_clients_id: Vec<uuid::Uuid>,
+    ) -> domain::services::ServiceResult<()> {
+        let random_id = self.get_random_chunk_from_uuid(id).await;
+        let mut pipe = redis::pipe();
+        pipe.atomic()
+            .cmd("DEL")
+            .arg(Self::campaign_stats_keys(&random_id))
+            .ignore()
+            .cmd("SET")
+            .arg(format!("campaign_views:{random_id}"))
+            .arg(views.len())
+            .ignore();
+
+        let mut client_views: std::collections::HashMap<uuid::Uuid, std::collections::BTreeMap<u32, u32>> =
+            std::collections::HashMap::new();
+        for (client_id, advanced_time) in views {
+            *client_views
+                .entry(client_id)
+                .or_default()
+                .entry(advanced_time)
+                .or_default() += 1;
+        }
+        for (client_id, views) in client_views {
+            let views = serde_json::to_string(&views).map_err(|_| domain::services::ServiceError::Unknown)?;
+            pipe.cmd("HSET")
+                .arg(format!("campaign_client_views:{random_id}"))
+                .arg(client_id.to_string())
+                .arg(views)
+                .ignore();
+        }
+        for client_id in click_clients_id {
+            pipe.cmd("SADD")
+                .arg(format!("campaign_clickers:{random_id}"))
+                .arg(client_id.to_string())
+                .ignore();
+        }
+
+        let mut conn = self.repo.get_conn().await?;
+        pipe.query::<()>(&mut conn)
+            .map_err(|_| domain::services::ServiceError::Cash("Redis set campaign stats error".to_string()))
+    }
+
+    /// Replaces the spend counters of a campaign
+    ///
+    /// The recording scripts increment the counters before the event is
+    /// stored in the database, so the counters of an active campaign are
+    /// ahead of the database. Only call this before the campaign is
+    /// activated, while no script can change the counters.
+    ///
+    /// # Arguments
+    /// * `id` - Campaign id
+    /// * `spent` - Total spend of the campaign
+    /// * `advanced_time` - Current day
+    /// * `spent_today` - Spend of the campaign on the current day
+    pub async fn set_campaign_spent(
+        &self,
+        id: &uuid::Uuid,
+        spent: f64,
+        advanced_time: u32,
+        spent_today: f64,
+    ) -> domain::services::ServiceResult<()> {
+        let random_id = self.get_random_chunk_from_uuid(id).await;
+        let mut conn = self.repo.get_conn().await?;
+
+        redis::pipe()
+            .atomic()
+            .cmd("SET")
+            .arg(format!("campaign_spent:{random_id}"))
+            .arg(spent)
+            .ignore()
+            .cmd("DEL")
+            .arg(format!("campaign_spent_by_day:{random_id}"))
+            .ignore()
+            .cmd("HSET")
+            .arg(format!("campaign_spent_by_day:{random_id}"))
+            .arg(advanced_time)
+            .arg(spent_today)
+            .ignore()
+            .query::<()>(&mut conn)
+            .map_err(|_| domain::services::ServiceError::Cash("Redis set campaign spent error".to_string()))
+    }
+
+    /// Records an impression of a campaign to a client if the campaign is
+    /// still allowed to show it
+    ///
+    /// The impressions limit, the budgets and the frequency cap are checked
+    /// and the counters are updated by one Lua script, so concurrent workers
+    /// can't lose updates or overshoot the limits.
+    ///
+    /// # Returns
+    /// Total and daily spend of the campaign after the impression, `None` when
+    /// the impression isn't allowed anymore or the campaign is no longer
+    /// active
+    pub async fn record_campaign_view(
+        &self,
+        campaign: &domain::schemas::ActiveCampaignSchema,
+        client_id: &uuid::Uuid,
+        advanced_time: u32,
+        cost: f64,
+    ) -> domain::services::ServiceResult<Option<(f64, f64)>> {
+        let random_id = self.get_random_chunk_from_uuid(&campaign.campaign_id).await;
+        let (cap_impressions, cap_since) =
+            domain::services::AdsService::frequency_cap_window(&campaign.frequency_cap, advanced_time);
+        let mut conn = self.repo.get_conn().await?;
+
+        RECORD_VIEW_SCRIPT
+            .key(format!("active_campaign:{random_id}"))
+            .key(format!("campaign_views:{random_id}"))
+            .key(format!("campaign_client_views:{random_id}"))
+            .key(format!("campaign_spent:{random_id}"))
+            .key(format!("campaign_spent_by_day:{random_id}"))
+            .arg(client_id.to_string())
+            .arg(advanced_time)
+            .arg(cost)
+            .arg(domain::services::AdsService::max_views(campaign))
+            .arg(campaign.budget.map_or(String::new(), |budget| budget.to_string()))
+            .arg(campaign.daily_budget.map_or(String::new(), |budget| budget.to_string()))
+            .arg(cap_impressions)
+            .arg(cap_since)
+            .invoke(&mut conn)
+            .map_err(|_| domain::services::ServiceError::Cash("Redis record view error".to_string()))
+    }
+
+    /// Reverts an impression recorded by `record_campaign_view`, used when
+    /// the impression can't be stored in the database
+    pub async fn undo_campaign_view(
+        &self,
+        id: &uuid::Uuid,
+        client_id: &uuid::Uuid,
+        advanced_time: u32,
+        cost: f64,
+    ) -> domain::services::ServiceResult<()> {
+        let random_id = self.get_random_chunk_from_uuid(id).await;
+        let mut conn = self.repo.get_conn().await?;
+
+        UNDO_VIEW_SCRIPT
+            .key(format!("active_campaign:{random_id}"))
+            .key(format!("campaign_views:{random_id}"))
+            .key(format!("campaign_client_views:{random_id}"))
+            .key(format!("campaign_spent:{random_id}"))
+            .key(format!("campaign_spent_by_day:{random_id}"))
+            .arg(client_id.to_string())
+            .arg(advanced_time)
+            .arg(cost)
+            .invoke::<()>(&mut conn)
+            .map_err(|_| domain::services::ServiceError::Cash("Redis undo view error".to_string()))
+    }
+
+    /// Records the first click of a client on a campaign the client has seen
+    ///
+    /// The check and the update are done by one Lua script.
+    ///
+    /// # Returns
+    /// Total and daily spend of the campaign after the click, `None` when the
+    /// client has already clicked the campaign
+    pub async fn record_campaign_click(
+        &self,
+        id: &uuid::Uuid,
+        client_id: &uuid::Uuid,
+        advanced_time: u32,
+        cost: f64,
+    ) -> domain::services::ServiceResult<Option<(f64, f64)>> {
+        let random_id = self.get_random_chunk_from_uuid(id).await;
+        let mut conn = self.repo.get_conn().await?;
+
+        let result: redis::Value = RECORD_CLICK_SCRIPT
+            .key(format!("active_campaign:{random_id}"))
+            .key(format!("campaign_client_views:{random_id}"))
+            .key(format!("campaign_clickers:{random_id}"))
+            .key(format!("campaign_spent:{random_id}"))
+            .key(format!("campaign_spent_by_day:{random_id}"))
+            .arg(client_id.to_string())
+            .arg(advanced_time)
+            .arg(cost)
+            .invoke(&mut conn)
+            .map_err(|_| domain::services::ServiceError::Cash("Redis record click error".to_string()))?;
+
+        match result {
+            redis::Value::Int(-2) => Err(domain::services::ServiceError::Validation(
+                "campaign is not active".into(),
+            )),
+            redis::Value::Int(-1) => Err(domain::services::ServiceError::Validation(
+                "client never view this this campaign".into(),
+            )),
+            redis::Value::Int(_) => Ok(None),
+            spent => redis::from_redis_value(&spent)
+                .map(Some)
+                .map_err(|_| domain::services::ServiceError::Cash("Redis record click error".to_string())),
+        }
+    }
+
+    /// Reverts a click recorded by `record_campaign_click`, used when the
+    /// click can't be stored in the database
+    pub async fn undo_campaign_click(
+        &self,
+        id: &uuid::Uuid,
+        client_id: &uuid::Uuid,
+        advanced_time: u32,
+        cost: f64,
+    ) -> domain::services::ServiceResult<()> {
+        let random_id = self.get_random_chunk_from_uuid(id).await;
+        let mut conn = self.repo.get_conn().await?;
+
+        UNDO_CLICK_SCRIPT
+            .key(format!("active_campaign:{random_id}"))
+            .key(format!("campaign_clickers:{random_id}"))
+            .key(format!("campaign_spent:{random_id}"))
+            .key(format!("campaign_spent_by_day:{random_id}"))
+            .arg(client_id.to_string())
+            .arg(advanced_time)
+            .arg(cost)
+            .invoke::<()>(&mut conn)
+            .map_err(|_| domain::services::ServiceError::Cash("Redis undo click error".to_string()))
+    }
+
+    /// Deletes impression, click and spend counters of a campaign
+    async fn del_campaign_stats(&self, id: &uuid::Uuid) -> domain::services::ServiceResult<()> {
+        let random_id = self.get_random_chunk_from_uuid(id).await;
+        let mut conn = self.repo.get_conn().await?;
+
+        redis::cmd("DEL")
+            .arg(Self::campaign_stats_keys(&random_id))
+            .query::<()>(&mut conn)
+            .map_err(|_| domain::services::ServiceError::Cash("Redis delete campaign stats error".to_string()))
+    }
+
+    /// Returns the keys of impression, click and spend counters of a campaign
+    fn campaign_stats_keys(random_id: &str) -> Vec<String> {
+        [
+            "campaign_views",
+            "campaign_client_views",
+            "campaign_clickers",
+            "campaign_spent",
+            "campaign_spent_by_day",
+        ]
+        .iter()
+        .map(|prefix| format!("{prefix}:{random_id}"))
+        .collect()
     }
 
     /// Retrieves a single active campaign by ID
@@ -85,9 +622,74 @@ impl<'p> RedisService<'p> {
         data: domain::schemas::ActiveCampaignSchema,
     ) -> domain::services::ServiceResult<()> {
         let random_id = self.get_random_chunk_from_uuid(&data.campaign_id).await;
+        domain::services::TargetingIndexService::upsert(&data);
         self.repo.set(&format!("active_campaign:{random_id}"), data).await
     }
 
+    /// Replaces the creatives of an active campaign, nothing is stored when
+    /// the campaign isn't active
+    ///
+    /// The record is watched while the creatives are loaded and is written in
+    /// a transaction, so a concurrent change of the creatives or the campaign
+    /// restarts the update instead of being overwritten. The creatives are
+    /// loaded after the record is watched, so every update sees the creatives
+    /// committed before it.
+    ///
+    /// # Arguments
+    /// * `id` - UUID of the campaign
+    /// * `load_creatives` - Loads all creatives of the campaign
+    pub async fn set_active_campaign_creatives<F, Fut>(
+        &self,
+        id: &uuid::Uuid,
+        load_creatives: F,
+    ) -> domain::services::ServiceResult<()>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = domain::services::ServiceResult<Vec<domain::schemas::CreativeSchema>>>,
+    {
+        let key = format!("active_campaign:{}", self.get_random_chunk_from_uuid(id).await);
+        let mut conn = self.repo.get_conn().await?;
+
+        loop {
+            redis::cmd("WATCH")
+                .arg(&key)
+                .query::<()>(&mut conn)
+                .map_err(|_| domain::services::ServiceError::Cash("Redis WATCH error".to_string()))?;
+
+            let creatives = match load_creatives().await {
+                Ok(creatives) => creatives,
+                Err(e) => {
+                    redis::cmd("UNWATCH")
+                        .query::<()>(&mut conn)
+                        .map_err(|_| domain::services::ServiceError::Cash("Redis UNWATCH error".to_string()))?;
+                    return Err(e);
+                },
+            };
+
+            let data: Option<Vec<u8>> = redis::cmd("GET")
+                .arg(&key)
+                .query(&mut conn)
+                .map_err(|_| domain::services::ServiceError::Cash("Redis GET error".to_string()))?;
+            let Some(mut campaign) = data.and_then(|data| Self::decode_active_campaign(&key, &data)) else {
+                redis::cmd("UNWATCH")
+                    .query::<()>(&mut conn)
+                    .map_err(|_| domain::services::ServiceError::Cash("Redis UNWATCH error".to_string()))?;
+                return Ok(());
+            };
+            campaign.creatives = creatives;
+
+            let result: Option<()> = redis::pipe()
+                .atomic()
+                .set(&key, campaign)
+                .ignore()
+                .query(&mut conn)
+                .map_err(|_| domain::services::ServiceError::Cash("Redis SET error".to_string()))?;
+            if result.is_some() {
+                return Ok(());
+            }
+        }
+    }
+
     /// Retrieves the list of obscene words used for moderation
     pub async fn get_obscene_words(&self) -> domain::services::ServiceResult<Vec<String>> {
         let word: String = self.repo.get("obscene_words").await?;
@@ -131,6 +733,51 @@ impl<'p> RedisService<'p> {
     pub async fn set_is_activate_auto_moderate(&self, data: bool) -> domain::services::ServiceResult<()> {
         self.repo.set("is_activate_auto_moderate", data).await
     }
+
+    /// Gets the runtime ranking weights, defaulting to the given weights if
+    /// not set
+    pub async fn get_ads_config(
+        &self,
+        default: domain::schemas::AdsConfigSchema,
+    ) -> domain::services::ServiceResult<domain::schemas::AdsConfigSchema> {
+        match self.repo.get::<Option<String>>("ads_config").await? {
+            Some(data) => serde_json::from_str(&data)
+                .map_err(|_| domain::services::ServiceError::Cash("Redis ads config is corrupted".to_string())),
+            None => Ok(default),
+        }
+    }
+
+    /// Sets the runtime ranking weights
+    pub async fn set_ads_config(&self, data: &domain::schemas::AdsConfigSchema) -> domain::services::ServiceResult<()> {
+        let data = serde_json::to_string(data).map_err(|_| domain::services::ServiceError::Unknown)?;
+        self.repo.set("ads_config", data).await
+    }
+
+    /// Gets the cached running experiment
+    ///
+    /// # Returns
+    /// * `ServiceResult<Option<Option<ExperimentSchema>>>` - `None` when the
+    ///   running experiment is not cached, `Some(None)` when no experiment is
+    ///   running
+    pub async fn get_active_experiment(
+        &self,
+    ) -> domain::services::ServiceResult<Option<Option<domain::schemas::ExperimentSchema>>> {
+        match self.repo.get::<Option<String>>("active_experiment").await? {
+            Some(data) => serde_json::from_str(&data).map(Some).map_err(|_| {
+                domain::services::ServiceError::Cash("Redis active experiment is corrupted".to_string())
+            }),
+            None => Ok(None),
+        }
+    }
+
+    /// Caches the running experiment, `None` when no experiment is running
+    pub async fn set_active_experiment(
+        &self,
+        data: Option<&domain::schemas::ExperimentSchema>,
+    ) -> domain::services::ServiceResult<()> {
+        let data = serde_json::to_string(&data).map_err(|_| domain::services::ServiceError::Unknown)?;
+        self.repo.set("active_experiment", data).await
+    }
 }
 
 impl redis::ToRedisArgs for domain::schemas::ActiveCampaignSchema {
@@ -138,8 +785,10 @@ impl redis::ToRedisArgs for domain::schemas::ActiveCampaignSchema {
     where
         W: ?Sized + redis::RedisWrite,
     {
+        // Fields are written by name, so records stay readable when fields
+        // with a default are added
         let mut buf = Vec::new();
-        self.serialize(&mut rmp_serde::Serializer::new(&mut buf))
+        self.serialize(&mut rmp_serde::Serializer::new(&mut buf).with_struct_map())
             .expect("Failed to serialize ActiveCampaignSchema to MessagePack");
 
         out.write_arg(&buf);
@@ -162,3 +811,329 @@ impl redis::FromRedisValue for domain::schemas::ActiveCampaignSchema {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn create_test_pool() -> &'static infrastructure::database_connection::redis::RedisPool {
+        let config = infrastructure::configurate::RedisConfig {
+            host: std::env::var("REDIS_HOST").unwrap_or("127.0.0.1".into()),
+            port: std::env::var("REDIS_PORT").map_or(6379, |port| port.parse().unwrap()),
+            db: 15,
+        };
+        Box::leak(Box::new(
+            infrastructure::database_connection::redis::RedisPool::new(config).await,
+        ))
+    }
+
+    fn create_test_campaign(
+        impressions_limit: u32,
+        frequency_cap: Option<domain::schemas::FrequencyCapSchema>,
+    ) -> domain::schemas::ActiveCampaignSchema {
+        domain::schemas::ActiveCampaignSchema {
+            impressions_limit,
+            frequency_cap,
+            ..domain::schemas::ActiveCampaignSchema::test_fixture()
+        }
+    }
+
+    async fn record_views_in_parallel(
+        pool: &'static infrastructure::database_connection::redis::RedisPool,
+        campaign: &domain::schemas::ActiveCampaignSchema,
+        client_ids: Vec<uuid::Uuid>,
+    ) -> usize {
+        let tasks: Vec<_> = client_ids
+            .into_iter()
+            .map(|client_id| {
+                let campaign = campaign.clone();
+                tokio::spawn(async move {
+                    RedisService::new(pool)
+                        .record_campaign_view(&campaign, &client_id, 1, 1.)
+                        .await
+                        .unwrap()
+                        .is_some()
+                })
+            })
+            .collect();
+
+        let mut recorded = 0;
+        for task in tasks {
+            recorded += task.await.unwrap() as usize;
+        }
+        recorded
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+    #[ignore = "requires a local Redis"]
+    async fn parallel_views_are_not_lost_and_respect_limits() {
+        let pool = create_test_pool().await;
+        let service = RedisService::new(pool);
+
+        let mut campaign = create_test_campaign(100, None);
+        service.set_active_campaign(campaign.clone()).await.unwrap();
+        let recorded =
+            record_views_in_parallel(pool, &campaign, (0..400).map(|_| uuid::Uuid::new_v4()).collect()).await;
+
+        let mut campaigns = vec![campaign.clone()];
+        service.load_campaign_stats(&mut campaigns, None, 1).await.unwrap();
+        assert_eq!(
+            recorded,
+            domain::services::AdsService::max_views(&campaign) as usize + 1
+        );
+        assert_eq!(campaigns[0].views_count as usize, recorded);
+        assert_eq!(campaigns[0].spent, recorded as f64);
+
+        campaign = create_test_campaign(
+            1000,
+            Some(domain::schemas::FrequencyCapSchema {
+                impressions: 3,
+                period: None,
+            }),
+        );
+        service.set_active_campaign(campaign.clone()).await.unwrap();
+        let client_id = uuid::Uuid::new_v4();
+        let recorded = record_views_in_parallel(pool, &campaign, vec![client_id; 50]).await;
+
+        let mut campaigns = vec![campaign.clone()];
+        service
+            .load_campaign_stats(&mut campaigns, Some(client_id), 1)
+            .await
+            .unwrap();
+        assert_eq!(recorded, 3);
+        assert_eq!(campaigns[0].views_count, 3);
+        assert_eq!(campaigns[0].view_clients[&client_id][&1], 3);
+
+        for campaign in campaigns {
+            service.del_active_campaigns(&campaign.campaign_id).await.unwrap();
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+    #[ignore = "requires a local Redis"]
+    async fn parallel_clicks_are_counted_once() {
+        let pool = create_test_pool().await;
+        let service = RedisService::new(pool);
+        let campaign = create_test_campaign(100, None);
+        service.set_active_campaign(campaign.clone()).await.unwrap();
+        let client_id = uuid::Uuid::new_v4();
+
+        assert!(matches!(
+            service
+                .record_campaign_click(&campaign.campaign_id, &client_id, 1, 2.)
+                .await,
+            Err(domain::services::ServiceError::Validation(_))
+        ));
+        service
+            .record_campaign_view(&campaign, &client_id, 1, 1.)
+            .await
+            .unwrap();
+
+        let tasks: Vec<_> = (0..50)
+            .map(|_| {
+                let campaign_id = campaign.campaign_id;
+                tokio::spawn(async move {
+                    RedisService::new(pool)
+                        .record_campaign_click(&campaign_id, &client_id, 1, 2.)
+                        .await
+                        .unwrap()
+                        .is_some()
+                })
+            })
+            .collect();
+        let mut recorded = 0;
+        for task in tasks {
+            recorded += task.await.unwrap() as usize;
+        }
+
+        let mut campaigns = vec![campaign.clone()];
+        service.load_campaign_stats(&mut campaigns, None, 1).await.unwrap();
+        assert_eq!(recorded, 1);
+        assert_eq!(campaigns[0].clicks_count, 1);
+        assert_eq!(campaigns[0].spent, 3.);
+
+        service.del_active_campaigns(&campaign.campaign_id).await.unwrap();
+    }
+
+    #[test]
+    fn active_campaign_keeps_parsed_expression_in_msgpack() {
+        let mut campaign = create_test_campaign(10, None);
+        campaign.targeting.expression = Some(r#"age >= 18 AND interest IN ["sport", "travel"]"#.into());
+        campaign.targeting_expression = campaign
+            .targeting
+            .expression
+            .as_deref()
+            .map(|expression| domain::services::TargetingExpression::parse(expression).unwrap());
+
+        let value = redis::Value::BulkString(redis::ToRedisArgs::to_redis_args(&campaign).remove(0));
+        let restored: domain::schemas::ActiveCampaignSchema = redis::FromRedisValue::from_redis_value(&value).unwrap();
+
+        assert_eq!(restored.targeting_expression, campaign.targeting_expression);
+    }
+
+    #[test]
+    fn active_campaign_is_stored_with_field_names() {
+        let campaign = create_test_campaign(10, None);
+        let data = redis::ToRedisArgs::to_redis_args(&campaign).remove(0);
+
+        let fields: std::collections::BTreeMap<String, serde::de::IgnoredAny> = rmp_serde::from_slice(&data).unwrap();
+        assert!(fields.contains_key("campaign_id"));
+        assert!(fields.contains_key("creatives"));
+
+        // Positional record of the version without budgets and counter keys
+        let legacy = (
+            campaign.campaign_id,
+            campaign.advertiser_id,
+            campaign.impressions_limit,
+            campaign.clicks_limit,
+            campaign.cost_per_impression,
+            campaign.cost_per_click,
+            campaign.ad_title.clone(),
+            campaign.ad_text.clone(),
+            campaign.start_date,
+            campaign.end_date,
+            Vec::<uuid::Uuid>::new(),
+            Vec::<uuid::Uuid>::new(),
+            campaign.targeting.clone(),
+        );
+        let mut positional = Vec::new();
+        legacy.serialize(&mut rmp_serde::Serializer::new(&mut positional)).unwrap();
+
+        assert_eq!(
+            RedisService::decode_active_campaign("active_campaign:test", &data),
+            Some(campaign)
+        );
+        assert!(RedisService::decode_active_campaign("active_campaign:test", &positional).is_none());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a local Redis"]
+    async fn undecodable_active_campaigns_are_dropped() {
+        let pool = create_test_pool().await;
+        let service = RedisService::new(pool);
+        let campaign = create_test_campaign(100, None);
+        service.set_active_campaign(campaign.clone()).await.unwrap();
+        let random_id = service.get_random_chunk_from_uuid(&campaign.campaign_id).await;
+        service
+            .repo
+            .set(&format!("active_campaign:{random_id}"), vec![0x93u8, 0x01, 0x02, 0x03])
+            .await
+            .unwrap();
+
+        let campaigns = service.get_all_active_campaigns().await.unwrap();
+
+        assert!(campaigns.iter().all(|active| active.campaign_id != campaign.campaign_id));
+        assert!(!service.is_active_campaign(&campaign.campaign_id).await.unwrap());
+        assert!(service
+            .get_active_campaigns_by_ids(&[campaign.campaign_id])
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a local Redis"]
+    async fn deleted_campaign_counters_are_not_recreated() {
+        let pool = create_test_pool().await;
+        let service = RedisService::new(pool);
+        let campaign = create_test_campaign(100, None);
+        let client_id = uuid::Uuid::new_v4();
+
+        service.set_active_campaign(campaign.clone()).await.unwrap();
+        service.del_active_campaigns(&campaign.campaign_id).await.unwrap();
+
+        assert_eq!(
+            service
+                .record_campaign_view(&campaign, &client_id, 1, 1.)
+                .await
+                .unwrap(),
+            None
+        );
+        let random_id = service.get_random_chunk_from_uuid(&campaign.campaign_id).await;
+        let mut conn = service.repo.get_conn().await.unwrap();
+        let exists: u32 = redis::cmd("EXISTS")
+            .arg(RedisService::campaign_stats_keys(&random_id))
+            .query(&mut conn)
+            .unwrap();
+        assert_eq!(exists, 0);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a local Redis"]
+    async fn undone_view_and_click_restore_counters() {
+        let pool = create_test_pool().await;
+        let service = RedisService::new(pool);
+        let campaign = create_test_campaign(100, None);
+        let client_id = uuid::Uuid::new_v4();
+        service.set_active_campaign(campaign.clone()).await.unwrap();
+
+        service
+            .record_campaign_view(&campaign, &client_id, 1, 1.)
+            .await
+            .unwrap();
+        service
+            .record_campaign_click(&campaign.campaign_id, &client_id, 1, 2.)
+            .await
+            .unwrap();
+        service
+            .undo_campaign_click(&campaign.campaign_id, &client_id, 1, 2.)
+            .await
+            .unwrap();
+        service
+            .undo_campaign_view(&campaign.campaign_id, &client_id, 1, 1.)
+            .await
+            .unwrap();
+
+        let mut campaigns = vec![campaign.clone()];
+        service
+            .load_campaign_stats(&mut campaigns, Some(client_id), 1)
+            .await
+            .unwrap();
+        assert_eq!(campaigns[0].views_count, 0);
+        assert_eq!(campaigns[0].clicks_count, 0);
+        assert_eq!(campaigns[0].spent, 0.);
+        assert!(campaigns[0].view_clients.is_empty());
+
+        service.del_active_campaigns(&campaign.campaign_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a local Redis"]
+    async fn concurrent_campaign_change_restarts_creatives_update() {
+        let pool = create_test_pool().await;
+        let service = RedisService::new(pool);
+        let campaign = create_test_campaign(100, None);
+        service.set_active_campaign(campaign.clone()).await.unwrap();
+        let creative = domain::schemas::CreativeSchema {
+            creative_id: uuid::Uuid::new_v4(),
+            campaign_id: campaign.campaign_id,
+            ad_title: "Creative title".into(),
+            ad_text: "Creative text".into(),
+            image_names: vec![],
+            prompt_version_id: None,
+        };
+        let loads = std::sync::atomic::AtomicUsize::new(0);
+
+        service
+            .set_active_campaign_creatives(&campaign.campaign_id, || async {
+                if loads.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 0 {
+                    RedisService::new(pool)
+                        .set_active_campaign(domain::schemas::ActiveCampaignSchema {
+                            ad_title: "Updated title".into(),
+                            ..campaign.clone()
+                        })
+                        .await?;
+                }
+                Ok(vec![creative.clone()])
+            })
+            .await
+            .unwrap();
+
+        let stored = service.get_active_campaign(&campaign.campaign_id).await.unwrap();
+        assert_eq!(loads.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert_eq!(stored.ad_title, "Updated title");
+        assert_eq!(stored.creatives, vec![creative]);
+
+        service.del_active_campaigns(&campaign.campaign_id).await.unwrap();
+    }
+}
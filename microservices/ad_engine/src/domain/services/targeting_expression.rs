@@ -0,0 +1,473 @@
+use crate::domain;
+
+/// Maximum nesting of `NOT` and parentheses in a targeting expression
+pub const MAX_NESTING_DEPTH: usize = 32;
+/// Maximum number of comparisons in a targeting expression
+pub const MAX_COMPARISONS: usize = 128;
+
+/// Boolean targeting expression of a campaign
+///
+/// Grammar, keywords are case-insensitive:
+///
+/// ```text
+/// expression := and ("OR" and)*
+/// and        := not ("AND" not)*
+/// not        := "NOT" not | "(" expression ")" | comparison
+/// comparison := field ("=" | "!=" | ">" | ">=" | "<" | "<=") value
+///             | field "IN" "[" value ("," value)* "]"
+/// value      := "string" | number | true | false
+/// ```
+///
+/// For example `age >= 18 AND (interest IN ["sport", "travel"]) AND NOT
+/// location = "Kazan"`. Fields are `age`, `gender`, `location` and the keys of
+/// the client attributes. `location` is compared hierarchically like in the
+/// rest of the targeting, strings are compared case-insensitively like
+/// `gender` and `location` in the rest of the targeting, a field holding a
+/// list matches when any of its items matches, and a comparison with a
+/// missing field is false.
+///
+/// `NOT` and parentheses nest at most [`MAX_NESTING_DEPTH`] levels deep and an
+/// expression has at most [`MAX_COMPARISONS`] comparisons, every value of an
+/// `IN` list counting as one, so parsing and evaluation can't overflow the
+/// stack or take unbounded time.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub enum TargetingExpression {
+    And(Box<TargetingExpression>, Box<TargetingExpression>),
+    Or(Box<TargetingExpression>, Box<TargetingExpression>),
+    Not(Box<TargetingExpression>),
+    Compare(String, CompareOperator, serde_json::Value),
+    In(String, Vec<serde_json::Value>),
+}
+
+/// Comparison operator of a targeting expression
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum CompareOperator {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Value(serde_json::Value),
+    Operator(CompareOperator),
+    And,
+    Or,
+    Not,
+    In,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+}
+
+impl TargetingExpression {
+    /// Parses a targeting expression
+    ///
+    /// # Arguments
+    /// * `expression` - Source text of the expression
+    ///
+    /// # Returns
+    /// Parsed expression or a validation error describing the problem
+    pub fn parse(expression: &str) -> domain::services::ServiceResult<Self> {
+        let tokens = Self::tokenize(expression)?;
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+            depth: 0,
+            comparisons: 0,
+        };
+
+        let parsed = parser.parse_or()?;
+        if parser.position != parser.tokens.len() {
+            return Err(Self::error(format!("unexpected token at {}", parser.position)));
+        }
+        Ok(parsed)
+    }
+
+    /// Evaluates the expression for a client
+    ///
+    /// # Arguments
+    /// * `fields` - Client fields and attributes by name
+    pub fn evaluate(&self, fields: &serde_json::Map<String, serde_json::Value>) -> bool {
+        match self {
+            Self::And(left, right) => left.evaluate(fields) && right.evaluate(fields),
+            Self::Or(left, right) => left.evaluate(fields) || right.evaluate(fields),
+            Self::Not(inner) => !inner.evaluate(fields),
+            Self::Compare(field, operator, value) => fields
+                .get(field)
+                .is_some_and(|actual| Self::matches(actual, |item| Self::compare(field, item, *operator, value))),
+            Self::In(field, values) => fields.get(field).is_some_and(|actual| {
+                Self::matches(actual, |item| {
+                    values
+                        .iter()
+                        .any(|value| Self::compare(field, item, CompareOperator::Eq, value))
+                })
+            }),
+        }
+    }
+
+    /// Applies a predicate to a field value or to every item of a list value
+    fn matches(actual: &serde_json::Value, predicate: impl Fn(&serde_json::Value) -> bool) -> bool {
+        match actual {
+            serde_json::Value::Array(items) => items.iter().any(predicate),
+            item => predicate(item),
+        }
+    }
+
+    /// Compares a single field value with an expression value
+    fn compare(
+        field: &str,
+        actual: &serde_json::Value,
+        operator: CompareOperator,
+        expected: &serde_json::Value,
+    ) -> bool {
+        use std::cmp::Ordering;
+
+        let ordering = match (actual, expected) {
+            (serde_json::Value::String(actual), serde_json::Value::String(expected)) if field == "location" => {
+                return match operator {
+                    CompareOperator::Eq => domain::services::AdsService::is_sublocation(actual, expected),
+                    CompareOperator::Ne => !domain::services::AdsService::is_sublocation(actual, expected),
+                    _ => false,
+                };
+            },
+            (serde_json::Value::String(actual), serde_json::Value::String(expected)) => {
+                Some(actual.to_lowercase().cmp(&expected.to_lowercase()))
+            },
+            (serde_json::Value::Number(actual), serde_json::Value::Number(expected)) => actual
+                .as_f64()
+                .zip(expected.as_f64())
+                .and_then(|(a, e)| a.partial_cmp(&e)),
+            (serde_json::Value::Bool(actual), serde_json::Value::Bool(expected)) => Some(actual.cmp(expected)),
+            _ => None,
+        };
+
+        ordering.is_some_and(|ordering| match operator {
+            CompareOperator::Eq => ordering == Ordering::Equal,
+            CompareOperator::Ne => ordering != Ordering::Equal,
+            CompareOperator::Gt => ordering == Ordering::Greater,
+            CompareOperator::Ge => ordering != Ordering::Less,
+            CompareOperator::Lt => ordering == Ordering::Less,
+            CompareOperator::Le => ordering != Ordering::Greater,
+        })
+    }
+
+    /// Splits the source text of an expression into tokens
+    fn tokenize(expression: &str) -> domain::services::ServiceResult<Vec<Token>> {
+        let chars: Vec<char> = expression.chars().collect();
+        let mut tokens = Vec::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+            match c {
+                _ if c.is_whitespace() => i += 1,
+                '(' | ')' | '[' | ']' | ',' => {
+                    tokens.push(match c {
+                        '(' => Token::LParen,
+                        ')' => Token::RParen,
+                        '[' => Token::LBracket,
+                        ']' => Token::RBracket,
+                        _ => Token::Comma,
+                    });
+                    i += 1;
+                },
+                '=' | '!' | '<' | '>' => {
+                    let with_eq = chars.get(i + 1) == Some(&'=');
+                    let operator = match (c, with_eq) {
+                        ('=', _) => CompareOperator::Eq,
+                        ('!', true) => CompareOperator::Ne,
+                        ('<', true) => CompareOperator::Le,
+                        ('<', false) => CompareOperator::Lt,
+                        ('>', true) => CompareOperator::Ge,
+                        ('>', false) => CompareOperator::Gt,
+                        _ => return Err(Self::error(format!("unexpected `{c}` at {i}"))),
+                    };
+                    tokens.push(Token::Operator(operator));
+                    i += if with_eq && c != '=' { 2 } else { 1 };
+                },
+                '"' => {
+                    let start = i + 1;
+                    let end = chars[start..]
+                        .iter()
+                        .position(|&c| c == '"')
+                        .map(|p| start + p)
+                        .ok_or_else(|| Self::error(format!("unterminated string at {i}")))?;
+                    tokens.push(Token::Value(serde_json::Value::String(
+                        chars[start..end].iter().collect(),
+                    )));
+                    i = end + 1;
+                },
+                _ if c.is_ascii_digit() || c == '-' => {
+                    let start = i;
+                    i += 1;
+                    while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                        i += 1;
+                    }
+                    let number: String = chars[start..i].iter().collect();
+                    let number = number
+                        .parse::<f64>()
+                        .ok()
+                        .and_then(serde_json::Number::from_f64)
+                        .ok_or_else(|| Self::error(format!("invalid number `{number}` at {start}")))?;
+                    tokens.push(Token::Value(serde_json::Value::Number(number)));
+                },
+                _ if c.is_alphabetic() || c == '_' => {
+                    let start = i;
+                    while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.') {
+                        i += 1;
+                    }
+                    let word: String = chars[start..i].iter().collect();
+                    tokens.push(match word.to_uppercase().as_str() {
+                        "AND" => Token::And,
+                        "OR" => Token::Or,
+                        "NOT" => Token::Not,
+                        "IN" => Token::In,
+                        "TRUE" => Token::Value(serde_json::Value::Bool(true)),
+                        "FALSE" => Token::Value(serde_json::Value::Bool(false)),
+                        _ => Token::Ident(word),
+                    });
+                },
+                _ => return Err(Self::error(format!("unexpected `{c}` at {i}"))),
+            }
+        }
+
+        Ok(tokens)
+    }
+
+    fn error(message: String) -> domain::services::ServiceError {
+        domain::services::ServiceError::Validation(format!("invalid targeting expression: {message}"))
+    }
+}
+
+/// Recursive descent parser over the tokens of an expression
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    /// Current nesting of `NOT` and parentheses
+    depth: usize,
+    /// Number of comparisons and `IN` values parsed so far
+    comparisons: usize,
+}
+
+impl Parser {
+    fn parse_or(&mut self) -> domain::services::ServiceResult<TargetingExpression> {
+        let mut left = self.parse_and()?;
+        while self.eat(&Token::Or) {
+            left = TargetingExpression::Or(Box::new(left), Box::new(self.parse_and()?));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> domain::services::ServiceResult<TargetingExpression> {
+        let mut left = self.parse_not()?;
+        while self.eat(&Token::And) {
+            left = TargetingExpression::And(Box::new(left), Box::new(self.parse_not()?));
+        }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> domain::services::ServiceResult<TargetingExpression> {
+        if self.eat(&Token::Not) {
+            self.enter()?;
+            let inner = self.parse_not()?;
+            self.depth -= 1;
+            return Ok(TargetingExpression::Not(Box::new(inner)));
+        }
+        if self.eat(&Token::LParen) {
+            self.enter()?;
+            let inner = self.parse_or()?;
+            self.expect(&Token::RParen)?;
+            self.depth -= 1;
+            return Ok(inner);
+        }
+        self.parse_comparison()
+    }
+
+    /// Enters a nested `NOT` or parentheses
+    fn enter(&mut self) -> domain::services::ServiceResult<()> {
+        self.depth += 1;
+        if self.depth > MAX_NESTING_DEPTH {
+            return Err(TargetingExpression::error(format!(
+                "nesting deeper than {MAX_NESTING_DEPTH} at token {}",
+                self.position
+            )));
+        }
+        Ok(())
+    }
+
+    /// Counts a comparison or a value of an `IN` list
+    fn count_comparison(&mut self) -> domain::services::ServiceResult<()> {
+        self.comparisons += 1;
+        if self.comparisons > MAX_COMPARISONS {
+            return Err(TargetingExpression::error(format!(
+                "more than {MAX_COMPARISONS} comparisons"
+            )));
+        }
+        Ok(())
+    }
+
+    fn parse_comparison(&mut self) -> domain::services::ServiceResult<TargetingExpression> {
+        self.count_comparison()?;
+
+        let field = match self.next() {
+            Some(Token::Ident(field)) => field,
+            _ => return Err(self.unexpected("field name")),
+        };
+
+        match self.next() {
+            Some(Token::Operator(operator)) => Ok(TargetingExpression::Compare(field, operator, self.parse_value()?)),
+            Some(Token::In) => {
+                self.expect(&Token::LBracket)?;
+                let mut values = vec![self.parse_value()?];
+                while self.eat(&Token::Comma) {
+                    self.count_comparison()?;
+                    values.push(self.parse_value()?);
+                }
+                self.expect(&Token::RBracket)?;
+                Ok(TargetingExpression::In(field, values))
+            },
+            _ => Err(self.unexpected("comparison operator or IN")),
+        }
+    }
+
+    fn parse_value(&mut self) -> domain::services::ServiceResult<serde_json::Value> {
+        match self.next() {
+            Some(Token::Value(value)) => Ok(value),
+            _ => Err(self.unexpected("value")),
+        }
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.tokens.get(self.position) == Some(token) {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expect(&mut self, token: &Token) -> domain::services::ServiceResult<()> {
+        if self.eat(token) {
+            return Ok(());
+        }
+        Err(self.unexpected(&format!("{token:?}")))
+    }
+
+    fn unexpected(&self, expected: &str) -> domain::services::ServiceError {
+        TargetingExpression::error(format!("expected {expected} at token {}", self.position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn fields(value: serde_json::Value) -> serde_json::Map<String, serde_json::Value> {
+        value.as_object().cloned().unwrap()
+    }
+
+    #[test]
+    fn evaluates_example_expression() {
+        let expression =
+            TargetingExpression::parse(r#"age >= 18 AND (interest IN ["sport","travel"]) AND NOT location = "Kazan""#)
+                .unwrap();
+
+        assert!(expression.evaluate(&fields(json!({
+            "age": 25, "location": "Moscow", "interest": ["music", "travel"]
+        }))));
+        assert!(!expression.evaluate(&fields(json!({
+            "age": 25, "location": "Kazan, center", "interest": "sport"
+        }))));
+        assert!(!expression.evaluate(&fields(json!({
+            "age": 16, "location": "Moscow", "interest": "sport"
+        }))));
+        assert!(!expression.evaluate(&fields(json!({ "age": 25, "location": "Moscow" }))));
+    }
+
+    #[test]
+    fn or_binds_weaker_than_and() {
+        let expression = TargetingExpression::parse(r#"device = "ios" or device = "android" and income > 3"#).unwrap();
+
+        assert!(expression.evaluate(&fields(json!({ "device": "ios", "income": 1 }))));
+        assert!(!expression.evaluate(&fields(json!({ "device": "android", "income": 1 }))));
+        assert!(expression.evaluate(&fields(json!({ "device": "android", "income": 4.5 }))));
+    }
+
+    #[test]
+    fn invalid_expressions_rejected() {
+        for expression in [
+            "",
+            "age >=",
+            "age 18",
+            r#"interest IN ["sport""#,
+            "(age > 1",
+            "age > 1 AND",
+            r#"location = "Kazan"#,
+            "age > 1 age",
+            "age ! 1",
+        ] {
+            assert!(
+                matches!(
+                    TargetingExpression::parse(expression),
+                    Err(domain::services::ServiceError::Validation(_))
+                ),
+                "{expression}"
+            );
+        }
+    }
+
+    #[test]
+    fn nesting_is_limited() {
+        let nested = |depth: usize| format!("{}age > 1{}", "NOT (".repeat(depth), ")".repeat(depth));
+
+        assert!(TargetingExpression::parse(&nested(MAX_NESTING_DEPTH / 2)).is_ok());
+        assert!(matches!(
+            TargetingExpression::parse(&nested(MAX_NESTING_DEPTH)),
+            Err(domain::services::ServiceError::Validation(_))
+        ));
+        assert!(matches!(
+            TargetingExpression::parse(&"NOT ".repeat(100_000)),
+            Err(domain::services::ServiceError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn comparisons_are_limited() {
+        let chain = |count: usize| vec!["age > 1"; count].join(" AND ");
+
+        assert!(TargetingExpression::parse(&chain(MAX_COMPARISONS)).is_ok());
+        assert!(matches!(
+            TargetingExpression::parse(&chain(MAX_COMPARISONS + 1)),
+            Err(domain::services::ServiceError::Validation(_))
+        ));
+
+        let list = |count: usize| format!("age > 1 AND age IN [{}]", vec!["1"; count].join(", "));
+        assert!(TargetingExpression::parse(&list(MAX_COMPARISONS - 1)).is_ok());
+        assert!(matches!(
+            TargetingExpression::parse(&list(MAX_COMPARISONS)),
+            Err(domain::services::ServiceError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn strings_are_compared_case_insensitively() {
+        let expression = TargetingExpression::parse(r#"gender = "male" AND device IN ["iOS"]"#).unwrap();
+
+        assert!(expression.evaluate(&fields(json!({ "gender": "MALE", "device": "ios" }))));
+        assert!(!expression.evaluate(&fields(json!({ "gender": "FEMALE", "device": "ios" }))));
+    }
+}
@@ -0,0 +1,355 @@
+use crate::domain;
+
+lazy_static::lazy_static! {
+    static ref TARGETING_INDEX: std::sync::RwLock<TargetingIndex> = std::sync::RwLock::new(TargetingIndex::default());
+}
+
+/// Width of an age bucket in years
+const AGE_BUCKET_WIDTH: u8 = 10;
+
+/// Maximum client age
+const MAX_AGE: u8 = 160;
+
+/// Inverted index of active campaigns by targeting
+///
+/// Maps a gender, an age bucket and a location to the set of campaigns that
+/// may target it. Campaigns without a gender or location restriction are kept
+/// in separate "any" sets. The index is coarse: it never misses a matching
+/// campaign, but candidates still have to pass the exact targeting check
+/// (age borders inside a bucket, excluded locations, expressions).
+#[derive(Debug, Default)]
+pub struct TargetingIndex {
+    built: bool,
+    by_gender: std::collections::HashMap<String, std::collections::HashSet<uuid::Uuid>>,
+    any_gender: std::collections::HashSet<uuid::Uuid>,
+    by_age_bucket: std::collections::HashMap<u8, std::collections::HashSet<uuid::Uuid>>,
+    by_location: std::collections::HashMap<String, std::collections::HashSet<uuid::Uuid>>,
+    any_location: std::collections::HashSet<uuid::Uuid>,
+    keys: std::collections::HashMap<uuid::Uuid, TargetingKeys>,
+}
+
+/// Keys a campaign is stored under in the index, kept to remove the campaign
+/// without scanning every set
+#[derive(Debug)]
+struct TargetingKeys {
+    /// Genders of the campaign, empty for any gender
+    genders: Vec<String>,
+    age_buckets: std::ops::RangeInclusive<u8>,
+    /// Normalized locations of the campaign, empty for any location
+    locations: Vec<String>,
+}
+
+impl TargetingIndex {
+    /// Replaces the content of the index with the given campaigns
+    pub fn rebuild(&mut self, campaigns: &[domain::schemas::ActiveCampaignSchema]) {
+        *self = Self {
+            built: true,
+            ..Self::default()
+        };
+        for campaign in campaigns {
+            self.insert(campaign);
+        }
+    }
+
+    /// Adds a campaign to the index or replaces its previous targeting
+    pub fn upsert(&mut self, campaign: &domain::schemas::ActiveCampaignSchema) {
+        self.remove(&campaign.campaign_id);
+        self.insert(campaign);
+    }
+
+    /// Removes a campaign from the index
+    pub fn remove(&mut self, campaign_id: &uuid::Uuid) {
+        let Some(keys) = self.keys.remove(campaign_id) else {
+            return;
+        };
+
+        if keys.genders.is_empty() {
+            self.any_gender.remove(campaign_id);
+        }
+        for gender in &keys.genders {
+            Self::remove_from(&mut self.by_gender, gender, campaign_id);
+        }
+        for bucket in keys.age_buckets {
+            Self::remove_from(&mut self.by_age_bucket, &bucket, campaign_id);
+        }
+        if keys.locations.is_empty() {
+            self.any_location.remove(campaign_id);
+        }
+        for location in &keys.locations {
+            Self::remove_from(&mut self.by_location, location, campaign_id);
+        }
+    }
+
+    /// Selects campaigns that may target a client
+    ///
+    /// # Returns
+    /// Ids of candidate campaigns, `None` while the index hasn't been built
+    pub fn candidates(&self, age: u8, gender: &str, location: &str) -> Option<Vec<uuid::Uuid>> {
+        if !self.built {
+            return None;
+        }
+
+        let empty = std::collections::HashSet::new();
+        let by_gender = self.by_gender.get(&gender.to_uppercase()).unwrap_or(&empty);
+        let by_age = self.by_age_bucket.get(&Self::age_bucket(age)).unwrap_or(&empty);
+        let by_location: std::collections::HashSet<&uuid::Uuid> = Self::location_prefixes(location)
+            .iter()
+            .filter_map(|prefix| self.by_location.get(prefix))
+            .flatten()
+            .chain(self.any_location.iter())
+            .collect();
+
+        Some(
+            by_age
+                .iter()
+                .filter(|id| by_gender.contains(id) || self.any_gender.contains(id))
+                .filter(|id| by_location.contains(id))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    fn insert(&mut self, campaign: &domain::schemas::ActiveCampaignSchema) {
+        let id = campaign.campaign_id;
+        let targeting = &campaign.targeting;
+
+        let mut genders: Vec<String> = targeting
+            .gender
+            .iter()
+            .chain(targeting.genders.iter().flatten())
+            .map(|gender| gender.to_uppercase())
+            .collect();
+        if genders.iter().any(|gender| gender == "ALL") {
+            genders.clear();
+        }
+        if genders.is_empty() {
+            self.any_gender.insert(id);
+        }
+        for gender in &genders {
+            self.by_gender.entry(gender.clone()).or_default().insert(id);
+        }
+
+        let age_from = Self::age_bucket(targeting.age_from.unwrap_or(0));
+        let age_to = Self::age_bucket(targeting.age_to.unwrap_or(MAX_AGE));
+        for bucket in age_from..=age_to {
+            self.by_age_bucket.entry(bucket).or_default().insert(id);
+        }
+
+        let locations: Vec<String> = targeting
+            .location
+            .iter()
+            .chain(targeting.locations.iter().flatten())
+            .filter_map(|location| Self::location_prefixes(location).pop())
+            .collect();
+        if locations.is_empty() {
+            self.any_location.insert(id);
+        }
+        for location in &locations {
+            self.by_location.entry(location.clone()).or_default().insert(id);
+        }
+
+        self.keys.insert(
+            id,
+            TargetingKeys {
+                genders,
+                age_buckets: age_from..=age_to,
+                locations,
+            },
+        );
+    }
+
+    /// Removes a campaign from the set of a key, the empty set is dropped
+    fn remove_from<K: std::hash::Hash + Eq>(
+        sets: &mut std::collections::HashMap<K, std::collections::HashSet<uuid::Uuid>>,
+        key: &K,
+        campaign_id: &uuid::Uuid,
+    ) {
+        if let Some(ids) = sets.get_mut(key) {
+            ids.remove(campaign_id);
+            if ids.is_empty() {
+                sets.remove(key);
+            }
+        }
+    }
+
+    fn age_bucket(age: u8) -> u8 {
+        age.min(MAX_AGE) / AGE_BUCKET_WIDTH
+    }
+
+    /// Returns the normalized prefixes of a hierarchical location, shortest
+    /// first, e.g. `moscow` and `moscow,mcad` for `Moscow, mcad`
+    fn location_prefixes(location: &str) -> Vec<String> {
+        let parts: Vec<String> = location
+            .split(',')
+            .map(|part| part.trim().to_lowercase())
+            .filter(|part| !part.is_empty())
+            .collect();
+
+        (1..=parts.len()).map(|len| parts[..len].join(",")).collect()
+    }
+}
+
+/// Service for the process-wide targeting index of active campaigns
+///
+/// The index lives in the memory of the process, it is rebuilt from Redis on
+/// every time advance and kept up to date when active campaigns are stored or
+/// deleted through [`domain::services::RedisService`]. Other instances change
+/// active campaigns only in Redis, so the index is also rebuilt periodically
+/// by [`domain::usecase::TargetingIndexSyncUsecase`].
+#[derive(Debug)]
+pub struct TargetingIndexService;
+
+impl TargetingIndexService {
+    /// Replaces the index with the given active campaigns
+    pub fn rebuild(campaigns: &[domain::schemas::ActiveCampaignSchema]) {
+        if let Ok(mut index) = TARGETING_INDEX.write() {
+            index.rebuild(campaigns);
+        }
+    }
+
+    /// Adds or updates an active campaign in the index
+    pub fn upsert(campaign: &domain::schemas::ActiveCampaignSchema) {
+        if let Ok(mut index) = TARGETING_INDEX.write() {
+            index.upsert(campaign);
+        }
+    }
+
+    /// Removes an active campaign from the index
+    pub fn remove(campaign_id: &uuid::Uuid) {
+        if let Ok(mut index) = TARGETING_INDEX.write() {
+            index.remove(campaign_id);
+        }
+    }
+
+    /// Selects ids of active campaigns that may target a client, `None` when
+    /// the index isn't built yet
+    pub fn candidates(age: u8, gender: &str, location: &str) -> Option<Vec<uuid::Uuid>> {
+        TARGETING_INDEX
+            .read()
+            .ok()
+            .and_then(|index| index.candidates(age, gender, location))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn create_test_campaign(
+        targeting: domain::schemas::TargetingCampaignSchema,
+    ) -> domain::schemas::ActiveCampaignSchema {
+        domain::schemas::ActiveCampaignSchema {
+            targeting,
+            ..domain::schemas::ActiveCampaignSchema::test_fixture()
+        }
+    }
+
+    fn sorted(mut ids: Vec<Uuid>) -> Vec<Uuid> {
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn candidates_none_until_built() {
+        let mut index = TargetingIndex::default();
+        assert_eq!(index.candidates(25, "MALE", "Moscow"), None);
+
+        index.rebuild(&[]);
+        assert_eq!(index.candidates(25, "MALE", "Moscow"), Some(vec![]));
+    }
+
+    #[test]
+    fn candidates_intersect_gender_age_and_location() {
+        let untargeted = create_test_campaign(Default::default());
+        let male_moscow = create_test_campaign(domain::schemas::TargetingCampaignSchema {
+            gender: Some("MALE".into()),
+            location: Some("Moscow".into()),
+            ..Default::default()
+        });
+        let female = create_test_campaign(domain::schemas::TargetingCampaignSchema {
+            genders: Some(vec!["FEMALE".into()]),
+            ..Default::default()
+        });
+        let young_mcad = create_test_campaign(domain::schemas::TargetingCampaignSchema {
+            age_from: Some(18),
+            age_to: Some(24),
+            locations: Some(vec!["Kazan".into(), "Moscow, mcad".into()]),
+            ..Default::default()
+        });
+
+        let mut index = TargetingIndex::default();
+        index.rebuild(&[
+            untargeted.clone(),
+            male_moscow.clone(),
+            female.clone(),
+            young_mcad.clone(),
+        ]);
+
+        assert_eq!(
+            sorted(index.candidates(25, "male", "moscow, MCAD").unwrap()),
+            sorted(vec![
+                untargeted.campaign_id,
+                male_moscow.campaign_id,
+                young_mcad.campaign_id
+            ])
+        );
+        assert_eq!(
+            sorted(index.candidates(35, "FEMALE", "Moscow").unwrap()),
+            sorted(vec![untargeted.campaign_id, female.campaign_id])
+        );
+        assert_eq!(
+            sorted(index.candidates(19, "FEMALE", "Kazan").unwrap()),
+            sorted(vec![untargeted.campaign_id, female.campaign_id, young_mcad.campaign_id])
+        );
+    }
+
+    #[test]
+    fn upsert_replaces_and_remove_drops_campaign() {
+        let mut campaign = create_test_campaign(domain::schemas::TargetingCampaignSchema {
+            location: Some("Moscow".into()),
+            ..Default::default()
+        });
+
+        let mut index = TargetingIndex::default();
+        index.rebuild(&[campaign.clone()]);
+        assert_eq!(index.candidates(25, "MALE", "Moscow"), Some(vec![campaign.campaign_id]));
+
+        campaign.targeting.location = Some("Kazan".into());
+        index.upsert(&campaign);
+        assert_eq!(index.candidates(25, "MALE", "Moscow"), Some(vec![]));
+        assert_eq!(index.candidates(25, "MALE", "Kazan"), Some(vec![campaign.campaign_id]));
+
+        index.remove(&campaign.campaign_id);
+        assert_eq!(index.candidates(25, "MALE", "Kazan"), Some(vec![]));
+    }
+
+    #[test]
+    fn remove_drops_only_sets_of_campaign() {
+        let targeted = create_test_campaign(domain::schemas::TargetingCampaignSchema {
+            gender: Some("MALE".into()),
+            age_from: Some(20),
+            age_to: Some(29),
+            location: Some("Moscow".into()),
+            ..Default::default()
+        });
+        let untargeted = create_test_campaign(domain::schemas::TargetingCampaignSchema::default());
+
+        let mut index = TargetingIndex::default();
+        index.rebuild(&[targeted.clone(), untargeted.clone()]);
+        index.remove(&targeted.campaign_id);
+
+        assert!(index.by_gender.is_empty());
+        assert!(index.by_location.is_empty());
+        assert!(index.keys.get(&targeted.campaign_id).is_none());
+        assert_eq!(
+            index.candidates(25, "MALE", "Moscow"),
+            Some(vec![untargeted.campaign_id])
+        );
+
+        index.remove(&untargeted.campaign_id);
+        assert!(index.by_age_bucket.is_empty());
+        assert!(index.any_gender.is_empty() && index.any_location.is_empty());
+    }
+}
@@ -7,6 +7,7 @@ pub struct AdsClickUsecase<'p> {
     campaign_service: domain::services::CampaignService,
     client_service: domain::services::ClientService,
     campaign_stat_service: domain::services::CampaignStatService,
+    budget_service: domain::services::BudgetService,
     redis_service: domain::services::RedisService<'p>,
     db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
 }
@@ -20,11 +21,21 @@ impl<'p> AdsClickUsecase<'p> {
             campaign_service: domain::services::CampaignService,
             campaign_stat_service: domain::services::CampaignStatService,
             client_service: domain::services::ClientService,
+            budget_service: domain::services::BudgetService,
             redis_service: domain::services::RedisService::new(redis_pool),
             db_pool,
         }
     }
 
+    /// Records a click of a client on a campaign
+    ///
+    /// The click costs `cost_per_click` with every
+    /// [`domain::services::AdsStrategy`].
+    ///
+    /// The click is charged in Redis first and reverted when it can't be
+    /// stored in the database. The click row is keyed by the campaign and the
+    /// client, so a retried click stores the row even when Redis has already
+    /// counted the click.
     pub async fn click(
         &self,
         campaign_id: uuid::Uuid,
@@ -53,13 +64,13 @@ impl<'p> AdsClickUsecase<'p> {
         let advanced_time = self.redis_service.get_advance_time().await?;
         let mut campaign = self.redis_service.get_active_campaign(&campaign_id).await?;
 
-        if !campaign.view_clients_id.contains(&client.client_id) {
-            return Err(domain::services::ServiceError::Validation(
-                "client never view this this campaign".into(),
-            ));
-        }
+        let spent = self
+            .redis_service
+            .record_campaign_click(&campaign_id, &client.client_id, advanced_time, campaign.cost_per_click)
+            .await?;
 
-        self.campaign_stat_service
+        if let Err(e) = self
+            .campaign_stat_service
             .click_campaign(
                 campaign_id,
                 client.client_id,
@@ -67,17 +78,30 @@ impl<'p> AdsClickUsecase<'p> {
                 advanced_time,
                 infrastructure::repository::sqlx_lib::PgCampaignRepository::new(self.db_pool),
             )
-            .await?;
-
-        if campaign.click_clients_id.contains(&client.client_id) {
-            return Ok(());
+            .await
+        {
+            if spent.is_some() {
+                if let Err(undo_error) = self
+                    .redis_service
+                    .undo_campaign_click(&campaign_id, &client.client_id, advanced_time, campaign.cost_per_click)
+                    .await
+                {
+                    tracing::error!("Failed to undo click of campaign {}: {}", campaign_id, undo_error);
+                }
+            }
+            return Err(e);
         }
 
-        campaign.click_clients_id.push(client.client_id);
+        let Some(spent) = spent else {
+            return Ok(());
+        };
 
         domain::services::PrometheusService::ads_clicks(advanced_time, campaign.cost_per_click);
 
-        self.redis_service.set_active_campaign(campaign).await?;
+        (campaign.spent, campaign.spent_today) = spent;
+        if self.budget_service.is_exhausted(&campaign) {
+            return self.redis_service.del_active_campaigns(&campaign_id).await;
+        }
 
         Ok(())
     }
@@ -0,0 +1,46 @@
+use validator::Validate;
+
+use crate::{domain, infrastructure};
+
+pub struct AdsConfigUsecase<'p> {
+    redis_service: domain::services::RedisService<'p>,
+    app_state: &'p domain::configurate::AppState,
+}
+
+impl<'p> AdsConfigUsecase<'p> {
+    pub fn new(
+        redis_pool: &'p infrastructure::database_connection::redis::RedisPool,
+        app_state: &'p domain::configurate::AppState,
+    ) -> Self {
+        Self {
+            redis_service: domain::services::RedisService::new(redis_pool),
+            app_state,
+        }
+    }
+
+    pub async fn get(&self) -> domain::services::ServiceResult<domain::schemas::AdsConfigSchema> {
+        self.redis_service
+            .get_ads_config(domain::schemas::AdsConfigSchema::from(self.app_state))
+            .await
+    }
+
+    pub async fn set(
+        &self,
+        ads_config: domain::schemas::AdsConfigSchema,
+    ) -> domain::services::ServiceResult<domain::schemas::AdsConfigSchema> {
+        ads_config.validate()?;
+        domain::validators::validate_ads_config_data(&ads_config, self.app_state.ads_weights_sum_to_one).await?;
+
+        let previous = self.get().await?;
+        self.redis_service.set_ads_config(&ads_config).await?;
+
+        tracing::info!(
+            target: "audit",
+            previous = ?previous,
+            current = ?ads_config,
+            "Ranking weights changed"
+        );
+
+        self.get().await
+    }
+}
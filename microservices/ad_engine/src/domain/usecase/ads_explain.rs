@@ -0,0 +1,65 @@
+use crate::{
+    domain,
+    infrastructure::{self, repository::IRepo},
+};
+
+pub struct AdsExplainUsecase<'p> {
+    ads_get: domain::usecase::AdsGetUsecase<'p>,
+    redis_service: domain::services::RedisService<'p>,
+    db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+    app_state: &'p domain::configurate::AppState,
+}
+
+impl<'p> AdsExplainUsecase<'p> {
+    pub fn new(
+        db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+        redis_pool: &'p infrastructure::database_connection::redis::RedisPool,
+        app_state: &'p domain::configurate::AppState,
+    ) -> Self {
+        Self {
+            ads_get: domain::usecase::AdsGetUsecase::new(db_pool, redis_pool, app_state),
+            redis_service: domain::services::RedisService::new(redis_pool),
+            db_pool,
+            app_state,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        client_id: uuid::Uuid,
+    ) -> domain::services::ServiceResult<domain::schemas::AdsExplainResponse> {
+        let mut active_campaigns = self.redis_service.get_all_active_campaigns().await?;
+        let advanced_time = self.redis_service.get_advance_time().await?;
+        self.redis_service
+            .load_campaign_stats(&mut active_campaigns, Some(client_id), advanced_time)
+            .await?;
+        let weights = self
+            .redis_service
+            .get_ads_config(domain::schemas::AdsConfigSchema::from(self.app_state))
+            .await?;
+        let (assignment, arm) = self.ads_get.get_experiment_arm(&client_id).await?.unzip();
+
+        // Exploration is left out, so the explanation shows the deterministic
+        // ranking
+        let campaigns = self
+            .ads_get
+            .create_ads_service(&weights, arm.as_ref())
+            .with_exploration(None)
+            .explain_ads(
+                active_campaigns,
+                client_id,
+                advanced_time,
+                infrastructure::repository::sqlx_lib::PgClientRepository::new(self.db_pool),
+                infrastructure::repository::sqlx_lib::PgScoreRepository::new(self.db_pool),
+            )
+            .await?;
+
+        Ok(domain::schemas::AdsExplainResponse {
+            client_id,
+            current_date: advanced_time,
+            experiment_id: assignment.as_ref().map(|assignment| assignment.experiment_id),
+            arm: assignment.map(|assignment| assignment.arm),
+            campaigns,
+        })
+    }
+}
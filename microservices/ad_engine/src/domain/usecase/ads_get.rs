@@ -4,11 +4,14 @@ use crate::{
 };
 
 pub struct AdsGetUsecase<'p> {
-    ads_service: domain::services::AdsService,
+    experiment_service: domain::services::ExperimentService,
+    shadow_ads_service: Option<domain::services::AdsService>,
     campaign_stat_service: domain::services::CampaignStatService,
+    budget_service: domain::services::BudgetService,
     redis_service: domain::services::RedisService<'p>,
     db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
     redis_pool: &'p infrastructure::database_connection::redis::RedisPool,
+    app_state: &'p domain::configurate::AppState,
     moderate_text_service: domain::services::ModerateTextService,
 }
 
@@ -16,69 +19,373 @@ impl<'p> AdsGetUsecase<'p> {
     pub fn new(
         db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
         redis_pool: &'p infrastructure::database_connection::redis::RedisPool,
-        app_state: &domain::configurate::AppState,
+        app_state: &'p domain::configurate::AppState,
     ) -> Self {
         Self {
-            ads_service: domain::services::AdsService::new(
-                app_state.ads_weight_profit,
-                app_state.ads_weight_relevance,
-                app_state.ads_weight_fulfillment,
-                app_state.ads_weight_time_left,
-            ),
+            experiment_service: domain::services::ExperimentService,
+            shadow_ads_service: app_state.ads_shadow_enabled.then(|| {
+                domain::services::AdsService::new(
+                    app_state.ads_shadow_weight_profit,
+                    app_state.ads_shadow_weight_relevance,
+                    app_state.ads_shadow_weight_fulfillment,
+                    app_state.ads_shadow_weight_time_left,
+                )
+                .with_strategy(app_state.ads_shadow_strategy)
+                .with_pacing(app_state.ads_pacing_enabled.then(|| {
+                    domain::services::PacingService::new(
+                        app_state.ads_pacing_curve_exponent,
+                        app_state.ads_pacing_boost,
+                    )
+                }))
+                .with_ctr_learner(app_state.ads_ctr_learning_enabled.then(|| {
+                    domain::services::CtrLearnerService::new(
+                        app_state.ads_ctr_prior_alpha,
+                        app_state.ads_ctr_prior_beta,
+                        app_state.ads_ctr_learning_weight,
+                    )
+                }))
+            }),
             moderate_text_service: domain::services::ModerateTextService::new(app_state.auto_moderating_sensitivity),
             campaign_stat_service: domain::services::CampaignStatService,
+            budget_service: domain::services::BudgetService,
             redis_service: domain::services::RedisService::new(redis_pool),
             db_pool,
             redis_pool,
+            app_state,
         }
     }
 
-    pub async fn execute(&self, client_id: uuid::Uuid) -> domain::services::ServiceResult<domain::schemas::AdSchema> {
-        let active_campaigns = self.redis_service.get_all_active_campaigns().await?;
-        let advanced_time = self.redis_service.get_advance_time().await?;
+    /// Creates the service ranking campaigns for a client, the weights and
+    /// the strategy of the experiment arm of the client override the
+    /// runtime ones
+    pub(crate) fn create_ads_service(
+        &self,
+        weights: &domain::schemas::AdsConfigSchema,
+        arm: Option<&domain::schemas::ExperimentArmSchema>,
+    ) -> domain::services::AdsService {
+        let app_state = self.app_state;
+        let weights = arm.map_or_else(|| weights.clone(), |arm| arm.weights(weights));
 
-        let mut ads = self
-            .ads_service
-            .recommendation_ads(
-                active_campaigns,
-                client_id,
-                advanced_time,
+        domain::services::AdsService::new(
+            weights.weight_profit,
+            weights.weight_relevance,
+            weights.weight_fulfillment,
+            weights.weight_time_left,
+        )
+        .with_strategy(arm.and_then(|arm| arm.strategy).unwrap_or(app_state.ads_strategy))
+        .with_pacing(app_state.ads_pacing_enabled.then(|| {
+            domain::services::PacingService::new(app_state.ads_pacing_curve_exponent, app_state.ads_pacing_boost)
+        }))
+        .with_ctr_learner(app_state.ads_ctr_learning_enabled.then(|| {
+            domain::services::CtrLearnerService::new(
+                app_state.ads_ctr_prior_alpha,
+                app_state.ads_ctr_prior_beta,
+                app_state.ads_ctr_learning_weight,
+            )
+        }))
+        .with_exploration(self.create_exploration_service())
+    }
+
+    /// Creates the exploration of the ranking over the random generator of
+    /// the process, so every request continues its sequence
+    fn create_exploration_service(&self) -> Option<domain::services::ExplorationService> {
+        self.app_state.ads_exploration_enabled.then(|| {
+            domain::services::ExplorationService::new(
+                self.app_state.ads_exploration_policy,
+                self.app_state.ads_exploration_epsilon,
+                self.app_state.ads_exploration_rng.clone(),
+            )
+        })
+    }
+
+    /// Gets the running experiment from Redis, it is loaded from the database
+    /// only when Redis has no cached experiment yet and is cached on creation
+    /// and stop of experiments afterwards
+    async fn get_active_experiment(
+        &self,
+    ) -> domain::services::ServiceResult<Option<domain::schemas::ExperimentSchema>> {
+        if let Some(experiment) = self.redis_service.get_active_experiment().await? {
+            return Ok(experiment);
+        }
+
+        let experiment = self
+            .experiment_service
+            .get_active(infrastructure::repository::sqlx_lib::PgExperimentRepository::new(
+                self.db_pool,
+            ))
+            .await?;
+        self.redis_service.set_active_experiment(experiment.as_ref()).await?;
+
+        Ok(experiment)
+    }
+
+    /// Resolves the arm of the running experiment the client is assigned to
+    ///
+    /// # Returns
+    /// The assignment and the arm of the client, `None` without a running
+    /// experiment or for an experiment without arms
+    pub(crate) async fn get_experiment_arm(
+        &self,
+        client_id: &uuid::Uuid,
+    ) -> domain::services::ServiceResult<
+        Option<(domain::schemas::ExperimentAssignmentSchema, domain::schemas::ExperimentArmSchema)>,
+    > {
+        let Some(experiment) = self.get_active_experiment().await? else {
+            return Ok(None);
+        };
+
+        Ok(self.experiment_service.assign(&experiment, client_id).map(|arm| {
+            (
+                domain::schemas::ExperimentAssignmentSchema {
+                    experiment_id: experiment.experiment_id,
+                    arm: arm.name.clone(),
+                },
+                arm.clone(),
+            )
+        }))
+    }
+
+    pub async fn execute(
+        &self,
+        client_id: uuid::Uuid,
+        limit: u32,
+    ) -> domain::services::ServiceResult<Vec<domain::schemas::RankedAdSchema>> {
+        if limit == 0 {
+            return Err(domain::services::ServiceError::Validation(
+                "limit must be more than 0".into(),
+            ));
+        }
+
+        let (assignment, arm) = self.get_experiment_arm(&client_id).await?.unzip();
+
+        let weights = self
+            .redis_service
+            .get_ads_config(domain::schemas::AdsConfigSchema::from(self.app_state))
+            .await?;
+
+        let ads_service = self.create_ads_service(&weights, arm.as_ref());
+        let client = ads_service
+            .get_client(
                 infrastructure::repository::sqlx_lib::PgClientRepository::new(self.db_pool),
-                infrastructure::repository::sqlx_lib::PgScoreRepository::new(self.db_pool),
+                client_id,
             )
             .await?;
 
-        let mut campaign = self.redis_service.get_active_campaign(&ads.ad_id).await?;
+        let mut active_campaigns = self.get_candidate_campaigns(&client).await?;
+        let advanced_time = self.redis_service.get_advance_time().await?;
+        self.redis_service
+            .load_campaign_stats(&mut active_campaigns, Some(client_id), advanced_time)
+            .await?;
+        let shadow_campaigns = self.shadow_ads_service.as_ref().map(|_| active_campaigns.clone());
+
+        let ml_scores = domain::services::MlScoresCache::new(
+            infrastructure::repository::sqlx_lib::PgScoreRepository::new(self.db_pool),
+        );
+        let ranked_ads = ads_service
+            .recommendation_ads(active_campaigns, &client, advanced_time, limit as usize, &ml_scores)
+            .await?;
 
-        if !campaign.view_clients_id.contains(&client_id) {
-            self.campaign_stat_service
-                .view_campaign(
-                    ads.ad_id,
-                    client_id,
-                    campaign.cost_per_impression,
-                    advanced_time,
-                    infrastructure::repository::sqlx_lib::PgCampaignRepository::new(self.db_pool),
+        if let (Some(shadow_ads_service), Some(shadow_campaigns)) = (&self.shadow_ads_service, shadow_campaigns) {
+            Self::compare_shadow_ranking(
+                shadow_ads_service,
+                shadow_campaigns,
+                &client,
+                advanced_time,
+                &ranked_ads[0],
+                &ml_scores,
+            )
+            .await;
+        }
+
+        let mut ads = Vec::with_capacity(ranked_ads.len());
+        for ad in ranked_ads {
+            if self
+                .record_impression(&ad, client_id, advanced_time, assignment.clone())
+                .await?
+            {
+                ads.push(ad);
+            }
+        }
+        if ads.is_empty() {
+            return Err(domain::services::ServiceError::Repository(
+                infrastructure::repository::RepoError::ObjDoesNotExists("Suitable campaigns".into()),
+            ));
+        }
+
+        let is_activate_auto_moderate = self.redis_service.get_is_activate_auto_moderate().await?;
+        for ad in ads.iter_mut() {
+            let new_texts = self
+                .moderate_text_service
+                .hide_abusive_content(
+                    &[ad.ad_text.clone(), ad.ad_title.clone()],
+                    is_activate_auto_moderate,
+                    infrastructure::repository::redis::RedisObsceneWordRepository::new(self.redis_pool, self.db_pool),
                 )
                 .await?;
 
-            campaign.view_clients_id.push(client_id);
-            domain::services::PrometheusService::ads_visits(advanced_time, campaign.cost_per_impression);
+            ad.ad_text = new_texts[0].clone();
+            ad.ad_title = new_texts[1].clone();
+        }
+
+        Ok(ads)
+    }
+
+    /// Selects active campaigns that may target the client with the targeting
+    /// index, the index is built from all active campaigns on the first call
+    async fn get_candidate_campaigns(
+        &self,
+        client: &infrastructure::repository::sqlx_lib::ClientReturningSchema,
+    ) -> domain::services::ServiceResult<Vec<domain::schemas::ActiveCampaignSchema>> {
+        match domain::services::TargetingIndexService::candidates(client.age_u8(), &client.gender, &client.location) {
+            Some(campaign_ids) => self.redis_service.get_active_campaigns_by_ids(&campaign_ids).await,
+            None => {
+                let active_campaigns = self.redis_service.get_all_active_campaigns().await?;
+                domain::services::TargetingIndexService::rebuild(&active_campaigns);
+                Ok(active_campaigns)
+            },
+        }
+    }
+
+    /// Ranks the campaigns with the shadow configuration and compares its
+    /// winner with the served one, the shadow winner is never served
+    ///
+    /// The revenue delta is estimated by the impression prices of the
+    /// winners. The shadow ranking reuses the ML scores loaded for the served
+    /// ranking, so it doesn't query the database again. Failures of the shadow
+    /// ranking are logged and don't affect the served ads.
+    async fn compare_shadow_ranking<R>(
+        shadow_ads_service: &domain::services::AdsService,
+        active_campaigns: Vec<domain::schemas::ActiveCampaignSchema>,
+        client: &infrastructure::repository::sqlx_lib::ClientReturningSchema,
+        advanced_time: u32,
+        served_ad: &domain::schemas::RankedAdSchema,
+        ml_scores: &domain::services::MlScoresCache<R>,
+    ) where
+        R: domain::services::repository::IGetMlScores + Send + Sync,
+    {
+        let shadow_ads = shadow_ads_service
+            .recommendation_ads(active_campaigns, client, advanced_time, 1, ml_scores)
+            .await;
+
+        match shadow_ads {
+            Ok(shadow_ads) => {
+                let shadow_ad = &shadow_ads[0];
+                let is_differs = shadow_ad.ad_id != served_ad.ad_id;
+                let revenue_delta = shadow_ad.price - served_ad.price;
 
-            self.redis_service.set_active_campaign(campaign).await?;
+                tracing::info!(
+                    served_ad_id = %served_ad.ad_id,
+                    shadow_ad_id = %shadow_ad.ad_id,
+                    is_differs,
+                    revenue_delta,
+                    "Shadow ranking compared"
+                );
+                domain::services::PrometheusService::shadow_ranking(is_differs, revenue_delta);
+            },
+            Err(e) => tracing::warn!("Failed shadow ranking: {}", e),
         }
+    }
+
+    /// Records an impression of a ranked ad
+    ///
+    /// The counters are updated in Redis first, so the limits are checked
+    /// atomically, and are reverted when the impression can't be stored in the
+    /// database.
+    ///
+    /// # Returns
+    /// `false` when a concurrent request has already used up the limits of the
+    /// campaign or has stopped it, and the ad must not be shown
+    async fn record_impression(
+        &self,
+        ad: &domain::schemas::RankedAdSchema,
+        client_id: uuid::Uuid,
+        advanced_time: u32,
+        experiment: Option<domain::schemas::ExperimentAssignmentSchema>,
+    ) -> domain::services::ServiceResult<bool> {
+        let (campaign_id, cost) = (ad.ad_id, ad.price);
+        let Some(mut campaign) = self
+            .redis_service
+            .get_active_campaigns_by_ids(&[campaign_id])
+            .await?
+            .pop()
+        else {
+            return Ok(false);
+        };
 
-        let new_texts = self
-            .moderate_text_service
-            .hide_abusive_content(
-                &[ads.ad_text, ads.ad_title],
-                self.redis_service.get_is_activate_auto_moderate().await?,
-                infrastructure::repository::redis::RedisObsceneWordRepository::new(self.redis_pool, self.db_pool),
+        let Some(spent) = self
+            .redis_service
+            .record_campaign_view(&campaign, &client_id, advanced_time, cost)
+            .await?
+        else {
+            return Ok(false);
+        };
+
+        if let Err(e) = self
+            .campaign_stat_service
+            .view_campaign(
+                campaign_id,
+                client_id,
+                cost,
+                advanced_time,
+                ad.creative_id,
+                experiment,
+                infrastructure::repository::sqlx_lib::PgCampaignRepository::new(self.db_pool),
             )
-            .await?;
+            .await
+        {
+            if let Err(undo_error) = self
+                .redis_service
+                .undo_campaign_view(&campaign_id, &client_id, advanced_time, cost)
+                .await
+            {
+                tracing::error!("Failed to undo impression of campaign {}: {}", campaign_id, undo_error);
+            }
+            return Err(e);
+        }
 
-        ads.ad_text = new_texts[0].clone();
-        ads.ad_title = new_texts[1].clone();
+        domain::services::PrometheusService::ads_visits(advanced_time, cost);
 
-        Ok(ads)
+        (campaign.spent, campaign.spent_today) = spent;
+        if self.budget_service.is_exhausted(&campaign) {
+            self.redis_service.del_active_campaigns(&campaign_id).await?;
+        }
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn requests_continue_exploration_sequence() {
+        let mut config = infrastructure::configurate::test_config();
+        config.ads_recommendation.exploration.enabled = true;
+        config.ads_recommendation.exploration.policy = domain::services::ExplorationPolicy::Thompson;
+        config.ads_recommendation.exploration.seed = Some(42);
+        let app_state = domain::configurate::AppState::from(&config);
+        let db_pool = sqlx::postgres::PgPoolOptions::new()
+            .connect_lazy(&config.database.postgres.postgres_conn)
+            .unwrap();
+        let redis_pool = infrastructure::database_connection::redis::RedisPool::lazy(config.database.redis.clone());
+
+        let reference = domain::services::ExplorationService::new(
+            domain::services::ExplorationPolicy::Thompson,
+            0.0,
+            domain::services::ExplorationService::create_rng(Some(42)),
+        );
+        let expected: Vec<f64> = (0..2).map(|_| reference.sample_beta(2.0, 5.0)).collect();
+
+        let samples: Vec<f64> = (0..2)
+            .map(|_| {
+                AdsGetUsecase::new(&db_pool, &redis_pool, &app_state)
+                    .create_exploration_service()
+                    .unwrap()
+                    .sample_beta(2.0, 5.0)
+            })
+            .collect();
+
+        assert_eq!(samples, expected);
     }
 }
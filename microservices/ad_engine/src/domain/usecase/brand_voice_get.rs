@@ -0,0 +1,33 @@
+use crate::{
+    domain,
+    infrastructure::{self, repository::IRepo},
+};
+
+pub struct BrandVoiceGetUsecase<'p> {
+    brand_voice_service: domain::services::BrandVoiceService,
+    db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+}
+
+impl<'p> BrandVoiceGetUsecase<'p> {
+    pub fn new(db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool) -> Self {
+        Self {
+            brand_voice_service: domain::services::BrandVoiceService,
+            db_pool,
+        }
+    }
+
+    pub async fn get(
+        self,
+        advertiser_id: uuid::Uuid,
+    ) -> domain::services::ServiceResult<domain::schemas::BrandVoiceSchema> {
+        self.brand_voice_service
+            .get(
+                advertiser_id,
+                infrastructure::repository::sqlx_lib::PgBrandVoiceRepository::new(self.db_pool),
+            )
+            .await?
+            .ok_or(domain::services::ServiceError::Repository(
+                infrastructure::repository::RepoError::ObjDoesNotExists("Brand voice".into()),
+            ))
+    }
+}
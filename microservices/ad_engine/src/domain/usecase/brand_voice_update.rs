@@ -0,0 +1,36 @@
+use validator::Validate;
+
+use crate::{
+    domain,
+    infrastructure::{self, repository::IRepo},
+};
+
+pub struct BrandVoiceUpdateUsecase<'p> {
+    brand_voice_service: domain::services::BrandVoiceService,
+    db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+}
+
+impl<'p> BrandVoiceUpdateUsecase<'p> {
+    pub fn new(db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool) -> Self {
+        Self {
+            brand_voice_service: domain::services::BrandVoiceService,
+            db_pool,
+        }
+    }
+
+    pub async fn update(
+        self,
+        advertiser_id: uuid::Uuid,
+        update_data: domain::schemas::BrandVoiceUpdateRequest,
+    ) -> domain::services::ServiceResult<domain::schemas::BrandVoiceSchema> {
+        update_data.validate()?;
+
+        self.brand_voice_service
+            .update(
+                advertiser_id,
+                update_data,
+                infrastructure::repository::sqlx_lib::PgBrandVoiceRepository::new(self.db_pool),
+            )
+            .await
+    }
+}
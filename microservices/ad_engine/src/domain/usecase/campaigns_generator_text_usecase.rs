@@ -6,8 +6,10 @@ use crate::{
 };
 
 pub struct CampaignsGeneratorTextUsecase<'p> {
-    yandex_gpt_service: domain::services::YandexGptService,
+    gpt_service: domain::services::GptService,
+    generation_prompts: domain::usecase::GenerationPromptsGetUsecase<'p>,
     campaign_service: domain::services::CampaignService,
+    creative_service: domain::services::CreativeService,
     moderate_text_service: domain::services::ModerateTextService,
     redis_service: domain::services::RedisService<'p>,
     redis_pool: &'p infrastructure::database_connection::redis::RedisPool,
@@ -21,28 +23,92 @@ impl<'p> CampaignsGeneratorTextUsecase<'p> {
         app_state: &'p domain::configurate::AppState,
     ) -> Self {
         Self {
-            yandex_gpt_service: domain::services::YandexGptService::new(
-                app_state.yandex_folder_id.clone(),
-                app_state.yandex_api_key.clone(),
-                app_state.gpt_temperature,
-                app_state.gpt_max_tokens,
-                app_state.system_prompt_for_generate_title.clone(),
-                app_state.system_prompt_for_generate_body.clone(),
-            ),
+            gpt_service: Self::create_gpt_service(app_state),
+            generation_prompts: domain::usecase::GenerationPromptsGetUsecase::new(db_pool, app_state),
             moderate_text_service: domain::services::ModerateTextService::new(app_state.auto_moderating_sensitivity),
             campaign_service: domain::services::CampaignService,
+            creative_service: domain::services::CreativeService,
             redis_service: domain::services::RedisService::new(redis_pool),
             db_pool,
             redis_pool,
         }
     }
 
+    /// Creates the service generating texts with the LLM provider from config
+    pub(super) fn create_gpt_service(app_state: &domain::configurate::AppState) -> domain::services::GptService {
+        domain::services::GptService::new(Self::create_llm_client(app_state))
+    }
+
+    /// Creates the client of the LLM provider selected in config with
+    /// timeouts, retries and the circuit breaker
+    fn create_llm_client(
+        app_state: &domain::configurate::AppState,
+    ) -> Box<dyn domain::services::ILlmClient + Send + Sync> {
+        let llm_client: Box<dyn domain::services::ILlmClient + Send + Sync> = match app_state.llm_provider {
+            domain::services::LlmProvider::Yandex => Box::new(infrastructure::gpt::yandex::YandexGptClient::new(
+                app_state.yandex_folder_id.clone(),
+                app_state.yandex_api_key.clone(),
+                app_state.yandex_iam_url.clone(),
+                app_state.yandex_completion_url.clone(),
+                app_state.yandex_iam_token_refresh_before,
+                app_state.gpt_temperature,
+                app_state.gpt_max_tokens,
+            )),
+            domain::services::LlmProvider::Openai => Box::new(infrastructure::gpt::openai::OpenAiClient::new(
+                app_state.openai_base_url.clone(),
+                app_state.openai_api_key.clone(),
+                app_state.openai_model.clone(),
+                app_state.gpt_temperature,
+                app_state.gpt_max_tokens,
+            )),
+            domain::services::LlmProvider::Mock => Box::new(infrastructure::gpt::mock::MockLlmClient),
+        };
+
+        Box::new(infrastructure::gpt::ResilientLlmClient::new(
+            llm_client,
+            app_state.llm_retry_policy.clone(),
+        ))
+    }
+
+    /// Generates the title and the text of a campaign without creatives
     pub async fn generate(
         &self,
         generate_schema: domain::schemas::CampaignsGenerateTextRequest,
         advertiser_id: uuid::Uuid,
         campaign_id: uuid::Uuid,
     ) -> domain::services::ServiceResult<domain::schemas::CampaignSchema> {
+        self.check_source_texts(&generate_schema).await?;
+
+        let mut campaign = self.get_campaign(advertiser_id, campaign_id).await?;
+        self.check_without_creatives(campaign_id).await?;
+        let prompts = self.get_prompts(&campaign).await?;
+
+        self.gpt_service
+            .generate_text_for_campaign(&mut campaign, generate_schema, &prompts)
+            .await?;
+
+        self.save_campaign_texts(campaign).await
+    }
+
+    /// Returns the service generating the texts
+    pub(super) fn gpt_service(&self) -> &domain::services::GptService {
+        &self.gpt_service
+    }
+
+    /// Gets the prompts for the generation of the texts of a campaign
+    pub(super) async fn get_prompts(
+        &self,
+        campaign: &domain::schemas::CampaignSchema,
+    ) -> domain::services::ServiceResult<domain::services::GenerationPrompts> {
+        self.generation_prompts.get(campaign).await
+    }
+
+    /// Validates the generation request and checks the source texts for
+    /// abusive words
+    pub(super) async fn check_source_texts(
+        &self,
+        generate_schema: &domain::schemas::CampaignsGenerateTextRequest,
+    ) -> domain::services::ServiceResult<()> {
         generate_schema.validate()?;
         self.moderate_text_service
             .check_abusive_content(
@@ -55,40 +121,85 @@ impl<'p> CampaignsGeneratorTextUsecase<'p> {
             )
             .await?;
 
-        let mut campaign = self
-            .campaign_service
+        Ok(())
+    }
+
+    /// Checks that a campaign has no creatives
+    ///
+    /// Creatives are served instead of the title and the text of the campaign,
+    /// so the texts of a campaign with creatives are not generated, its
+    /// creatives are generated instead.
+    pub(super) async fn check_without_creatives(&self, campaign_id: uuid::Uuid) -> domain::services::ServiceResult<()> {
+        let creatives = self
+            .creative_service
+            .get_list(
+                campaign_id,
+                infrastructure::repository::sqlx_lib::PgCreativeRepository::new(self.db_pool),
+            )
+            .await?;
+        if !creatives.is_empty() {
+            return Err(domain::services::ServiceError::Validation(
+                "campaign has creatives, its texts are not served, generate creatives instead".into(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Gets a campaign of an advertiser
+    pub(super) async fn get_campaign(
+        &self,
+        advertiser_id: uuid::Uuid,
+        campaign_id: uuid::Uuid,
+    ) -> domain::services::ServiceResult<domain::schemas::CampaignSchema> {
+        self.campaign_service
             .get_by_id(
                 advertiser_id,
                 campaign_id,
                 infrastructure::repository::sqlx_lib::PgCampaignRepository::new(self.db_pool),
             )
-            .await?;
+            .await
+    }
 
-        self.yandex_gpt_service
-            .generate_text_for_campaign(&mut campaign, generate_schema)
+    /// Saves the title, the text and the prompt version of a campaign to the
+    /// database in one statement and to the active copy in Redis
+    pub(super) async fn save_campaign_texts(
+        &self,
+        campaign: domain::schemas::CampaignSchema,
+    ) -> domain::services::ServiceResult<domain::schemas::CampaignSchema> {
+        let campaign = self
+            .campaign_service
+            .update_texts(
+                campaign.advertiser_id,
+                campaign.campaign_id,
+                campaign.ad_title,
+                campaign.ad_text,
+                campaign.prompt_version_id,
+                infrastructure::repository::sqlx_lib::PgCampaignRepository::new(self.db_pool),
+            )
             .await?;
 
+        self.refresh_active_campaign_texts(&campaign).await?;
+
+        Ok(campaign)
+    }
+
+    /// Copies the title and the text of a campaign saved to the database to
+    /// its active copy in Redis
+    pub(super) async fn refresh_active_campaign_texts(
+        &self,
+        campaign: &domain::schemas::CampaignSchema,
+    ) -> domain::services::ServiceResult<()> {
         let advanced_time = self.redis_service.get_advance_time().await?;
         if advanced_time <= campaign.end_date && advanced_time >= campaign.start_date {
-            let mut active_campaign = self.redis_service.get_active_campaign(&campaign_id).await?;
+            let mut active_campaign = self.redis_service.get_active_campaign(&campaign.campaign_id).await?;
             active_campaign.ad_text = campaign.ad_text.clone();
             active_campaign.ad_title = campaign.ad_title.clone();
             self.redis_service.set_active_campaign(active_campaign).await?;
         }
 
-        let campaign = self
-            .campaign_service
-            .update(
-                domain::schemas::CampaignsUpdateRequest::from(campaign),
-                advertiser_id,
-                campaign_id,
-                advanced_time,
-                infrastructure::repository::sqlx_lib::PgCampaignRepository::new(self.db_pool),
-            )
-            .await?;
-
         domain::services::PrometheusService::increment_campaign_updated(advanced_time);
 
-        Ok(campaign)
+        Ok(())
     }
 }
@@ -43,9 +43,12 @@ impl<'p> CampaignsCreateUsecase<'p> {
             create_data.targeting.age_to,
             create_data.impressions_limit,
             create_data.clicks_limit,
+            create_data.budget,
+            create_data.daily_budget,
             time_advance,
         )
         .await?;
+        domain::validators::validate_targeting_data(&create_data.targeting).await?;
 
         self.moderate_text_service
             .check_abusive_content(
@@ -66,7 +69,7 @@ impl<'p> CampaignsCreateUsecase<'p> {
 
         let advanced_time = self.redis_service.get_advance_time().await.unwrap_or(0);
         if advanced_time <= campaign.end_date && advanced_time >= campaign.start_date {
-            let active_campaign = domain::schemas::ActiveCampaignSchema::from((campaign.clone(), vec![], vec![]));
+            let active_campaign = domain::schemas::ActiveCampaignSchema::from(campaign.clone());
             self.redis_service.set_active_campaign(active_campaign).await?;
         }
 
@@ -8,6 +8,8 @@ use crate::{
 pub struct CampaignsUpdateUsecase<'p> {
     campaign_service: domain::services::CampaignService,
     campaign_stat_service: domain::services::CampaignStatService,
+    creative_service: domain::services::CreativeService,
+    budget_service: domain::services::BudgetService,
     moderate_text_service: domain::services::ModerateTextService,
     redis_service: domain::services::RedisService<'p>,
     db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
@@ -23,6 +25,8 @@ impl<'p> CampaignsUpdateUsecase<'p> {
         Self {
             campaign_service: domain::services::CampaignService,
             campaign_stat_service: domain::services::CampaignStatService,
+            creative_service: domain::services::CreativeService,
+            budget_service: domain::services::BudgetService,
             moderate_text_service: domain::services::ModerateTextService::new(app_state.auto_moderating_sensitivity),
             redis_service: domain::services::RedisService::new(redis_pool),
             db_pool,
@@ -46,9 +50,12 @@ impl<'p> CampaignsUpdateUsecase<'p> {
             update_data.targeting.age_to,
             update_data.impressions_limit,
             update_data.clicks_limit,
+            update_data.budget,
+            update_data.daily_budget,
             time_advance,
         )
         .await?;
+        domain::validators::validate_targeting_data(&update_data.targeting).await?;
 
         self.moderate_text_service
             .check_abusive_content(
@@ -71,17 +78,56 @@ impl<'p> CampaignsUpdateUsecase<'p> {
 
         let advanced_time = self.redis_service.get_advance_time().await.unwrap_or(0);
         if advanced_time <= campaign.end_date && advanced_time >= campaign.start_date {
-            let (view_clients_id, click_clients_id) = self
-                .campaign_stat_service
-                .get_or_create_uniq_id(
+            let mut active_campaign = domain::schemas::ActiveCampaignSchema::from(campaign.clone());
+            active_campaign.creatives = self
+                .creative_service
+                .get_list(
                     campaign.campaign_id,
-                    infrastructure::repository::sqlx_lib::PgCampaignRepository::new(self.db_pool),
+                    infrastructure::repository::sqlx_lib::PgCreativeRepository::new(self.db_pool),
                 )
                 .await?;
 
-            let active_campaign =
-                domain::schemas::ActiveCampaignSchema::from((campaign.clone(), view_clients_id, click_clients_id));
-            self.redis_service.set_active_campaign(active_campaign).await?;
+            // Counters of an already active campaign are kept, they are ahead
+            // of the database while impressions and clicks are recorded
+            if self.redis_service.is_active_campaign(&campaign.campaign_id).await? {
+                self.redis_service
+                    .load_campaign_stats(std::slice::from_mut(&mut active_campaign), None, advanced_time)
+                    .await?;
+            } else {
+                let (view_clients, click_clients_id) = self
+                    .campaign_stat_service
+                    .get_or_create_uniq_id(
+                        campaign.campaign_id,
+                        infrastructure::repository::sqlx_lib::PgCampaignRepository::new(self.db_pool),
+                    )
+                    .await?;
+                self.redis_service
+                    .set_campaign_stats(&campaign.campaign_id, view_clients, click_clients_id)
+                    .await?;
+
+                (active_campaign.spent, active_campaign.spent_today) = self
+                    .campaign_stat_service
+                    .get_spent(
+                        campaign.campaign_id,
+                        advanced_time,
+                        infrastructure::repository::sqlx_lib::PgCampaignRepository::new(self.db_pool),
+                    )
+                    .await?;
+                self.redis_service
+                    .set_campaign_spent(
+                        &campaign.campaign_id,
+                        active_campaign.spent,
+                        advanced_time,
+                        active_campaign.spent_today,
+                    )
+                    .await?;
+            }
+
+            if self.budget_service.is_exhausted(&active_campaign) {
+                self.redis_service.del_active_campaigns(&campaign.campaign_id).await?;
+            } else {
+                self.redis_service.set_active_campaign(active_campaign).await?;
+            }
         }
 
         domain::services::PrometheusService::increment_campaign_updated(advanced_time);
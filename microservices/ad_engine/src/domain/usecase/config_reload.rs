@@ -0,0 +1,64 @@
+use crate::{domain, infrastructure};
+
+pub struct ConfigReloadUsecase {
+    path_to_conf_file: std::path::PathBuf,
+    config: infrastructure::configurate::Config,
+    app_state: std::sync::Arc<domain::configurate::SharedAppState>,
+}
+
+impl ConfigReloadUsecase {
+    pub fn new(
+        path_to_conf_file: std::path::PathBuf,
+        config: infrastructure::configurate::Config,
+        app_state: std::sync::Arc<domain::configurate::SharedAppState>,
+    ) -> Self {
+        Self {
+            path_to_conf_file,
+            config,
+            app_state,
+        }
+    }
+
+    /// Parses the configuration again and replaces the application state
+    ///
+    /// The whole reload is rejected and the previous state is kept when the
+    /// configuration is invalid or changes settings that need a restart.
+    pub async fn execute(&mut self) -> domain::services::ServiceResult<()> {
+        let config = infrastructure::configurate::try_parse(self.path_to_conf_file.clone())
+            .await
+            .map_err(|e| domain::services::ServiceError::Validation(format!("invalid config: {}", e)))?;
+
+        let changes = self.config.immutable_changes(&config);
+        if !changes.is_empty() {
+            return Err(domain::services::ServiceError::Validation(format!(
+                "settings can't be changed without a restart: {}",
+                changes.join(", ")
+            )));
+        }
+
+        self.app_state
+            .store(domain::configurate::AppState::from(&config).keep_process_state(&self.app_state.load()));
+        self.config = config;
+
+        Ok(())
+    }
+
+    /// Reloads the configuration on every SIGHUP until the process stops
+    #[cfg(unix)]
+    pub async fn run(mut self) {
+        let mut hangup = match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+            Ok(hangup) => hangup,
+            Err(e) => {
+                tracing::warn!("Failed to listen SIGHUP, config hot reload is disabled: {}", e);
+                return;
+            },
+        };
+
+        while hangup.recv().await.is_some() {
+            match self.execute().await {
+                Ok(()) => tracing::info!("Config reloaded on SIGHUP."),
+                Err(e) => tracing::error!("Config reload rejected, the previous config is kept: {}", e),
+            }
+        }
+    }
+}
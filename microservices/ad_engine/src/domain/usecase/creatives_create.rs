@@ -0,0 +1,112 @@
+use validator::Validate;
+
+use crate::{
+    domain,
+    infrastructure::{self, repository::IRepo},
+};
+
+pub struct CreativesCreateUsecase<'p> {
+    creative_service: domain::services::CreativeService,
+    campaign_service: domain::services::CampaignService,
+    campaign_image_service: domain::services::CampaignImageService,
+    moderate_text_service: domain::services::ModerateTextService,
+    redis_service: domain::services::RedisService<'p>,
+    db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+    redis_pool: &'p infrastructure::database_connection::redis::RedisPool,
+}
+
+impl<'p> CreativesCreateUsecase<'p> {
+    pub fn new(
+        db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+        redis_pool: &'p infrastructure::database_connection::redis::RedisPool,
+        app_state: &'p domain::configurate::AppState,
+    ) -> Self {
+        Self {
+            creative_service: domain::services::CreativeService,
+            campaign_service: domain::services::CampaignService,
+            campaign_image_service: domain::services::CampaignImageService,
+            moderate_text_service: domain::services::ModerateTextService::new(app_state.auto_moderating_sensitivity),
+            redis_service: domain::services::RedisService::new(redis_pool),
+            db_pool,
+            redis_pool,
+        }
+    }
+
+    pub async fn create(
+        &self,
+        create_data: domain::schemas::CreativeCreateRequest,
+        advertiser_id: uuid::Uuid,
+        campaign_id: uuid::Uuid,
+    ) -> domain::services::ServiceResult<domain::schemas::CreativeSchema> {
+        create_data.validate()?;
+
+        self.moderate_text_service
+            .check_abusive_content(
+                &[create_data.ad_text.clone(), create_data.ad_title.clone()],
+                self.redis_service.get_is_activate_auto_moderate().await?,
+                infrastructure::repository::redis::RedisObsceneWordRepository::new(self.redis_pool, self.db_pool),
+            )
+            .await?;
+
+        self.campaign_service
+            .get_by_id(
+                advertiser_id,
+                campaign_id,
+                infrastructure::repository::sqlx_lib::PgCampaignRepository::new(self.db_pool),
+            )
+            .await?;
+
+        if let Some(image_names) = &create_data.image_names {
+            let campaign_image_names = self
+                .campaign_image_service
+                .get_names(
+                    campaign_id,
+                    infrastructure::repository::sqlx_lib::PgCampaignImageRepository::new(self.db_pool),
+                )
+                .await?;
+            if let Some(image_name) = image_names.iter().find(|name| !campaign_image_names.contains(name)) {
+                return Err(domain::services::ServiceError::Validation(format!(
+                    "image {} is not uploaded to the campaign",
+                    image_name
+                )));
+            }
+        }
+
+        let mut creatives = self.save(campaign_id, vec![create_data], None).await?;
+
+        Ok(creatives.remove(0))
+    }
+
+    /// Stores creatives of a campaign and adds them to the rotation of the
+    /// active campaign
+    ///
+    /// The rotation is reloaded from the database, so creatives added or
+    /// deleted concurrently are not lost.
+    pub(super) async fn save(
+        &self,
+        campaign_id: uuid::Uuid,
+        creatives: Vec<domain::schemas::CreativeCreateRequest>,
+        prompt_version_id: Option<uuid::Uuid>,
+    ) -> domain::services::ServiceResult<Vec<domain::schemas::CreativeSchema>> {
+        let creatives = self
+            .creative_service
+            .create(
+                campaign_id,
+                creatives,
+                prompt_version_id,
+                infrastructure::repository::sqlx_lib::PgCreativeRepository::new(self.db_pool),
+            )
+            .await?;
+
+        self.redis_service
+            .set_active_campaign_creatives(&campaign_id, || {
+                self.creative_service.get_list(
+                    campaign_id,
+                    infrastructure::repository::sqlx_lib::PgCreativeRepository::new(self.db_pool),
+                )
+            })
+            .await?;
+
+        Ok(creatives)
+    }
+}
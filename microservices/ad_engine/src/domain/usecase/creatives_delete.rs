@@ -0,0 +1,59 @@
+use crate::{
+    domain,
+    infrastructure::{self, repository::IRepo},
+};
+
+pub struct CreativesDeleteUsecase<'p> {
+    creative_service: domain::services::CreativeService,
+    campaign_service: domain::services::CampaignService,
+    redis_service: domain::services::RedisService<'p>,
+    db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+}
+
+impl<'p> CreativesDeleteUsecase<'p> {
+    pub fn new(
+        db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+        redis_pool: &'p infrastructure::database_connection::redis::RedisPool,
+    ) -> Self {
+        Self {
+            creative_service: domain::services::CreativeService,
+            campaign_service: domain::services::CampaignService,
+            redis_service: domain::services::RedisService::new(redis_pool),
+            db_pool,
+        }
+    }
+
+    pub async fn delete(
+        &self,
+        advertiser_id: uuid::Uuid,
+        campaign_id: uuid::Uuid,
+        creative_id: uuid::Uuid,
+    ) -> domain::services::ServiceResult<()> {
+        self.campaign_service
+            .get_by_id(
+                advertiser_id,
+                campaign_id,
+                infrastructure::repository::sqlx_lib::PgCampaignRepository::new(self.db_pool),
+            )
+            .await?;
+
+        self.creative_service
+            .delete(
+                campaign_id,
+                creative_id,
+                infrastructure::repository::sqlx_lib::PgCreativeRepository::new(self.db_pool),
+            )
+            .await?;
+
+        self.redis_service
+            .set_active_campaign_creatives(&campaign_id, || {
+                self.creative_service.get_list(
+                    campaign_id,
+                    infrastructure::repository::sqlx_lib::PgCreativeRepository::new(self.db_pool),
+                )
+            })
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use validator::Validate;
+
+use crate::{domain, infrastructure};
+
+pub struct CreativesGenerateUsecase<'p> {
+    generator: domain::usecase::CampaignsGeneratorTextUsecase<'p>,
+    creatives_create: domain::usecase::CreativesCreateUsecase<'p>,
+}
+
+impl<'p> CreativesGenerateUsecase<'p> {
+    pub fn new(
+        db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+        redis_pool: &'p infrastructure::database_connection::redis::RedisPool,
+        app_state: &'p domain::configurate::AppState,
+    ) -> Self {
+        Self {
+            generator: domain::usecase::CampaignsGeneratorTextUsecase::new(db_pool, redis_pool, app_state),
+            creatives_create: domain::usecase::CreativesCreateUsecase::new(db_pool, redis_pool, app_state),
+        }
+    }
+
+    /// Generates creatives with the LLM and adds them to the campaign
+    pub async fn generate(
+        &self,
+        generate_schema: domain::schemas::CreativesGenerateRequest,
+        advertiser_id: uuid::Uuid,
+        campaign_id: uuid::Uuid,
+    ) -> domain::services::ServiceResult<Vec<domain::schemas::CreativeSchema>> {
+        generate_schema.validate()?;
+        self.generator
+            .check_source_texts(&domain::schemas::CampaignsGenerateTextRequest {
+                generate_type: "ALL".into(),
+                ad_title: generate_schema.ad_title.clone(),
+                ad_text: generate_schema.ad_text.clone(),
+            })
+            .await?;
+
+        let campaign = self.generator.get_campaign(advertiser_id, campaign_id).await?;
+        let prompts = self.generator.get_prompts(&campaign).await?;
+
+        let variants = self
+            .generator
+            .gpt_service()
+            .generate_variants(
+                &generate_schema.ad_title.unwrap_or(campaign.ad_title),
+                &generate_schema.ad_text.unwrap_or(campaign.ad_text),
+                generate_schema.variants,
+                &prompts,
+            )
+            .await?;
+
+        self.creatives_create
+            .save(
+                campaign_id,
+                variants
+                    .into_iter()
+                    .map(|(ad_title, ad_text)| domain::schemas::CreativeCreateRequest {
+                        ad_title,
+                        ad_text,
+                        image_names: None,
+                    })
+                    .collect(),
+                prompts.prompt_version_id,
+            )
+            .await
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{
+    domain,
+    infrastructure::{self, repository::IRepo},
+};
+
+pub struct CreativesGetListUsecase<'p> {
+    creative_service: domain::services::CreativeService,
+    campaign_service: domain::services::CampaignService,
+    db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+}
+
+impl<'p> CreativesGetListUsecase<'p> {
+    pub fn new(db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool) -> Self {
+        Self {
+            creative_service: domain::services::CreativeService,
+            campaign_service: domain::services::CampaignService,
+            db_pool,
+        }
+    }
+
+    pub async fn get(
+        &self,
+        advertiser_id: uuid::Uuid,
+        campaign_id: uuid::Uuid,
+    ) -> domain::services::ServiceResult<Vec<domain::schemas::CreativeSchema>> {
+        self.campaign_service
+            .get_by_id(
+                advertiser_id,
+                campaign_id,
+                infrastructure::repository::sqlx_lib::PgCampaignRepository::new(self.db_pool),
+            )
+            .await?;
+
+        self.creative_service
+            .get_list(
+                campaign_id,
+                infrastructure::repository::sqlx_lib::PgCreativeRepository::new(self.db_pool),
+            )
+            .await
+    }
+}
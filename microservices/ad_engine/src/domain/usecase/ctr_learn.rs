@@ -0,0 +1,34 @@
+use crate::{
+    domain,
+    infrastructure::{self, repository::IRepo},
+};
+
+pub struct CtrLearnUsecase<'p> {
+    db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+}
+
+impl<'p> CtrLearnUsecase<'p> {
+    pub fn new(db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool) -> Self {
+        Self { db_pool }
+    }
+
+    pub async fn execute(&self) -> domain::services::ServiceResult<()> {
+        domain::services::CtrLearnerService::learn(
+            &domain::services::CtrEstimates::global(),
+            infrastructure::repository::sqlx_lib::PgScoreRepository::new(self.db_pool),
+        )
+        .await
+    }
+
+    /// Refreshes the learned CTR estimates every `refresh_interval` until the
+    /// process stops, failed refreshes keep the previous estimates
+    pub async fn run(&self, refresh_interval: std::time::Duration) {
+        let mut interval = tokio::time::interval(refresh_interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.execute().await {
+                tracing::warn!("Failed to refresh learned CTR estimates: {}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use validator::Validate;
+
+use crate::{
+    domain,
+    infrastructure::{self, repository::IRepo},
+};
+
+pub struct ExperimentsCreateUsecase<'p> {
+    experiment_service: domain::services::ExperimentService,
+    redis_service: domain::services::RedisService<'p>,
+    db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+    app_state: &'p domain::configurate::AppState,
+}
+
+impl<'p> ExperimentsCreateUsecase<'p> {
+    pub fn new(
+        db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+        redis_pool: &'p infrastructure::database_connection::redis::RedisPool,
+        app_state: &'p domain::configurate::AppState,
+    ) -> Self {
+        Self {
+            experiment_service: domain::services::ExperimentService,
+            redis_service: domain::services::RedisService::new(redis_pool),
+            db_pool,
+            app_state,
+        }
+    }
+
+    pub async fn create(
+        self,
+        create_data: domain::schemas::ExperimentCreateRequest,
+    ) -> domain::services::ServiceResult<domain::schemas::ExperimentSchema> {
+        create_data.validate()?;
+        let runtime_weights = self
+            .redis_service
+            .get_ads_config(domain::schemas::AdsConfigSchema::from(self.app_state))
+            .await?;
+        domain::validators::validate_experiment_data(
+            &create_data.arms,
+            &runtime_weights,
+            self.app_state.ads_weights_sum_to_one,
+        )
+        .await?;
+
+        let advanced_time = self.redis_service.get_advance_time().await?;
+        let experiment = self
+            .experiment_service
+            .create(
+                create_data,
+                advanced_time,
+                infrastructure::repository::sqlx_lib::PgExperimentRepository::new(self.db_pool),
+            )
+            .await?;
+        self.redis_service.set_active_experiment(Some(&experiment)).await?;
+
+        Ok(experiment)
+    }
+}
@@ -0,0 +1,26 @@
+use crate::{
+    domain,
+    infrastructure::{self, repository::IRepo},
+};
+
+pub struct ExperimentsGetListUsecase<'p> {
+    experiment_service: domain::services::ExperimentService,
+    db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+}
+
+impl<'p> ExperimentsGetListUsecase<'p> {
+    pub fn new(db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool) -> Self {
+        Self {
+            experiment_service: domain::services::ExperimentService,
+            db_pool,
+        }
+    }
+
+    pub async fn get(self) -> domain::services::ServiceResult<Vec<domain::schemas::ExperimentSchema>> {
+        self.experiment_service
+            .get_list(infrastructure::repository::sqlx_lib::PgExperimentRepository::new(
+                self.db_pool,
+            ))
+            .await
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{
+    domain,
+    infrastructure::{self, repository::IRepo},
+};
+
+pub struct ExperimentsStopUsecase<'p> {
+    experiment_service: domain::services::ExperimentService,
+    redis_service: domain::services::RedisService<'p>,
+    db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+}
+
+impl<'p> ExperimentsStopUsecase<'p> {
+    pub fn new(
+        db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+        redis_pool: &'p infrastructure::database_connection::redis::RedisPool,
+    ) -> Self {
+        Self {
+            experiment_service: domain::services::ExperimentService,
+            redis_service: domain::services::RedisService::new(redis_pool),
+            db_pool,
+        }
+    }
+
+    pub async fn stop(
+        self,
+        experiment_id: uuid::Uuid,
+    ) -> domain::services::ServiceResult<domain::schemas::ExperimentSchema> {
+        let advanced_time = self.redis_service.get_advance_time().await?;
+        let experiment = self
+            .experiment_service
+            .stop(
+                experiment_id,
+                advanced_time,
+                infrastructure::repository::sqlx_lib::PgExperimentRepository::new(self.db_pool),
+            )
+            .await?;
+        self.redis_service.set_active_experiment(None).await?;
+
+        Ok(experiment)
+    }
+}
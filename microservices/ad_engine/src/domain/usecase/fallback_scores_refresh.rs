@@ -0,0 +1,32 @@
+use crate::{
+    domain,
+    infrastructure::{self, repository::IRepo},
+};
+
+pub struct FallbackScoresRefreshUsecase<'p> {
+    db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+}
+
+impl<'p> FallbackScoresRefreshUsecase<'p> {
+    pub fn new(db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool) -> Self {
+        Self { db_pool }
+    }
+
+    pub async fn execute(&self) -> domain::services::ServiceResult<()> {
+        domain::services::FallbackScoreService::new()
+            .refresh(infrastructure::repository::sqlx_lib::PgScoreRepository::new(self.db_pool))
+            .await
+    }
+
+    /// Refreshes the fallback relevance scores every `refresh_interval` until
+    /// the process stops, failed refreshes keep the previous scores
+    pub async fn run(&self, refresh_interval: std::time::Duration) {
+        let mut interval = tokio::time::interval(refresh_interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.execute().await {
+                tracing::warn!("Failed to refresh fallback relevance scores: {}", e);
+            }
+        }
+    }
+}
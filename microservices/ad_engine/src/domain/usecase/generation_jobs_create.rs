@@ -0,0 +1,53 @@
+use crate::{
+    domain,
+    infrastructure::{self, repository::IRepo},
+};
+
+pub struct GenerationJobsCreateUsecase<'p> {
+    generator: domain::usecase::CampaignsGeneratorTextUsecase<'p>,
+    generation_job_service: domain::services::GenerationJobService,
+    db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+    app_state: &'p std::sync::Arc<domain::configurate::AppState>,
+}
+
+impl<'p> GenerationJobsCreateUsecase<'p> {
+    pub fn new(
+        db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+        redis_pool: &'p infrastructure::database_connection::redis::RedisPool,
+        app_state: &'p std::sync::Arc<domain::configurate::AppState>,
+    ) -> Self {
+        Self {
+            generator: domain::usecase::CampaignsGeneratorTextUsecase::new(db_pool, redis_pool, app_state),
+            generation_job_service: domain::services::GenerationJobService,
+            db_pool,
+            app_state,
+        }
+    }
+
+    /// Creates a job and generates its texts in the background, campaigns
+    /// with creatives are rejected
+    pub async fn create(
+        &self,
+        generate_schema: domain::schemas::CampaignsGenerateTextRequest,
+        advertiser_id: uuid::Uuid,
+        campaign_id: uuid::Uuid,
+    ) -> domain::services::ServiceResult<domain::schemas::GenerationJobSchema> {
+        self.generator.check_source_texts(&generate_schema).await?;
+
+        let campaign = self.generator.get_campaign(advertiser_id, campaign_id).await?;
+        self.generator.check_without_creatives(campaign_id).await?;
+
+        let job = self
+            .generation_job_service
+            .create(
+                &campaign,
+                generate_schema,
+                infrastructure::repository::sqlx_lib::PgGenerationJobRepository::new(self.db_pool),
+            )
+            .await?;
+
+        domain::usecase::GenerationJobsRunUsecase::spawn(self.db_pool, self.app_state, job.job_id);
+
+        Ok(job)
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{
+    domain,
+    infrastructure::{self, repository::IRepo},
+};
+
+pub struct GenerationJobsDecideUsecase<'p> {
+    generator: domain::usecase::CampaignsGeneratorTextUsecase<'p>,
+    generation_job_service: domain::services::GenerationJobService,
+    db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+}
+
+impl<'p> GenerationJobsDecideUsecase<'p> {
+    pub fn new(
+        db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+        redis_pool: &'p infrastructure::database_connection::redis::RedisPool,
+        app_state: &'p domain::configurate::AppState,
+    ) -> Self {
+        Self {
+            generator: domain::usecase::CampaignsGeneratorTextUsecase::new(db_pool, redis_pool, app_state),
+            generation_job_service: domain::services::GenerationJobService,
+            db_pool,
+        }
+    }
+
+    /// Applies the generated texts of a done job to the campaign
+    ///
+    /// The job is marked accepted and the campaign is updated in one
+    /// transaction, so concurrent decisions can't apply texts of a rejected
+    /// job and a failed update doesn't leave the job accepted. The active copy
+    /// of the campaign in Redis is updated after the commit. A job of a
+    /// campaign which got creatives meanwhile can't be accepted, its texts
+    /// would not be served.
+    pub async fn accept(&self, job_id: uuid::Uuid) -> domain::services::ServiceResult<domain::schemas::CampaignSchema> {
+        let job = self.check_done(job_id).await?;
+        self.generator.check_without_creatives(job.campaign_id).await?;
+
+        let job = self
+            .generation_job_service
+            .accept(
+                job_id,
+                infrastructure::repository::sqlx_lib::PgGenerationJobRepository::new(self.db_pool),
+            )
+            .await?;
+
+        let campaign = self.generator.get_campaign(job.advertiser_id, job.campaign_id).await?;
+        self.generator.refresh_active_campaign_texts(&campaign).await?;
+
+        Ok(campaign)
+    }
+
+    /// Discards the generated texts of a done job
+    pub async fn reject(
+        &self,
+        job_id: uuid::Uuid,
+    ) -> domain::services::ServiceResult<domain::schemas::GenerationJobSchema> {
+        self.check_done(job_id).await?;
+
+        self.generation_job_service
+            .reject(
+                job_id,
+                infrastructure::repository::sqlx_lib::PgGenerationJobRepository::new(self.db_pool),
+            )
+            .await
+    }
+
+    /// Checks that a job is done, the status is checked again when the
+    /// decision is stored
+    async fn check_done(
+        &self,
+        job_id: uuid::Uuid,
+    ) -> domain::services::ServiceResult<domain::schemas::GenerationJobSchema> {
+        let job = self
+            .generation_job_service
+            .get_by_id(
+                job_id,
+                infrastructure::repository::sqlx_lib::PgGenerationJobRepository::new(self.db_pool),
+            )
+            .await?;
+        if job.status != domain::schemas::GenerationJobStatus::Done {
+            return Err(domain::services::ServiceError::Validation(format!(
+                "Only a done job can be accepted or rejected, the job is {:?}",
+                job.status
+            )));
+        }
+
+        Ok(job)
+    }
+}
@@ -0,0 +1,30 @@
+use crate::{
+    domain,
+    infrastructure::{self, repository::IRepo},
+};
+
+pub struct GenerationJobsGetUsecase<'p> {
+    generation_job_service: domain::services::GenerationJobService,
+    db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+}
+
+impl<'p> GenerationJobsGetUsecase<'p> {
+    pub fn new(db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool) -> Self {
+        Self {
+            generation_job_service: domain::services::GenerationJobService,
+            db_pool,
+        }
+    }
+
+    pub async fn get(
+        &self,
+        job_id: uuid::Uuid,
+    ) -> domain::services::ServiceResult<domain::schemas::GenerationJobSchema> {
+        self.generation_job_service
+            .get_by_id(
+                job_id,
+                infrastructure::repository::sqlx_lib::PgGenerationJobRepository::new(self.db_pool),
+            )
+            .await
+    }
+}
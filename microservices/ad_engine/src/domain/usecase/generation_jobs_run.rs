@@ -0,0 +1,95 @@
+use crate::{
+    domain,
+    infrastructure::{self, repository::IRepo},
+};
+
+pub struct GenerationJobsRunUsecase<'p> {
+    gpt_service: domain::services::GptService,
+    generation_prompts: domain::usecase::GenerationPromptsGetUsecase<'p>,
+    generation_job_service: domain::services::GenerationJobService,
+    campaign_service: domain::services::CampaignService,
+    db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+    app_state: &'p std::sync::Arc<domain::configurate::AppState>,
+}
+
+impl<'p> GenerationJobsRunUsecase<'p> {
+    pub fn new(
+        db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+        app_state: &'p std::sync::Arc<domain::configurate::AppState>,
+    ) -> Self {
+        Self {
+            gpt_service: domain::usecase::CampaignsGeneratorTextUsecase::create_gpt_service(app_state),
+            generation_prompts: domain::usecase::GenerationPromptsGetUsecase::new(db_pool, app_state),
+            generation_job_service: domain::services::GenerationJobService,
+            campaign_service: domain::services::CampaignService,
+            db_pool,
+            app_state,
+        }
+    }
+
+    /// Runs a job in a background task
+    ///
+    /// The task waits for a permit of `generation_jobs.max_concurrent`, the
+    /// job stays pending meanwhile.
+    pub fn spawn(
+        db_pool: &infrastructure::database_connection::sqlx_lib::SqlxPool,
+        app_state: &std::sync::Arc<domain::configurate::AppState>,
+        job_id: uuid::Uuid,
+    ) {
+        let (db_pool, app_state) = (db_pool.clone(), app_state.clone());
+        tokio::spawn(async move {
+            let Ok(_permit) = app_state.generation_job_permits.clone().acquire_owned().await else {
+                return;
+            };
+            if let Err(e) = GenerationJobsRunUsecase::new(&db_pool, &app_state).run(job_id).await {
+                tracing::error!("Failed to run generation job {}: {}", job_id, e);
+            }
+        });
+    }
+
+    /// Generates the texts of a pending job with the current prompts of the
+    /// campaign
+    ///
+    /// A job whose campaign or prompts can't be loaded is marked failed.
+    pub async fn run(
+        &self,
+        job_id: uuid::Uuid,
+    ) -> domain::services::ServiceResult<Option<domain::schemas::GenerationJobSchema>> {
+        self.generation_job_service
+            .run(
+                job_id,
+                self.app_state.generation_job_lease,
+                &self.gpt_service,
+                |advertiser_id, campaign_id| async move {
+                    let campaign = self
+                        .campaign_service
+                        .get_by_id(
+                            advertiser_id,
+                            campaign_id,
+                            infrastructure::repository::sqlx_lib::PgCampaignRepository::new(self.db_pool),
+                        )
+                        .await?;
+                    self.generation_prompts.get(&campaign).await
+                },
+                infrastructure::repository::sqlx_lib::PgGenerationJobRepository::new(self.db_pool),
+            )
+            .await
+    }
+
+    /// Resumes pending jobs and running jobs whose lease expired, for example
+    /// because their replica stopped
+    pub async fn resume(&self) -> domain::services::ServiceResult<usize> {
+        let job_ids = self
+            .generation_job_service
+            .requeue_unfinished(infrastructure::repository::sqlx_lib::PgGenerationJobRepository::new(
+                self.db_pool,
+            ))
+            .await?;
+
+        for job_id in &job_ids {
+            Self::spawn(self.db_pool, self.app_state, *job_id);
+        }
+
+        Ok(job_ids.len())
+    }
+}
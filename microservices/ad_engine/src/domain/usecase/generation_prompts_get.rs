@@ -0,0 +1,70 @@
+use crate::{
+    domain,
+    infrastructure::{self, repository::IRepo},
+};
+
+pub struct GenerationPromptsGetUsecase<'p> {
+    advertiser_service: domain::services::AdvertiserService,
+    brand_voice_service: domain::services::BrandVoiceService,
+    prompt_template_service: domain::services::PromptTemplateService,
+    db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+    app_state: &'p domain::configurate::AppState,
+}
+
+impl<'p> GenerationPromptsGetUsecase<'p> {
+    pub fn new(
+        db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+        app_state: &'p domain::configurate::AppState,
+    ) -> Self {
+        Self {
+            advertiser_service: domain::services::AdvertiserService,
+            brand_voice_service: domain::services::BrandVoiceService,
+            prompt_template_service: domain::services::PromptTemplateService,
+            db_pool,
+            app_state,
+        }
+    }
+
+    /// Gets the prompts for the generation of the texts of a campaign
+    ///
+    /// The template is chosen by the brand voice profile of the advertiser,
+    /// otherwise the default template is used, otherwise the prompts from
+    /// config. Variables are filled from the advertiser, the campaign
+    /// targeting and the brand voice profile.
+    pub async fn get(
+        &self,
+        campaign: &domain::schemas::CampaignSchema,
+    ) -> domain::services::ServiceResult<domain::services::GenerationPrompts> {
+        let advertiser = self
+            .advertiser_service
+            .get_by_id(
+                campaign.advertiser_id,
+                infrastructure::repository::sqlx_lib::PgAdvertiserRepository::new(self.db_pool),
+            )
+            .await?;
+
+        let brand_voice = self
+            .brand_voice_service
+            .get(
+                campaign.advertiser_id,
+                infrastructure::repository::sqlx_lib::PgBrandVoiceRepository::new(self.db_pool),
+            )
+            .await?;
+        let template_id = brand_voice.as_ref().and_then(|brand_voice| brand_voice.template_id);
+
+        let variables = domain::services::PromptVariables::new(advertiser.name, &campaign.targeting, brand_voice);
+
+        self.prompt_template_service
+            .get_generation_prompts(
+                template_id,
+                &variables,
+                domain::services::GenerationPrompts {
+                    prompt_version_id: None,
+                    title_prompt: self.app_state.system_prompt_for_generate_title.clone(),
+                    text_prompt: self.app_state.system_prompt_for_generate_body.clone(),
+                },
+                infrastructure::repository::sqlx_lib::PgPromptTemplateRepository::new(self.db_pool),
+            )
+            .await
+    }
+}
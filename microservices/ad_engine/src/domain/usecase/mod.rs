@@ -4,9 +4,13 @@
 //! are related to each other.
 
 mod ads_click;
+mod ads_config;
+mod ads_explain;
 mod ads_get;
 mod advertiser_bulk_register;
 mod advertiser_profile;
+mod brand_voice_get;
+mod brand_voice_update;
 mod campaign_delete_image;
 mod campaign_get_image;
 mod campaigns_generator_text_usecase;
@@ -19,18 +23,44 @@ mod campaings_gete_list;
 mod campaings_update;
 mod client_bulk_register;
 mod client_profile;
+mod config_reload;
+mod creatives_create;
+mod creatives_delete;
+mod creatives_generate;
+mod creatives_get_list;
+mod ctr_learn;
+mod experiments_create;
+mod experiments_get_list;
+mod experiments_stop;
+mod fallback_scores_refresh;
+mod generation_jobs_create;
+mod generation_jobs_decide;
+mod generation_jobs_get;
+mod generation_jobs_run;
+mod generation_prompts_get;
 mod ml_score;
 mod moderate_add_list;
 mod moderate_delete_list;
 mod moderate_get_list;
 mod moderate_set_settings;
+mod prompt_templates_create;
+mod prompt_templates_create_version;
+mod prompt_templates_get_list;
+mod prompt_templates_get_versions;
 mod stat_campaign;
+mod stat_experiment;
+mod stat_prompt_template;
+mod targeting_index_sync;
 mod time_advance;
 
 pub use ads_click::AdsClickUsecase;
+pub use ads_config::AdsConfigUsecase;
+pub use ads_explain::AdsExplainUsecase;
 pub use ads_get::AdsGetUsecase;
 pub use advertiser_bulk_register::AdvertiserBulkRegisterUsecase;
 pub use advertiser_profile::AdvertiserProfileUsecase;
+pub use brand_voice_get::BrandVoiceGetUsecase;
+pub use brand_voice_update::BrandVoiceUpdateUsecase;
 pub use campaign_delete_image::CampaignsDeleteImageUsecase;
 pub use campaign_get_image::CampaignsGetImageUsecase;
 pub use campaigns_generator_text_usecase::CampaignsGeneratorTextUsecase;
@@ -43,10 +73,32 @@ pub use campaings_gete_list::CampaignsGetListUsecase;
 pub use campaings_update::CampaignsUpdateUsecase;
 pub use client_bulk_register::ClientBulkRegisterUsecase;
 pub use client_profile::ClientProfileUsecase;
+pub use config_reload::ConfigReloadUsecase;
+pub use creatives_create::CreativesCreateUsecase;
+pub use creatives_delete::CreativesDeleteUsecase;
+pub use creatives_generate::CreativesGenerateUsecase;
+pub use creatives_get_list::CreativesGetListUsecase;
+pub use ctr_learn::CtrLearnUsecase;
+pub use experiments_create::ExperimentsCreateUsecase;
+pub use experiments_get_list::ExperimentsGetListUsecase;
+pub use experiments_stop::ExperimentsStopUsecase;
+pub use fallback_scores_refresh::FallbackScoresRefreshUsecase;
+pub use generation_jobs_create::GenerationJobsCreateUsecase;
+pub use generation_jobs_decide::GenerationJobsDecideUsecase;
+pub use generation_jobs_get::GenerationJobsGetUsecase;
+pub use generation_jobs_run::GenerationJobsRunUsecase;
+pub use generation_prompts_get::GenerationPromptsGetUsecase;
 pub use ml_score::MlScoreUsecase;
 pub use moderate_add_list::ModerateAddListUsecase;
 pub use moderate_delete_list::ModerateDeleteListUsecase;
 pub use moderate_get_list::ModerateGetListUsecase;
 pub use moderate_set_settings::ModerateSetSettingsUsecase;
+pub use prompt_templates_create::PromptTemplatesCreateUsecase;
+pub use prompt_templates_create_version::PromptTemplatesCreateVersionUsecase;
+pub use prompt_templates_get_list::PromptTemplatesGetListUsecase;
+pub use prompt_templates_get_versions::PromptTemplatesGetVersionsUsecase;
 pub use stat_campaign::StatCampaignUsecase;
+pub use stat_experiment::StatExperimentUsecase;
+pub use stat_prompt_template::StatPromptTemplateUsecase;
+pub use targeting_index_sync::TargetingIndexSyncUsecase;
 pub use time_advance::TimeAdvanceUsecase;
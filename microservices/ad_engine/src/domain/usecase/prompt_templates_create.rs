@@ -0,0 +1,34 @@
+use validator::Validate;
+
+use crate::{
+    domain,
+    infrastructure::{self, repository::IRepo},
+};
+
+pub struct PromptTemplatesCreateUsecase<'p> {
+    prompt_template_service: domain::services::PromptTemplateService,
+    db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+}
+
+impl<'p> PromptTemplatesCreateUsecase<'p> {
+    pub fn new(db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool) -> Self {
+        Self {
+            prompt_template_service: domain::services::PromptTemplateService,
+            db_pool,
+        }
+    }
+
+    pub async fn create(
+        self,
+        create_data: domain::schemas::PromptTemplateCreateRequest,
+    ) -> domain::services::ServiceResult<domain::schemas::PromptTemplateSchema> {
+        create_data.validate()?;
+
+        self.prompt_template_service
+            .create(
+                create_data,
+                infrastructure::repository::sqlx_lib::PgPromptTemplateRepository::new(self.db_pool),
+            )
+            .await
+    }
+}
@@ -0,0 +1,36 @@
+use validator::Validate;
+
+use crate::{
+    domain,
+    infrastructure::{self, repository::IRepo},
+};
+
+pub struct PromptTemplatesCreateVersionUsecase<'p> {
+    prompt_template_service: domain::services::PromptTemplateService,
+    db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+}
+
+impl<'p> PromptTemplatesCreateVersionUsecase<'p> {
+    pub fn new(db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool) -> Self {
+        Self {
+            prompt_template_service: domain::services::PromptTemplateService,
+            db_pool,
+        }
+    }
+
+    pub async fn create(
+        self,
+        template_id: uuid::Uuid,
+        create_data: domain::schemas::PromptTemplateVersionCreateRequest,
+    ) -> domain::services::ServiceResult<domain::schemas::PromptTemplateVersionSchema> {
+        create_data.validate()?;
+
+        self.prompt_template_service
+            .create_version(
+                template_id,
+                create_data,
+                infrastructure::repository::sqlx_lib::PgPromptTemplateRepository::new(self.db_pool),
+            )
+            .await
+    }
+}
@@ -0,0 +1,26 @@
+use crate::{
+    domain,
+    infrastructure::{self, repository::IRepo},
+};
+
+pub struct PromptTemplatesGetListUsecase<'p> {
+    prompt_template_service: domain::services::PromptTemplateService,
+    db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+}
+
+impl<'p> PromptTemplatesGetListUsecase<'p> {
+    pub fn new(db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool) -> Self {
+        Self {
+            prompt_template_service: domain::services::PromptTemplateService,
+            db_pool,
+        }
+    }
+
+    pub async fn get_list(self) -> domain::services::ServiceResult<Vec<domain::schemas::PromptTemplateSchema>> {
+        self.prompt_template_service
+            .get_list(infrastructure::repository::sqlx_lib::PgPromptTemplateRepository::new(
+                self.db_pool,
+            ))
+            .await
+    }
+}
@@ -6,16 +6,30 @@ use crate::{
 pub struct StatCampaignUsecase<'p> {
     campaign_stat_service: domain::services::CampaignStatService,
     campaign_service: domain::services::CampaignService,
+    creative_service: domain::services::CreativeService,
     aggregate_stat_service: domain::services::AggregateStatService,
+    budget_service: domain::services::BudgetService,
+    pacing_service: Option<domain::services::PacingService>,
+    redis_service: domain::services::RedisService<'p>,
     db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
 }
 
 impl<'p> StatCampaignUsecase<'p> {
-    pub fn new(db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool) -> Self {
+    pub fn new(
+        db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+        redis_pool: &'p infrastructure::database_connection::redis::RedisPool,
+        app_state: &domain::configurate::AppState,
+    ) -> Self {
         Self {
             campaign_stat_service: domain::services::CampaignStatService,
             campaign_service: domain::services::CampaignService,
+            creative_service: domain::services::CreativeService,
             aggregate_stat_service: domain::services::AggregateStatService,
+            budget_service: domain::services::BudgetService,
+            pacing_service: app_state.ads_pacing_enabled.then(|| {
+                domain::services::PacingService::new(app_state.ads_pacing_curve_exponent, app_state.ads_pacing_boost)
+            }),
+            redis_service: domain::services::RedisService::new(redis_pool),
             db_pool,
         }
     }
@@ -28,9 +42,22 @@ impl<'p> StatCampaignUsecase<'p> {
         let (impressions, clicks, spent_imp, spent_clk) =
             self.aggregate_stat_service.calculate_total_stats(&stat_by_day);
 
-        Ok(self
-            .aggregate_stat_service
-            .create_stat_response(impressions, clicks, spent_imp, spent_clk))
+        let repo = infrastructure::repository::sqlx_lib::PgCampaignRepository::new(self.db_pool);
+        let budget_remaining = self
+            .campaign_stat_service
+            .get_budgets_by_advertiser(advertiser_id, repo)
+            .await?
+            .into_iter()
+            .filter_map(|(budget, spent)| self.budget_service.remaining(budget, spent))
+            .reduce(|a, b| a + b);
+
+        Ok(self.aggregate_stat_service.create_stat_response(
+            impressions,
+            clicks,
+            spent_imp,
+            spent_clk,
+            budget_remaining,
+        ))
     }
 
     pub async fn get_with_advertisers_by_day(
@@ -54,9 +81,31 @@ impl<'p> StatCampaignUsecase<'p> {
         let (impressions, clicks, spent_imp, spent_clk) =
             self.aggregate_stat_service.calculate_total_stats(&stat_by_day);
 
-        Ok(self
-            .aggregate_stat_service
-            .create_stat_response(impressions, clicks, spent_imp, spent_clk))
+        let repo = infrastructure::repository::sqlx_lib::PgCampaignRepository::new(self.db_pool);
+        let budget = self.campaign_stat_service.get_budget(campaign_id, repo).await?;
+
+        let mut stat = self.aggregate_stat_service.create_stat_response(
+            impressions,
+            clicks,
+            spent_imp,
+            spent_clk,
+            self.budget_service.remaining(budget, spent_imp + spent_clk),
+        );
+        stat.pacing = self.get_pacing(campaign_id).await;
+
+        Ok(stat)
+    }
+
+    async fn get_pacing(&self, campaign_id: uuid::Uuid) -> Option<domain::schemas::PacingSchema> {
+        let pacing_service = self.pacing_service.as_ref()?;
+        let mut campaigns = vec![self.redis_service.get_active_campaign(&campaign_id).await.ok()?];
+        let advanced_time = self.redis_service.get_advance_time().await.ok()?;
+        self.redis_service
+            .load_campaign_stats(&mut campaigns, None, advanced_time)
+            .await
+            .ok()?;
+
+        Some(pacing_service.calculate(&campaigns[0], advanced_time))
     }
 
     pub async fn get_by_day(
@@ -66,4 +115,12 @@ impl<'p> StatCampaignUsecase<'p> {
         let repo = infrastructure::repository::sqlx_lib::PgCampaignRepository::new(self.db_pool);
         self.campaign_stat_service.get_by_day(campaign_id, repo).await
     }
+
+    pub async fn get_by_creative(
+        &self,
+        campaign_id: uuid::Uuid,
+    ) -> domain::services::ServiceResult<Vec<domain::schemas::CreativeStatSchema>> {
+        let repo = infrastructure::repository::sqlx_lib::PgCreativeRepository::new(self.db_pool);
+        self.creative_service.get_stat(campaign_id, repo).await
+    }
 }
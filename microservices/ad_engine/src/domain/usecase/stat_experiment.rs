@@ -0,0 +1,30 @@
+use crate::{
+    domain,
+    infrastructure::{self, repository::IRepo},
+};
+
+pub struct StatExperimentUsecase<'p> {
+    experiment_service: domain::services::ExperimentService,
+    db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+}
+
+impl<'p> StatExperimentUsecase<'p> {
+    pub fn new(db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool) -> Self {
+        Self {
+            experiment_service: domain::services::ExperimentService,
+            db_pool,
+        }
+    }
+
+    pub async fn get(
+        &self,
+        experiment_id: uuid::Uuid,
+    ) -> domain::services::ServiceResult<domain::schemas::ExperimentStatResponse> {
+        self.experiment_service
+            .get_stat(
+                experiment_id,
+                infrastructure::repository::sqlx_lib::PgExperimentRepository::new(self.db_pool),
+            )
+            .await
+    }
+}
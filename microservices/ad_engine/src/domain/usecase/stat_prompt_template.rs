@@ -0,0 +1,30 @@
+use crate::{
+    domain,
+    infrastructure::{self, repository::IRepo},
+};
+
+pub struct StatPromptTemplateUsecase<'p> {
+    prompt_template_service: domain::services::PromptTemplateService,
+    db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
+}
+
+impl<'p> StatPromptTemplateUsecase<'p> {
+    pub fn new(db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool) -> Self {
+        Self {
+            prompt_template_service: domain::services::PromptTemplateService,
+            db_pool,
+        }
+    }
+
+    pub async fn get(
+        &self,
+        template_id: uuid::Uuid,
+    ) -> domain::services::ServiceResult<Vec<domain::schemas::PromptVersionStatSchema>> {
+        self.prompt_template_service
+            .get_stat(
+                template_id,
+                infrastructure::repository::sqlx_lib::PgPromptTemplateRepository::new(self.db_pool),
+            )
+            .await
+    }
+}
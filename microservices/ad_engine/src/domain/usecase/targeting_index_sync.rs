@@ -0,0 +1,32 @@
+use crate::{domain, infrastructure};
+
+pub struct TargetingIndexSyncUsecase<'p> {
+    redis_service: domain::services::RedisService<'p>,
+}
+
+impl<'p> TargetingIndexSyncUsecase<'p> {
+    pub fn new(redis_pool: &'p infrastructure::database_connection::redis::RedisPool) -> Self {
+        Self {
+            redis_service: domain::services::RedisService::new(redis_pool),
+        }
+    }
+
+    pub async fn execute(&self) -> domain::services::ServiceResult<()> {
+        domain::services::TargetingIndexService::rebuild(&self.redis_service.get_all_active_campaigns().await?);
+        Ok(())
+    }
+
+    /// Rebuilds the targeting index from Redis every `resync_interval` until
+    /// the process stops, so changes of active campaigns made by other
+    /// instances reach the index of this one; failed rebuilds keep the
+    /// previous index
+    pub async fn run(&self, resync_interval: std::time::Duration) {
+        let mut interval = tokio::time::interval(resync_interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.execute().await {
+                tracing::warn!("Failed to resync the targeting index: {}", e);
+            }
+        }
+    }
+}
@@ -8,6 +8,8 @@ use crate::{
 pub struct TimeAdvanceUsecase<'p> {
     campaign_service: domain::services::CampaignService,
     campaign_stat_service: domain::services::CampaignStatService,
+    creative_service: domain::services::CreativeService,
+    budget_service: domain::services::BudgetService,
     redis_service: domain::services::RedisService<'p>,
     db_pool: &'p infrastructure::database_connection::sqlx_lib::SqlxPool,
 }
@@ -20,6 +22,8 @@ impl<'p> TimeAdvanceUsecase<'p> {
         Self {
             campaign_service: domain::services::CampaignService,
             campaign_stat_service: domain::services::CampaignStatService,
+            creative_service: domain::services::CreativeService,
+            budget_service: domain::services::BudgetService,
             redis_service: domain::services::RedisService::new(redis_pool),
             db_pool,
         }
@@ -51,21 +55,71 @@ impl<'p> TimeAdvanceUsecase<'p> {
             )
             .await?;
 
+        let campaign_ids: Vec<uuid::Uuid> = campaigns.iter().map(|campaign| campaign.campaign_id).collect();
+        let mut creatives = self
+            .creative_service
+            .get_by_campaigns(
+                &campaign_ids,
+                infrastructure::repository::sqlx_lib::PgCreativeRepository::new(self.db_pool),
+            )
+            .await?;
+
         for campaign in campaigns {
-            let (view_clients_id, click_clients_id) = self
-                .campaign_stat_service
-                .get_or_create_uniq_id(
-                    campaign.campaign_id,
-                    infrastructure::repository::sqlx_lib::PgCampaignRepository::new(self.db_pool),
-                )
-                .await?;
+            let mut campaign_active = domain::schemas::ActiveCampaignSchema::from(campaign);
+            campaign_active.creatives = creatives.remove(&campaign_active.campaign_id).unwrap_or_default();
+
+            // Counters of an already active campaign are kept, they are ahead
+            // of the database while impressions and clicks are recorded
+            if self.redis_service.is_active_campaign(&campaign_active.campaign_id).await? {
+                self.redis_service
+                    .load_campaign_stats(
+                        std::slice::from_mut(&mut campaign_active),
+                        None,
+                        advance_schema.current_date,
+                    )
+                    .await?;
+            } else {
+                let (view_clients, click_clients_id) = self
+                    .campaign_stat_service
+                    .get_or_create_uniq_id(
+                        campaign_active.campaign_id,
+                        infrastructure::repository::sqlx_lib::PgCampaignRepository::new(self.db_pool),
+                    )
+                    .await?;
+                self.redis_service
+                    .set_campaign_stats(&campaign_active.campaign_id, view_clients, click_clients_id)
+                    .await?;
 
-            let campaign_active =
-                domain::schemas::ActiveCampaignSchema::from((campaign, view_clients_id, click_clients_id));
+                (campaign_active.spent, campaign_active.spent_today) = self
+                    .campaign_stat_service
+                    .get_spent(
+                        campaign_active.campaign_id,
+                        advance_schema.current_date,
+                        infrastructure::repository::sqlx_lib::PgCampaignRepository::new(self.db_pool),
+                    )
+                    .await?;
+                self.redis_service
+                    .set_campaign_spent(
+                        &campaign_active.campaign_id,
+                        campaign_active.spent,
+                        advance_schema.current_date,
+                        campaign_active.spent_today,
+                    )
+                    .await?;
+            }
+
+            if self.budget_service.is_exhausted(&campaign_active) {
+                self.redis_service
+                    .del_active_campaigns(&campaign_active.campaign_id)
+                    .await?;
+                continue;
+            }
 
             self.redis_service.set_active_campaign(campaign_active).await?;
         }
 
+        domain::services::TargetingIndexService::rebuild(&self.redis_service.get_all_active_campaigns().await?);
+
         Ok(domain::schemas::TimeAdvanceResponse {
             current_date: self.redis_service.get_advance_time().await?,
         })
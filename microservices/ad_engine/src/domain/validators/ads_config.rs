@@ -0,0 +1,38 @@
+use crate::domain;
+
+/// Validates that all weights are finite numbers
+async fn validate_finite_weights(weights: &[f64]) -> Result<(), domain::services::ServiceError> {
+    if weights.iter().any(|weight| !weight.is_finite()) {
+        return Err(domain::services::ServiceError::Validation(
+            "weights must be finite numbers".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Validates that the weights sum to 1 when it is required by the
+/// configuration
+async fn validate_weights_sum(weights: &[f64], is_sum_to_one: bool) -> Result<(), domain::services::ServiceError> {
+    if is_sum_to_one && (weights.iter().sum::<f64>() - 1.0).abs() > 1e-6 {
+        return Err(domain::services::ServiceError::Validation(
+            "sum of weights must be equal to 1".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Validates the ranking weights for consistency
+pub async fn validate_ads_config_data(
+    ads_config: &domain::schemas::AdsConfigSchema,
+    is_sum_to_one: bool,
+) -> Result<(), domain::services::ServiceError> {
+    let weights = [
+        ads_config.weight_profit,
+        ads_config.weight_relevance,
+        ads_config.weight_fulfillment,
+        ads_config.weight_time_left,
+    ];
+
+    validate_finite_weights(&weights).await?;
+    validate_weights_sum(&weights, is_sum_to_one).await
+}
@@ -47,6 +47,27 @@ async fn validate_limits_range(
     Ok(())
 }
 
+/// Validates that budgets are not negative and the daily budget does not
+/// exceed the total budget
+async fn validate_budget_range(
+    budget: Option<f64>,
+    daily_budget: Option<f64>,
+) -> Result<(), domain::services::ServiceError> {
+    if budget.is_some_and(|budget| budget < 0.0) || daily_budget.is_some_and(|daily_budget| daily_budget < 0.0) {
+        return Err(domain::services::ServiceError::Validation(
+            "budget and daily_budget must be more or equal to 0".into(),
+        ));
+    }
+    if let (Some(budget), Some(daily_budget)) = (budget, daily_budget) {
+        if daily_budget > budget {
+            return Err(domain::services::ServiceError::Validation(
+                "daily_budget must be under or equal to budget".into(),
+            ));
+        }
+    }
+    Ok(())
+}
+
 /// Validates all campaign parameters for consistency
 pub async fn validate_campaign_data(
     start_date: u32,
@@ -55,10 +76,13 @@ pub async fn validate_campaign_data(
     age_to: Option<u8>,
     impressions_limit: u32,
     clicks_limit: u32,
+    budget: Option<f64>,
+    daily_budget: Option<f64>,
     time_advance: u32,
 ) -> Result<(), domain::services::ServiceError> {
     validate_start_date(start_date, time_advance).await?;
     validate_date_range(start_date, end_date).await?;
     validate_age_range(age_from, age_to).await?;
-    validate_limits_range(impressions_limit, clicks_limit).await
+    validate_limits_range(impressions_limit, clicks_limit).await?;
+    validate_budget_range(budget, daily_budget).await
 }
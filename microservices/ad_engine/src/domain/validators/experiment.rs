@@ -0,0 +1,49 @@
+use crate::domain;
+
+/// Validates that the names of experiment arms are unique
+async fn validate_unique_arm_names(
+    arms: &[domain::schemas::ExperimentArmSchema],
+) -> Result<(), domain::services::ServiceError> {
+    let mut names = std::collections::HashSet::new();
+    if !arms.iter().all(|arm| names.insert(arm.name.as_str())) {
+        return Err(domain::services::ServiceError::Validation(
+            "names of arms must be unique".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Validates the weights of every experiment arm merged onto the runtime
+/// weights, like the runtime weights themselves
+async fn validate_arm_weights(
+    arms: &[domain::schemas::ExperimentArmSchema],
+    runtime_weights: &domain::schemas::AdsConfigSchema,
+    is_sum_to_one: bool,
+) -> Result<(), domain::services::ServiceError> {
+    for arm in arms {
+        domain::validators::validate_ads_config_data(&arm.weights(runtime_weights), is_sum_to_one)
+            .await
+            .map_err(|e| match e {
+                domain::services::ServiceError::Validation(message) => {
+                    domain::services::ServiceError::Validation(format!("arm {}: {}", arm.name, message))
+                },
+                e => e,
+            })?;
+    }
+    Ok(())
+}
+
+/// Validates all experiment parameters for consistency
+///
+/// # Arguments
+/// * `arms` - Arms of the experiment
+/// * `runtime_weights` - Current ranking weights the arms override
+/// * `is_sum_to_one` - Whether the weights of every arm must sum to 1
+pub async fn validate_experiment_data(
+    arms: &[domain::schemas::ExperimentArmSchema],
+    runtime_weights: &domain::schemas::AdsConfigSchema,
+    is_sum_to_one: bool,
+) -> Result<(), domain::services::ServiceError> {
+    validate_unique_arm_names(arms).await?;
+    validate_arm_weights(arms, runtime_weights, is_sum_to_one).await
+}
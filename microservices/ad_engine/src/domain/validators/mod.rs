@@ -1,4 +1,10 @@
+mod ads_config;
 mod campaign;
+mod experiment;
 mod regexes;
+mod targeting;
+pub use ads_config::validate_ads_config_data;
 pub use campaign::validate_campaign_data;
+pub use experiment::validate_experiment_data;
 pub use regexes::{RE_GENDER, RE_GENERATE_TYPE};
+pub use targeting::validate_targeting_data;
@@ -0,0 +1,71 @@
+use crate::domain;
+
+/// Validates that every targeted gender is MALE or FEMALE
+async fn validate_genders(genders: &[String]) -> Result<(), domain::services::ServiceError> {
+    if genders
+        .iter()
+        .any(|gender| !domain::validators::RE_GENDER.is_match(gender))
+    {
+        return Err(domain::services::ServiceError::Validation(
+            "Gender not equal MALE or FEMALE".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Validates that locations are not empty
+async fn validate_locations(locations: &[String]) -> Result<(), domain::services::ServiceError> {
+    if locations
+        .iter()
+        .any(|location| location.split(',').all(|part| part.trim().is_empty()))
+    {
+        return Err(domain::services::ServiceError::Validation(
+            "locations must not be empty".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Validates that no included location lies inside an excluded one, otherwise
+/// it could never be targeted
+async fn validate_exclude_locations(
+    locations: &[String],
+    exclude_locations: &[String],
+) -> Result<(), domain::services::ServiceError> {
+    for location in locations {
+        if exclude_locations
+            .iter()
+            .any(|exclude_location| domain::services::AdsService::is_sublocation(location, exclude_location))
+        {
+            return Err(domain::services::ServiceError::Validation(format!(
+                "location `{location}` is excluded by exclude_locations"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Validates include and exclude lists and the expression of the campaign
+/// targeting
+pub async fn validate_targeting_data(
+    targeting: &domain::schemas::TargetingCampaignSchema,
+) -> Result<(), domain::services::ServiceError> {
+    let genders = targeting.genders.clone().unwrap_or_default();
+    let locations: Vec<String> = targeting
+        .location
+        .iter()
+        .chain(targeting.locations.iter().flatten())
+        .cloned()
+        .collect();
+    let exclude_locations = targeting.exclude_locations.clone().unwrap_or_default();
+
+    validate_genders(&genders).await?;
+    validate_locations(&locations).await?;
+    validate_locations(&exclude_locations).await?;
+    validate_exclude_locations(&locations, &exclude_locations).await?;
+
+    if let Some(expression) = &targeting.expression {
+        domain::services::TargetingExpression::parse(expression)?;
+    }
+    Ok(())
+}
@@ -30,25 +30,65 @@
 
 mod deserializer;
 mod schemas;
+mod validation;
 
 pub use schemas::{Config, CorsConfig, HttpServerConfig, LoggerConfig, PostgresConfig, RedisConfig};
 
 pub async fn parse(path_to_conf_file: std::path::PathBuf) -> Config {
+    try_parse(path_to_conf_file).await.expect("Failed parsing config")
+}
+
+/// Parses and validates the configuration without panicking, used to reload
+/// the configuration of a running server and to check it with
+/// `--check-config`
+///
+/// All problems of an invalid configuration are reported in one error, every
+/// problem on its own line with the yaml path of the setting.
+pub async fn try_parse(path_to_conf_file: std::path::PathBuf) -> Result<Config, config::ConfigError> {
     dotenv::dotenv().ok();
 
     let environment = std::env::var("APP_ENVIRONMENT").unwrap_or("prod".into());
     if !["local", "prod"].contains(&environment.as_str()) {
-        panic!("Failed load APP_ENVIRONMENT. APP_ENVIRONMENT must be `local` or `prod`.");
+        return Err(config::ConfigError::Message(
+            "Failed load APP_ENVIRONMENT. APP_ENVIRONMENT must be `local` or `prod`.".into(),
+        ));
     }
 
-    let parsing_schemas: schemas::Config = config::Config::builder()
+    let config: Config = config::Config::builder()
         .add_source(config::File::from(path_to_conf_file.join("base")).required(true))
         .add_source(config::File::from(path_to_conf_file.join(environment)).required(true))
         .add_source(config::Environment::with_prefix("APP").separator("__"))
-        .build()
-        .expect("Filed set source for config")
-        .try_deserialize()
-        .expect("Failed parsing config");
+        .build()?
+        .try_deserialize()?;
+
+    config.validate().map_err(|problems| {
+        config::ConfigError::Message(format!(
+            "Invalid config:\n{}",
+            problems
+                .iter()
+                .map(|problem| format!("  {}", problem))
+                .collect::<Vec<_>>()
+                .join("\n")
+        ))
+    })?;
 
-    parsing_schemas
+    Ok(config)
+}
+
+/// Parses the configuration of the `local` environment from the repository
+/// without the environment variables, used by tests
+#[cfg(test)]
+pub fn test_config() -> Config {
+    config::Config::builder()
+        .add_source(config::File::from_str(
+            include_str!("../../../conf/base.yaml"),
+            config::FileFormat::Yaml,
+        ))
+        .add_source(config::File::from_str(
+            include_str!("../../../conf/local.yaml"),
+            config::FileFormat::Yaml,
+        ))
+        .build()
+        .and_then(config::Config::try_deserialize)
+        .expect("Failed parsing test config")
 }
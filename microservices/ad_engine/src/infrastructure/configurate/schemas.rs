@@ -1,13 +1,82 @@
-#[derive(serde::Deserialize)]
+#[derive(Clone, serde::Deserialize)]
 pub struct Config {
     pub http_server: HttpServerConfig,
     pub logger: LoggerConfig,
     pub database: DatabaseConfig,
     pub cors: CorsConfig,
+    pub llm: LlmConfig,
     pub yandex: YandexConfig,
     pub ads_recommendation: AdsRecommendationConfig,
     pub upload_content: UploadContentConfig,
     pub auto_moderating: AutoModeratingConfig,
+    #[serde(default)]
+    pub generation_jobs: GenerationJobsConfig,
+}
+
+impl Config {
+    /// Lists the settings that differ from another configuration but can't be
+    /// changed without a restart: the server is bound, pools, logger, CORS and
+    /// the limit of running generation jobs are created and the CTR learner,
+    /// the fallback scores refresh and the targeting index resync are spawned
+    /// only at startup
+    ///
+    /// # Returns
+    /// Yaml paths of the changed settings
+    pub fn immutable_changes(&self, other: &Config) -> Vec<&'static str> {
+        let mut changes = Vec::new();
+        if self.http_server != other.http_server {
+            changes.push("http_server");
+        }
+        if self.logger != other.logger {
+            changes.push("logger");
+        }
+        if self.database != other.database {
+            changes.push("database");
+        }
+        if self.cors != other.cors {
+            changes.push("cors");
+        }
+        if self.ads_recommendation.ctr_learning.enabled != other.ads_recommendation.ctr_learning.enabled {
+            changes.push("ads_recommendation.ctr_learning.enabled");
+        }
+        if self.ads_recommendation.ctr_learning.refresh_interval
+            != other.ads_recommendation.ctr_learning.refresh_interval
+        {
+            changes.push("ads_recommendation.ctr_learning.refresh_interval");
+        }
+        if self.ads_recommendation.targeting_index.resync_interval
+            != other.ads_recommendation.targeting_index.resync_interval
+        {
+            changes.push("ads_recommendation.targeting_index.resync_interval");
+        }
+        if self.ads_recommendation.fallback_scores.refresh_interval
+            != other.ads_recommendation.fallback_scores.refresh_interval
+        {
+            changes.push("ads_recommendation.fallback_scores.refresh_interval");
+        }
+        if self.generation_jobs.max_concurrent != other.generation_jobs.max_concurrent {
+            changes.push("generation_jobs.max_concurrent");
+        }
+        changes
+    }
+}
+
+#[serde_with::serde_as]
+#[derive(Clone, serde::Deserialize)]
+#[serde(default)]
+pub struct GenerationJobsConfig {
+    #[serde_as(as = "serde_with::DurationSeconds")]
+    pub lease: std::time::Duration,
+    pub max_concurrent: usize,
+}
+
+impl Default for GenerationJobsConfig {
+    fn default() -> Self {
+        Self {
+            lease: std::time::Duration::from_secs(60),
+            max_concurrent: 4,
+        }
+    }
 }
 
 #[derive(Clone, serde::Deserialize)]
@@ -28,25 +97,161 @@ pub struct AdsRecommendationConfig {
     pub weight_relevance: f64,
     pub weight_fulfillment: f64,
     pub weight_time_left: f64,
+    #[serde(default)]
+    pub weights_sum_to_one: bool,
+    #[serde(default)]
+    pub strategy: crate::domain::services::AdsStrategy,
+    pub pacing: PacingConfig,
+    #[serde(default)]
+    pub ctr_learning: CtrLearningConfig,
+    pub exploration: ExplorationConfig,
+    pub shadow: ShadowRankingConfig,
+    #[serde(default)]
+    pub targeting_index: TargetingIndexConfig,
+    #[serde(default)]
+    pub fallback_scores: FallbackScoresConfig,
 }
 
+#[serde_with::serde_as]
 #[derive(Clone, serde::Deserialize)]
-pub struct YandexConfig {
-    pub api_key: String,
-    pub folder_id: String,
-    pub gpt: GptYandexConfig,
+#[serde(default)]
+pub struct FallbackScoresConfig {
+    #[serde_as(as = "serde_with::DurationSeconds")]
+    pub refresh_interval: std::time::Duration,
+}
+
+impl Default for FallbackScoresConfig {
+    fn default() -> Self {
+        Self {
+            refresh_interval: std::time::Duration::from_secs(60),
+        }
+    }
+}
+
+#[serde_with::serde_as]
+#[derive(Clone, serde::Deserialize)]
+#[serde(default)]
+pub struct TargetingIndexConfig {
+    #[serde_as(as = "serde_with::DurationSeconds")]
+    pub resync_interval: std::time::Duration,
+}
+
+impl Default for TargetingIndexConfig {
+    fn default() -> Self {
+        Self {
+            resync_interval: std::time::Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Clone, serde::Deserialize)]
+pub struct PacingConfig {
+    pub enabled: bool,
+    pub curve_exponent: f64,
+    pub boost: f64,
 }
 
 #[derive(Clone, serde::Deserialize)]
-pub struct GptYandexConfig {
+pub struct ShadowRankingConfig {
+    pub enabled: bool,
+    pub weight_profit: f64,
+    pub weight_relevance: f64,
+    pub weight_fulfillment: f64,
+    pub weight_time_left: f64,
+    #[serde(default)]
+    pub strategy: crate::domain::services::AdsStrategy,
+}
+
+#[derive(Clone, serde::Deserialize)]
+pub struct ExplorationConfig {
+    pub enabled: bool,
+    #[serde(default)]
+    pub policy: crate::domain::services::ExplorationPolicy,
+    pub epsilon: f64,
+    pub seed: Option<u64>,
+}
+
+#[serde_with::serde_as]
+#[derive(Clone, serde::Deserialize)]
+#[serde(default)]
+pub struct CtrLearningConfig {
+    pub enabled: bool,
+    pub weight: f64,
+    pub prior_alpha: f64,
+    pub prior_beta: f64,
+    #[serde_as(as = "serde_with::DurationSeconds")]
+    pub refresh_interval: std::time::Duration,
+}
+
+impl Default for CtrLearningConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            weight: 0.3,
+            prior_alpha: 1.0,
+            prior_beta: 49.0,
+            refresh_interval: std::time::Duration::from_secs(60),
+        }
+    }
+}
+
+#[serde_with::serde_as]
+#[derive(Clone, serde::Deserialize)]
+pub struct LlmConfig {
+    #[serde(default)]
+    pub provider: crate::domain::services::LlmProvider,
     pub temperature: f32,
     pub max_tokens: u32,
     pub system_prompt_for_generate_title: String,
     pub system_prompt_for_generate_body: String,
+    #[serde_as(as = "serde_with::DurationSeconds")]
+    pub request_timeout: std::time::Duration,
+    pub retry: LlmRetryConfig,
+    pub circuit_breaker: LlmCircuitBreakerConfig,
+    pub openai: OpenAiConfig,
+}
+
+#[serde_with::serde_as]
+#[derive(Clone, serde::Deserialize)]
+pub struct LlmRetryConfig {
+    pub max_attempts: u32,
+    #[serde_as(as = "serde_with::DurationMilliSeconds")]
+    pub base_delay: std::time::Duration,
+    #[serde_as(as = "serde_with::DurationMilliSeconds")]
+    pub max_delay: std::time::Duration,
 }
 
 #[serde_with::serde_as]
 #[derive(Clone, serde::Deserialize)]
+pub struct LlmCircuitBreakerConfig {
+    pub failure_threshold: u32,
+    #[serde_as(as = "serde_with::DurationSeconds")]
+    pub open_duration: std::time::Duration,
+}
+
+#[derive(Clone, serde::Deserialize)]
+pub struct OpenAiConfig {
+    pub base_url: String,
+    #[serde(default)]
+    pub api_key: String,
+    pub model: String,
+}
+
+#[serde_with::serde_as]
+#[derive(Clone, serde::Deserialize)]
+pub struct YandexConfig {
+    #[serde(default)]
+    pub api_key: String,
+    #[serde(default)]
+    pub folder_id: String,
+    pub iam_url: String,
+    pub completion_url: String,
+    #[serde_as(as = "serde_with::DurationSeconds")]
+    pub iam_token_refresh_before: std::time::Duration,
+}
+
+#[serde_with::serde_as]
+#[derive(Clone, PartialEq, serde::Deserialize)]
 pub struct HttpServerConfig {
     pub port: u16,
     pub host: std::net::Ipv4Addr,
@@ -61,7 +266,7 @@ pub struct HttpServerConfig {
     pub limit_size_media: usize,
 }
 
-#[derive(Clone, serde::Deserialize)]
+#[derive(Clone, PartialEq, serde::Deserialize)]
 pub struct LoggerConfig {
     #[serde(deserialize_with = "crate::infrastructure::configurate::deserializer::deserialize_level_filter")]
     pub max_level_cmd: tracing::level_filters::LevelFilter,
@@ -72,26 +277,26 @@ pub struct LoggerConfig {
     pub log_dir: std::path::PathBuf,
 }
 
-#[derive(Clone, serde::Deserialize)]
+#[derive(Clone, PartialEq, serde::Deserialize)]
 pub struct DatabaseConfig {
     pub postgres: PostgresConfig,
     pub redis: RedisConfig,
 }
 
-#[derive(Clone, serde::Deserialize)]
+#[derive(Clone, PartialEq, serde::Deserialize)]
 pub struct RedisConfig {
     pub host: String,
     pub port: usize,
     pub db: usize,
 }
 
-#[derive(Clone, serde::Deserialize)]
+#[derive(Clone, PartialEq, serde::Deserialize)]
 pub struct PostgresConfig {
     pub max_connections: u32,
     pub postgres_conn: String,
 }
 
-#[derive(Clone, serde::Deserialize)]
+#[derive(Clone, PartialEq, serde::Deserialize)]
 pub struct CorsConfig {
     pub allowed_methods: Vec<String>,
     pub allowed_headers: Vec<String>,
@@ -0,0 +1,208 @@
+use super::schemas::Config;
+
+/// HTTP methods allowed in `cors.allowed_methods`
+const KNOWN_HTTP_METHODS: [&str; 9] = [
+    "GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS", "CONNECT", "TRACE",
+];
+
+/// Problem found in a setting of the configuration
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigProblem {
+    /// Yaml path of the setting, for example `ads_recommendation.weight_profit`
+    pub path: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Collects the problems of a configuration instead of stopping on the first
+#[derive(Default)]
+struct ConfigValidator {
+    problems: Vec<ConfigProblem>,
+}
+
+impl ConfigValidator {
+    fn problem(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.problems.push(ConfigProblem {
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+
+    fn non_negative(&mut self, path: &str, value: f64) {
+        if !value.is_finite() || value < 0.0 {
+            self.problem(path, format!("must be a non-negative number, got {}", value));
+        }
+    }
+
+    fn positive(&mut self, path: &str, value: f64) {
+        if !value.is_finite() || value <= 0.0 {
+            self.problem(path, format!("must be a positive number, got {}", value));
+        }
+    }
+
+    fn unit_interval(&mut self, path: &str, value: f64) {
+        if !(0.0..=1.0).contains(&value) {
+            self.problem(path, format!("must be in range 0..1, got {}", value));
+        }
+    }
+
+    fn not_empty(&mut self, path: &str, value: &str) {
+        if value.trim().is_empty() {
+            self.problem(path, "must be set");
+        }
+    }
+
+    fn url(&mut self, path: &str, value: &str) {
+        if reqwest::Url::parse(value).is_err() {
+            self.problem(path, format!("must be an absolute URL, got `{}`", value));
+        }
+    }
+
+    fn not_zero(&mut self, path: &str, value: usize) {
+        if value == 0 {
+            self.problem(path, "must be more than 0");
+        }
+    }
+}
+
+impl Config {
+    /// Checks the values the types of the configuration can't express
+    ///
+    /// # Returns
+    /// * `Result<(), Vec<ConfigProblem>>` - Every problem found with the yaml
+    ///   path of its setting
+    pub fn validate(&self) -> Result<(), Vec<ConfigProblem>> {
+        let mut validator = ConfigValidator::default();
+
+        validator.not_zero("http_server.start_workers", self.http_server.start_workers);
+        validator.not_zero(
+            "database.postgres.max_connections",
+            self.database.postgres.max_connections as usize,
+        );
+        validator.not_empty("database.postgres.postgres_conn", &self.database.postgres.postgres_conn);
+        validator.not_empty("database.redis.host", &self.database.redis.host);
+
+        for (index, method) in self.cors.allowed_methods.iter().enumerate() {
+            if !KNOWN_HTTP_METHODS.contains(&method.as_str()) {
+                validator.problem(
+                    format!("cors.allowed_methods[{}]", index),
+                    format!(
+                        "unknown HTTP method `{}`, expected one of {}",
+                        method,
+                        KNOWN_HTTP_METHODS.join(", ")
+                    ),
+                );
+            }
+        }
+
+        if self.generation_jobs.lease.as_secs() == 0 {
+            validator.problem("generation_jobs.lease", "must be at least 1 second");
+        }
+        validator.not_zero("generation_jobs.max_concurrent", self.generation_jobs.max_concurrent);
+
+        validator.unit_interval("llm.temperature", self.llm.temperature.into());
+        if self.llm.max_tokens == 0 {
+            validator.problem("llm.max_tokens", "must be more than 0");
+        }
+        if self.llm.request_timeout.is_zero() {
+            validator.problem("llm.request_timeout", "must be more than 0");
+        }
+        if self.llm.retry.max_attempts == 0 {
+            validator.problem("llm.retry.max_attempts", "must be more than 0");
+        }
+        if self.llm.retry.base_delay > self.llm.retry.max_delay {
+            validator.problem("llm.retry.base_delay", "must not be more than llm.retry.max_delay");
+        }
+        if self.llm.circuit_breaker.failure_threshold == 0 {
+            validator.problem("llm.circuit_breaker.failure_threshold", "must be more than 0");
+        }
+        match self.llm.provider {
+            crate::domain::services::LlmProvider::Yandex => {
+                validator.not_empty("yandex.api_key", &self.yandex.api_key);
+                validator.not_empty("yandex.folder_id", &self.yandex.folder_id);
+                validator.url("yandex.iam_url", &self.yandex.iam_url);
+                validator.url("yandex.completion_url", &self.yandex.completion_url);
+            },
+            crate::domain::services::LlmProvider::Openai => {
+                validator.url("llm.openai.base_url", &self.llm.openai.base_url);
+                validator.not_empty("llm.openai.model", &self.llm.openai.model);
+            },
+            crate::domain::services::LlmProvider::Mock => {},
+        }
+
+        let ads = &self.ads_recommendation;
+        validator.non_negative("ads_recommendation.weight_profit", ads.weight_profit);
+        validator.non_negative("ads_recommendation.weight_relevance", ads.weight_relevance);
+        validator.non_negative("ads_recommendation.weight_fulfillment", ads.weight_fulfillment);
+        validator.non_negative("ads_recommendation.weight_time_left", ads.weight_time_left);
+        validator.positive("ads_recommendation.pacing.curve_exponent", ads.pacing.curve_exponent);
+        validator.non_negative("ads_recommendation.pacing.boost", ads.pacing.boost);
+        validator.unit_interval("ads_recommendation.ctr_learning.weight", ads.ctr_learning.weight);
+        validator.positive(
+            "ads_recommendation.ctr_learning.prior_alpha",
+            ads.ctr_learning.prior_alpha,
+        );
+        validator.positive(
+            "ads_recommendation.ctr_learning.prior_beta",
+            ads.ctr_learning.prior_beta,
+        );
+        if ads.ctr_learning.refresh_interval.is_zero() {
+            validator.problem(
+                "ads_recommendation.ctr_learning.refresh_interval",
+                "must be more than 0",
+            );
+        }
+        if ads.fallback_scores.refresh_interval.is_zero() {
+            validator.problem(
+                "ads_recommendation.fallback_scores.refresh_interval",
+                "must be more than 0",
+            );
+        }
+        if ads.targeting_index.resync_interval.is_zero() {
+            validator.problem(
+                "ads_recommendation.targeting_index.resync_interval",
+                "must be more than 0",
+            );
+        }
+        validator.unit_interval("ads_recommendation.exploration.epsilon", ads.exploration.epsilon);
+        if ads.exploration.enabled
+            && ads.exploration.policy == crate::domain::services::ExplorationPolicy::Thompson
+            && !ads.ctr_learning.enabled
+        {
+            validator.problem(
+                "ads_recommendation.exploration.policy",
+                "`thompson` requires ads_recommendation.ctr_learning.enabled",
+            );
+        }
+        validator.non_negative("ads_recommendation.shadow.weight_profit", ads.shadow.weight_profit);
+        validator.non_negative(
+            "ads_recommendation.shadow.weight_relevance",
+            ads.shadow.weight_relevance,
+        );
+        validator.non_negative(
+            "ads_recommendation.shadow.weight_fulfillment",
+            ads.shadow.weight_fulfillment,
+        );
+        validator.non_negative(
+            "ads_recommendation.shadow.weight_time_left",
+            ads.shadow.weight_time_left,
+        );
+
+        validator.unit_interval("auto_moderating.sensitivity", self.auto_moderating.sensitivity.into());
+
+        if self.upload_content.support_mime.is_empty() {
+            validator.problem("upload_content.support_mime", "must contain at least one MIME type");
+        }
+        validator.not_zero("upload_content.max_size", self.upload_content.max_size);
+
+        match validator.problems.is_empty() {
+            true => Ok(()),
+            false => Err(validator.problems),
+        }
+    }
+}
@@ -33,6 +33,17 @@ impl RedisPool {
         Self { pool }
     }
 
+    /// Creates a pool that connects on the first use, used by tests that
+    /// never reach Redis
+    #[cfg(test)]
+    pub fn lazy(config: infrastructure::configurate::RedisConfig) -> Self {
+        let url = format!("redis://{}:{}/{}", config.host, config.port, config.db);
+        let client = redis::Client::open(url).expect("Failed starting Redis. Not found url.");
+        let pool = r2d2::Pool::builder().min_idle(Some(0)).build_unchecked(client);
+
+        Self { pool }
+    }
+
     /// Retrieves a connection from the pool.
     ///
     /// # Returns
@@ -0,0 +1,38 @@
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum LlmError {
+    #[error("no response in {0:?}")]
+    Timeout(std::time::Duration),
+
+    #[error("provider responded {status}: {message}")]
+    Status { status: u16, message: String },
+
+    #[error("failed to connect: {0}")]
+    Connection(String),
+
+    #[error("invalid response: {0}")]
+    InvalidResponse(String),
+
+    #[error("circuit breaker is open after repeated failures")]
+    CircuitOpen,
+}
+
+impl LlmError {
+    /// Whether the error is caused by unavailability of the provider, so the
+    /// request may succeed when retried
+    pub fn is_transient(&self) -> bool {
+        match self {
+            LlmError::Timeout(_) | LlmError::Connection(_) => true,
+            LlmError::Status { status, .. } => *status == 429 || *status >= 500,
+            LlmError::InvalidResponse(_) | LlmError::CircuitOpen => false,
+        }
+    }
+}
+
+impl From<reqwest::Error> for LlmError {
+    fn from(value: reqwest::Error) -> Self {
+        match value.is_decode() {
+            true => LlmError::InvalidResponse(value.to_string()),
+            false => LlmError::Connection(value.to_string()),
+        }
+    }
+}
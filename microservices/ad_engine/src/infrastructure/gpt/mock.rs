@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+
+use crate::{domain, infrastructure};
+
+/// A deterministic LLM client that works without network.
+///
+/// The response is built from the user prompt and a hash of the system
+/// prompt, so the same prompts always give the same text and title and body
+/// prompts give different texts. Used in offline development and in tests.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MockLlmClient;
+
+impl MockLlmClient {
+    /// Returns the FNV-1a hash of a text
+    fn hash(text: &str) -> u32 {
+        text.bytes()
+            .fold(0x811c9dc5, |hash, byte| (hash ^ byte as u32).wrapping_mul(0x01000193))
+    }
+}
+
+#[async_trait]
+impl domain::services::ILlmClient for MockLlmClient {
+    async fn ask_gpt(&self, user_prompt: &str, system_prompt: &str) -> infrastructure::gpt::LlmResult<String> {
+        Ok(format!(
+            "[mock {:08x}] {}",
+            Self::hash(system_prompt),
+            user_prompt.trim()
+        ))
+    }
+}
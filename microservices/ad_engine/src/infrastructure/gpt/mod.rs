@@ -1 +1,38 @@
+//! # GPT
+//! Clients of the LLM providers used to generate campaign texts. Every
+//! provider implements [`crate::domain::services::ILlmClient`], the selected
+//! one is wrapped in [`ResilientLlmClient`] for timeouts, retries and the
+//! circuit breaker.
+
+mod error;
+pub mod mock;
+pub mod openai;
+mod resilient;
 pub mod yandex;
+
+pub use error::LlmError;
+pub use resilient::{ResilientLlmClient, RetryPolicy};
+
+pub type LlmResult<T> = Result<T, LlmError>;
+
+/// Converts a non-2xx response of a provider into an error with the status and
+/// the message of the provider
+///
+/// Both Yandex and OpenAI-compatible APIs put the message into
+/// `error.message`, the raw body is used when there is no such field.
+pub(crate) async fn error_from_response(response: reqwest::Response) -> LlmError {
+    let status = response.status().as_u16();
+    let body = response.text().await.unwrap_or_default();
+
+    let message = serde_json::from_str::<serde_json::Value>(&body)
+        .ok()
+        .and_then(|json| {
+            json.pointer("/error/message")
+                .or_else(|| json.get("message"))
+                .and_then(|message| message.as_str())
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| body.chars().take(500).collect());
+
+    LlmError::Status { status, message }
+}
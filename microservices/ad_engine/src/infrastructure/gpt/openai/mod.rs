@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+
+use crate::{domain, infrastructure};
+
+mod schemas;
+
+/// A client for servers with the OpenAI-compatible chat completions API.
+///
+/// Works with OpenAI itself and with local servers like llama.cpp or vLLM, the
+/// server is selected by the base URL.
+#[derive(Debug)]
+pub struct OpenAiClient {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: String,
+    model: String,
+    temperature: f32,
+    max_tokens: u32,
+}
+
+impl OpenAiClient {
+    /// Creates a new instance of OpenAiClient.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - Base URL of the API, for example `http://localhost:8080/v1`
+    /// * `api_key` - Bearer token, not sent when empty
+    /// * `model` - Name of the model
+    /// * `temperature` - Controls randomness in the model's output
+    /// * `max_tokens` - Maximum number of tokens in the generated response
+    pub fn new(base_url: String, api_key: String, model: String, temperature: f32, max_tokens: u32) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url,
+            api_key,
+            model,
+            temperature,
+            max_tokens,
+        }
+    }
+}
+
+#[async_trait]
+impl domain::services::ILlmClient for OpenAiClient {
+    async fn ask_gpt(&self, user_prompt: &str, system_prompt: &str) -> infrastructure::gpt::LlmResult<String> {
+        let request_body = schemas::ChatCompletionRequest {
+            model: &self.model,
+            messages: vec![
+                schemas::Message {
+                    role: "system".to_string(),
+                    content: system_prompt.to_string(),
+                },
+                schemas::Message {
+                    role: "user".to_string(),
+                    content: user_prompt.to_string(),
+                },
+            ],
+            temperature: self.temperature,
+            max_tokens: self.max_tokens,
+        };
+
+        let mut request = self
+            .client
+            .post(format!("{}/chat/completions", self.base_url.trim_end_matches('/')))
+            .json(&request_body);
+        if !self.api_key.is_empty() {
+            request = request.bearer_auth(&self.api_key);
+        }
+
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            let error = infrastructure::gpt::error_from_response(response).await;
+            tracing::error!("Failed request to OpenAI-compatible API: {}", error);
+            return Err(error);
+        }
+
+        let completion: schemas::ChatCompletionResponse = response.json().await?;
+
+        completion
+            .choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message.content)
+            .ok_or(infrastructure::gpt::LlmError::InvalidResponse(
+                "chat completion response has no choices".into(),
+            ))
+    }
+}
@@ -0,0 +1,23 @@
+#[derive(Debug, serde::Serialize)]
+pub struct ChatCompletionRequest<'a> {
+    pub model: &'a str,
+    pub messages: Vec<Message>,
+    pub temperature: f32,
+    pub max_tokens: u32,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Message {
+    pub role: String,
+    pub content: String,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct ChatCompletionResponse {
+    pub choices: Vec<Choice>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct Choice {
+    pub message: Message,
+}
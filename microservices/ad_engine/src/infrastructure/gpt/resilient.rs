@@ -0,0 +1,309 @@
+use async_trait::async_trait;
+use rand::Rng;
+
+use crate::{domain, infrastructure};
+
+lazy_static::lazy_static! {
+    static ref CIRCUIT_BREAKER: std::sync::Arc<CircuitBreaker> = std::sync::Arc::new(CircuitBreaker::default());
+}
+
+/// Timeout, retry and circuit breaker settings of LLM requests
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Timeout of one attempt
+    pub timeout: std::time::Duration,
+
+    /// Number of attempts including the first one
+    pub max_attempts: u32,
+
+    /// Delay before the first retry, doubled for every next retry
+    pub base_delay: std::time::Duration,
+
+    /// Upper bound of the delay between retries
+    pub max_delay: std::time::Duration,
+
+    /// Number of consecutive failures that opens the circuit breaker
+    pub failure_threshold: u32,
+
+    /// How long the open circuit breaker rejects requests
+    pub open_duration: std::time::Duration,
+}
+
+#[derive(Debug, Default)]
+struct CircuitBreakerState {
+    failures: u32,
+    opened_at: Option<std::time::Instant>,
+}
+
+/// Circuit breaker of the LLM provider.
+///
+/// After `failure_threshold` consecutive transient failures the breaker opens
+/// and requests fail at once for `open_duration`. Then requests are let
+/// through again: a success closes the breaker, a failure opens it for
+/// another `open_duration`.
+#[derive(Debug, Default)]
+pub struct CircuitBreaker {
+    state: std::sync::Mutex<CircuitBreakerState>,
+}
+
+impl CircuitBreaker {
+    /// Returns the process-wide circuit breaker
+    pub fn global() -> std::sync::Arc<Self> {
+        CIRCUIT_BREAKER.clone()
+    }
+
+    /// Whether a request may be sent to the provider
+    pub fn allow(&self, open_duration: std::time::Duration) -> bool {
+        match self.state.lock() {
+            Ok(state) => state
+                .opened_at
+                .map_or(true, |opened_at| opened_at.elapsed() >= open_duration),
+            Err(_) => true,
+        }
+    }
+
+    /// Closes the breaker after a successful request
+    pub fn record_success(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            *state = CircuitBreakerState::default();
+        }
+    }
+
+    /// Counts a failed request, opens the breaker after `failure_threshold`
+    /// consecutive failures
+    pub fn record_failure(&self, failure_threshold: u32) {
+        if let Ok(mut state) = self.state.lock() {
+            state.failures += 1;
+            if state.failures >= failure_threshold {
+                state.opened_at = Some(std::time::Instant::now());
+            }
+        }
+    }
+}
+
+/// LLM client that adds timeouts, retries with jittered exponential backoff
+/// and the circuit breaker to another client.
+///
+/// Only transient errors (timeouts, connection errors, 429 and 5xx statuses)
+/// are retried and counted by the circuit breaker.
+pub struct ResilientLlmClient {
+    llm_client: Box<dyn domain::services::ILlmClient + Send + Sync>,
+    policy: RetryPolicy,
+    circuit_breaker: std::sync::Arc<CircuitBreaker>,
+}
+
+impl ResilientLlmClient {
+    /// Creates a new ResilientLlmClient instance with the process-wide circuit
+    /// breaker
+    ///
+    /// # Arguments
+    /// * `llm_client` - Client of the LLM provider
+    /// * `policy` - Timeout, retry and circuit breaker settings
+    pub fn new(llm_client: Box<dyn domain::services::ILlmClient + Send + Sync>, policy: RetryPolicy) -> Self {
+        Self {
+            llm_client,
+            policy,
+            circuit_breaker: CircuitBreaker::global(),
+        }
+    }
+
+    /// Replaces the circuit breaker of the client
+    #[cfg(test)]
+    pub fn with_circuit_breaker(mut self, circuit_breaker: std::sync::Arc<CircuitBreaker>) -> Self {
+        self.circuit_breaker = circuit_breaker;
+        self
+    }
+
+    /// Returns a random delay before the retry with "full jitter": from zero
+    /// to `base_delay * 2^retry`, but not more than `max_delay`
+    ///
+    /// # Arguments
+    /// * `retry` - Number of the retry, starting from 0
+    fn backoff(&self, retry: u32) -> std::time::Duration {
+        let cap = self
+            .policy
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.policy.max_delay);
+
+        cap.mul_f64(rand::thread_rng().gen_range(0.0..=1.0))
+    }
+}
+
+#[async_trait]
+impl domain::services::ILlmClient for ResilientLlmClient {
+    async fn ask_gpt(&self, user_prompt: &str, system_prompt: &str) -> infrastructure::gpt::LlmResult<String> {
+        let mut attempt = 1;
+        loop {
+            if !self.circuit_breaker.allow(self.policy.open_duration) {
+                return Err(infrastructure::gpt::LlmError::CircuitOpen);
+            }
+
+            let result = tokio::time::timeout(self.policy.timeout, self.llm_client.ask_gpt(user_prompt, system_prompt))
+                .await
+                .unwrap_or(Err(infrastructure::gpt::LlmError::Timeout(self.policy.timeout)));
+
+            match result {
+                Ok(text) => {
+                    self.circuit_breaker.record_success();
+                    return Ok(text);
+                },
+                Err(e) if e.is_transient() => {
+                    self.circuit_breaker.record_failure(self.policy.failure_threshold);
+                    if attempt >= self.policy.max_attempts {
+                        return Err(e);
+                    }
+
+                    let delay = self.backoff(attempt - 1);
+                    tracing::warn!("LLM request failed on attempt {}, retry in {:?}: {}", attempt, delay, e);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                },
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    };
+
+    use super::*;
+    use crate::{domain::services::ILlmClient, infrastructure::gpt::LlmError};
+
+    /// Client that returns scripted results, then successes
+    struct ScriptedLlmClient {
+        results: Mutex<std::vec::IntoIter<infrastructure::gpt::LlmResult<String>>>,
+        delay: std::time::Duration,
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl ILlmClient for ScriptedLlmClient {
+        async fn ask_gpt(&self, _: &str, _: &str) -> infrastructure::gpt::LlmResult<String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            let result = self.results.lock().unwrap().next();
+            result.unwrap_or(Ok("generated".into()))
+        }
+    }
+
+    fn create_test_policy() -> RetryPolicy {
+        RetryPolicy {
+            timeout: std::time::Duration::from_millis(100),
+            max_attempts: 3,
+            base_delay: std::time::Duration::from_millis(1),
+            max_delay: std::time::Duration::from_millis(4),
+            failure_threshold: 5,
+            open_duration: std::time::Duration::from_secs(60),
+        }
+    }
+
+    fn create_test_client(
+        results: Vec<infrastructure::gpt::LlmResult<String>>,
+        delay: std::time::Duration,
+        policy: RetryPolicy,
+    ) -> (ResilientLlmClient, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let scripted = ScriptedLlmClient {
+            results: Mutex::new(results.into_iter()),
+            delay,
+            calls: calls.clone(),
+        };
+        let client = ResilientLlmClient::new(Box::new(scripted), policy)
+            .with_circuit_breaker(Arc::new(CircuitBreaker::default()));
+        (client, calls)
+    }
+
+    fn status(status: u16) -> infrastructure::gpt::LlmResult<String> {
+        Err(LlmError::Status {
+            status,
+            message: "error".into(),
+        })
+    }
+
+    #[tokio::test]
+    async fn transient_errors_are_retried() {
+        let (client, calls) = create_test_client(
+            vec![status(503), Err(LlmError::Connection("reset".into()))],
+            std::time::Duration::ZERO,
+            create_test_policy(),
+        );
+
+        assert_eq!(client.ask_gpt("user", "system").await, Ok("generated".into()));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_retried() {
+        let (client, calls) = create_test_client(vec![status(400)], std::time::Duration::ZERO, create_test_policy());
+
+        assert_eq!(client.ask_gpt("user", "system").await, status(400));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn retries_are_bounded() {
+        let (client, calls) = create_test_client(
+            vec![status(500), status(502), status(429), status(500)],
+            std::time::Duration::ZERO,
+            create_test_policy(),
+        );
+
+        assert_eq!(client.ask_gpt("user", "system").await, status(429));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn slow_response_times_out() {
+        let policy = RetryPolicy {
+            max_attempts: 1,
+            ..create_test_policy()
+        };
+        let (client, _) = create_test_client(vec![], std::time::Duration::from_secs(5), policy.clone());
+
+        assert_eq!(
+            client.ask_gpt("user", "system").await,
+            Err(LlmError::Timeout(policy.timeout))
+        );
+    }
+
+    #[tokio::test]
+    async fn circuit_breaker_rejects_requests_after_failures() {
+        let policy = RetryPolicy {
+            max_attempts: 1,
+            failure_threshold: 2,
+            ..create_test_policy()
+        };
+        let (client, calls) = create_test_client(
+            vec![status(500), status(500)],
+            std::time::Duration::ZERO,
+            policy.clone(),
+        );
+
+        assert_eq!(client.ask_gpt("user", "system").await, status(500));
+        assert_eq!(client.ask_gpt("user", "system").await, status(500));
+        assert_eq!(client.ask_gpt("user", "system").await, Err(LlmError::CircuitOpen));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let breaker = CircuitBreaker::default();
+        breaker.record_failure(1);
+        assert!(!breaker.allow(policy.open_duration));
+        assert!(breaker.allow(std::time::Duration::ZERO));
+        breaker.record_success();
+        assert!(breaker.allow(policy.open_duration));
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        let (client, _) = create_test_client(vec![], std::time::Duration::ZERO, create_test_policy());
+
+        for retry in 0..40 {
+            assert!(client.backoff(retry) <= std::time::Duration::from_millis(4));
+        }
+    }
+}
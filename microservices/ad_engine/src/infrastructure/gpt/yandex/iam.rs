@@ -0,0 +1,76 @@
+use super::schemas;
+use crate::infrastructure;
+
+lazy_static::lazy_static! {
+    static ref IAM_TOKEN_CACHE: std::sync::Arc<IamTokenCache> = std::sync::Arc::new(IamTokenCache::default());
+}
+
+/// IAM token with the OAuth token it was issued for
+#[derive(Debug)]
+struct CachedIamToken {
+    oauth_token: String,
+    iam_token: String,
+    expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Cache of the Yandex IAM token shared by the GPT clients.
+///
+/// The token is reused until it's about to expire. The lock is held while a
+/// new token is fetched, so concurrent requests wait for one refresh instead
+/// of fetching a token each.
+#[derive(Debug, Default)]
+pub struct IamTokenCache {
+    token: tokio::sync::Mutex<Option<CachedIamToken>>,
+}
+
+impl IamTokenCache {
+    /// Returns the process-wide cache
+    pub fn global() -> std::sync::Arc<Self> {
+        IAM_TOKEN_CACHE.clone()
+    }
+
+    /// Returns a cached IAM token or fetches a new one
+    ///
+    /// A new token is fetched when there is no token for `oauth_token`, when
+    /// the token expires in less than `refresh_before` or when the token is
+    /// `rejected` by the API.
+    ///
+    /// # Arguments
+    /// * `oauth_token` - OAuth token the IAM token is issued for
+    /// * `refresh_before` - How long before the expiry the token is refreshed
+    /// * `rejected` - Token the API responded 401 to
+    /// * `fetch` - Requests a new token from the IAM API
+    pub async fn get<F, Fut>(
+        &self,
+        oauth_token: &str,
+        refresh_before: std::time::Duration,
+        rejected: Option<&str>,
+        fetch: F,
+    ) -> infrastructure::gpt::LlmResult<String>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = infrastructure::gpt::LlmResult<schemas::IamTokenResponse>>,
+    {
+        let mut token = self.token.lock().await;
+
+        if let Some(cached) = token.as_ref() {
+            let refresh_at =
+                cached.expires_at - chrono::Duration::from_std(refresh_before).unwrap_or(chrono::Duration::zero());
+            if cached.oauth_token == oauth_token
+                && chrono::Utc::now() < refresh_at
+                && rejected != Some(cached.iam_token.as_str())
+            {
+                return Ok(cached.iam_token.clone());
+            }
+        }
+
+        let response = fetch().await?;
+        *token = Some(CachedIamToken {
+            oauth_token: oauth_token.to_string(),
+            iam_token: response.iam_token.clone(),
+            expires_at: response.expires_at,
+        });
+
+        Ok(response.iam_token)
+    }
+}
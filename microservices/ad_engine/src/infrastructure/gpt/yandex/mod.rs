@@ -1,28 +1,43 @@
-use anyhow::Context;
+use async_trait::async_trait;
 
+use crate::{domain, infrastructure};
+
+mod iam;
 mod schemas;
 
+pub use iam::IamTokenCache;
+
 /// A client for interacting with Yandex GPT API.
 ///
 /// This client handles authentication and communication with the Yandex GPT
 /// service, allowing you to send prompts and receive AI-generated responses.
+/// IAM tokens are reused between requests through [`IamTokenCache`].
 #[derive(Debug)]
 pub struct YandexGptClient {
     client: reqwest::Client,
     folder_id: String,
     auth_token: String,
+    iam_url: String,
+    completion_url: String,
+    iam_token_refresh_before: std::time::Duration,
+    token_cache: std::sync::Arc<IamTokenCache>,
     temperature: f32,
     max_tokens: u32,
 }
 
 impl YandexGptClient {
-    /// Creates a new instance of YandexGptClient.
+    /// Creates a new instance of YandexGptClient with the process-wide IAM
+    /// token cache.
     ///
     /// # Arguments
     ///
     /// * `folder_id` - The Yandex Cloud folder ID where the GPT model is
     ///   located
     /// * `auth_token` - OAuth token for authentication with Yandex Cloud
+    /// * `iam_url` - URL of the IAM tokens endpoint
+    /// * `completion_url` - URL of the completion endpoint
+    /// * `iam_token_refresh_before` - How long before the expiry the IAM token
+    ///   is refreshed
     /// * `temperature` - Controls randomness in the model's output (0.0 to 1.0)
     /// * `max_tokens` - Maximum number of tokens in the generated response
     ///
@@ -30,57 +45,96 @@ impl YandexGptClient {
     ///
     /// Returns a new `YandexGptClient` instance configured with the provided
     /// parameters.
-    pub fn new(folder_id: String, auth_token: String, temperature: f32, max_tokens: u32) -> Self {
+    pub fn new(
+        folder_id: String,
+        auth_token: String,
+        iam_url: String,
+        completion_url: String,
+        iam_token_refresh_before: std::time::Duration,
+        temperature: f32,
+        max_tokens: u32,
+    ) -> Self {
         Self {
             client: reqwest::Client::new(),
             folder_id,
             auth_token,
+            iam_url,
+            completion_url,
+            iam_token_refresh_before,
+            token_cache: IamTokenCache::global(),
 
             temperature,
             max_tokens,
         }
     }
 
+    /// Replaces the IAM token cache of the client
+    #[cfg(test)]
+    pub fn with_token_cache(mut self, token_cache: std::sync::Arc<IamTokenCache>) -> Self {
+        self.token_cache = token_cache;
+        self
+    }
+
     /// Retrieves an IAM token for API authentication.
     ///
-    /// Makes a request to Yandex IAM service to exchange the OAuth token for an
-    /// IAM token.
+    /// Returns the cached token while it's fresh, otherwise exchanges the
+    /// OAuth token for a new IAM token.
+    ///
+    /// # Arguments
+    ///
+    /// * `rejected` - Token the completion API responded 401 to, it's replaced
+    ///   even if it hasn't expired
     ///
     /// # Returns
     ///
     /// Returns a Result containing the IAM token string or an error if the
     /// request fails.
-    async fn get_iam_token(&self) -> anyhow::Result<String> {
+    async fn get_iam_token(&self, rejected: Option<&str>) -> infrastructure::gpt::LlmResult<String> {
+        self.token_cache
+            .get(&self.auth_token, self.iam_token_refresh_before, rejected, || {
+                self.fetch_iam_token()
+            })
+            .await
+    }
+
+    /// Exchanges the OAuth token for a new IAM token.
+    async fn fetch_iam_token(&self) -> infrastructure::gpt::LlmResult<schemas::IamTokenResponse> {
+        tracing::debug!("Fetching a new Yandex IAM token");
+
         let response = self
             .client
-            .post("https://iam.api.cloud.yandex.net/iam/v1/tokens")
+            .post(&self.iam_url)
             .json(&serde_json::json!({"yandexPassportOauthToken": self.auth_token.clone()}))
             .send()
-            .await
-            .context("Failed to send IAM token request")?;
-
-        let token_response: schemas::IamTokenResponse =
-            response.json().await.context("Failed to parse IAM token response")?;
+            .await?;
+        if !response.status().is_success() {
+            let error = infrastructure::gpt::error_from_response(response).await;
+            tracing::error!("Failed request to Yandex IAM: {}", error);
+            return Err(error);
+        }
 
-        Ok(token_response.iam_token)
+        Ok(response.json().await?)
     }
 
-    /// Sends a prompt to Yandex GPT and returns the generated response.
-    ///
-    /// # Arguments
-    ///
-    /// * `user_prompt` - The main prompt text to send to the model
-    /// * `system_prompt` - System-level instructions that guide the model's
-    ///   behavior
-    ///
-    /// # Returns
-    ///
-    /// Returns a Result containing the generated response text or an error if
-    /// the request fails.
-
-    pub async fn ask_gpt(&self, user_prompt: &str, system_prompt: &str) -> anyhow::Result<String> {
-        let iam_token = self.get_iam_token().await?;
+    /// Sends a completion request authorized with an IAM token.
+    async fn send_completion(
+        &self,
+        iam_token: &str,
+        request_body: &serde_json::Value,
+    ) -> infrastructure::gpt::LlmResult<reqwest::Response> {
+        Ok(self
+            .client
+            .post(&self.completion_url)
+            .bearer_auth(iam_token)
+            .json(request_body)
+            .send()
+            .await?)
+    }
+}
 
+#[async_trait]
+impl domain::services::ILlmClient for YandexGptClient {
+    async fn ask_gpt(&self, user_prompt: &str, system_prompt: &str) -> infrastructure::gpt::LlmResult<String> {
         let request_body = serde_json::json!({
             "modelUri": format!("gpt://{}/yandexgpt", self.folder_id),
             "generationOptions": schemas::GenerationOptions {
@@ -99,24 +153,220 @@ impl YandexGptClient {
             ],
         });
 
-        let response = self
-            .client
-            .post("https://llm.api.cloud.yandex.net/foundationModels/v1/completion")
-            .header("Authorization", format!("Bearer {}", iam_token))
-            .json(&request_body)
-            .send()
-            .await
-            .context("Failed to send GPT request")?;
+        let iam_token = self.get_iam_token(None).await?;
+        let mut response = self.send_completion(&iam_token, &request_body).await?;
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            tracing::warn!("Yandex GPT rejected the IAM token, refreshing it");
+            let iam_token = self.get_iam_token(Some(&iam_token)).await?;
+            response = self.send_completion(&iam_token, &request_body).await?;
+        }
 
-        let gpt_response: schemas::GptResponse = response
-            .json()
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed request ro ya gpt: {:#?}", e);
-                e
-            })
-            .context("Failed to parse GPT response")?;
+        if !response.status().is_success() {
+            let error = infrastructure::gpt::error_from_response(response).await;
+            tracing::error!("Failed request ro ya gpt: {}", error);
+            return Err(error);
+        }
+
+        let gpt_response: schemas::GptResponse = response.json().await?;
+
+        gpt_response
+            .result
+            .alternatives
+            .into_iter()
+            .next()
+            .map(|alternative| alternative.message.text)
+            .ok_or(infrastructure::gpt::LlmError::InvalidResponse(
+                "GPT response has no alternatives".into(),
+            ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    };
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::*;
+    use crate::domain::services::ILlmClient;
+
+    /// Local HTTP server with the IAM and completion endpoints
+    struct StubServer {
+        url: String,
+        iam_calls: Arc<AtomicUsize>,
+        completion_calls: Arc<AtomicUsize>,
+    }
+
+    impl StubServer {
+        /// Starts the stub
+        ///
+        /// # Arguments
+        /// * `token_ttl` - Lifetime of the issued IAM tokens
+        /// * `completion_statuses` - Statuses of the first completion
+        ///   responses, the rest are 200
+        async fn start(token_ttl: chrono::Duration, completion_statuses: Vec<u16>) -> Self {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let iam_calls = Arc::new(AtomicUsize::new(0));
+            let completion_calls = Arc::new(AtomicUsize::new(0));
+            let completion_statuses = Arc::new(Mutex::new(completion_statuses.into_iter()));
+
+            let (iam, completion) = (iam_calls.clone(), completion_calls.clone());
+            tokio::spawn(async move {
+                loop {
+                    let (mut stream, _) = listener.accept().await.unwrap();
+                    let (iam, completion, statuses) = (iam.clone(), completion.clone(), completion_statuses.clone());
+                    tokio::spawn(async move {
+                        let request = Self::read_request(&mut stream).await;
+                        let (status, body) = if request.starts_with("POST /iam") {
+                            let number = iam.fetch_add(1, Ordering::SeqCst) + 1;
+                            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                            let expires_at = chrono::Utc::now() + token_ttl;
+                            (
+                                200,
+                                serde_json::json!({"iamToken": format!("token-{}", number), "expiresAt": expires_at})
+                                    .to_string(),
+                            )
+                        } else {
+                            completion.fetch_add(1, Ordering::SeqCst);
+                            match statuses.lock().unwrap().next().unwrap_or(200) {
+                                200 => (
+                                    200,
+                                    serde_json::json!({"result": {"alternatives": [
+                                        {"message": {"role": "assistant", "text": "generated"}}
+                                    ]}})
+                                    .to_string(),
+                                ),
+                                status => (
+                                    status,
+                                    serde_json::json!({"error": {"httpCode": status, "message": "stub error"}})
+                                        .to_string(),
+                                ),
+                            }
+                        };
+
+                        let response = format!(
+                            "HTTP/1.1 {} Stub\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: \
+                             close\r\n\r\n{}",
+                            status,
+                            body.len(),
+                            body
+                        );
+                        stream.write_all(response.as_bytes()).await.unwrap();
+                    });
+                }
+            });
+
+            Self {
+                url,
+                iam_calls,
+                completion_calls,
+            }
+        }
+
+        /// Reads the request line, headers and body of a request
+        async fn read_request(stream: &mut tokio::net::TcpStream) -> String {
+            let mut request = Vec::new();
+            let mut buffer = [0; 4096];
+            loop {
+                let read = stream.read(&mut buffer).await.unwrap();
+                request.extend_from_slice(&buffer[..read]);
+
+                let text = String::from_utf8_lossy(&request).to_string();
+                if let Some(headers_end) = text.find("\r\n\r\n") {
+                    let content_length = text[..headers_end]
+                        .lines()
+                        .find_map(|line| {
+                            line.to_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().to_string())
+                        })
+                        .and_then(|length| length.parse::<usize>().ok())
+                        .unwrap_or(0);
+                    if read == 0 || request.len() >= headers_end + 4 + content_length {
+                        return text;
+                    }
+                }
+                if read == 0 {
+                    return text;
+                }
+            }
+        }
+
+        fn client(&self, refresh_before: std::time::Duration) -> YandexGptClient {
+            YandexGptClient::new(
+                "folder".into(),
+                "oauth".into(),
+                format!("{}/iam", self.url),
+                format!("{}/completion", self.url),
+                refresh_before,
+                0.3,
+                100,
+            )
+            .with_token_cache(Arc::new(IamTokenCache::default()))
+        }
+    }
 
-        Ok(gpt_response.result.alternatives[0].message.text.clone())
+    #[tokio::test]
+    async fn iam_token_is_reused_between_requests() {
+        let stub = StubServer::start(chrono::Duration::hours(12), vec![]).await;
+        let client = stub.client(std::time::Duration::from_secs(3600));
+
+        assert_eq!(client.ask_gpt("title", "system").await.unwrap(), "generated");
+        assert_eq!(client.ask_gpt("body", "system").await.unwrap(), "generated");
+
+        assert_eq!(stub.iam_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(stub.completion_calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn iam_token_is_refreshed_before_expiry() {
+        let stub = StubServer::start(chrono::Duration::minutes(30), vec![]).await;
+        let client = stub.client(std::time::Duration::from_secs(3600));
+
+        client.ask_gpt("title", "system").await.unwrap();
+        client.ask_gpt("body", "system").await.unwrap();
+
+        assert_eq!(stub.iam_calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn concurrent_requests_fetch_one_token() {
+        let stub = StubServer::start(chrono::Duration::hours(12), vec![]).await;
+        let client = stub.client(std::time::Duration::from_secs(3600));
+
+        let responses = futures::future::join_all((0..8).map(|_| client.ask_gpt("title", "system"))).await;
+
+        assert!(responses.iter().all(|response| response.is_ok()));
+        assert_eq!(stub.iam_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(stub.completion_calls.load(Ordering::SeqCst), 8);
+    }
+
+    #[tokio::test]
+    async fn unauthorized_response_forces_refresh() {
+        let stub = StubServer::start(chrono::Duration::hours(12), vec![401]).await;
+        let client = stub.client(std::time::Duration::from_secs(3600));
+
+        assert_eq!(client.ask_gpt("title", "system").await.unwrap(), "generated");
+
+        assert_eq!(stub.iam_calls.load(Ordering::SeqCst), 2);
+        assert_eq!(stub.completion_calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn error_status_carries_provider_message() {
+        let stub = StubServer::start(chrono::Duration::hours(12), vec![400]).await;
+        let client = stub.client(std::time::Duration::from_secs(3600));
+
+        assert_eq!(
+            client.ask_gpt("title", "system").await,
+            Err(infrastructure::gpt::LlmError::Status {
+                status: 400,
+                message: "stub error".into()
+            })
+        );
     }
 }
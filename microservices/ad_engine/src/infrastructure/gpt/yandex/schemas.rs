@@ -2,6 +2,8 @@
 pub struct IamTokenResponse {
     #[serde(rename = "iamToken")]
     pub iam_token: String,
+    #[serde(rename = "expiresAt")]
+    pub expires_at: chrono::DateTime<chrono::Utc>,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -18,6 +18,10 @@ pub struct AppMetrics {
     pub total_make_money_visits: prometheus::Gauge,
     pub total_make_money_clicks: prometheus::Gauge,
 
+    pub shadow_rankings: prometheus::IntCounter,
+    pub shadow_winner_differs: prometheus::IntCounter,
+    pub shadow_revenue_delta: prometheus::Gauge,
+
     pub http_requests_total: prometheus::IntCounterVec,
     pub http_response_time: prometheus::HistogramVec,
 }
@@ -91,6 +95,24 @@ impl AppMetrics {
             )
             .expect("Failed create metric make_money_clicks".into()),
 
+            shadow_rankings: prometheus::register_int_counter!(prometheus::opts!(
+                "shadow_rankings_total",
+                "Total ads requests ranked by the shadow configuration",
+            ))
+            .expect("Failed create metric shadow_rankings".into()),
+
+            shadow_winner_differs: prometheus::register_int_counter!(prometheus::opts!(
+                "shadow_winner_differs_total",
+                "Total ads requests where the shadow winner differs from the served one",
+            ))
+            .expect("Failed create metric shadow_winner_differs".into()),
+
+            shadow_revenue_delta: prometheus::register_gauge!(prometheus::opts!(
+                "shadow_revenue_delta_total",
+                "Total impression revenue the shadow winners would make minus the served ones",
+            ))
+            .expect("Failed create metric shadow_revenue_delta".into()),
+
             http_requests_total: prometheus::register_int_counter_vec!(
                 prometheus::opts!("http_requests_total", "HTTP requests total"),
                 &["method", "path"]
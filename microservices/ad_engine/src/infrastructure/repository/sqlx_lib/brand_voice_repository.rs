@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+
+use crate::{domain, infrastructure};
+
+#[derive(Debug)]
+pub struct PgBrandVoiceRepository<'p> {
+    db_pool: &'p sqlx::Pool<sqlx::Postgres>,
+}
+
+impl<'p> infrastructure::repository::IRepo<'p> for PgBrandVoiceRepository<'p> {
+    fn new(db_pool: &'p sqlx::Pool<sqlx::Postgres>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct BrandVoiceReturningSchema {
+    pub advertiser_id: uuid::Uuid,
+    pub tone: String,
+    pub language: String,
+    pub guidelines: String,
+    pub template_id: Option<uuid::Uuid>,
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::IUpsertBrandVoice for PgBrandVoiceRepository<'p> {
+    async fn upsert(
+        &self,
+        advertiser_id: uuid::Uuid,
+        brand_voice: domain::schemas::BrandVoiceUpdateRequest,
+    ) -> infrastructure::repository::RepoResult<BrandVoiceReturningSchema> {
+        let brand_voice = sqlx::query_as!(
+            BrandVoiceReturningSchema,
+            r#"
+            INSERT INTO advertiser_brand_voices (advertiser_id, tone, language, guidelines, template_id)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (advertiser_id) DO UPDATE
+            SET tone = EXCLUDED.tone,
+                language = EXCLUDED.language,
+                guidelines = EXCLUDED.guidelines,
+                template_id = EXCLUDED.template_id,
+                updated_at = NOW()
+            RETURNING advertiser_id, tone, language, guidelines, template_id
+            "#,
+            advertiser_id,
+            brand_voice.tone,
+            brand_voice.language,
+            brand_voice.guidelines.unwrap_or_default(),
+            brand_voice.template_id,
+        )
+        .fetch_one(self.db_pool)
+        .await
+        .map_err(|e| {
+            let message = e.to_string();
+            if message.contains("violates foreign key constraint \"advertiser_brand_voices_advertiser_id_fkey\"") {
+                return infrastructure::repository::RepoError::ObjDoesNotExists("advertiser".to_string());
+            }
+            if message.contains("violates foreign key constraint \"advertiser_brand_voices_template_id_fkey\"") {
+                return infrastructure::repository::RepoError::ObjDoesNotExists("prompt template".to_string());
+            }
+            e.into()
+        })?;
+
+        Ok(brand_voice)
+    }
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::IGetBrandVoice for PgBrandVoiceRepository<'p> {
+    async fn get(
+        &self,
+        advertiser_id: uuid::Uuid,
+    ) -> infrastructure::repository::RepoResult<Option<BrandVoiceReturningSchema>> {
+        let brand_voice = sqlx::query_as!(
+            BrandVoiceReturningSchema,
+            r#"
+            SELECT advertiser_id, tone, language, guidelines, template_id
+            FROM advertiser_brand_voices
+            WHERE advertiser_id = $1
+            "#,
+            advertiser_id,
+        )
+        .fetch_optional(self.db_pool)
+        .await?;
+
+        Ok(brand_voice)
+    }
+}
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use bigdecimal::FromPrimitive;
+use bigdecimal::{FromPrimitive, ToPrimitive};
 
 use crate::{domain, infrastructure};
 
@@ -27,6 +27,10 @@ pub struct CampaignReturningSchema {
     pub start_date: i64,
     pub end_date: i64,
     pub targeting: Option<serde_json::Value>,
+    pub frequency_cap: Option<serde_json::Value>,
+    pub budget: Option<bigdecimal::BigDecimal>,
+    pub daily_budget: Option<bigdecimal::BigDecimal>,
+    pub prompt_version_id: Option<uuid::Uuid>,
 }
 
 #[async_trait]
@@ -49,9 +53,12 @@ impl<'p> domain::services::repository::ICreateCampaign for PgCampaignRepository<
                 ad_text,
                 start_date,
                 end_date,
-                targeting
+                targeting,
+                frequency_cap,
+                budget,
+                daily_budget
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
             RETURNING *
             "#,
             advertiser_id,
@@ -66,6 +73,13 @@ impl<'p> domain::services::repository::ICreateCampaign for PgCampaignRepository<
             campaign.start_date as i32,
             campaign.end_date as i32,
             serde_json::to_value(&campaign.targeting).map_err(|_| infrastructure::repository::RepoError::Unknown)?,
+            campaign
+                .frequency_cap
+                .map(|frequency_cap| serde_json::to_value(&frequency_cap))
+                .transpose()
+                .map_err(|_| infrastructure::repository::RepoError::Unknown)?,
+            campaign.budget.and_then(bigdecimal::BigDecimal::from_f64),
+            campaign.daily_budget.and_then(bigdecimal::BigDecimal::from_f64),
         )
         .fetch_one(self.db_pool)
         .await
@@ -98,8 +112,14 @@ impl<'p> domain::services::repository::IUpdateCampaign for PgCampaignRepository<
                 cost_per_clicks = $2,
                 ad_title = $3,
                 ad_text = $4,
-                targeting = $5
-            WHERE advertiser_id = $6 AND id = $7
+                targeting = $5,
+                frequency_cap = $6,
+                budget = $7,
+                daily_budget = $8,
+                prompt_version_id = CASE
+                    WHEN ad_title = $3 AND ad_text = $4 THEN prompt_version_id
+                END
+            WHERE advertiser_id = $9 AND id = $10
             RETURNING *
             "#,
             bigdecimal::BigDecimal::from_f64(campaign.cost_per_impression)
@@ -109,6 +129,13 @@ impl<'p> domain::services::repository::IUpdateCampaign for PgCampaignRepository<
             campaign.ad_title,
             campaign.ad_text,
             serde_json::to_value(&campaign.targeting).map_err(|_| infrastructure::repository::RepoError::Unknown)?,
+            campaign
+                .frequency_cap
+                .map(|frequency_cap| serde_json::to_value(&frequency_cap))
+                .transpose()
+                .map_err(|_| infrastructure::repository::RepoError::Unknown)?,
+            campaign.budget.and_then(bigdecimal::BigDecimal::from_f64),
+            campaign.daily_budget.and_then(bigdecimal::BigDecimal::from_f64),
             advertiser_id,
             campaign_id
         )
@@ -119,6 +146,39 @@ impl<'p> domain::services::repository::IUpdateCampaign for PgCampaignRepository<
     }
 }
 
+#[async_trait]
+impl<'p> domain::services::repository::IUpdateCampaignTexts for PgCampaignRepository<'p> {
+    async fn update_texts(
+        &self,
+        advertiser_id: uuid::Uuid,
+        campaign_id: uuid::Uuid,
+        ad_title: String,
+        ad_text: String,
+        prompt_version_id: Option<uuid::Uuid>,
+    ) -> infrastructure::repository::RepoResult<CampaignReturningSchema> {
+        let campaign = sqlx::query_as!(
+            CampaignReturningSchema,
+            r#"
+            UPDATE campaigns
+            SET ad_title = $1,
+                ad_text = $2,
+                prompt_version_id = $3
+            WHERE advertiser_id = $4 AND id = $5
+            RETURNING *
+            "#,
+            ad_title,
+            ad_text,
+            prompt_version_id,
+            advertiser_id,
+            campaign_id,
+        )
+        .fetch_one(self.db_pool)
+        .await?;
+
+        Ok(campaign)
+    }
+}
+
 #[async_trait]
 impl<'p> domain::services::repository::IDeleteCampaign for PgCampaignRepository<'p> {
     async fn delete(
@@ -248,16 +308,19 @@ impl<'p> domain::services::repository::IGetOrCreateUniqIdForStatCampaign for PgC
     async fn get_or_create_uniq_id(
         &self,
         campaign_id: uuid::Uuid,
-    ) -> infrastructure::repository::RepoResult<(Vec<uuid::Uuid>, Vec<uuid::Uuid>)> {
-        let view_clients: Vec<uuid::Uuid> = sqlx::query_scalar!(
+    ) -> infrastructure::repository::RepoResult<(Vec<(uuid::Uuid, u32)>, Vec<uuid::Uuid>)> {
+        let view_clients: Vec<(uuid::Uuid, u32)> = sqlx::query!(
             r#"
-            SELECT client_id FROM views_clients
+            SELECT client_id, advanced_time FROM views_clients
             WHERE campaign_id = $1
             "#,
             campaign_id
         )
         .fetch_all(self.db_pool)
-        .await?;
+        .await?
+        .into_iter()
+        .map(|view| (view.client_id, view.advanced_time as u32))
+        .collect();
 
         let click_clients: Vec<uuid::Uuid> = sqlx::query_scalar!(
             r#"
@@ -281,17 +344,32 @@ impl<'p> domain::services::repository::IViewCampaign for PgCampaignRepository<'p
         client_id: uuid::Uuid,
         cost: f64,
         advanced_time: u32,
+        creative_id: Option<uuid::Uuid>,
+        experiment: Option<domain::schemas::ExperimentAssignmentSchema>,
     ) -> infrastructure::repository::RepoResult<()> {
+        let (experiment_id, experiment_arm) = experiment
+            .map(|experiment| (experiment.experiment_id, experiment.arm))
+            .unzip();
+
         sqlx::query!(
             r#"
-            INSERT INTO views_clients (campaign_id, client_id, cost, advanced_time)
-            VALUES ($1, $2, $3, $4)
-            ON CONFLICT (campaign_id, client_id) DO NOTHING
+            INSERT INTO views_clients (
+                campaign_id, client_id, cost, advanced_time, experiment_id, experiment_arm, creative_id,
+                prompt_version_id
+            )
+            SELECT $1, $2, $3, $4, $5, $6, $7::UUID,
+                CASE WHEN $7::UUID IS NULL
+                    THEN (SELECT prompt_version_id FROM campaigns WHERE id = $1)
+                    ELSE (SELECT prompt_version_id FROM campaign_creatives WHERE id = $7)
+                END
             "#,
             campaign_id,
             client_id,
             bigdecimal::BigDecimal::from_f64(cost).ok_or(infrastructure::repository::RepoError::Unknown)?,
-            advanced_time as i64
+            advanced_time as i64,
+            experiment_id,
+            experiment_arm,
+            creative_id
         )
         .execute(self.db_pool)
         .await?;
@@ -310,8 +388,18 @@ impl<'p> domain::services::repository::IClickCampaign for PgCampaignRepository<'
     ) -> infrastructure::repository::RepoResult<()> {
         sqlx::query!(
             r#"
-            INSERT INTO clicks_clients (campaign_id, client_id, cost, advanced_time)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO clicks_clients (
+                campaign_id, client_id, cost, advanced_time, experiment_id, experiment_arm, creative_id,
+                prompt_version_id
+            )
+            SELECT $1, $2, $3, $4, v.experiment_id, v.experiment_arm, v.creative_id, v.prompt_version_id
+            FROM (SELECT 1) s
+            LEFT JOIN LATERAL (
+                SELECT experiment_id, experiment_arm, creative_id, prompt_version_id FROM views_clients
+                WHERE campaign_id = $1 AND client_id = $2
+                ORDER BY advanced_time DESC
+                LIMIT 1
+            ) v ON TRUE
             ON CONFLICT (campaign_id, client_id) DO NOTHING
             "#,
             campaign_id,
@@ -325,6 +413,83 @@ impl<'p> domain::services::repository::IClickCampaign for PgCampaignRepository<'
     }
 }
 
+#[async_trait]
+impl<'p> domain::services::repository::IGetSpentCampaign for PgCampaignRepository<'p> {
+    async fn get_spent(
+        &self,
+        campaign_id: uuid::Uuid,
+        advanced_time: u32,
+    ) -> infrastructure::repository::RepoResult<(f64, f64)> {
+        let spent = sqlx::query!(
+            r#"
+            SELECT
+                COALESCE(SUM(cost), 0) as "spent!",
+                COALESCE(SUM(cost) FILTER (WHERE advanced_time = $2), 0) as "spent_today!"
+            FROM (
+                SELECT cost, advanced_time FROM views_clients WHERE campaign_id = $1
+                UNION ALL
+                SELECT cost, advanced_time FROM clicks_clients WHERE campaign_id = $1
+            ) s
+            "#,
+            campaign_id,
+            advanced_time as i64
+        )
+        .fetch_one(self.db_pool)
+        .await?;
+
+        Ok((
+            spent.spent.to_f64().unwrap_or(0.0),
+            spent.spent_today.to_f64().unwrap_or(0.0),
+        ))
+    }
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::IGetBudgetCampaign for PgCampaignRepository<'p> {
+    async fn get_budget(&self, campaign_id: uuid::Uuid) -> infrastructure::repository::RepoResult<Option<f64>> {
+        let budget = sqlx::query_scalar!(
+            r#"
+            SELECT budget FROM campaigns
+            WHERE id = $1
+            "#,
+            campaign_id
+        )
+        .fetch_optional(self.db_pool)
+        .await?;
+
+        Ok(budget.flatten().and_then(|budget| budget.to_f64()))
+    }
+
+    async fn get_budgets_by_advertiser(
+        &self,
+        advertiser_id: uuid::Uuid,
+    ) -> infrastructure::repository::RepoResult<Vec<(Option<f64>, f64)>> {
+        let budgets = sqlx::query!(
+            r#"
+            SELECT
+                c.budget,
+                (SELECT COALESCE(SUM(cost), 0) FROM views_clients WHERE campaign_id = c.id)
+                    + (SELECT COALESCE(SUM(cost), 0) FROM clicks_clients WHERE campaign_id = c.id) as "spent!"
+            FROM campaigns c
+            WHERE c.advertiser_id = $1
+            "#,
+            advertiser_id
+        )
+        .fetch_all(self.db_pool)
+        .await?;
+
+        Ok(budgets
+            .into_iter()
+            .map(|row| {
+                (
+                    row.budget.and_then(|budget| budget.to_f64()),
+                    row.spent.to_f64().unwrap_or(0.0),
+                )
+            })
+            .collect())
+    }
+}
+
 #[derive(sqlx::FromRow, Clone)]
 pub struct StatDailyReturningSchema {
     pub impressions_count: Option<i32>,
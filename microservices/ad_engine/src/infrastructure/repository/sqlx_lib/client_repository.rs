@@ -20,6 +20,15 @@ pub struct ClientReturningSchema {
     pub age: i32,
     pub location: String,
     pub gender: String,
+    pub attributes: serde_json::Value,
+}
+
+impl ClientReturningSchema {
+    /// Returns the age of the client clamped to the range of `u8`, ages are
+    /// validated on registration, so only corrupted rows are clamped
+    pub fn age_u8(&self) -> u8 {
+        u8::try_from(self.age.max(0)).unwrap_or(u8::MAX)
+    }
 }
 
 #[async_trait]
@@ -31,27 +40,30 @@ impl<'p> domain::services::repository::IRegisterBulkClient for PgClientRepositor
         locations: Vec<String>,
         genders: Vec<String>,
         ages: Vec<i32>,
+        attributes: Vec<serde_json::Value>,
     ) -> infrastructure::repository::RepoResult<Vec<infrastructure::repository::sqlx_lib::ClientReturningSchema>> {
         let mut transaction = self.db_pool.begin().await?;
 
         let clients = sqlx::query_as!(
             ClientReturningSchema,
             r#"
-            INSERT INTO clients (id, login, location, gender, age)
-            SELECT * FROM UNNEST($1::UUID[], $2::VARCHAR[], $3::VARCHAR[], $4::VARCHAR[], $5::INT[])
+            INSERT INTO clients (id, login, location, gender, age, attributes)
+            SELECT * FROM UNNEST($1::UUID[], $2::VARCHAR[], $3::VARCHAR[], $4::VARCHAR[], $5::INT[], $6::JSONB[])
             ON CONFLICT (id)
             DO UPDATE SET
                 login = EXCLUDED.login,
                 location = EXCLUDED.location,
                 gender = EXCLUDED.gender,
-                age = EXCLUDED.age
-            RETURNING id AS client_id, login, age, location, gender;
+                age = EXCLUDED.age,
+                attributes = EXCLUDED.attributes
+            RETURNING id AS client_id, login, age, location, gender, attributes;
             "#,
             &client_ids,
             &logins,
             &locations,
             &genders,
-            &ages
+            &ages,
+            &attributes
         )
         .fetch_all(&mut *transaction)
         .await?;
@@ -71,7 +83,7 @@ impl<'p> domain::services::repository::IGetClientById for PgClientRepository<'p>
         let client = sqlx::query_as!(
             ClientReturningSchema,
             r#"
-            SELECT id AS client_id, login, age, location, gender
+            SELECT id AS client_id, login, age, location, gender, attributes
             FROM clients
             WHERE id = $1
             "#,
@@ -88,12 +100,17 @@ impl<'p> domain::services::repository::IGetClientById for PgClientRepository<'p>
 /// schema.
 impl From<infrastructure::repository::sqlx_lib::ClientReturningSchema> for domain::schemas::ClientProfileSchema {
     fn from(user: infrastructure::repository::sqlx_lib::ClientReturningSchema) -> Self {
+        let age = user.age_u8();
         Self {
             client_id: user.client_id,
             login: user.login,
             location: user.location,
             gender: user.gender,
-            age: user.age as u8,
+            age,
+            attributes: match user.attributes {
+                serde_json::Value::Object(attributes) => attributes,
+                _ => serde_json::Map::new(),
+            },
         }
     }
 }
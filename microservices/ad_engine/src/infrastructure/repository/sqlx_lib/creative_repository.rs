@@ -0,0 +1,192 @@
+use async_trait::async_trait;
+
+use crate::{domain, infrastructure};
+
+#[derive(Debug)]
+pub struct PgCreativeRepository<'p> {
+    db_pool: &'p sqlx::Pool<sqlx::Postgres>,
+}
+
+impl<'p> infrastructure::repository::IRepo<'p> for PgCreativeRepository<'p> {
+    fn new(db_pool: &'p sqlx::Pool<sqlx::Postgres>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct CreativeReturningSchema {
+    pub id: uuid::Uuid,
+    pub campaign_id: uuid::Uuid,
+    pub ad_title: String,
+    pub ad_text: String,
+    pub image_names: Vec<String>,
+    pub prompt_version_id: Option<uuid::Uuid>,
+}
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct CreativeStatReturningSchema {
+    pub creative_id: uuid::Uuid,
+    pub ad_title: String,
+    pub deleted: bool,
+    pub impressions_count: Option<i32>,
+    pub clicks_count: Option<i32>,
+    pub spent_impressions: Option<bigdecimal::BigDecimal>,
+    pub spent_clicks: Option<bigdecimal::BigDecimal>,
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::ICreateCreatives for PgCreativeRepository<'p> {
+    async fn create(
+        &self,
+        campaign_id: uuid::Uuid,
+        creatives: Vec<domain::schemas::CreativeCreateRequest>,
+        prompt_version_id: Option<uuid::Uuid>,
+    ) -> infrastructure::repository::RepoResult<Vec<CreativeReturningSchema>> {
+        let mut transaction = self.db_pool.begin().await?;
+
+        let mut created = Vec::with_capacity(creatives.len());
+        for creative in creatives {
+            let creative = sqlx::query_as!(
+                CreativeReturningSchema,
+                r#"
+                INSERT INTO campaign_creatives (campaign_id, ad_title, ad_text, image_names, prompt_version_id)
+                VALUES ($1, $2, $3, $4, $5)
+                RETURNING id, campaign_id, ad_title, ad_text, image_names, prompt_version_id
+                "#,
+                campaign_id,
+                creative.ad_title,
+                creative.ad_text,
+                &creative.image_names.unwrap_or_default(),
+                prompt_version_id,
+            )
+            .fetch_one(&mut *transaction)
+            .await?;
+
+            created.push(creative);
+        }
+
+        transaction.commit().await?;
+        Ok(created)
+    }
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::IGetCreativeList for PgCreativeRepository<'p> {
+    async fn get_list(
+        &self,
+        campaign_id: uuid::Uuid,
+    ) -> infrastructure::repository::RepoResult<Vec<CreativeReturningSchema>> {
+        let creatives = sqlx::query_as!(
+            CreativeReturningSchema,
+            r#"
+            SELECT id, campaign_id, ad_title, ad_text, image_names, prompt_version_id FROM campaign_creatives
+            WHERE campaign_id = $1 AND deleted_at IS NULL
+            ORDER BY created_at, id
+            "#,
+            campaign_id
+        )
+        .fetch_all(self.db_pool)
+        .await?;
+
+        Ok(creatives)
+    }
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::IGetCreativesByCampaigns for PgCreativeRepository<'p> {
+    async fn get_by_campaigns(
+        &self,
+        campaign_ids: &[uuid::Uuid],
+    ) -> infrastructure::repository::RepoResult<Vec<CreativeReturningSchema>> {
+        let creatives = sqlx::query_as!(
+            CreativeReturningSchema,
+            r#"
+            SELECT id, campaign_id, ad_title, ad_text, image_names, prompt_version_id FROM campaign_creatives
+            WHERE campaign_id = ANY($1) AND deleted_at IS NULL
+            ORDER BY campaign_id, created_at, id
+            "#,
+            campaign_ids
+        )
+        .fetch_all(self.db_pool)
+        .await?;
+
+        Ok(creatives)
+    }
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::IDeleteCreative for PgCreativeRepository<'p> {
+    async fn delete(
+        &self,
+        campaign_id: uuid::Uuid,
+        creative_id: uuid::Uuid,
+    ) -> infrastructure::repository::RepoResult<()> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE campaign_creatives
+            SET deleted_at = NOW()
+            WHERE campaign_id = $1 AND id = $2 AND deleted_at IS NULL
+            "#,
+            campaign_id,
+            creative_id,
+        )
+        .execute(self.db_pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(infrastructure::repository::RepoError::ObjDoesNotExists(
+                "Creative".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::IGetCreativeStat for PgCreativeRepository<'p> {
+    async fn get_stat(
+        &self,
+        campaign_id: uuid::Uuid,
+    ) -> infrastructure::repository::RepoResult<Vec<CreativeStatReturningSchema>> {
+        let stats = sqlx::query_as!(
+            CreativeStatReturningSchema,
+            r#"
+            SELECT
+                cr.id as "creative_id",
+                cr.ad_title,
+                cr.deleted_at IS NOT NULL as "deleted!",
+                v.impressions_count as "impressions_count?",
+                c.clicks_count as "clicks_count?",
+                v.spent_impressions as "spent_impressions?",
+                c.spent_clicks as "spent_clicks?"
+            FROM campaign_creatives cr
+            LEFT JOIN
+                (SELECT
+                    creative_id,
+                    COUNT(*)::INTEGER as "impressions_count",
+                    SUM(cost) as "spent_impressions"
+                FROM views_clients
+                WHERE campaign_id = $1
+                GROUP BY creative_id) v
+            ON v.creative_id = cr.id
+            LEFT JOIN
+                (SELECT
+                    creative_id,
+                    COUNT(*)::INTEGER as "clicks_count",
+                    SUM(cost) as "spent_clicks"
+                FROM clicks_clients
+                WHERE campaign_id = $1
+                GROUP BY creative_id) c
+            ON c.creative_id = cr.id
+            WHERE cr.campaign_id = $1
+            ORDER BY cr.created_at, cr.id
+            "#,
+            campaign_id
+        )
+        .fetch_all(self.db_pool)
+        .await?;
+
+        Ok(stats)
+    }
+}
@@ -0,0 +1,181 @@
+use async_trait::async_trait;
+
+use crate::{domain, infrastructure};
+
+#[derive(Debug)]
+pub struct PgExperimentRepository<'p> {
+    db_pool: &'p sqlx::Pool<sqlx::Postgres>,
+}
+
+impl<'p> infrastructure::repository::IRepo<'p> for PgExperimentRepository<'p> {
+    fn new(db_pool: &'p sqlx::Pool<sqlx::Postgres>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub struct ExperimentReturningSchema {
+    pub id: uuid::Uuid,
+    pub name: String,
+    pub arms: serde_json::Value,
+    pub start_date: i32,
+    pub end_date: Option<i32>,
+}
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct ExperimentStatReturningSchema {
+    pub arm: Option<String>,
+    pub impressions_count: Option<i32>,
+    pub clicks_count: Option<i32>,
+    pub spent_impressions: Option<bigdecimal::BigDecimal>,
+    pub spent_clicks: Option<bigdecimal::BigDecimal>,
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::ICreateExperiment for PgExperimentRepository<'p> {
+    async fn create(
+        &self,
+        experiment: domain::schemas::ExperimentCreateRequest,
+        start_date: u32,
+    ) -> infrastructure::repository::RepoResult<ExperimentReturningSchema> {
+        let experiment = sqlx::query_as!(
+            ExperimentReturningSchema,
+            r#"
+            INSERT INTO experiments (name, arms, start_date)
+            VALUES ($1, $2, $3)
+            RETURNING *
+            "#,
+            experiment.name,
+            serde_json::to_value(&experiment.arms).map_err(|_| infrastructure::repository::RepoError::Unknown)?,
+            start_date as i32,
+        )
+        .fetch_one(self.db_pool)
+        .await?;
+
+        Ok(experiment)
+    }
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::IStopExperiment for PgExperimentRepository<'p> {
+    async fn stop(
+        &self,
+        experiment_id: uuid::Uuid,
+        end_date: u32,
+    ) -> infrastructure::repository::RepoResult<ExperimentReturningSchema> {
+        sqlx::query_as!(
+            ExperimentReturningSchema,
+            r#"
+            UPDATE experiments
+            SET end_date = $1
+            WHERE id = $2 AND end_date IS NULL
+            RETURNING *
+            "#,
+            end_date as i32,
+            experiment_id,
+        )
+        .fetch_optional(self.db_pool)
+        .await?
+        .ok_or(infrastructure::repository::RepoError::ObjDoesNotExists(
+            "Running experiment".into(),
+        ))
+    }
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::IGetExperimentList for PgExperimentRepository<'p> {
+    async fn get_list(&self) -> infrastructure::repository::RepoResult<Vec<ExperimentReturningSchema>> {
+        let experiments = sqlx::query_as!(
+            ExperimentReturningSchema,
+            r#"
+            SELECT * FROM experiments
+            ORDER BY start_date DESC
+            "#,
+        )
+        .fetch_all(self.db_pool)
+        .await?;
+
+        Ok(experiments)
+    }
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::IGetActiveExperiment for PgExperimentRepository<'p> {
+    async fn get_active(&self) -> infrastructure::repository::RepoResult<Option<ExperimentReturningSchema>> {
+        let experiment = sqlx::query_as!(
+            ExperimentReturningSchema,
+            r#"
+            SELECT * FROM experiments
+            WHERE end_date IS NULL
+            "#,
+        )
+        .fetch_optional(self.db_pool)
+        .await?;
+
+        Ok(experiment)
+    }
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::IGetExperimentById for PgExperimentRepository<'p> {
+    async fn get_by_id(
+        &self,
+        experiment_id: uuid::Uuid,
+    ) -> infrastructure::repository::RepoResult<ExperimentReturningSchema> {
+        sqlx::query_as!(
+            ExperimentReturningSchema,
+            r#"
+            SELECT * FROM experiments
+            WHERE id = $1
+            "#,
+            experiment_id,
+        )
+        .fetch_optional(self.db_pool)
+        .await?
+        .ok_or(infrastructure::repository::RepoError::ObjDoesNotExists(
+            "Experiment".into(),
+        ))
+    }
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::IGetExperimentStat for PgExperimentRepository<'p> {
+    async fn get_stat(
+        &self,
+        experiment_id: uuid::Uuid,
+    ) -> infrastructure::repository::RepoResult<Vec<ExperimentStatReturningSchema>> {
+        let stats = sqlx::query_as!(
+            ExperimentStatReturningSchema,
+            r#"
+            SELECT
+                COALESCE(v.experiment_arm, c.experiment_arm) as "arm",
+                COALESCE(v.impressions_count, 0) as "impressions_count",
+                COALESCE(c.clicks_count, 0) as "clicks_count",
+                COALESCE(v.spent_impressions, 0) as "spent_impressions",
+                COALESCE(c.spent_clicks, 0) as "spent_clicks"
+            FROM
+                (SELECT
+                    experiment_arm,
+                    COUNT(*)::INTEGER as "impressions_count",
+                    SUM(cost) as "spent_impressions"
+                FROM views_clients
+                WHERE experiment_id = $1
+                GROUP BY experiment_arm) v
+            FULL JOIN
+                (SELECT
+                    experiment_arm,
+                    COUNT(*)::INTEGER as "clicks_count",
+                    SUM(cost) as "spent_clicks"
+                FROM clicks_clients
+                WHERE experiment_id = $1
+                GROUP BY experiment_arm) c
+            ON v.experiment_arm = c.experiment_arm
+            "#,
+            experiment_id
+        )
+        .fetch_all(self.db_pool)
+        .await?;
+
+        Ok(stats)
+    }
+}
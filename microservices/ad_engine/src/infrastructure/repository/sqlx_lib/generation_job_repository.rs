@@ -0,0 +1,271 @@
+use async_trait::async_trait;
+
+use crate::{domain, infrastructure};
+
+#[derive(Debug)]
+pub struct PgGenerationJobRepository<'p> {
+    db_pool: &'p sqlx::Pool<sqlx::Postgres>,
+}
+
+impl<'p> infrastructure::repository::IRepo<'p> for PgGenerationJobRepository<'p> {
+    fn new(db_pool: &'p sqlx::Pool<sqlx::Postgres>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct GenerationJobReturningSchema {
+    pub id: uuid::Uuid,
+    pub advertiser_id: uuid::Uuid,
+    pub campaign_id: uuid::Uuid,
+    pub generate_type: String,
+    pub source_title: String,
+    pub source_text: String,
+    pub status: String,
+    pub ad_title: Option<String>,
+    pub ad_text: Option<String>,
+    pub error: Option<String>,
+    pub prompt_version_id: Option<uuid::Uuid>,
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::ICreateGenerationJob for PgGenerationJobRepository<'p> {
+    async fn create(
+        &self,
+        advertiser_id: uuid::Uuid,
+        campaign_id: uuid::Uuid,
+        generate_type: &str,
+        source_title: &str,
+        source_text: &str,
+    ) -> infrastructure::repository::RepoResult<GenerationJobReturningSchema> {
+        let job = sqlx::query_as!(
+            GenerationJobReturningSchema,
+            r#"
+            INSERT INTO generation_jobs (advertiser_id, campaign_id, generate_type, source_title, source_text)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, advertiser_id, campaign_id, generate_type, source_title, source_text, status, ad_title, ad_text, error, prompt_version_id
+            "#,
+            advertiser_id,
+            campaign_id,
+            generate_type,
+            source_title,
+            source_text,
+        )
+        .fetch_one(self.db_pool)
+        .await?;
+
+        Ok(job)
+    }
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::IGetGenerationJobById for PgGenerationJobRepository<'p> {
+    async fn get_by_id(
+        &self,
+        job_id: uuid::Uuid,
+    ) -> infrastructure::repository::RepoResult<GenerationJobReturningSchema> {
+        sqlx::query_as!(
+            GenerationJobReturningSchema,
+            r#"
+            SELECT id, advertiser_id, campaign_id, generate_type, source_title, source_text, status, ad_title, ad_text, error, prompt_version_id
+            FROM generation_jobs
+            WHERE id = $1
+            "#,
+            job_id,
+        )
+        .fetch_optional(self.db_pool)
+        .await?
+        .ok_or(infrastructure::repository::RepoError::ObjDoesNotExists(
+            "Generation job".into(),
+        ))
+    }
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::IRunGenerationJob for PgGenerationJobRepository<'p> {
+    async fn start(
+        &self,
+        job_id: uuid::Uuid,
+        lease_id: uuid::Uuid,
+        lease: std::time::Duration,
+    ) -> infrastructure::repository::RepoResult<Option<GenerationJobReturningSchema>> {
+        let job = sqlx::query_as!(
+            GenerationJobReturningSchema,
+            r#"
+            UPDATE generation_jobs
+            SET status = 'RUNNING', lease_id = $2, lease_expires_at = NOW() + make_interval(secs => $3), updated_at = NOW()
+            WHERE id = $1 AND status = 'PENDING'
+            RETURNING id, advertiser_id, campaign_id, generate_type, source_title, source_text, status, ad_title, ad_text, error, prompt_version_id
+            "#,
+            job_id,
+            lease_id,
+            lease.as_secs_f64(),
+        )
+        .fetch_optional(self.db_pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    async fn renew_lease(
+        &self,
+        job_id: uuid::Uuid,
+        lease_id: uuid::Uuid,
+        lease: std::time::Duration,
+    ) -> infrastructure::repository::RepoResult<bool> {
+        let renewed = sqlx::query!(
+            r#"
+            UPDATE generation_jobs
+            SET lease_expires_at = NOW() + make_interval(secs => $3)
+            WHERE id = $1 AND status = 'RUNNING' AND lease_id = $2
+            "#,
+            job_id,
+            lease_id,
+            lease.as_secs_f64(),
+        )
+        .execute(self.db_pool)
+        .await?
+        .rows_affected();
+
+        Ok(renewed == 1)
+    }
+
+    async fn complete(
+        &self,
+        job_id: uuid::Uuid,
+        lease_id: uuid::Uuid,
+        ad_title: Option<String>,
+        ad_text: Option<String>,
+        prompt_version_id: Option<uuid::Uuid>,
+    ) -> infrastructure::repository::RepoResult<Option<GenerationJobReturningSchema>> {
+        let job = sqlx::query_as!(
+            GenerationJobReturningSchema,
+            r#"
+            UPDATE generation_jobs
+            SET status = 'DONE', ad_title = $3, ad_text = $4, prompt_version_id = $5, updated_at = NOW()
+            WHERE id = $1 AND status = 'RUNNING' AND lease_id = $2
+            RETURNING id, advertiser_id, campaign_id, generate_type, source_title, source_text, status, ad_title, ad_text, error, prompt_version_id
+            "#,
+            job_id,
+            lease_id,
+            ad_title,
+            ad_text,
+            prompt_version_id,
+        )
+        .fetch_optional(self.db_pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    async fn fail(
+        &self,
+        job_id: uuid::Uuid,
+        lease_id: uuid::Uuid,
+        error: String,
+    ) -> infrastructure::repository::RepoResult<Option<GenerationJobReturningSchema>> {
+        let job = sqlx::query_as!(
+            GenerationJobReturningSchema,
+            r#"
+            UPDATE generation_jobs
+            SET status = 'FAILED', error = $3, updated_at = NOW()
+            WHERE id = $1 AND status = 'RUNNING' AND lease_id = $2
+            RETURNING id, advertiser_id, campaign_id, generate_type, source_title, source_text, status, ad_title, ad_text, error, prompt_version_id
+            "#,
+            job_id,
+            lease_id,
+            error,
+        )
+        .fetch_optional(self.db_pool)
+        .await?;
+
+        Ok(job)
+    }
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::IRejectGenerationJob for PgGenerationJobRepository<'p> {
+    async fn reject(&self, job_id: uuid::Uuid) -> infrastructure::repository::RepoResult<GenerationJobReturningSchema> {
+        sqlx::query_as!(
+            GenerationJobReturningSchema,
+            r#"
+            UPDATE generation_jobs
+            SET status = 'REJECTED', updated_at = NOW()
+            WHERE id = $1 AND status = 'DONE'
+            RETURNING id, advertiser_id, campaign_id, generate_type, source_title, source_text, status, ad_title, ad_text, error, prompt_version_id
+            "#,
+            job_id,
+        )
+        .fetch_optional(self.db_pool)
+        .await?
+        .ok_or(infrastructure::repository::RepoError::ObjDoesNotExists(
+            "Generated job".into(),
+        ))
+    }
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::IAcceptGenerationJob for PgGenerationJobRepository<'p> {
+    async fn accept(&self, job_id: uuid::Uuid) -> infrastructure::repository::RepoResult<GenerationJobReturningSchema> {
+        let mut transaction = self.db_pool.begin().await?;
+
+        let job = sqlx::query_as!(
+            GenerationJobReturningSchema,
+            r#"
+            UPDATE generation_jobs
+            SET status = 'ACCEPTED', updated_at = NOW()
+            WHERE id = $1 AND status = 'DONE'
+            RETURNING id, advertiser_id, campaign_id, generate_type, source_title, source_text, status, ad_title, ad_text, error, prompt_version_id
+            "#,
+            job_id,
+        )
+        .fetch_optional(&mut *transaction)
+        .await?
+        .ok_or(infrastructure::repository::RepoError::ObjDoesNotExists(
+            "Generated job".into(),
+        ))?;
+
+        sqlx::query!(
+            r#"
+            UPDATE campaigns
+            SET ad_title = COALESCE($2, ad_title),
+                ad_text = COALESCE($3, ad_text),
+                prompt_version_id = $4
+            WHERE id = $1
+            "#,
+            job.campaign_id,
+            job.ad_title,
+            job.ad_text,
+            job.prompt_version_id,
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(job)
+    }
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::IRequeueGenerationJobs for PgGenerationJobRepository<'p> {
+    async fn requeue_unfinished(&self) -> infrastructure::repository::RepoResult<Vec<uuid::Uuid>> {
+        let jobs = sqlx::query_scalar!(
+            r#"
+            WITH expired AS (
+                UPDATE generation_jobs
+                SET status = 'PENDING', lease_id = NULL, lease_expires_at = NULL, updated_at = NOW()
+                WHERE status = 'RUNNING' AND lease_expires_at < NOW()
+                RETURNING id
+            )
+            SELECT id AS "id!" FROM expired
+            UNION ALL
+            SELECT id AS "id!" FROM generation_jobs WHERE status = 'PENDING'
+            "#,
+        )
+        .fetch_all(self.db_pool)
+        .await?;
+
+        Ok(jobs)
+    }
+}
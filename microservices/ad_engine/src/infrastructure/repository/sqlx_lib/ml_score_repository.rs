@@ -19,7 +19,7 @@ impl<'p> domain::services::repository::IGetMlScores for PgScoreRepository<'p> {
         &self,
         client_id: uuid::Uuid,
         advertisers_id: Vec<uuid::Uuid>,
-    ) -> infrastructure::repository::RepoResult<Vec<f64>> {
+    ) -> infrastructure::repository::RepoResult<Vec<Option<f64>>> {
         if advertisers_id.is_empty() {
             return Ok(Vec::new());
         }
@@ -40,15 +40,204 @@ impl<'p> domain::services::repository::IGetMlScores for PgScoreRepository<'p> {
             .map(|record| (record.advertiser_id, record.score))
             .collect();
 
-        let result: Vec<f64> = advertisers_id
+        let result: Vec<Option<f64>> = advertisers_id
             .into_iter()
-            .map(|advertiser_id| scores_map.get(&advertiser_id).copied().unwrap_or(0.0))
+            .map(|advertiser_id| scores_map.get(&advertiser_id).copied())
             .collect();
 
         Ok(result)
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentScoreReturningSchema {
+    pub advertiser_id: uuid::Uuid,
+    pub age_bucket: i32,
+    pub gender: String,
+    pub region: String,
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdvertiserScoreReturningSchema {
+    pub advertiser_id: uuid::Uuid,
+    pub score: Option<f64>,
+    pub ctr: Option<f64>,
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::IGetFallbackScoreStatistics for PgScoreRepository<'p> {
+    async fn get_segment_scores(&self) -> infrastructure::repository::RepoResult<Vec<SegmentScoreReturningSchema>> {
+        let scores = sqlx::query_as!(
+            SegmentScoreReturningSchema,
+            r#"
+            SELECT
+                s.advertiser_id AS "advertiser_id!",
+                c.age / 10 AS "age_bucket!",
+                c.gender AS "gender!",
+                LOWER(TRIM(SPLIT_PART(c.location, ',', 1))) AS "region!",
+                AVG(s.score) AS "score!"
+            FROM ml_scores s
+            JOIN clients c ON c.id = s.client_id
+            GROUP BY 1, 2, 3, 4
+            "#
+        )
+        .fetch_all(self.db_pool)
+        .await?;
+
+        Ok(scores)
+    }
+
+    async fn get_advertiser_scores(
+        &self,
+    ) -> infrastructure::repository::RepoResult<Vec<AdvertiserScoreReturningSchema>> {
+        let scores = sqlx::query_as!(
+            AdvertiserScoreReturningSchema,
+            r#"
+            WITH scores AS (
+                SELECT advertiser_id, AVG(score) AS score FROM ml_scores GROUP BY advertiser_id
+            ), views AS (
+                SELECT ca.advertiser_id, COUNT(*) AS views
+                FROM views_clients v
+                JOIN campaigns ca ON ca.id = v.campaign_id
+                GROUP BY ca.advertiser_id
+            ), clicks AS (
+                SELECT ca.advertiser_id, COUNT(*) AS clicks
+                FROM clicks_clients c
+                JOIN campaigns ca ON ca.id = c.campaign_id
+                GROUP BY ca.advertiser_id
+            )
+            SELECT
+                COALESCE(scores.advertiser_id, views.advertiser_id) AS "advertiser_id!",
+                scores.score,
+                COALESCE(clicks.clicks, 0)::FLOAT8 / NULLIF(views.views, 0) AS ctr
+            FROM scores
+            FULL JOIN views ON views.advertiser_id = scores.advertiser_id
+            LEFT JOIN clicks ON clicks.advertiser_id = views.advertiser_id
+            "#
+        )
+        .fetch_all(self.db_pool)
+        .await?;
+
+        Ok(scores)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CampaignCtrReturningSchema {
+    pub campaign_id: uuid::Uuid,
+    pub views: i64,
+    pub clicks: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentCtrReturningSchema {
+    pub advertiser_id: uuid::Uuid,
+    pub age_bucket: i32,
+    pub gender: String,
+    pub region: String,
+    pub views: i64,
+    pub clicks: i64,
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::IGetCtrStatistics for PgScoreRepository<'p> {
+    async fn get_ctr_watermark(&self) -> infrastructure::repository::RepoResult<i64> {
+        let watermark = sqlx::query_scalar!(
+            r#"
+            SELECT pg_snapshot_xmin(pg_current_snapshot())::TEXT::BIGINT AS "watermark!"
+            "#
+        )
+        .fetch_one(self.db_pool)
+        .await?;
+
+        Ok(watermark)
+    }
+
+    async fn get_campaign_ctr_statistics(
+        &self,
+        since: Option<i64>,
+        until: i64,
+    ) -> infrastructure::repository::RepoResult<Vec<CampaignCtrReturningSchema>> {
+        let statistics = sqlx::query_as!(
+            CampaignCtrReturningSchema,
+            r#"
+            WITH views AS (
+                SELECT campaign_id, COUNT(*) AS views
+                FROM views_clients
+                WHERE ($1::BIGINT IS NULL OR recorded_xid >= $1::BIGINT::TEXT::XID8)
+                    AND recorded_xid < $2::BIGINT::TEXT::XID8
+                GROUP BY campaign_id
+            ), clicks AS (
+                SELECT campaign_id, COUNT(*) AS clicks
+                FROM clicks_clients
+                WHERE ($1::BIGINT IS NULL OR recorded_xid >= $1::BIGINT::TEXT::XID8)
+                    AND recorded_xid < $2::BIGINT::TEXT::XID8
+                GROUP BY campaign_id
+            )
+            SELECT
+                campaign_id AS "campaign_id!",
+                COALESCE(views.views, 0) AS "views!",
+                COALESCE(clicks.clicks, 0) AS "clicks!"
+            FROM views
+            FULL JOIN clicks USING (campaign_id)
+            "#,
+            since,
+            until
+        )
+        .fetch_all(self.db_pool)
+        .await?;
+
+        Ok(statistics)
+    }
+
+    async fn get_segment_ctr_statistics(
+        &self,
+        since: Option<i64>,
+        until: i64,
+    ) -> infrastructure::repository::RepoResult<Vec<SegmentCtrReturningSchema>> {
+        let statistics = sqlx::query_as!(
+            SegmentCtrReturningSchema,
+            r#"
+            WITH views AS (
+                SELECT ca.advertiser_id, cl.age / 10 AS age_bucket, cl.gender,
+                    LOWER(TRIM(SPLIT_PART(cl.location, ',', 1))) AS region, COUNT(*) AS views
+                FROM views_clients v
+                JOIN campaigns ca ON ca.id = v.campaign_id
+                JOIN clients cl ON cl.id = v.client_id
+                WHERE ($1::BIGINT IS NULL OR v.recorded_xid >= $1::BIGINT::TEXT::XID8)
+                    AND v.recorded_xid < $2::BIGINT::TEXT::XID8
+                GROUP BY 1, 2, 3, 4
+            ), clicks AS (
+                SELECT ca.advertiser_id, cl.age / 10 AS age_bucket, cl.gender,
+                    LOWER(TRIM(SPLIT_PART(cl.location, ',', 1))) AS region, COUNT(*) AS clicks
+                FROM clicks_clients c
+                JOIN campaigns ca ON ca.id = c.campaign_id
+                JOIN clients cl ON cl.id = c.client_id
+                WHERE ($1::BIGINT IS NULL OR c.recorded_xid >= $1::BIGINT::TEXT::XID8)
+                    AND c.recorded_xid < $2::BIGINT::TEXT::XID8
+                GROUP BY 1, 2, 3, 4
+            )
+            SELECT
+                advertiser_id AS "advertiser_id!",
+                age_bucket AS "age_bucket!",
+                gender AS "gender!",
+                region AS "region!",
+                COALESCE(views.views, 0) AS "views!",
+                COALESCE(clicks.clicks, 0) AS "clicks!"
+            FROM views
+            FULL JOIN clicks USING (advertiser_id, age_bucket, gender, region)
+            "#,
+            since,
+            until
+        )
+        .fetch_all(self.db_pool)
+        .await?;
+
+        Ok(statistics)
+    }
+}
+
 #[async_trait]
 impl<'p> domain::services::repository::ISetMlScore for PgScoreRepository<'p> {
     async fn set_ml_score(
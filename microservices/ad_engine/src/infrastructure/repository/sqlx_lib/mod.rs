@@ -1,18 +1,34 @@
 mod advertiser_repository;
+mod brand_voice_repository;
 mod campaign_image_repository;
 mod campaigns_repository;
 mod client_repository;
+mod creative_repository;
+mod experiment_repository;
+mod generation_job_repository;
 mod ml_score_repository;
 mod moderate_list_repository;
 mod obscene_words_repository;
+mod prompt_template_repository;
 
 pub use advertiser_repository::{AdvertiserReturningSchema, PgAdvertiserRepository};
+pub use brand_voice_repository::{BrandVoiceReturningSchema, PgBrandVoiceRepository};
 pub use campaign_image_repository::PgCampaignImageRepository;
 pub use campaigns_repository::{CampaignReturningSchema, PgCampaignRepository, StatDailyReturningSchema};
 pub use client_repository::{ClientReturningSchema, PgClientRepository};
-pub use ml_score_repository::PgScoreRepository;
+pub use creative_repository::{CreativeReturningSchema, CreativeStatReturningSchema, PgCreativeRepository};
+pub use experiment_repository::{ExperimentReturningSchema, ExperimentStatReturningSchema, PgExperimentRepository};
+pub use generation_job_repository::{GenerationJobReturningSchema, PgGenerationJobRepository};
+pub use ml_score_repository::{
+    AdvertiserScoreReturningSchema, CampaignCtrReturningSchema, PgScoreRepository, SegmentCtrReturningSchema,
+    SegmentScoreReturningSchema,
+};
 pub use moderate_list_repository::PgModerateListRepository;
 pub use obscene_words_repository::PgObsceneWordRepository;
+pub use prompt_template_repository::{
+    PgPromptTemplateRepository, PromptTemplateReturningSchema, PromptTemplateVersionReturningSchema,
+    PromptVersionStatReturningSchema,
+};
 
 impl From<sqlx::Error> for super::RepoError {
     fn from(err: sqlx::Error) -> Self {
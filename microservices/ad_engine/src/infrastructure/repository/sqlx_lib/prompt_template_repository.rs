@@ -0,0 +1,264 @@
+use async_trait::async_trait;
+
+use crate::{domain, infrastructure};
+
+#[derive(Debug)]
+pub struct PgPromptTemplateRepository<'p> {
+    db_pool: &'p sqlx::Pool<sqlx::Postgres>,
+}
+
+impl<'p> infrastructure::repository::IRepo<'p> for PgPromptTemplateRepository<'p> {
+    fn new(db_pool: &'p sqlx::Pool<sqlx::Postgres>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct PromptTemplateReturningSchema {
+    pub id: uuid::Uuid,
+    pub name: String,
+    pub is_default: bool,
+    pub version_id: uuid::Uuid,
+    pub version: i32,
+    pub title_prompt: String,
+    pub text_prompt: String,
+}
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct PromptTemplateVersionReturningSchema {
+    pub id: uuid::Uuid,
+    pub template_id: uuid::Uuid,
+    pub version: i32,
+    pub title_prompt: String,
+    pub text_prompt: String,
+}
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct PromptVersionStatReturningSchema {
+    pub version_id: uuid::Uuid,
+    pub version: i32,
+    pub creatives_count: Option<i32>,
+    pub campaigns_count: Option<i32>,
+    pub impressions_count: Option<i32>,
+    pub clicks_count: Option<i32>,
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::ICreatePromptTemplate for PgPromptTemplateRepository<'p> {
+    async fn create(
+        &self,
+        template: domain::schemas::PromptTemplateCreateRequest,
+    ) -> infrastructure::repository::RepoResult<PromptTemplateReturningSchema> {
+        let mut transaction = self.db_pool.begin().await?;
+
+        let is_default = template.is_default.unwrap_or(false);
+        if is_default {
+            sqlx::query!(
+                r#"
+                UPDATE prompt_templates
+                SET is_default = FALSE
+                WHERE is_default
+                "#
+            )
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        let created = sqlx::query!(
+            r#"
+            INSERT INTO prompt_templates (name, is_default)
+            VALUES ($1, $2)
+            RETURNING id, name, is_default
+            "#,
+            template.name,
+            is_default,
+        )
+        .fetch_one(&mut *transaction)
+        .await?;
+
+        let version = sqlx::query_as!(
+            PromptTemplateVersionReturningSchema,
+            r#"
+            INSERT INTO prompt_template_versions (template_id, version, title_prompt, text_prompt)
+            VALUES ($1, 1, $2, $3)
+            RETURNING id, template_id, version, title_prompt, text_prompt
+            "#,
+            created.id,
+            template.title_prompt,
+            template.text_prompt,
+        )
+        .fetch_one(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(PromptTemplateReturningSchema {
+            id: created.id,
+            name: created.name,
+            is_default: created.is_default,
+            version_id: version.id,
+            version: version.version,
+            title_prompt: version.title_prompt,
+            text_prompt: version.text_prompt,
+        })
+    }
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::IGetPromptTemplateList for PgPromptTemplateRepository<'p> {
+    async fn get_list(&self) -> infrastructure::repository::RepoResult<Vec<PromptTemplateReturningSchema>> {
+        let templates = sqlx::query_as!(
+            PromptTemplateReturningSchema,
+            r#"
+            SELECT
+                t.id,
+                t.name,
+                t.is_default,
+                v.id as "version_id!",
+                v.version as "version!",
+                v.title_prompt as "title_prompt!",
+                v.text_prompt as "text_prompt!"
+            FROM prompt_templates t
+            JOIN LATERAL
+                (SELECT id, version, title_prompt, text_prompt
+                FROM prompt_template_versions
+                WHERE template_id = t.id
+                ORDER BY version DESC
+                LIMIT 1) v
+            ON TRUE
+            ORDER BY t.created_at, t.id
+            "#
+        )
+        .fetch_all(self.db_pool)
+        .await?;
+
+        Ok(templates)
+    }
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::ICreatePromptTemplateVersion for PgPromptTemplateRepository<'p> {
+    async fn create_version(
+        &self,
+        template_id: uuid::Uuid,
+        version: domain::schemas::PromptTemplateVersionCreateRequest,
+    ) -> infrastructure::repository::RepoResult<PromptTemplateVersionReturningSchema> {
+        sqlx::query_as!(
+            PromptTemplateVersionReturningSchema,
+            r#"
+            INSERT INTO prompt_template_versions (template_id, version, title_prompt, text_prompt)
+            SELECT
+                t.id,
+                COALESCE((SELECT MAX(version) FROM prompt_template_versions WHERE template_id = t.id), 0) + 1,
+                $2,
+                $3
+            FROM prompt_templates t
+            WHERE t.id = $1
+            RETURNING id, template_id, version, title_prompt, text_prompt
+            "#,
+            template_id,
+            version.title_prompt,
+            version.text_prompt,
+        )
+        .fetch_optional(self.db_pool)
+        .await?
+        .ok_or(infrastructure::repository::RepoError::ObjDoesNotExists(
+            "Prompt template".into(),
+        ))
+    }
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::IGetPromptTemplateVersions for PgPromptTemplateRepository<'p> {
+    async fn get_versions(
+        &self,
+        template_id: uuid::Uuid,
+    ) -> infrastructure::repository::RepoResult<Vec<PromptTemplateVersionReturningSchema>> {
+        let versions = sqlx::query_as!(
+            PromptTemplateVersionReturningSchema,
+            r#"
+            SELECT id, template_id, version, title_prompt, text_prompt
+            FROM prompt_template_versions
+            WHERE template_id = $1
+            ORDER BY version DESC
+            "#,
+            template_id,
+        )
+        .fetch_all(self.db_pool)
+        .await?;
+
+        // Every template is created with its first version
+        if versions.is_empty() {
+            return Err(infrastructure::repository::RepoError::ObjDoesNotExists(
+                "Prompt template".into(),
+            ));
+        }
+
+        Ok(versions)
+    }
+
+    async fn get_latest_version(
+        &self,
+        template_id: Option<uuid::Uuid>,
+    ) -> infrastructure::repository::RepoResult<Option<PromptTemplateVersionReturningSchema>> {
+        let version = sqlx::query_as!(
+            PromptTemplateVersionReturningSchema,
+            r#"
+            SELECT v.id, v.template_id, v.version, v.title_prompt, v.text_prompt
+            FROM prompt_template_versions v
+            JOIN prompt_templates t ON t.id = v.template_id
+            WHERE t.id = $1 OR ($1::UUID IS NULL AND t.is_default)
+            ORDER BY v.version DESC
+            LIMIT 1
+            "#,
+            template_id,
+        )
+        .fetch_optional(self.db_pool)
+        .await?;
+
+        Ok(version)
+    }
+}
+
+#[async_trait]
+impl<'p> domain::services::repository::IGetPromptVersionStat for PgPromptTemplateRepository<'p> {
+    async fn get_version_stat(
+        &self,
+        template_id: uuid::Uuid,
+    ) -> infrastructure::repository::RepoResult<Vec<PromptVersionStatReturningSchema>> {
+        let stats = sqlx::query_as!(
+            PromptVersionStatReturningSchema,
+            r#"
+            SELECT
+                v.id as "version_id",
+                v.version,
+                (SELECT COUNT(*)::INTEGER
+                FROM campaign_creatives cr
+                WHERE cr.prompt_version_id = v.id) as "creatives_count?",
+                (SELECT COUNT(*)::INTEGER
+                FROM campaigns ca
+                WHERE ca.prompt_version_id = v.id) as "campaigns_count?",
+                (SELECT COUNT(*)::INTEGER
+                FROM views_clients vc
+                WHERE vc.prompt_version_id = v.id) as "impressions_count?",
+                (SELECT COUNT(*)::INTEGER
+                FROM clicks_clients cc
+                WHERE cc.prompt_version_id = v.id) as "clicks_count?"
+            FROM prompt_template_versions v
+            WHERE v.template_id = $1
+            ORDER BY v.version
+            "#,
+            template_id,
+        )
+        .fetch_all(self.db_pool)
+        .await?;
+
+        if stats.is_empty() {
+            return Err(infrastructure::repository::RepoError::ObjDoesNotExists(
+                "Prompt template".into(),
+            ));
+        }
+
+        Ok(stats)
+    }
+}
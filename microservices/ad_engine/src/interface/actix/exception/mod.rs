@@ -37,7 +37,7 @@ impl actix_web::error::ResponseError for domain::services::ServiceError {
             domain::services::ServiceError::Validation(_) => actix_web::http::StatusCode::BAD_REQUEST,
             domain::services::ServiceError::Unknown => actix_web::http::StatusCode::IM_A_TEAPOT,
             domain::services::ServiceError::Cash(_) => actix_web::http::StatusCode::IM_A_TEAPOT,
-            domain::services::ServiceError::GptNotResponse(_) => actix_web::http::StatusCode::SERVICE_UNAVAILABLE,
+            domain::services::ServiceError::GptNotResponse(llm) => llm.into(),
             domain::services::ServiceError::Censorship(_) => actix_web::http::StatusCode::NOT_ACCEPTABLE,
             domain::services::ServiceError::PayloadError(_) => actix_web::http::StatusCode::BAD_REQUEST,
         }
@@ -65,3 +65,19 @@ impl From<&infrastructure::repository::RepoError> for actix_web::http::StatusCod
         }
     }
 }
+
+/// Conversion implementation from LLM errors to HTTP status codes
+///
+/// A provider that didn't respond in time gives a gateway timeout, any other
+/// failure of the provider makes the text generation unavailable.
+impl From<&infrastructure::gpt::LlmError> for actix_web::http::StatusCode {
+    fn from(value: &infrastructure::gpt::LlmError) -> Self {
+        match value {
+            infrastructure::gpt::LlmError::Timeout(_) => actix_web::http::StatusCode::GATEWAY_TIMEOUT,
+            infrastructure::gpt::LlmError::Status { .. }
+            | infrastructure::gpt::LlmError::Connection(_)
+            | infrastructure::gpt::LlmError::InvalidResponse(_)
+            | infrastructure::gpt::LlmError::CircuitOpen => actix_web::http::StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+}
@@ -26,7 +26,7 @@ use crate::{domain, infrastructure, interface};
 pub struct HttpServer {
     config: infrastructure::configurate::HttpServerConfig,
     cors_config: infrastructure::configurate::CorsConfig,
-    app_state: domain::configurate::AppState,
+    app_state: std::sync::Arc<domain::configurate::SharedAppState>,
     connection_pool: infrastructure::database_connection::sqlx_lib::SqlxPool,
     redis_pool: infrastructure::database_connection::redis::RedisPool,
 }
@@ -37,12 +37,12 @@ impl HttpServer {
     /// # Arguments
     /// * `config` - Server configuration
     /// * `cors_config` - CORS configuration
-    /// * `app_state` - Application state
+    /// * `app_state` - Application state, replaced on config reload
     /// * `fn_connection_pool` - Database connection pool
     pub async fn new(
         config: infrastructure::configurate::HttpServerConfig,
         cors_config: infrastructure::configurate::CorsConfig,
-        app_state: domain::configurate::AppState,
+        app_state: std::sync::Arc<domain::configurate::SharedAppState>,
         connection_pool: infrastructure::database_connection::sqlx_lib::SqlxPool,
         redis_pool: infrastructure::database_connection::redis::RedisPool,
     ) -> Self {
@@ -99,6 +99,9 @@ impl HttpServer {
             .service(super::routers::client_scope("/clients"))
             .service(super::routers::advertisers_scope("/advertisers"))
             .service(super::routers::moderate_scope("/moderate"))
+            .service(super::routers::experiments_scope("/experiments"))
+            .service(super::routers::jobs_scope("/jobs"))
+            .service(super::routers::prompt_templates_scope("/prompt_templates"))
     }
 
     /// Configures Swagger documentation UI
@@ -117,7 +120,7 @@ impl HttpServer {
             .allow_any_origin()
             .allowed_methods(self.cors_config.allowed_methods.iter().map(|str_method| {
                 actix_web::http::Method::from_bytes(str_method.as_bytes())
-                    .expect("Config contain not verifying http method (cors.allowed_methods)")
+                    .expect("Config contain not verifying http method, cors.allowed_methods is checked on parsing")
             }))
             .allowed_headers(self.cors_config.allowed_headers.clone())
             .max_age(self.cors_config.max_age)
@@ -138,7 +141,7 @@ impl interface::IServer for HttpServer {
 
         let config = &server.clone().config;
 
-        let app_state = actix_web::web::Data::new(server.app_state.clone());
+        let app_state = actix_web::web::Data::from(server.app_state.clone());
 
         let database_pool_data = server.get_database_pool_data().await;
         let redis_pool_data = server.get_redis_pool_data().await;
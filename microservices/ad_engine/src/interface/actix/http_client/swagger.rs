@@ -9,24 +9,47 @@
         super::super::routers::ml_score::ml_score_handler,
         super::super::routers::ads::ads_handler,
         super::super::routers::ads::ads_click_handler,
+        super::super::routers::ads::ads_explain_handler,
+        super::super::routers::ads::ads_config_get_handler,
+        super::super::routers::ads::ads_config_set_handler,
         super::super::routers::stats::stat_campaign_daily_handler,
         super::super::routers::stats::stat_campaign_handler,
         super::super::routers::stats::stat_advertisers_daily_handler,
         super::super::routers::stats::stat_advertisers_handler,
+        super::super::routers::stats::stat_experiment_handler,
+        super::super::routers::stats::stat_campaign_creatives_handler,
+        super::super::routers::stats::stat_prompt_template_handler,
         super::super::routers::client::client_bulk_handler,
         super::super::routers::client::client_by_id_handler,
         super::super::routers::advertisers::advertiser_bulk_handler,
         super::super::routers::advertisers::advertiser_by_id_handler,
+        super::super::routers::advertisers::advertiser_brand_voice_get_handler,
+        super::super::routers::advertisers::advertiser_brand_voice_update_handler,
         super::super::routers::moderate::moderate_config_handler,
         super::super::routers::moderate::moderate_add_list_handler,
         super::super::routers::moderate::moderate_get_list_handler,
         super::super::routers::moderate::moderate_delete_list_handler,
+        super::super::routers::experiments::experiments_create_handler,
+        super::super::routers::experiments::experiments_get_list_handler,
+        super::super::routers::experiments::experiments_stop_handler,
+        super::super::routers::prompt_templates::prompt_templates_create_handler,
+        super::super::routers::prompt_templates::prompt_templates_get_list_handler,
+        super::super::routers::prompt_templates::prompt_templates_create_version_handler,
+        super::super::routers::prompt_templates::prompt_templates_get_versions_handler,
         super::super::routers::advertisers::campaigns::campaigns_create_handler,
         super::super::routers::advertisers::campaigns::campaigns_generate_text_handler,
+        super::super::routers::advertisers::campaigns::campaigns_generate_text_job_handler,
+        super::super::routers::jobs::jobs_get_handler,
+        super::super::routers::jobs::jobs_accept_handler,
+        super::super::routers::jobs::jobs_reject_handler,
         super::super::routers::advertisers::campaigns::campaigns_update_handler,
         super::super::routers::advertisers::campaigns::campaigns_delete_handler,
         super::super::routers::advertisers::campaigns::campaigns_get_by_id_handler,
         super::super::routers::advertisers::campaigns::campaigns_get_list_handler,
+        super::super::routers::advertisers::campaigns::creatives::creatives_create_handler,
+        super::super::routers::advertisers::campaigns::creatives::creatives_generate_handler,
+        super::super::routers::advertisers::campaigns::creatives::creatives_get_list_handler,
+        super::super::routers::advertisers::campaigns::creatives::creatives_delete_handler,
         super::super::routers::advertisers::campaigns::images::upload_image_campaign_handler,
         super::super::routers::advertisers::campaigns::images::get_campaign_image_handler,
         super::super::routers::advertisers::campaigns::images::get_campaign_name_images_handler,
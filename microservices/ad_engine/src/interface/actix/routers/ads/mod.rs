@@ -3,12 +3,16 @@ use crate::{domain, infrastructure, interface};
 pub fn ads_scope(path: &str) -> actix_web::Scope {
     actix_web::web::scope(path)
         .service(ads_handler)
+        .service(ads_explain_handler)
+        .service(ads_config_get_handler)
+        .service(ads_config_set_handler)
         .service(ads_click_handler)
 }
 
 #[derive(serde::Deserialize, Debug)]
 struct AdsQuery {
     client_id: uuid::Uuid,
+    limit: Option<u32>,
 }
 
 #[utoipa::path(
@@ -17,9 +21,10 @@ struct AdsQuery {
     tag = "Ads", 
     params(
         ("client_id" = uuid::Uuid, Query, description = "Id client for getting ads", example = "3fa85f64-5717-4562-b3fc-2c963f66afa6"),
+        ("limit" = Option<u32>, Query, description = "Number of ads for the feed. Without it the single best ad is returned", example = 3),
     ),
     responses(
-        (status = 200, description = "List campaigns", body = domain::schemas::AdSchema),
+        (status = 200, description = "Best ad, or a ranked list of ads when `limit` is set", body = domain::schemas::AdsResponse),
         (status = 400, description = "Bad request", body = interface::actix::exception::ExceptionResponse),
         (status = 404, description = "There is no suitable advertisement", body = interface::actix::exception::ExceptionResponse),
         (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse)
@@ -38,15 +43,61 @@ pub async fn ads_handler(
     ads_query: actix_web::web::Query<AdsQuery>,
     db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
     redis_pool: actix_web::web::Data<infrastructure::database_connection::redis::RedisPool>,
-    app_state: actix_web::web::Data<domain::configurate::AppState>,
+    app_state: actix_web::web::Data<domain::configurate::SharedAppState>,
 ) -> interface::actix::ActixResult<actix_web::HttpResponse> {
     let pagination = ads_query.into_inner();
 
-    let ads = domain::usecase::AdsGetUsecase::new(db_pool.get_ref(), redis_pool.get_ref(), app_state.get_ref())
-        .execute(pagination.client_id)
+    let mut ads = domain::usecase::AdsGetUsecase::new(db_pool.get_ref(), redis_pool.get_ref(), &app_state.load())
+        .execute(pagination.client_id, pagination.limit.unwrap_or(1))
         .await?;
 
-    Ok(actix_web::HttpResponse::Ok().json(ads))
+    let response = match pagination.limit {
+        Some(_) => domain::schemas::AdsResponse::Ranked(ads),
+        None => domain::schemas::AdsResponse::Single(ads.remove(0).into()),
+    };
+
+    Ok(actix_web::HttpResponse::Ok().json(response))
+}
+
+#[derive(serde::Deserialize, Debug)]
+struct AdsExplainQuery {
+    client_id: uuid::Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/ads/explain",
+    tag = "Ads",
+    params(
+        ("client_id" = uuid::Uuid, Query, description = "Id client to explain the ads decision for", example = "3fa85f64-5717-4562-b3fc-2c963f66afa6"),
+    ),
+    responses(
+        (status = 200, description = "Decision about every active campaign, no impression is recorded", body = domain::schemas::AdsExplainResponse),
+        (status = 400, description = "Bad request", body = interface::actix::exception::ExceptionResponse),
+        (status = 404, description = "Not found this client", body = interface::actix::exception::ExceptionResponse),
+        (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse)
+    )
+)]
+#[actix_web::get("/explain")]
+#[tracing::instrument(
+    name = "ads_explain_handler",
+    skip(db_pool, redis_pool, app_state),
+    fields(
+        client_id = %explain_query.client_id,
+        request_id = %uuid::Uuid::new_v4()
+    )
+)]
+pub async fn ads_explain_handler(
+    explain_query: actix_web::web::Query<AdsExplainQuery>,
+    db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+    redis_pool: actix_web::web::Data<infrastructure::database_connection::redis::RedisPool>,
+    app_state: actix_web::web::Data<domain::configurate::SharedAppState>,
+) -> interface::actix::ActixResult<actix_web::HttpResponse> {
+    let response = domain::usecase::AdsExplainUsecase::new(db_pool.get_ref(), redis_pool.get_ref(), &app_state.load())
+        .execute(explain_query.client_id)
+        .await?;
+
+    Ok(actix_web::HttpResponse::Ok().json(response))
 }
 
 #[utoipa::path(
@@ -83,3 +134,50 @@ pub async fn ads_click_handler(
 
     Ok(actix_web::HttpResponse::NoContent().into())
 }
+
+#[utoipa::path(
+    get,
+    path = "/ads/config",
+    tag = "Ads",
+    responses(
+        (status = 200, description = "Current ranking weights", body = domain::schemas::AdsConfigSchema),
+        (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse)
+    )
+)]
+#[actix_web::get("/config")]
+#[tracing::instrument(name = "ads_config_get_handler", skip(redis_pool, app_state))]
+pub async fn ads_config_get_handler(
+    redis_pool: actix_web::web::Data<infrastructure::database_connection::redis::RedisPool>,
+    app_state: actix_web::web::Data<domain::configurate::SharedAppState>,
+) -> interface::actix::ActixResult<actix_web::HttpResponse> {
+    let response = domain::usecase::AdsConfigUsecase::new(redis_pool.get_ref(), &app_state.load())
+        .get()
+        .await?;
+
+    Ok(actix_web::HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    put,
+    path = "/ads/config",
+    tag = "Ads",
+    request_body = domain::schemas::AdsConfigSchema,
+    responses(
+        (status = 200, description = "Ranking weights are changed", body = domain::schemas::AdsConfigSchema),
+        (status = 400, description = "Bad weights", body = interface::actix::exception::ExceptionResponse),
+        (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse)
+    )
+)]
+#[actix_web::put("/config")]
+#[tracing::instrument(name = "ads_config_set_handler", skip(redis_pool, app_state))]
+pub async fn ads_config_set_handler(
+    ads_config_request: actix_web::web::Json<domain::schemas::AdsConfigSchema>,
+    redis_pool: actix_web::web::Data<infrastructure::database_connection::redis::RedisPool>,
+    app_state: actix_web::web::Data<domain::configurate::SharedAppState>,
+) -> interface::actix::ActixResult<actix_web::HttpResponse> {
+    let response = domain::usecase::AdsConfigUsecase::new(redis_pool.get_ref(), &app_state.load())
+        .set(ads_config_request.into_inner())
+        .await?;
+
+    Ok(actix_web::HttpResponse::Ok().json(response))
+}
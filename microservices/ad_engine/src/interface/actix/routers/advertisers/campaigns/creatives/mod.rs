@@ -0,0 +1,120 @@
+use crate::{domain, infrastructure, interface};
+
+pub fn creatives_scope(path: &str) -> actix_web::Scope {
+    actix_web::web::scope(path)
+        .service(creatives_create_handler)
+        .service(creatives_generate_handler)
+        .service(creatives_get_list_handler)
+        .service(creatives_delete_handler)
+}
+
+#[utoipa::path(
+    post,
+    path = "/advertisers/{advertiser_id}/campaigns/{campaign_id}/creatives",
+    tag = "Creatives",
+    request_body = domain::schemas::CreativeCreateRequest,
+    responses(
+        (status = 201, description = "Created creative", body = domain::schemas::CreativeSchema),
+        (status = 400, description = "Bad request", body = interface::actix::exception::ExceptionResponse),
+        (status = 404, description = "Campaign not found", body = interface::actix::exception::ExceptionResponse),
+        (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse)
+    )
+)]
+#[actix_web::post("")]
+#[tracing::instrument(name = "creatives_create_handler", skip(db_pool, redis_pool, app_state))]
+pub async fn creatives_create_handler(
+    creative_request: actix_web::web::Json<domain::schemas::CreativeCreateRequest>,
+    path_param: actix_web::web::Path<(uuid::Uuid, uuid::Uuid)>,
+    db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+    redis_pool: actix_web::web::Data<infrastructure::database_connection::redis::RedisPool>,
+    app_state: actix_web::web::Data<domain::configurate::SharedAppState>,
+) -> interface::actix::ActixResult<actix_web::HttpResponse> {
+    let (advertiser_id, campaign_id) = path_param.into_inner();
+    let creative =
+        domain::usecase::CreativesCreateUsecase::new(db_pool.get_ref(), redis_pool.get_ref(), &app_state.load())
+            .create(creative_request.into_inner(), advertiser_id, campaign_id)
+            .await?;
+
+    Ok(actix_web::HttpResponse::Created().json(creative))
+}
+
+#[utoipa::path(
+    post,
+    path = "/advertisers/{advertiser_id}/campaigns/{campaign_id}/creatives/generate",
+    tag = "Creatives",
+    request_body = domain::schemas::CreativesGenerateRequest,
+    responses(
+        (status = 201, description = "Generated creatives", body = Vec<domain::schemas::CreativeSchema>),
+        (status = 400, description = "Bad request", body = interface::actix::exception::ExceptionResponse),
+        (status = 404, description = "Campaign not found", body = interface::actix::exception::ExceptionResponse),
+        (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse),
+        (status = 503, description = "LLM not response", body = interface::actix::exception::ExceptionResponse),
+        (status = 504, description = "LLM timeout", body = interface::actix::exception::ExceptionResponse),
+    )
+)]
+#[actix_web::post("/generate")]
+#[tracing::instrument(name = "creatives_generate_handler", skip(db_pool, redis_pool, app_state))]
+pub async fn creatives_generate_handler(
+    generate_request: actix_web::web::Json<domain::schemas::CreativesGenerateRequest>,
+    path_param: actix_web::web::Path<(uuid::Uuid, uuid::Uuid)>,
+    db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+    redis_pool: actix_web::web::Data<infrastructure::database_connection::redis::RedisPool>,
+    app_state: actix_web::web::Data<domain::configurate::SharedAppState>,
+) -> interface::actix::ActixResult<actix_web::HttpResponse> {
+    let (advertiser_id, campaign_id) = path_param.into_inner();
+    let creatives =
+        domain::usecase::CreativesGenerateUsecase::new(db_pool.get_ref(), redis_pool.get_ref(), &app_state.load())
+            .generate(generate_request.into_inner(), advertiser_id, campaign_id)
+            .await?;
+
+    Ok(actix_web::HttpResponse::Created().json(creatives))
+}
+
+#[utoipa::path(
+    get,
+    path = "/advertisers/{advertiser_id}/campaigns/{campaign_id}/creatives",
+    tag = "Creatives",
+    responses(
+        (status = 200, description = "Got creatives", body = Vec<domain::schemas::CreativeSchema>),
+        (status = 404, description = "Campaign not found", body = interface::actix::exception::ExceptionResponse),
+        (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse)
+    )
+)]
+#[actix_web::get("")]
+#[tracing::instrument(name = "creatives_get_list_handler", skip(db_pool))]
+pub async fn creatives_get_list_handler(
+    path_param: actix_web::web::Path<(uuid::Uuid, uuid::Uuid)>,
+    db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+) -> interface::actix::ActixResult<actix_web::HttpResponse> {
+    let (advertiser_id, campaign_id) = path_param.into_inner();
+    let creatives = domain::usecase::CreativesGetListUsecase::new(db_pool.get_ref())
+        .get(advertiser_id, campaign_id)
+        .await?;
+
+    Ok(actix_web::HttpResponse::Ok().json(creatives))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/advertisers/{advertiser_id}/campaigns/{campaign_id}/creatives/{creative_id}",
+    tag = "Creatives",
+    responses(
+        (status = 204, description = "Deleted", body = ()),
+        (status = 404, description = "Campaign or creative not found", body = interface::actix::exception::ExceptionResponse),
+        (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse)
+    )
+)]
+#[actix_web::delete("/{creative_id}")]
+#[tracing::instrument(name = "creatives_delete_handler", skip(db_pool, redis_pool))]
+pub async fn creatives_delete_handler(
+    path_param: actix_web::web::Path<(uuid::Uuid, uuid::Uuid, uuid::Uuid)>,
+    db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+    redis_pool: actix_web::web::Data<infrastructure::database_connection::redis::RedisPool>,
+) -> interface::actix::ActixResult<actix_web::HttpResponse> {
+    let (advertiser_id, campaign_id, creative_id) = path_param.into_inner();
+    domain::usecase::CreativesDeleteUsecase::new(db_pool.get_ref(), redis_pool.get_ref())
+        .delete(advertiser_id, campaign_id, creative_id)
+        .await?;
+
+    Ok(actix_web::HttpResponse::NoContent().finish())
+}
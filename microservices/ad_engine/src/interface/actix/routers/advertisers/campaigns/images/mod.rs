@@ -37,11 +37,12 @@ pub fn images_scope(path: &str) -> actix_web::Scope {
 pub async fn upload_image_campaign_handler(
     path_param: actix_web::web::Path<(uuid::Uuid, uuid::Uuid)>,
     db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
-    app_state: actix_web::web::Data<domain::configurate::AppState>,
+    app_state: actix_web::web::Data<domain::configurate::SharedAppState>,
     request: actix_web::HttpRequest,
     payload: actix_web::web::Payload,
 ) -> interface::actix::ActixResult<actix_web::HttpResponse> {
     let (advertiser_id, campaign_id) = path_param.into_inner();
+    let app_state = app_state.load();
 
     let files: Vec<(String, Vec<u8>, String)> = interface::actix::http_client::loader_files(
         payload,
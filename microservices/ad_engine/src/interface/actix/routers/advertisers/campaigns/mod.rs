@@ -1,5 +1,6 @@
 use crate::{domain, infrastructure, interface};
 
+pub mod creatives;
 pub mod images;
 
 pub fn campaigns_scope(path: &str) -> actix_web::Scope {
@@ -10,6 +11,8 @@ pub fn campaigns_scope(path: &str) -> actix_web::Scope {
         .service(campaigns_get_by_id_handler)
         .service(campaigns_get_list_handler)
         .service(campaigns_generate_text_handler)
+        .service(campaigns_generate_text_job_handler)
+        .service(creatives::creatives_scope("/{campaign_id}/creatives"))
         .service(images::images_scope("/{campaign_id}/images"))
 }
 
@@ -31,10 +34,10 @@ pub async fn campaigns_create_handler(
     advertiser_id: actix_web::web::Path<uuid::Uuid>,
     db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
     redis_pool: actix_web::web::Data<infrastructure::database_connection::redis::RedisPool>,
-    app_state: actix_web::web::Data<domain::configurate::AppState>,
+    app_state: actix_web::web::Data<domain::configurate::SharedAppState>,
 ) -> interface::actix::ActixResult<actix_web::HttpResponse> {
     let campaign =
-        domain::usecase::CampaignsCreateUsecase::new(db_pool.get_ref(), redis_pool.get_ref(), app_state.get_ref())
+        domain::usecase::CampaignsCreateUsecase::new(db_pool.get_ref(), redis_pool.get_ref(), &app_state.load())
             .create(campaign_request.into_inner(), advertiser_id.into_inner())
             .await?;
 
@@ -45,10 +48,16 @@ pub async fn campaigns_create_handler(
     patch,
     path = "/advertisers/{advertiser_id}/campaigns/{campaign_id}/generate_text",
     tag = "Campaigns",
+    description = "Generates the title and the text of a campaign. Campaigns with creatives serve the creatives \
+                   instead of their texts and are rejected, their creatives are generated instead",
     request_body = domain::schemas::CampaignsGenerateTextRequest,
     responses(
         (status = 201, description = "Created campaign", body = domain::schemas::CampaignSchema),
-        (status = 400, description = "Bad request", body = interface::actix::exception::ExceptionResponse),
+        (
+            status = 400,
+            description = "Bad request or campaign has creatives",
+            body = interface::actix::exception::ExceptionResponse
+        ),
         (status = 404, description = "Not found", body = interface::actix::exception::ExceptionResponse),
         (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse),
         (status = 503, description = "Yandex GPT not response", body = interface::actix::exception::ExceptionResponse),
@@ -61,20 +70,53 @@ pub async fn campaigns_generate_text_handler(
     path_param: actix_web::web::Path<(uuid::Uuid, uuid::Uuid)>,
     db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
     redis_pool: actix_web::web::Data<infrastructure::database_connection::redis::RedisPool>,
-    app_state: actix_web::web::Data<domain::configurate::AppState>,
+    app_state: actix_web::web::Data<domain::configurate::SharedAppState>,
 ) -> interface::actix::ActixResult<actix_web::HttpResponse> {
     let (advertiser_id, campaign_id) = path_param.into_inner();
-    let campaign = domain::usecase::CampaignsGeneratorTextUsecase::new(
-        db_pool.get_ref(),
-        redis_pool.get_ref(),
-        app_state.get_ref(),
-    )
-    .generate(generate_request.into_inner(), advertiser_id, campaign_id)
-    .await?;
+    let campaign =
+        domain::usecase::CampaignsGeneratorTextUsecase::new(db_pool.get_ref(), redis_pool.get_ref(), &app_state.load())
+            .generate(generate_request.into_inner(), advertiser_id, campaign_id)
+            .await?;
 
     Ok(actix_web::HttpResponse::Created().json(campaign))
 }
 
+#[utoipa::path(
+    post,
+    path = "/advertisers/{advertiser_id}/campaigns/{campaign_id}/generate_text/jobs",
+    tag = "Campaigns",
+    description = "Starts generating the title and the text of a campaign in the background. Campaigns with \
+                   creatives serve the creatives instead of their texts and are rejected",
+    request_body = domain::schemas::CampaignsGenerateTextRequest,
+    responses(
+        (status = 202, description = "Created generation job", body = domain::schemas::GenerationJobSchema),
+        (
+            status = 400,
+            description = "Bad request or campaign has creatives",
+            body = interface::actix::exception::ExceptionResponse
+        ),
+        (status = 404, description = "Not found", body = interface::actix::exception::ExceptionResponse),
+        (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse),
+    )
+)]
+#[actix_web::post("/{campaign_id}/generate_text/jobs")]
+#[tracing::instrument(name = "campaigns_generate_text_job_handler", skip(db_pool, redis_pool, app_state))]
+pub async fn campaigns_generate_text_job_handler(
+    generate_request: actix_web::web::Json<domain::schemas::CampaignsGenerateTextRequest>,
+    path_param: actix_web::web::Path<(uuid::Uuid, uuid::Uuid)>,
+    db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+    redis_pool: actix_web::web::Data<infrastructure::database_connection::redis::RedisPool>,
+    app_state: actix_web::web::Data<domain::configurate::SharedAppState>,
+) -> interface::actix::ActixResult<actix_web::HttpResponse> {
+    let (advertiser_id, campaign_id) = path_param.into_inner();
+    let job =
+        domain::usecase::GenerationJobsCreateUsecase::new(db_pool.get_ref(), redis_pool.get_ref(), &app_state.load())
+            .create(generate_request.into_inner(), advertiser_id, campaign_id)
+            .await?;
+
+    Ok(actix_web::HttpResponse::Accepted().json(job))
+}
+
 #[utoipa::path(
     put,
     path = "/advertisers/{advertiser_id}/campaigns/{campaign_id}",
@@ -94,11 +136,11 @@ pub async fn campaigns_update_handler(
     path_param: actix_web::web::Path<(uuid::Uuid, uuid::Uuid)>,
     db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
     redis_pool: actix_web::web::Data<infrastructure::database_connection::redis::RedisPool>,
-    app_state: actix_web::web::Data<domain::configurate::AppState>,
+    app_state: actix_web::web::Data<domain::configurate::SharedAppState>,
 ) -> interface::actix::ActixResult<actix_web::HttpResponse> {
     let (advertiser_id, campaign_id) = path_param.into_inner();
     let campaign =
-        domain::usecase::CampaignsUpdateUsecase::new(db_pool.get_ref(), redis_pool.get_ref(), app_state.get_ref())
+        domain::usecase::CampaignsUpdateUsecase::new(db_pool.get_ref(), redis_pool.get_ref(), &app_state.load())
             .update(campaign_request.into_inner(), advertiser_id, campaign_id)
             .await?;
 
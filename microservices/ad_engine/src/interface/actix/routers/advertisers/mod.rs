@@ -5,6 +5,8 @@ pub fn advertisers_scope(path: &str) -> actix_web::Scope {
     actix_web::web::scope(path)
         .service(advertiser_bulk_handler)
         .service(advertiser_by_id_handler)
+        .service(advertiser_brand_voice_get_handler)
+        .service(advertiser_brand_voice_update_handler)
         .service(campaigns::campaigns_scope("/{advertiser_id}/campaigns"))
 }
 
@@ -25,15 +27,12 @@ pub async fn advertiser_bulk_handler(
     register_data: actix_web::web::Json<Vec<domain::schemas::AdvertiserProfileSchema>>,
     db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
     redis_pool: actix_web::web::Data<infrastructure::database_connection::redis::RedisPool>,
-    app_state: actix_web::web::Data<domain::configurate::AppState>,
+    app_state: actix_web::web::Data<domain::configurate::SharedAppState>,
 ) -> interface::actix::ActixResult<actix_web::HttpResponse> {
-    let regsiters_user = domain::usecase::AdvertiserBulkRegisterUsecase::new(
-        db_pool.get_ref(),
-        redis_pool.get_ref(),
-        app_state.get_ref(),
-    )
-    .registers(register_data.into_inner())
-    .await?;
+    let regsiters_user =
+        domain::usecase::AdvertiserBulkRegisterUsecase::new(db_pool.get_ref(), redis_pool.get_ref(), &app_state.load())
+            .registers(register_data.into_inner())
+            .await?;
 
     Ok(actix_web::HttpResponse::Created().json(regsiters_user))
 }
@@ -61,3 +60,52 @@ pub async fn advertiser_by_id_handler(
 
     Ok(actix_web::HttpResponse::Ok().json(user))
 }
+
+#[utoipa::path(
+    get,
+    path = "/advertisers/{advertiser_id}/brand_voice",
+    tag = "Advertiser",
+    responses(
+        (status = 200, description = "Got brand voice", body = domain::schemas::BrandVoiceSchema),
+        (status = 404, description = "Brand voice not found", body = interface::actix::exception::ExceptionResponse),
+        (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse),
+    )
+)]
+#[actix_web::get("/{advertiser_id}/brand_voice")]
+#[tracing::instrument(name = "advertiser_brand_voice_get_handler", skip(db_pool))]
+pub async fn advertiser_brand_voice_get_handler(
+    advertiser_id: actix_web::web::Path<uuid::Uuid>,
+    db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+) -> interface::actix::ActixResult<actix_web::HttpResponse> {
+    let brand_voice = domain::usecase::BrandVoiceGetUsecase::new(db_pool.get_ref())
+        .get(advertiser_id.into_inner())
+        .await?;
+
+    Ok(actix_web::HttpResponse::Ok().json(brand_voice))
+}
+
+#[utoipa::path(
+    put,
+    path = "/advertisers/{advertiser_id}/brand_voice",
+    tag = "Advertiser",
+    request_body = domain::schemas::BrandVoiceUpdateRequest,
+    responses(
+        (status = 200, description = "Set brand voice", body = domain::schemas::BrandVoiceSchema),
+        (status = 400, description = "Bad request", body = interface::actix::exception::ExceptionResponse),
+        (status = 404, description = "Advertiser or template not found", body = interface::actix::exception::ExceptionResponse),
+        (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse),
+    )
+)]
+#[actix_web::put("/{advertiser_id}/brand_voice")]
+#[tracing::instrument(name = "advertiser_brand_voice_update_handler", skip(db_pool))]
+pub async fn advertiser_brand_voice_update_handler(
+    brand_voice_request: actix_web::web::Json<domain::schemas::BrandVoiceUpdateRequest>,
+    advertiser_id: actix_web::web::Path<uuid::Uuid>,
+    db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+) -> interface::actix::ActixResult<actix_web::HttpResponse> {
+    let brand_voice = domain::usecase::BrandVoiceUpdateUsecase::new(db_pool.get_ref())
+        .update(advertiser_id.into_inner(), brand_voice_request.into_inner())
+        .await?;
+
+    Ok(actix_web::HttpResponse::Ok().json(brand_voice))
+}
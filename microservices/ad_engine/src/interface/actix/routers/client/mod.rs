@@ -23,10 +23,10 @@ pub async fn client_bulk_handler(
     register_data: actix_web::web::Json<Vec<domain::schemas::ClientProfileSchema>>,
     db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
     redis_pool: actix_web::web::Data<infrastructure::database_connection::redis::RedisPool>,
-    app_state: actix_web::web::Data<domain::configurate::AppState>,
+    app_state: actix_web::web::Data<domain::configurate::SharedAppState>,
 ) -> interface::actix::ActixResult<actix_web::HttpResponse> {
     let regsiters_user =
-        domain::usecase::ClientBulkRegisterUsecase::new(db_pool.get_ref(), redis_pool.get_ref(), app_state.get_ref())
+        domain::usecase::ClientBulkRegisterUsecase::new(db_pool.get_ref(), redis_pool.get_ref(), &app_state.load())
             .registers(register_data.into_inner())
             .await?;
 
@@ -0,0 +1,81 @@
+use crate::{domain, infrastructure, interface};
+
+pub fn experiments_scope(path: &str) -> actix_web::Scope {
+    actix_web::web::scope(path)
+        .service(experiments_create_handler)
+        .service(experiments_get_list_handler)
+        .service(experiments_stop_handler)
+}
+
+#[utoipa::path(
+    post,
+    path = "/experiments",
+    tag = "Experiments",
+    request_body = domain::schemas::ExperimentCreateRequest,
+    responses(
+        (status = 201, description = "Started experiment", body = domain::schemas::ExperimentSchema),
+        (status = 400, description = "Bad request", body = interface::actix::exception::ExceptionResponse),
+        (status = 409, description = "Another experiment is running", body = interface::actix::exception::ExceptionResponse),
+        (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse)
+    )
+)]
+#[actix_web::post("")]
+#[tracing::instrument(name = "experiments_create_handler", skip(db_pool, redis_pool, app_state))]
+pub async fn experiments_create_handler(
+    experiment_request: actix_web::web::Json<domain::schemas::ExperimentCreateRequest>,
+    db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+    redis_pool: actix_web::web::Data<infrastructure::database_connection::redis::RedisPool>,
+    app_state: actix_web::web::Data<domain::configurate::SharedAppState>,
+) -> interface::actix::ActixResult<actix_web::HttpResponse> {
+    let experiment =
+        domain::usecase::ExperimentsCreateUsecase::new(db_pool.get_ref(), redis_pool.get_ref(), &app_state.load())
+            .create(experiment_request.into_inner())
+            .await?;
+
+    Ok(actix_web::HttpResponse::Created().json(experiment))
+}
+
+#[utoipa::path(
+    get,
+    path = "/experiments",
+    tag = "Experiments",
+    responses(
+        (status = 200, description = "Got experiments", body = Vec<domain::schemas::ExperimentSchema>),
+        (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse)
+    )
+)]
+#[actix_web::get("")]
+#[tracing::instrument(name = "experiments_get_list_handler", skip(db_pool))]
+pub async fn experiments_get_list_handler(
+    db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+) -> interface::actix::ActixResult<actix_web::HttpResponse> {
+    let experiments = domain::usecase::ExperimentsGetListUsecase::new(db_pool.get_ref())
+        .get()
+        .await?;
+
+    Ok(actix_web::HttpResponse::Ok().json(experiments))
+}
+
+#[utoipa::path(
+    post,
+    path = "/experiments/{experiment_id}/stop",
+    tag = "Experiments",
+    responses(
+        (status = 200, description = "Stopped experiment", body = domain::schemas::ExperimentSchema),
+        (status = 404, description = "Running experiment not found", body = interface::actix::exception::ExceptionResponse),
+        (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse)
+    )
+)]
+#[actix_web::post("/{experiment_id}/stop")]
+#[tracing::instrument(name = "experiments_stop_handler", skip(db_pool, redis_pool))]
+pub async fn experiments_stop_handler(
+    experiment_id: actix_web::web::Path<uuid::Uuid>,
+    db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+    redis_pool: actix_web::web::Data<infrastructure::database_connection::redis::RedisPool>,
+) -> interface::actix::ActixResult<actix_web::HttpResponse> {
+    let experiment = domain::usecase::ExperimentsStopUsecase::new(db_pool.get_ref(), redis_pool.get_ref())
+        .stop(experiment_id.into_inner())
+        .await?;
+
+    Ok(actix_web::HttpResponse::Ok().json(experiment))
+}
@@ -0,0 +1,91 @@
+use crate::{domain, infrastructure, interface};
+
+pub fn jobs_scope(path: &str) -> actix_web::Scope {
+    actix_web::web::scope(path)
+        .service(jobs_get_handler)
+        .service(jobs_accept_handler)
+        .service(jobs_reject_handler)
+}
+
+#[utoipa::path(
+    get,
+    path = "/jobs/{job_id}",
+    tag = "Generation jobs",
+    responses(
+        (status = 200, description = "Got generation job", body = domain::schemas::GenerationJobSchema),
+        (status = 404, description = "Not found", body = interface::actix::exception::ExceptionResponse),
+        (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse)
+    )
+)]
+#[actix_web::get("/{job_id}")]
+#[tracing::instrument(name = "jobs_get_handler", skip(db_pool))]
+pub async fn jobs_get_handler(
+    job_id: actix_web::web::Path<uuid::Uuid>,
+    db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+) -> interface::actix::ActixResult<actix_web::HttpResponse> {
+    let job = domain::usecase::GenerationJobsGetUsecase::new(db_pool.get_ref())
+        .get(job_id.into_inner())
+        .await?;
+
+    Ok(actix_web::HttpResponse::Ok().json(job))
+}
+
+#[utoipa::path(
+    post,
+    path = "/jobs/{job_id}/accept",
+    tag = "Generation jobs",
+    description = "Applies the generated texts of a done job to its campaign. A campaign which got creatives after \
+                   the job was created serves the creatives instead of its texts, so the job is rejected",
+    responses(
+        (status = 200, description = "Updated campaign", body = domain::schemas::CampaignSchema),
+        (
+            status = 400,
+            description = "Job is not done or campaign has creatives",
+            body = interface::actix::exception::ExceptionResponse
+        ),
+        (status = 404, description = "Not found", body = interface::actix::exception::ExceptionResponse),
+        (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse)
+    )
+)]
+#[actix_web::post("/{job_id}/accept")]
+#[tracing::instrument(name = "jobs_accept_handler", skip(db_pool, redis_pool, app_state))]
+pub async fn jobs_accept_handler(
+    job_id: actix_web::web::Path<uuid::Uuid>,
+    db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+    redis_pool: actix_web::web::Data<infrastructure::database_connection::redis::RedisPool>,
+    app_state: actix_web::web::Data<domain::configurate::SharedAppState>,
+) -> interface::actix::ActixResult<actix_web::HttpResponse> {
+    let campaign =
+        domain::usecase::GenerationJobsDecideUsecase::new(db_pool.get_ref(), redis_pool.get_ref(), &app_state.load())
+            .accept(job_id.into_inner())
+            .await?;
+
+    Ok(actix_web::HttpResponse::Ok().json(campaign))
+}
+
+#[utoipa::path(
+    post,
+    path = "/jobs/{job_id}/reject",
+    tag = "Generation jobs",
+    responses(
+        (status = 200, description = "Rejected generation job", body = domain::schemas::GenerationJobSchema),
+        (status = 400, description = "Job is not done", body = interface::actix::exception::ExceptionResponse),
+        (status = 404, description = "Not found", body = interface::actix::exception::ExceptionResponse),
+        (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse)
+    )
+)]
+#[actix_web::post("/{job_id}/reject")]
+#[tracing::instrument(name = "jobs_reject_handler", skip(db_pool, redis_pool, app_state))]
+pub async fn jobs_reject_handler(
+    job_id: actix_web::web::Path<uuid::Uuid>,
+    db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+    redis_pool: actix_web::web::Data<infrastructure::database_connection::redis::RedisPool>,
+    app_state: actix_web::web::Data<domain::configurate::SharedAppState>,
+) -> interface::actix::ActixResult<actix_web::HttpResponse> {
+    let job =
+        domain::usecase::GenerationJobsDecideUsecase::new(db_pool.get_ref(), redis_pool.get_ref(), &app_state.load())
+            .reject(job_id.into_inner())
+            .await?;
+
+    Ok(actix_web::HttpResponse::Ok().json(job))
+}
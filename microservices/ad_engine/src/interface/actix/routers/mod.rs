@@ -1,19 +1,25 @@
 pub mod ads;
 pub mod advertisers;
 pub mod client;
+pub mod experiments;
 pub mod healthcheck;
+pub mod jobs;
 mod metrics;
 pub mod ml_score;
 pub mod moderate;
+pub mod prompt_templates;
 pub mod stats;
 pub mod time;
 
 pub use ads::ads_scope;
 pub use advertisers::advertisers_scope;
 pub use client::client_scope;
+pub use experiments::experiments_scope;
 pub use healthcheck::healthcheck_handler;
+pub use jobs::jobs_scope;
 pub use metrics::metrics_handler;
 pub use ml_score::ml_score_handler;
 pub use moderate::moderate_scope;
+pub use prompt_templates::prompt_templates_scope;
 pub use stats::stat_scope;
 pub use time::time_advance_handler;
@@ -0,0 +1,104 @@
+use crate::{domain, infrastructure, interface};
+
+pub fn prompt_templates_scope(path: &str) -> actix_web::Scope {
+    actix_web::web::scope(path)
+        .service(prompt_templates_create_handler)
+        .service(prompt_templates_get_list_handler)
+        .service(prompt_templates_create_version_handler)
+        .service(prompt_templates_get_versions_handler)
+}
+
+#[utoipa::path(
+    post,
+    path = "/prompt_templates",
+    tag = "Prompt templates",
+    request_body = domain::schemas::PromptTemplateCreateRequest,
+    responses(
+        (status = 201, description = "Created template", body = domain::schemas::PromptTemplateSchema),
+        (status = 400, description = "Bad request", body = interface::actix::exception::ExceptionResponse),
+        (status = 409, description = "Template with the name exists", body = interface::actix::exception::ExceptionResponse),
+        (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse)
+    )
+)]
+#[actix_web::post("")]
+#[tracing::instrument(name = "prompt_templates_create_handler", skip(db_pool))]
+pub async fn prompt_templates_create_handler(
+    template_request: actix_web::web::Json<domain::schemas::PromptTemplateCreateRequest>,
+    db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+) -> interface::actix::ActixResult<actix_web::HttpResponse> {
+    let template = domain::usecase::PromptTemplatesCreateUsecase::new(db_pool.get_ref())
+        .create(template_request.into_inner())
+        .await?;
+
+    Ok(actix_web::HttpResponse::Created().json(template))
+}
+
+#[utoipa::path(
+    get,
+    path = "/prompt_templates",
+    tag = "Prompt templates",
+    responses(
+        (status = 200, description = "Got templates with their latest versions", body = Vec<domain::schemas::PromptTemplateSchema>),
+        (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse)
+    )
+)]
+#[actix_web::get("")]
+#[tracing::instrument(name = "prompt_templates_get_list_handler", skip(db_pool))]
+pub async fn prompt_templates_get_list_handler(
+    db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+) -> interface::actix::ActixResult<actix_web::HttpResponse> {
+    let templates = domain::usecase::PromptTemplatesGetListUsecase::new(db_pool.get_ref())
+        .get_list()
+        .await?;
+
+    Ok(actix_web::HttpResponse::Ok().json(templates))
+}
+
+#[utoipa::path(
+    post,
+    path = "/prompt_templates/{template_id}/versions",
+    tag = "Prompt templates",
+    request_body = domain::schemas::PromptTemplateVersionCreateRequest,
+    responses(
+        (status = 201, description = "Created version", body = domain::schemas::PromptTemplateVersionSchema),
+        (status = 400, description = "Bad request", body = interface::actix::exception::ExceptionResponse),
+        (status = 404, description = "Template not found", body = interface::actix::exception::ExceptionResponse),
+        (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse)
+    )
+)]
+#[actix_web::post("/{template_id}/versions")]
+#[tracing::instrument(name = "prompt_templates_create_version_handler", skip(db_pool))]
+pub async fn prompt_templates_create_version_handler(
+    version_request: actix_web::web::Json<domain::schemas::PromptTemplateVersionCreateRequest>,
+    template_id: actix_web::web::Path<uuid::Uuid>,
+    db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+) -> interface::actix::ActixResult<actix_web::HttpResponse> {
+    let version = domain::usecase::PromptTemplatesCreateVersionUsecase::new(db_pool.get_ref())
+        .create(template_id.into_inner(), version_request.into_inner())
+        .await?;
+
+    Ok(actix_web::HttpResponse::Created().json(version))
+}
+
+#[utoipa::path(
+    get,
+    path = "/prompt_templates/{template_id}/versions",
+    tag = "Prompt templates",
+    responses(
+        (status = 200, description = "Got versions, the latest first", body = Vec<domain::schemas::PromptTemplateVersionSchema>),
+        (status = 404, description = "Template not found", body = interface::actix::exception::ExceptionResponse),
+        (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse)
+    )
+)]
+#[actix_web::get("/{template_id}/versions")]
+#[tracing::instrument(name = "prompt_templates_get_versions_handler", skip(db_pool))]
+pub async fn prompt_templates_get_versions_handler(
+    template_id: actix_web::web::Path<uuid::Uuid>,
+    db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+) -> interface::actix::ActixResult<actix_web::HttpResponse> {
+    let versions = domain::usecase::PromptTemplatesGetVersionsUsecase::new(db_pool.get_ref())
+        .get(template_id.into_inner())
+        .await?;
+
+    Ok(actix_web::HttpResponse::Ok().json(versions))
+}
@@ -3,9 +3,12 @@ use crate::{domain, infrastructure, interface};
 pub fn stat_scope(path: &str) -> actix_web::Scope {
     actix_web::web::scope(path)
         .service(stat_campaign_daily_handler)
+        .service(stat_campaign_creatives_handler)
         .service(stat_campaign_handler)
         .service(stat_advertisers_handler)
         .service(stat_advertisers_daily_handler)
+        .service(stat_experiment_handler)
+        .service(stat_prompt_template_handler)
 }
 
 #[utoipa::path(
@@ -19,18 +22,44 @@ pub fn stat_scope(path: &str) -> actix_web::Scope {
     )
 )]
 #[actix_web::get("/campaigns/{campaign_id}")]
-#[tracing::instrument(name = "stat_campaign_handler", skip(db_pool))]
+#[tracing::instrument(name = "stat_campaign_handler", skip(db_pool, redis_pool, app_state))]
 pub async fn stat_campaign_handler(
     campaign_id: actix_web::web::Path<uuid::Uuid>,
     db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+    redis_pool: actix_web::web::Data<infrastructure::database_connection::redis::RedisPool>,
+    app_state: actix_web::web::Data<domain::configurate::SharedAppState>,
 ) -> interface::actix::ActixResult<actix_web::HttpResponse> {
-    let stat = domain::usecase::StatCampaignUsecase::new(db_pool.get_ref())
+    let stat = domain::usecase::StatCampaignUsecase::new(db_pool.get_ref(), redis_pool.get_ref(), &app_state.load())
         .get(campaign_id.into_inner())
         .await?;
 
     Ok(actix_web::HttpResponse::Ok().json(stat))
 }
 
+#[utoipa::path(
+    get,
+    path = "/stats/campaigns/{campaign_id}/creatives",
+    tag = "Stats",
+    responses(
+        (status = 200, description = "Got stat of every creative", body = Vec<domain::schemas::CreativeStatSchema>),
+        (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse)
+    )
+)]
+#[actix_web::get("/campaigns/{campaign_id}/creatives")]
+#[tracing::instrument(name = "stat_campaign_creatives_handler", skip(db_pool, redis_pool, app_state))]
+pub async fn stat_campaign_creatives_handler(
+    campaign_id: actix_web::web::Path<uuid::Uuid>,
+    db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+    redis_pool: actix_web::web::Data<infrastructure::database_connection::redis::RedisPool>,
+    app_state: actix_web::web::Data<domain::configurate::SharedAppState>,
+) -> interface::actix::ActixResult<actix_web::HttpResponse> {
+    let stat = domain::usecase::StatCampaignUsecase::new(db_pool.get_ref(), redis_pool.get_ref(), &app_state.load())
+        .get_by_creative(campaign_id.into_inner())
+        .await?;
+
+    Ok(actix_web::HttpResponse::Ok().json(stat))
+}
+
 #[utoipa::path(
     get,
     path = "/stats/campaigns/{campaign_id}/daily",
@@ -42,12 +71,14 @@ pub async fn stat_campaign_handler(
     )
 )]
 #[actix_web::get("/campaigns/{campaign_id}/daily")]
-#[tracing::instrument(name = "stat_campaign_daily_handler", skip(db_pool))]
+#[tracing::instrument(name = "stat_campaign_daily_handler", skip(db_pool, redis_pool, app_state))]
 pub async fn stat_campaign_daily_handler(
     campaign_id: actix_web::web::Path<uuid::Uuid>,
     db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+    redis_pool: actix_web::web::Data<infrastructure::database_connection::redis::RedisPool>,
+    app_state: actix_web::web::Data<domain::configurate::SharedAppState>,
 ) -> interface::actix::ActixResult<actix_web::HttpResponse> {
-    let stat = domain::usecase::StatCampaignUsecase::new(db_pool.get_ref())
+    let stat = domain::usecase::StatCampaignUsecase::new(db_pool.get_ref(), redis_pool.get_ref(), &app_state.load())
         .get_by_day(campaign_id.into_inner())
         .await?;
 
@@ -65,12 +96,14 @@ pub async fn stat_campaign_daily_handler(
     )
 )]
 #[actix_web::get("/advertisers/{advertiser_id}/campaigns")]
-#[tracing::instrument(name = "stat_advertisers_handler", skip(db_pool))]
+#[tracing::instrument(name = "stat_advertisers_handler", skip(db_pool, redis_pool, app_state))]
 pub async fn stat_advertisers_handler(
     advertiser_id: actix_web::web::Path<uuid::Uuid>,
     db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+    redis_pool: actix_web::web::Data<infrastructure::database_connection::redis::RedisPool>,
+    app_state: actix_web::web::Data<domain::configurate::SharedAppState>,
 ) -> interface::actix::ActixResult<actix_web::HttpResponse> {
-    let stat = domain::usecase::StatCampaignUsecase::new(db_pool.get_ref())
+    let stat = domain::usecase::StatCampaignUsecase::new(db_pool.get_ref(), redis_pool.get_ref(), &app_state.load())
         .get_with_advertisers(advertiser_id.into_inner())
         .await?;
 
@@ -88,13 +121,62 @@ pub async fn stat_advertisers_handler(
     )
 )]
 #[actix_web::get("/advertisers/{advertiser_id}/campaigns/daily")]
-#[tracing::instrument(name = "stat_advertisers_daily_handler", skip(db_pool))]
+#[tracing::instrument(name = "stat_advertisers_daily_handler", skip(db_pool, redis_pool, app_state))]
 pub async fn stat_advertisers_daily_handler(
     advertiser_id: actix_web::web::Path<uuid::Uuid>,
     db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+    redis_pool: actix_web::web::Data<infrastructure::database_connection::redis::RedisPool>,
+    app_state: actix_web::web::Data<domain::configurate::SharedAppState>,
+) -> interface::actix::ActixResult<actix_web::HttpResponse> {
+    let stat: Vec<domain::schemas::StatDailyResponse> =
+        domain::usecase::StatCampaignUsecase::new(db_pool.get_ref(), redis_pool.get_ref(), &app_state.load())
+            .get_with_advertisers_by_day(advertiser_id.into_inner())
+            .await?;
+
+    Ok(actix_web::HttpResponse::Ok().json(stat))
+}
+
+#[utoipa::path(
+    get,
+    path = "/stats/experiments/{experiment_id}",
+    tag = "Stats",
+    responses(
+        (status = 200, description = "Got stat", body = domain::schemas::ExperimentStatResponse),
+        (status = 404, description = "Experiment not found", body = interface::actix::exception::ExceptionResponse),
+        (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse)
+    )
+)]
+#[actix_web::get("/experiments/{experiment_id}")]
+#[tracing::instrument(name = "stat_experiment_handler", skip(db_pool))]
+pub async fn stat_experiment_handler(
+    experiment_id: actix_web::web::Path<uuid::Uuid>,
+    db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
+) -> interface::actix::ActixResult<actix_web::HttpResponse> {
+    let stat = domain::usecase::StatExperimentUsecase::new(db_pool.get_ref())
+        .get(experiment_id.into_inner())
+        .await?;
+
+    Ok(actix_web::HttpResponse::Ok().json(stat))
+}
+
+#[utoipa::path(
+    get,
+    path = "/stats/prompt_templates/{template_id}",
+    tag = "Stats",
+    responses(
+        (status = 200, description = "Got stat of the creatives and campaign texts by template version", body = Vec<domain::schemas::PromptVersionStatSchema>),
+        (status = 404, description = "Template not found", body = interface::actix::exception::ExceptionResponse),
+        (status = 500, description = "Internal server error", body = interface::actix::exception::ExceptionResponse)
+    )
+)]
+#[actix_web::get("/prompt_templates/{template_id}")]
+#[tracing::instrument(name = "stat_prompt_template_handler", skip(db_pool))]
+pub async fn stat_prompt_template_handler(
+    template_id: actix_web::web::Path<uuid::Uuid>,
+    db_pool: actix_web::web::Data<infrastructure::database_connection::sqlx_lib::SqlxPool>,
 ) -> interface::actix::ActixResult<actix_web::HttpResponse> {
-    let stat: Vec<domain::schemas::StatDailyResponse> = domain::usecase::StatCampaignUsecase::new(db_pool.get_ref())
-        .get_with_advertisers_by_day(advertiser_id.into_inner())
+    let stat = domain::usecase::StatPromptTemplateUsecase::new(db_pool.get_ref())
+        .get(template_id.into_inner())
         .await?;
 
     Ok(actix_web::HttpResponse::Ok().json(stat))
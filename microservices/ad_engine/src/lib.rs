@@ -11,9 +11,27 @@ mod domain;
 mod infrastructure;
 mod interface;
 
+/// Parses and validates the configuration without starting the server
+///
+/// # Returns
+/// * `ExitCode` - Success if the configuration is valid, failure otherwise
+pub async fn check_config() -> std::process::ExitCode {
+    match configurate::try_parse(std::path::PathBuf::from("./conf")).await {
+        Ok(_) => {
+            println!("Config is valid.");
+            std::process::ExitCode::SUCCESS
+        },
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::ExitCode::FAILURE
+        },
+    }
+}
+
 pub async fn startapp() {
     // Config init
-    let config = configurate::parse(std::path::PathBuf::from("./conf")).await;
+    let path_to_conf_file = std::path::PathBuf::from("./conf");
+    let config = configurate::parse(path_to_conf_file.clone()).await;
 
     // Logger init
     tracing_lib::setup_logging(config.logger.clone()).await;
@@ -28,9 +46,61 @@ pub async fn startapp() {
     let redis_pool = database_connection::redis::RedisPool::new(config.database.redis.clone()).await;
 
     // App state init
-    let app_state = domain::configurate::AppState::from(&config);
+    let app_state = std::sync::Arc::new(domain::configurate::SharedAppState::new(
+        domain::configurate::AppState::from(&config),
+    ));
     tracing::info!("App state init complete.");
 
+    // CTR learner init
+    let initial_app_state = app_state.load();
+    if initial_app_state.ads_ctr_learning_enabled {
+        let (pool, refresh_interval) = (connection_pool.clone(), initial_app_state.ads_ctr_refresh_interval);
+        tokio::spawn(async move { domain::usecase::CtrLearnUsecase::new(&pool).run(refresh_interval).await });
+        tracing::info!("CTR learner started.");
+    }
+
+    // Fallback scores refresh init
+    let (pool, refresh_interval) = (
+        connection_pool.clone(),
+        initial_app_state.ads_fallback_scores_refresh_interval,
+    );
+    tokio::spawn(async move {
+        domain::usecase::FallbackScoresRefreshUsecase::new(&pool)
+            .run(refresh_interval)
+            .await
+    });
+    tracing::info!("Fallback scores refresh started.");
+
+    // Targeting index resync init
+    let (pool, resync_interval) = (
+        redis_pool.clone(),
+        initial_app_state.ads_targeting_index_resync_interval,
+    );
+    tokio::spawn(async move {
+        domain::usecase::TargetingIndexSyncUsecase::new(&pool)
+            .run(resync_interval)
+            .await
+    });
+    tracing::info!("Targeting index resync started.");
+
+    // Generation jobs resume
+    match domain::usecase::GenerationJobsRunUsecase::new(&connection_pool, &initial_app_state)
+        .resume()
+        .await
+    {
+        Ok(resumed) => tracing::info!("Resumed {} generation jobs.", resumed),
+        Err(e) => tracing::error!("Failed to resume generation jobs: {}", e),
+    }
+
+    // Config hot reload init
+    #[cfg(unix)]
+    {
+        let config_reload =
+            domain::usecase::ConfigReloadUsecase::new(path_to_conf_file, config.clone(), app_state.clone());
+        tokio::spawn(config_reload.run());
+        tracing::info!("Config hot reload on SIGHUP started.");
+    }
+
     // Http client init
     let http_client =
         interface::actix::HttpServer::new(config.http_server, config.cors, app_state, connection_pool, redis_pool)
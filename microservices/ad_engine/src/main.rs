@@ -1,6 +1,11 @@
-use ad_engine::startapp;
+use ad_engine::{check_config, startapp};
 
 #[tokio::main]
-async fn main() {
-    startapp().await
+async fn main() -> std::process::ExitCode {
+    if std::env::args().any(|arg| arg == "--check-config") {
+        return check_config().await;
+    }
+
+    startapp().await;
+    std::process::ExitCode::SUCCESS
 }
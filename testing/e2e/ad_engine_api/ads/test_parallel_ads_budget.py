@@ -0,0 +1,80 @@
+"""Parallel /ads requests against one campaign budget.
+
+Impressions are recorded by a Lua script in Redis, so concurrent requests
+must neither overspend the budget nor lose impressions.
+"""
+
+import os
+import uuid
+from concurrent.futures import ThreadPoolExecutor
+
+import requests
+
+AD_ENGINE_URL = f"http://{os.environ['AD_ENGINE_ADDRESS']}/api"
+
+CURRENT_DATE = 50
+COST_PER_IMPRESSION = 10
+BUDGET = 100
+CLIENTS_COUNT = 60
+LOCATION = "Parallel budget town"
+
+
+def get_ad(client_id: str) -> requests.Response:
+    return requests.get(f"{AD_ENGINE_URL}/ads", params={"client_id": client_id}, timeout=30)
+
+
+def test_parallel_ads_do_not_overspend_budget():
+    response = requests.post(f"{AD_ENGINE_URL}/time/advance", json={"current_date": CURRENT_DATE})
+    assert response.status_code == 200
+
+    advertiser_id = str(uuid.uuid4())
+    response = requests.post(
+        f"{AD_ENGINE_URL}/advertisers/bulk",
+        json=[{"advertiser_id": advertiser_id, "name": "parallel budget"}],
+    )
+    assert response.status_code == 201
+
+    response = requests.post(
+        f"{AD_ENGINE_URL}/advertisers/{advertiser_id}/campaigns",
+        json={
+            "ad_title": "Parallel budget",
+            "ad_text": "Parallel budget",
+            "impressions_limit": 1000,
+            "clicks_limit": 1000,
+            "cost_per_impression": COST_PER_IMPRESSION,
+            "cost_per_click": 0,
+            "budget": BUDGET,
+            "start_date": CURRENT_DATE,
+            "end_date": CURRENT_DATE,
+            "targeting": {"location": LOCATION},
+        },
+    )
+    assert response.status_code == 201
+    campaign_id = response.json()["campaign_id"]
+
+    client_ids = [str(uuid.uuid4()) for _ in range(CLIENTS_COUNT)]
+    response = requests.post(
+        f"{AD_ENGINE_URL}/clients/bulk",
+        json=[
+            {"client_id": client_id, "login": client_id, "location": LOCATION, "gender": "MALE", "age": 30}
+            for client_id in client_ids
+        ],
+    )
+    assert response.status_code == 201
+
+    with ThreadPoolExecutor(max_workers=30) as executor:
+        responses = list(executor.map(get_ad, client_ids))
+
+    assert all(response.status_code in (200, 404) for response in responses)
+    served = sum(
+        1 for response in responses if response.status_code == 200 and response.json()["ad_id"] == campaign_id
+    )
+
+    response = requests.get(f"{AD_ENGINE_URL}/stats/campaigns/{campaign_id}")
+    assert response.status_code == 200
+    stat = response.json()
+
+    assert 0 < served <= BUDGET // COST_PER_IMPRESSION
+    assert stat["impressions_count"] == served
+    assert stat["spent_total"] == served * COST_PER_IMPRESSION
+    assert stat["spent_total"] <= BUDGET